@@ -0,0 +1,85 @@
+import { Arc as ArcData, PolygonStyle, Position } from "../native";
+import * as PIXI from "pixi.js";
+import { strokeSegment } from "./dash";
+
+const ARC_KIND_SECTOR = 1;
+const ARC_KIND_SEGMENT = 2;
+
+export default class Arc {
+
+  arc: ArcData;
+  style: PolygonStyle;
+  selected: boolean;
+  graphics: PIXI.Graphics;
+
+  constructor(arc: ArcData, style: PolygonStyle) {
+
+    // Basic information
+    this.arc = arc;
+    this.style = style;
+    this.selected = false;
+
+    // Render information
+    this.graphics = new PIXI.Graphics();
+    this.setupGraphicsStyle();
+  }
+
+  updateArc(arc: ArcData) {
+    this.arc = arc;
+    this.setupGraphicsStyle();
+  }
+
+  updateStyle(style: PolygonStyle) {
+    this.style = style;
+    this.setupGraphicsStyle();
+  }
+
+  setSelected(selected: boolean) {
+    this.selected = selected;
+    this.setupGraphicsStyle();
+  }
+
+  setupGraphicsStyle() {
+    this.graphics.clear();
+
+    // The outline goes along the arc, then back through the center for a sector or straight
+    // along the chord for a circular segment
+    const { center, radius, start, sweep, kind } = this.arc;
+    const steps = Math.max(Math.ceil(sweep * radius / 2), 1);
+    const outline: Position[] = [];
+    for (let i = 0; i <= steps; i++) {
+      const theta = start + sweep * i / steps;
+      outline.push({ x: center.x + Math.cos(theta) * radius, y: center.y + Math.sin(theta) * radius });
+    }
+    if (kind === ARC_KIND_SECTOR) {
+      outline.push(center, outline[0]);
+    } else if (kind === ARC_KIND_SEGMENT) {
+      outline.push(outline[0]);
+    }
+
+    if (kind === ARC_KIND_SECTOR || kind === ARC_KIND_SEGMENT) {
+      this.graphics.beginFill(this.style.fill, this.style.fillAlpha);
+      this.graphics.lineStyle(0);
+      this.graphics.drawPolygon(outline.map(({ x, y }) => new PIXI.Point(x, y)));
+      this.graphics.endFill();
+    }
+
+    // The dash pattern goes on along the outline from the start of the arc
+    this.graphics.lineStyle(this.style.border.width, this.style.border.color, this.style.border.alpha);
+    let offset = 0;
+    outline.slice(1).forEach((to, i) => {
+      const from = outline[i];
+      strokeSegment(this.graphics, from, to, this.style.border.dash, offset);
+      offset += Math.sqrt((to.x - from.x) * (to.x - from.x) + (to.y - from.y) * (to.y - from.y));
+    });
+
+    if (this.selected) {
+      const selectOffset = this.style.border.width / 2 + 3;
+      this.graphics.lineStyle(1, 0xff00ff);
+      for (const r of [radius + selectOffset, radius - selectOffset]) {
+        this.graphics.moveTo(center.x + Math.cos(start) * r, center.y + Math.sin(start) * r);
+        this.graphics.arc(center.x, center.y, r, start, start + sweep);
+      }
+    }
+  }
+}
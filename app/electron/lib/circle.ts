@@ -1,5 +1,6 @@
 import { Circle as CircleData, CircleStyle } from "../native";
 import * as PIXI from "pixi.js";
+import { strokeCircle } from "./dash";
 
 export default class Circle {
 
@@ -39,9 +40,11 @@ export default class Circle {
     this.graphics.clear();
 
     this.graphics.beginFill(this.style.fill, this.style.fillAlpha);
-    this.graphics.lineStyle(this.style.border.width, this.style.border.color, this.style.border.alpha);
+    this.graphics.lineStyle(0);
     this.graphics.drawEllipse(this.circle.center.x, this.circle.center.y, this.circle.radius, this.circle.radius);
     this.graphics.endFill();
+    this.graphics.lineStyle(this.style.border.width, this.style.border.color, this.style.border.alpha);
+    strokeCircle(this.graphics, this.circle.center, this.circle.radius, this.style.border.dash);
 
     if (this.selected) {
       let offset = this.style.border.width / 2 + 3;
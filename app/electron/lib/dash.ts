@@ -0,0 +1,55 @@
+import { Dash, Position } from "../native";
+import * as PIXI from "pixi.js";
+
+// Same as `Dash` in core: the pattern starts at a point fixed in virtual space, so the dashes
+// don't slide along the shapes while panning
+
+// The `[start, end]` distances of the drawn parts along a stroke of `length`
+function splitLength(dash: Dash, length: number, offset: number): [number, number][] {
+  const period = dash.on + dash.off;
+  if (dash.on <= 0 || dash.off <= 0 || length / period > 10000) {
+    return [[0, length]];
+  }
+  const dashes: [number, number][] = [];
+  for (let start = -(((offset % period) + period) % period); start < length; start += period) {
+    const end = Math.min(start + dash.on, length);
+    if (end > 0) {
+      dashes.push([Math.max(start, 0), end]);
+    }
+  }
+  return dashes;
+}
+
+// Stroke from `from` to `to` with the current line style. `offset` is how far along the pattern
+// `from` is
+export function strokeSegment(graphics: PIXI.Graphics, from: Position, to: Position, dash: Dash | null, offset: number) {
+  if (!dash) {
+    graphics.moveTo(from.x, from.y);
+    graphics.lineTo(to.x, to.y);
+    return;
+  }
+  const length = Math.sqrt((to.x - from.x) * (to.x - from.x) + (to.y - from.y) * (to.y - from.y));
+  if (length === 0) {
+    return;
+  }
+  const dir = { x: (to.x - from.x) / length, y: (to.y - from.y) / length };
+  for (const [start, end] of splitLength(dash, length, offset)) {
+    graphics.moveTo(from.x + dir.x * start, from.y + dir.y * start);
+    graphics.lineTo(from.x + dir.x * end, from.y + dir.y * end);
+  }
+}
+
+// Stroke the circle with the current line style, the pattern starting at angle 0
+export function strokeCircle(graphics: PIXI.Graphics, center: Position, radius: number, dash: Dash | null) {
+  if (!dash) {
+    graphics.drawEllipse(center.x, center.y, radius, radius);
+    return;
+  }
+  if (radius <= 0) {
+    return;
+  }
+  for (const [start, end] of splitLength(dash, 2 * Math.PI * radius, 0)) {
+    graphics.moveTo(center.x + Math.cos(start / radius) * radius, center.y + Math.sin(start / radius) * radius);
+    graphics.arc(center.x, center.y, radius, start / radius, end / radius);
+  }
+}
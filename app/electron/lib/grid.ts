@@ -0,0 +1,61 @@
+import { Grid as GridData } from "../native";
+import * as PIXI from "pixi.js";
+
+// Same as `LABEL_FONT_SIZE` in core
+const FONT_SIZE = 14;
+
+export default class Grid {
+
+  grid: GridData | null;
+  screen: PIXI.Rectangle;
+  graphics: PIXI.Container;
+
+  constructor(screen: PIXI.Rectangle) {
+
+    // Basic information
+    this.grid = null;
+    this.screen = screen;
+
+    // Render information
+    this.graphics = new PIXI.Container();
+  }
+
+  updateGrid(grid: GridData | null) {
+    this.grid = grid;
+    this.setupGraphicsGrid();
+  }
+
+  setupGraphicsGrid() {
+    this.graphics.removeChildren().forEach((child) => child.destroy());
+    if (!this.grid) { return; }
+
+    const { width, height } = this.screen;
+    const lines = new PIXI.Graphics();
+    lines.lineStyle(0.5, 0xd9d9d9);
+    for (const x of this.grid.vertical) {
+      lines.moveTo(x, 0);
+      lines.lineTo(x, height);
+    }
+    for (const y of this.grid.horizontal) {
+      lines.moveTo(0, y);
+      lines.lineTo(width, y);
+    }
+    lines.lineStyle(1, 0x808080);
+    if (this.grid.xAxis !== null) {
+      lines.moveTo(0, this.grid.xAxis);
+      lines.lineTo(width, this.grid.xAxis);
+    }
+    if (this.grid.yAxis !== null) {
+      lines.moveTo(this.grid.yAxis, 0);
+      lines.lineTo(this.grid.yAxis, height);
+    }
+    this.graphics.addChild(lines);
+
+    for (const tick of this.grid.ticks) {
+      const text = new PIXI.Text(tick.text, { fontFamily: "sans-serif", fontSize: FONT_SIZE, fill: 0x808080 });
+      text.x = tick.position.x;
+      text.y = tick.position.y;
+      this.graphics.addChild(text);
+    }
+  }
+}
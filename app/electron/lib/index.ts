@@ -10,10 +10,19 @@ import Point from "./point";
 import Line from "./line";
 import Circle from "./circle";
 import Rectangle from "./rectangle";
+import Polygon from "./polygon";
+import Locus from "./locus";
+import Arc from "./arc";
+import Label from "./label";
+import Grid from "./grid";
 
 type RustChannel = Geopad.GeopadWorld;
 const RustChannel = Geopad.GeopadWorld;
 
+// Wheel deltas are in pixels, while the core expects lines
+const SCROLL_SPEED = 0.01;
+const PINCH_SPEED = 0.01;
+
 interface Storage<T> {
   [entity: string]: T
 }
@@ -29,11 +38,20 @@ export default class GeopadWorld {
   lineGroup: PIXI.display.Group;
   circleGroup: PIXI.display.Group;
   rectangleGroup: PIXI.display.Group;
+  labelGroup: PIXI.display.Group;
+  polygonGroup: PIXI.display.Group;
+  gridGroup: PIXI.display.Group;
 
   points: Storage<Point>;
   lines: Storage<Line>;
   circles: Storage<Circle>;
   rectangles: Storage<Rectangle>;
+  polygons: Storage<Polygon>;
+  loci: Storage<Locus>;
+  arcs: Storage<Arc>;
+  conics: Storage<Locus>; // Conics are drawn from their samples like loci
+  labels: Storage<Label>;
+  grid: Grid;
 
   constructor($canvas: JQuery<HTMLElement>) {
     this.$canvas = $canvas;
@@ -49,18 +67,24 @@ export default class GeopadWorld {
     this.app.renderer.autoResize = true;
 
     // Create the groups
-    this.rectangleGroup = new PIXI.display.Group(4, false);
+    this.rectangleGroup = new PIXI.display.Group(5, false);
+    this.labelGroup = new PIXI.display.Group(4, false);
     this.pointGroup = new PIXI.display.Group(3, false);
     this.lineGroup = new PIXI.display.Group(2, false);
     this.circleGroup = new PIXI.display.Group(1, false);
+    this.polygonGroup = new PIXI.display.Group(0, false);
+    this.gridGroup = new PIXI.display.Group(-1, false);
 
     // Setup stages
     this.app.stage = new PIXI.display.Stage();
     this.app.stage.sortableChildren = true;
     this.app.stage.addChild(new PIXI.display.Layer(this.rectangleGroup));
+    this.app.stage.addChild(new PIXI.display.Layer(this.labelGroup));
     this.app.stage.addChild(new PIXI.display.Layer(this.pointGroup));
     this.app.stage.addChild(new PIXI.display.Layer(this.lineGroup));
     this.app.stage.addChild(new PIXI.display.Layer(this.circleGroup));
+    this.app.stage.addChild(new PIXI.display.Layer(this.polygonGroup));
+    this.app.stage.addChild(new PIXI.display.Layer(this.gridGroup));
 
     // Setup canvas
     $canvas[0].appendChild(this.app.view);
@@ -68,12 +92,25 @@ export default class GeopadWorld {
     // Initialize Backend
     this.channel = new RustChannel();
     this.isShutdown = false;
+    if (this.channel.hasRecoverableSession() && confirm("The last session did not quit properly. Recover it?")) {
+      this.channel.recoverSession();
+    }
 
     // Geometry storages
     this.points = {};
     this.lines = {};
     this.circles = {};
     this.rectangles = {};
+    this.polygons = {};
+    this.loci = {};
+    this.arcs = {};
+    this.conics = {};
+    this.labels = {};
+
+    // The grid is always there, and empty when hidden
+    this.grid = new Grid(this.app.screen);
+    this.app.stage.addChild(this.grid.graphics);
+    this.grid.graphics.parentGroup = this.gridGroup;
 
     const poll = promisify(this.channel.poll.bind(this.channel));
 
@@ -106,6 +143,16 @@ export default class GeopadWorld {
       this.channel.onMouseMove(x, y, relX, relY);
     });
 
+    // The browser reports pinches as wheel events with control held
+    this.$canvas[0].addEventListener("wheel", (event) => {
+      event.preventDefault();
+      if (event.ctrlKey) {
+        this.channel.onPinch(-event.deltaY * PINCH_SPEED);
+      } else {
+        this.channel.onScroll(-event.deltaX * SCROLL_SPEED, -event.deltaY * SCROLL_SPEED);
+      }
+    }, { passive: false });
+
     $(document).keydown((event) => {
       let key = event.which;
       this.channel.onKeyDown(key);
@@ -144,6 +191,36 @@ export default class GeopadWorld {
         this.app.stage.addChild(rect.graphics);
         rect.graphics.parentGroup = this.rectangleGroup;
       } break;
+      case Geopad.EVENT_TYPE_INSERTED_POLYGON: {
+        const polygon = new Polygon(event.polygon, event.style);
+        this.polygons[event.entity] = polygon;
+        this.app.stage.addChild(polygon.graphics);
+        polygon.graphics.parentGroup = this.polygonGroup;
+      } break;
+      case Geopad.EVENT_TYPE_INSERTED_LOCUS: {
+        const locus = new Locus(event.locus, event.style);
+        this.loci[event.entity] = locus;
+        this.app.stage.addChild(locus.graphics);
+        locus.graphics.parentGroup = this.lineGroup;
+      } break;
+      case Geopad.EVENT_TYPE_INSERTED_ARC: {
+        const arc = new Arc(event.arc, event.style);
+        this.arcs[event.entity] = arc;
+        this.app.stage.addChild(arc.graphics);
+        arc.graphics.parentGroup = this.polygonGroup;
+      } break;
+      case Geopad.EVENT_TYPE_INSERTED_CONIC: {
+        const conic = new Locus(event.conic, event.style);
+        this.conics[event.entity] = conic;
+        this.app.stage.addChild(conic.graphics);
+        conic.graphics.parentGroup = this.lineGroup;
+      } break;
+      case Geopad.EVENT_TYPE_INSERTED_LABEL: {
+        const label = new Label(event.label);
+        this.labels[event.entity] = label;
+        this.app.stage.addChild(label.graphics);
+        label.graphics.parentGroup = this.labelGroup;
+      } break;
       case Geopad.EVENT_TYPE_UPDATED_POINT: {
         this.points[event.entity].updatePoint(event.point);
       } break;
@@ -156,11 +233,33 @@ export default class GeopadWorld {
       case Geopad.EVENT_TYPE_UPDATED_RECTANGLE: {
         this.rectangles[event.entity].updateRectangle(event.rect);
       } break;
+      case Geopad.EVENT_TYPE_UPDATED_POLYGON: {
+        this.polygons[event.entity].updatePolygon(event.polygon);
+      } break;
+      case Geopad.EVENT_TYPE_UPDATED_LOCUS: {
+        this.loci[event.entity].updateLocus(event.locus);
+      } break;
+      case Geopad.EVENT_TYPE_UPDATED_ARC: {
+        this.arcs[event.entity].updateArc(event.arc);
+      } break;
+      case Geopad.EVENT_TYPE_UPDATED_CONIC: {
+        this.conics[event.entity].updateLocus(event.conic);
+      } break;
+      case Geopad.EVENT_TYPE_UPDATED_LABEL: {
+        this.labels[event.entity].updateLabel(event.label);
+      } break;
       case Geopad.EVENT_TYPE_UPDATED_POINT_STYLE: {
         this.points[event.entity].updateStyle(event.style);
       } break;
       case Geopad.EVENT_TYPE_UPDATED_LINE_STYLE: {
-        this.lines[event.entity].updateStyle(event.style);
+        // Loci and conics are drawn with a line style as well
+        if (event.entity in this.lines) {
+          this.lines[event.entity].updateStyle(event.style);
+        } else if (event.entity in this.loci) {
+          this.loci[event.entity].updateStyle(event.style);
+        } else if (event.entity in this.conics) {
+          this.conics[event.entity].updateStyle(event.style);
+        }
       } break;
       case Geopad.EVENT_TYPE_UPDATED_CIRCLE_STYLE: {
         this.circles[event.entity].updateStyle(event.style);
@@ -168,6 +267,14 @@ export default class GeopadWorld {
       case Geopad.EVENT_TYPE_UPDATED_RECTANGLE_STYLE: {
         this.rectangles[event.entity].updateStyle(event.style);
       } break;
+      case Geopad.EVENT_TYPE_UPDATED_POLYGON_STYLE: {
+        // Arcs are drawn with a polygon style as well
+        if (event.entity in this.polygons) {
+          this.polygons[event.entity].updateStyle(event.style);
+        } else if (event.entity in this.arcs) {
+          this.arcs[event.entity].updateStyle(event.style);
+        }
+      } break;
       case Geopad.EVENT_TYPE_REMOVED_ENTITY: {
         if (event.entity in this.points) {
           this.app.stage.removeChild(this.points[event.entity].graphics);
@@ -181,8 +288,29 @@ export default class GeopadWorld {
         } else if (event.entity in this.rectangles) {
           this.app.stage.removeChild(this.rectangles[event.entity].graphics);
           delete this.rectangles[event.entity];
+        } else if (event.entity in this.polygons) {
+          this.app.stage.removeChild(this.polygons[event.entity].graphics);
+          delete this.polygons[event.entity];
+        } else if (event.entity in this.loci) {
+          this.app.stage.removeChild(this.loci[event.entity].graphics);
+          delete this.loci[event.entity];
+        } else if (event.entity in this.arcs) {
+          this.app.stage.removeChild(this.arcs[event.entity].graphics);
+          delete this.arcs[event.entity];
+        } else if (event.entity in this.conics) {
+          this.app.stage.removeChild(this.conics[event.entity].graphics);
+          delete this.conics[event.entity];
         }
       } break;
+      case Geopad.EVENT_TYPE_REMOVED_LABEL: {
+        if (event.entity in this.labels) {
+          this.app.stage.removeChild(this.labels[event.entity].graphics);
+          delete this.labels[event.entity];
+        }
+      } break;
+      case Geopad.EVENT_TYPE_UPDATED_GRID: {
+        this.grid.updateGrid(event.grid);
+      } break;
       case Geopad.EVENT_TYPE_SELECTED_ENTITY: {
         if (event.entity in this.points) {
           this.points[event.entity].setSelected(true);
@@ -190,6 +318,14 @@ export default class GeopadWorld {
           this.lines[event.entity].setSelected(true);
         } else if (event.entity in this.circles) {
           this.circles[event.entity].setSelected(true);
+        } else if (event.entity in this.polygons) {
+          this.polygons[event.entity].setSelected(true);
+        } else if (event.entity in this.loci) {
+          this.loci[event.entity].setSelected(true);
+        } else if (event.entity in this.arcs) {
+          this.arcs[event.entity].setSelected(true);
+        } else if (event.entity in this.conics) {
+          this.conics[event.entity].setSelected(true);
         }
       } break;
       case Geopad.EVENT_TYPE_DESELECTED_ENTITY: {
@@ -199,11 +335,23 @@ export default class GeopadWorld {
           this.lines[event.entity].setSelected(false);
         } else if (event.entity in this.circles) {
           this.circles[event.entity].setSelected(false);
+        } else if (event.entity in this.polygons) {
+          this.polygons[event.entity].setSelected(false);
+        } else if (event.entity in this.loci) {
+          this.loci[event.entity].setSelected(false);
+        } else if (event.entity in this.arcs) {
+          this.arcs[event.entity].setSelected(false);
+        } else if (event.entity in this.conics) {
+          this.conics[event.entity].setSelected(false);
         }
       }
     }
   }
 
+  renameSelected(text: string) {
+    this.channel.renameSelected(text);
+  }
+
   shutdown() {
     this.channel.shutdown();
     this.isShutdown = true;
@@ -0,0 +1,32 @@
+import { Label as LabelData } from "../native";
+import * as PIXI from "pixi.js";
+
+// Same as `LABEL_FONT_SIZE` in core
+const FONT_SIZE = 14;
+
+export default class Label {
+
+  label: LabelData;
+  graphics: PIXI.Text;
+
+  constructor(label: LabelData) {
+
+    // Basic information
+    this.label = label;
+
+    // Render information
+    this.graphics = new PIXI.Text(label.text, { fontFamily: "sans-serif", fontSize: FONT_SIZE, fill: 0x000000 });
+    this.setupGraphicsLabel();
+  }
+
+  updateLabel(label: LabelData) {
+    this.label = label;
+    this.graphics.text = label.text;
+    this.setupGraphicsLabel();
+  }
+
+  setupGraphicsLabel() {
+    this.graphics.x = this.label.rect.x;
+    this.graphics.y = this.label.rect.y;
+  }
+}
@@ -1,5 +1,6 @@
 import { Line, LineStyle } from "../native";
 import * as PIXI from "pixi.js";
+import { strokeSegment } from "./dash";
 
 export default class Point {
 
@@ -38,8 +39,7 @@ export default class Point {
   setupGraphicsStyle() {
     this.graphics.clear();
     this.graphics.lineStyle(this.style.width, this.style.color, this.style.alpha);
-    this.graphics.moveTo(this.line.from.x, this.line.from.y);
-    this.graphics.lineTo(this.line.to.x, this.line.to.y);
+    strokeSegment(this.graphics, this.line.from, this.line.to, this.style.dash, 0);
 
     if (this.selected) {
       let offset = this.style.width / 2 + 3;
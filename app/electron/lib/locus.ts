@@ -0,0 +1,66 @@
+import { Locus as LocusData, LineStyle } from "../native";
+import * as PIXI from "pixi.js";
+import { strokeSegment } from "./dash";
+
+export default class Locus {
+
+  locus: LocusData;
+  style: LineStyle;
+  selected: boolean;
+  graphics: PIXI.Graphics;
+
+  constructor(locus: LocusData, style: LineStyle) {
+
+    // Basic information
+    this.locus = locus;
+    this.style = style;
+    this.selected = false;
+
+    // Render information
+    this.graphics = new PIXI.Graphics();
+    this.setupGraphicsStyle();
+  }
+
+  updateLocus(locus: LocusData) {
+    this.locus = locus;
+    this.setupGraphicsStyle();
+  }
+
+  updateStyle(style: LineStyle) {
+    this.style = style;
+    this.setupGraphicsStyle();
+  }
+
+  setSelected(selected: boolean) {
+    this.selected = selected;
+    this.setupGraphicsStyle();
+  }
+
+  setupGraphicsStyle() {
+    this.graphics.clear();
+
+    // The dash pattern goes on along each path from its first point
+    this.graphics.lineStyle(this.style.width, this.style.color, this.style.alpha);
+    this.locus.forEach((path) => {
+      let offset = 0;
+      path.slice(1).forEach((to, i) => {
+        const from = path[i];
+        strokeSegment(this.graphics, from, to, this.style.dash, offset);
+        offset += Math.sqrt((to.x - from.x) * (to.x - from.x) + (to.y - from.y) * (to.y - from.y));
+      });
+    });
+
+    if (this.selected) {
+      this.graphics.lineStyle(this.style.width + 6, 0xff00ff, 0.5);
+      this.locus.forEach((path) => {
+        path.forEach(({ x, y }, i) => {
+          if (i === 0) {
+            this.graphics.moveTo(x, y);
+          } else {
+            this.graphics.lineTo(x, y);
+          }
+        });
+      });
+    }
+  }
+}
@@ -0,0 +1,64 @@
+import { Polygon as PolygonData, PolygonStyle } from "../native";
+import * as PIXI from "pixi.js";
+import { strokeSegment } from "./dash";
+
+export default class Polygon {
+
+  polygon: PolygonData;
+  style: PolygonStyle;
+  selected: boolean;
+  graphics: PIXI.Graphics;
+
+  constructor(polygon: PolygonData, style: PolygonStyle) {
+
+    // Basic information
+    this.polygon = polygon;
+    this.style = style;
+    this.selected = false;
+
+    // Render information
+    this.graphics = new PIXI.Graphics();
+    this.setupGraphicsStyle();
+  }
+
+  updatePolygon(polygon: PolygonData) {
+    this.polygon = polygon;
+    this.setupGraphicsStyle();
+  }
+
+  updateStyle(style: PolygonStyle) {
+    this.style = style;
+    this.setupGraphicsStyle();
+  }
+
+  setSelected(selected: boolean) {
+    this.selected = selected;
+    this.setupGraphicsStyle();
+  }
+
+  setupGraphicsStyle() {
+    this.graphics.clear();
+
+    const points = this.polygon.map(({ x, y }) => new PIXI.Point(x, y));
+    this.graphics.beginFill(this.style.fill, this.style.fillAlpha);
+    this.graphics.lineStyle(0);
+    this.graphics.drawPolygon(points);
+    this.graphics.endFill();
+
+    // The dash pattern goes on around the polygon from its first point
+    this.graphics.lineStyle(this.style.border.width, this.style.border.color, this.style.border.alpha);
+    let offset = 0;
+    this.polygon.forEach((from, i) => {
+      const to = this.polygon[(i + 1) % this.polygon.length];
+      strokeSegment(this.graphics, from, to, this.style.border.dash, offset);
+      offset += Math.sqrt((to.x - from.x) * (to.x - from.x) + (to.y - from.y) * (to.y - from.y));
+    });
+
+    if (this.selected) {
+      this.graphics.beginFill(0x000000, 0);
+      this.graphics.lineStyle(this.style.border.width + 6, 0xff00ff, 0.5);
+      this.graphics.drawPolygon(points);
+      this.graphics.endFill();
+    }
+  }
+}
@@ -13,6 +13,19 @@ export const EVENT_TYPE_UPDATED_RECTANGLE_STYLE = 12;
 export const EVENT_TYPE_REMOVED_ENTITY = 13;
 export const EVENT_TYPE_SELECTED_ENTITY = 14;
 export const EVENT_TYPE_DESELECTED_ENTITY = 15;
+export const EVENT_TYPE_INSERTED_POLYGON = 16;
+export const EVENT_TYPE_UPDATED_POLYGON = 17;
+export const EVENT_TYPE_UPDATED_POLYGON_STYLE = 18;
+export const EVENT_TYPE_INSERTED_LABEL = 19;
+export const EVENT_TYPE_UPDATED_LABEL = 20;
+export const EVENT_TYPE_REMOVED_LABEL = 21;
+export const EVENT_TYPE_UPDATED_GRID = 22;
+export const EVENT_TYPE_INSERTED_LOCUS = 23;
+export const EVENT_TYPE_UPDATED_LOCUS = 24;
+export const EVENT_TYPE_INSERTED_ARC = 25;
+export const EVENT_TYPE_UPDATED_ARC = 26;
+export const EVENT_TYPE_INSERTED_CONIC = 27;
+export const EVENT_TYPE_UPDATED_CONIC = 28;
 
 export type Position = {
   x: number,
@@ -33,10 +46,16 @@ export type Line = {
   to: Position,
 };
 
+export type Dash = {
+  on: number,
+  off: number,
+};
+
 export type LineStyle = {
   color: number,
   alpha: number,
   width: number,
+  dash: Dash | null, // Solid when null
 };
 
 export type Circle = {
@@ -63,6 +82,42 @@ export type RectangleStyle = {
   border: LineStyle,
 };
 
+export type Polygon = Position[];
+
+export type PolygonStyle = {
+  fill: number,
+  fillAlpha: number,
+  border: LineStyle,
+};
+
+export type Locus = Position[][]; // Separate runs of connected points
+
+export type Arc = {
+  center: Position,
+  radius: number,
+  start: number, // Angles in radians, going clockwise on the screen
+  sweep: number,
+  kind: number, // 0 for a plain arc, 1 for a sector and 2 for a circular segment
+};
+
+export type Label = {
+  text: string,
+  rect: Rectangle, // The area taken by the text
+};
+
+export type Tick = {
+  text: string,
+  position: Position, // The top left corner of the text
+};
+
+export type Grid = {
+  vertical: number[], // The x of every vertical line
+  horizontal: number[], // The y of every horizontal line
+  xAxis: number | null, // The y of the x axis, null when out of the screen
+  yAxis: number | null,
+  ticks: Tick[],
+};
+
 export type RenderUpdateEvent =
 | { type: 0 } // None
 | { type: 1, entity: string, point: Position, style: PointStyle }  // insert point event
@@ -79,16 +134,35 @@ export type RenderUpdateEvent =
 | { type: 12, entity: string, style: RectangleStyle }
 | { type: 13, entity: string } // remove point event
 | { type: 14, entity: string } // select point event
-| { type: 15, entity: string }; // deselect point event
+| { type: 15, entity: string } // deselect point event
+| { type: 16, entity: string, polygon: Polygon, style: PolygonStyle } // insert polygon event
+| { type: 17, entity: string, polygon: Polygon }
+| { type: 18, entity: string, style: PolygonStyle }
+| { type: 19, entity: string, label: Label } // insert label event
+| { type: 20, entity: string, label: Label }
+| { type: 21, entity: string } // remove label event
+| { type: 22, grid: Grid | null } // update grid event, null when the grid is hidden
+| { type: 23, entity: string, locus: Locus, style: LineStyle } // insert locus event
+| { type: 24, entity: string, locus: Locus }
+| { type: 25, entity: string, arc: Arc, style: PolygonStyle } // insert arc event
+| { type: 26, entity: string, arc: Arc }
+| { type: 27, entity: string, conic: Locus, style: LineStyle } // insert conic event, sampled like a locus
+| { type: 28, entity: string, conic: Locus };
 
 export class GeopadWorld {
   constructor();
   poll(callback: (event: RenderUpdateEvent) => void) : void;
   step() : void;
   onMouseMove(x: number, y: number, relX: number, relY: number) : void;
+  onScroll(x: number, y: number) : void; // In lines, positive upwards and to the left
+  onPinch(relPinch: number) : void; // Positive when zooming in
   onMouseDown() : void;
   onMouseUp() : void;
   onKeyDown(key: number) : void;
   onKeyUp(key: number) : void;
+  renameSelected(text: string) : void; // An empty text removes the label
+  hasRecoverableSession() : boolean; // When the last session did not quit properly
+  recoverSession() : void; // Bring back the construction of the last session
+  setHistoryLimits(maxEntries: number | null, maxBytes: number | null) : void; // No limit when null
   shutdown() : void;
 }
\ No newline at end of file
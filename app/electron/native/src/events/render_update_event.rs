@@ -1,5 +1,5 @@
 use specs::prelude::*;
-use core_lib::components::{screen_shapes::*, styles::*};
+use core_lib::{components::{screen_shapes::*, styles::*}, math::*, resources::GridLines};
 
 pub enum RenderUpdateEvent {
   None,
@@ -18,6 +18,19 @@ pub enum RenderUpdateEvent {
   SelectedEntity(Entity),
   DeselectedEntity(Entity),
   RemovedEntity(Entity),
+  InsertedPolygon(Entity, ScreenPolygon, PolygonStyle),
+  UpdatedPolygon(Entity, ScreenPolygon),
+  UpdatedPolygonStyle(Entity, PolygonStyle),
+  InsertedLabel(Entity, ScreenLabel),
+  UpdatedLabel(Entity, ScreenLabel),
+  RemovedLabel(Entity),
+  UpdatedGrid(Option<GridLines>, Vec<(String, Vector2)>), // The grid lines and the tick labels
+  InsertedLocus(Entity, ScreenLocus, LineStyle),
+  UpdatedLocus(Entity, ScreenLocus),
+  InsertedArc(Entity, ScreenArc, PolygonStyle),
+  UpdatedArc(Entity, ScreenArc),
+  InsertedConic(Entity, ScreenConic, LineStyle),
+  UpdatedConic(Entity, ScreenConic),
 }
 
 pub fn render_update_event_to_u32(event: &RenderUpdateEvent) -> u32 {
@@ -38,5 +51,18 @@ pub fn render_update_event_to_u32(event: &RenderUpdateEvent) -> u32 {
     RenderUpdateEvent::RemovedEntity(_) => 13,
     RenderUpdateEvent::SelectedEntity(_) => 14,
     RenderUpdateEvent::DeselectedEntity(_) => 15,
+    RenderUpdateEvent::InsertedPolygon(_, _, _) => 16,
+    RenderUpdateEvent::UpdatedPolygon(_, _) => 17,
+    RenderUpdateEvent::UpdatedPolygonStyle(_, _) => 18,
+    RenderUpdateEvent::InsertedLabel(_, _) => 19,
+    RenderUpdateEvent::UpdatedLabel(_, _) => 20,
+    RenderUpdateEvent::RemovedLabel(_) => 21,
+    RenderUpdateEvent::UpdatedGrid(_, _) => 22,
+    RenderUpdateEvent::InsertedLocus(_, _, _) => 23,
+    RenderUpdateEvent::UpdatedLocus(_, _) => 24,
+    RenderUpdateEvent::InsertedArc(_, _, _) => 25,
+    RenderUpdateEvent::UpdatedArc(_, _) => 26,
+    RenderUpdateEvent::InsertedConic(_, _, _) => 27,
+    RenderUpdateEvent::UpdatedConic(_, _) => 28,
   }
 }
\ No newline at end of file
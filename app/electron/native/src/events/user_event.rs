@@ -1,9 +1,13 @@
-use core_lib::math::*;
+use std::path::PathBuf;
+use core_lib::{math::*, resources::HistoryLimits};
 use core_ui::resources::*;
 
 pub enum UserEvent {
   Loop,
   Input(InputEvent),
+  RenameSelected(String),
+  Recover(PathBuf),
+  SetHistoryLimits(HistoryLimits),
   Shutdown,
 }
 
@@ -32,4 +36,5 @@ pub enum MotionEvent {
   MouseCursor(Vector2),
   MouseRelative(Vector2),
   MouseScroll(Vector2),
+  Pinch(f64),
 }
\ No newline at end of file
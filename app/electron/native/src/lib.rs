@@ -2,17 +2,20 @@
 extern crate core_ui;
 extern crate specs;
 
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
 use neon::context::Context;
 use neon::task::Task;
-use neon::types::{JsFunction, JsUndefined, JsNumber};
+use neon::types::{JsFunction, JsUndefined, JsNumber, JsString, JsValue};
 use neon::{declare_types, register_module};
 
 use specs::prelude::*;
 use core_lib::math::*;
+use core_lib::resources::HistoryLimits;
+use core_lib::utilities::{session_journal_dir, session_journal_path, HistoryJournal};
 use core_ui::{resources::*, setup_core_ui};
 
 pub mod events;
@@ -23,7 +26,7 @@ use events::*;
 use systems::*;
 use utilities::*;
 
-fn event_thread(user_event_rx: mpsc::Receiver<UserEvent>) -> mpsc::Receiver<RenderUpdateEvent> {
+fn event_thread(user_event_rx: mpsc::Receiver<UserEvent>, journal_path: PathBuf) -> mpsc::Receiver<RenderUpdateEvent> {
 
   // Create sending and receiving channels for the event data
   let (render_update_tx, render_update_rx) = mpsc::channel();
@@ -45,11 +48,13 @@ fn event_thread(user_event_rx: mpsc::Receiver<UserEvent>) -> mpsc::Receiver<Rend
     // Build the dispatcher
     let mut dispatcher = builder.build();
     dispatcher.setup(&mut world);
+    HistoryJournal::begin_session(&mut world, &journal_path);
 
     // Enter game loop
     while !world.fetch::<ExitState>().is_exiting() {
       dispatcher.dispatch(&mut world);
     }
+    HistoryJournal::end_session(&mut world, &journal_path);
   });
 
   render_update_rx
@@ -58,14 +63,18 @@ fn event_thread(user_event_rx: mpsc::Receiver<UserEvent>) -> mpsc::Receiver<Rend
 pub struct EventEmitter {
   emitter: Arc<Mutex<mpsc::Receiver<RenderUpdateEvent>>>,
   receiver: mpsc::Sender<UserEvent>,
+  previous_session: Option<PathBuf>, // Journal of the last session, when it did not quit properly
 }
 
 declare_types! {
   pub class JsEventEmitter for EventEmitter {
     init(_) {
       let (receiver, user_event_rx) = mpsc::channel();
-      let rx = event_thread(user_event_rx);
-      Ok(EventEmitter { emitter: Arc::new(Mutex::new(rx)), receiver })
+      let journal_dir = session_journal_dir();
+      let journal_path = session_journal_path(&journal_dir);
+      let previous_session = HistoryJournal::take_previous(&journal_dir);
+      let rx = event_thread(user_event_rx, journal_path);
+      Ok(EventEmitter { emitter: Arc::new(Mutex::new(rx)), receiver, previous_session })
     }
 
     method poll(mut cx) {
@@ -96,6 +105,25 @@ declare_types! {
       Ok(JsUndefined::new().upcast())
     }
 
+    method onScroll(mut cx) {
+      let this = cx.this();
+      let x = cx.argument::<JsNumber>(0)?.value() as f64;
+      let y = cx.argument::<JsNumber>(1)?.value() as f64;
+      cx.borrow(&this, |emitter| {
+        emitter.receiver.send(UserEvent::Input(InputEvent::Motion(MotionEvent::MouseScroll(vec2![x, y]))))
+      }).or_else(|err| cx.throw_error(&err.to_string()))?;
+      Ok(JsUndefined::new().upcast())
+    }
+
+    method onPinch(mut cx) {
+      let this = cx.this();
+      let rel_pinch = cx.argument::<JsNumber>(0)?.value() as f64;
+      cx.borrow(&this, |emitter| {
+        emitter.receiver.send(UserEvent::Input(InputEvent::Motion(MotionEvent::Pinch(rel_pinch))))
+      }).or_else(|err| cx.throw_error(&err.to_string()))?;
+      Ok(JsUndefined::new().upcast())
+    }
+
     method onMouseDown(mut cx) {
       let this = cx.this();
       cx.borrow(&this, |emitter| {
@@ -130,6 +158,37 @@ declare_types! {
       Ok(JsUndefined::new().upcast())
     }
 
+    method renameSelected(mut cx) {
+      let this = cx.this();
+      let text = cx.argument::<JsString>(0)?.value();
+      cx.borrow(&this, |emitter| emitter.receiver.send(UserEvent::RenameSelected(text))).or_else(|err| cx.throw_error(&err.to_string()))?;
+      Ok(JsUndefined::new().upcast())
+    }
+
+    method hasRecoverableSession(mut cx) {
+      let this = cx.this();
+      let has_previous = cx.borrow(&this, |emitter| emitter.previous_session.is_some());
+      Ok(cx.boolean(has_previous).upcast())
+    }
+
+    method recoverSession(mut cx) {
+      let this = cx.this();
+      cx.borrow(&this, |emitter| match &emitter.previous_session {
+        Some(path) => emitter.receiver.send(UserEvent::Recover(path.clone())),
+        None => Ok(()),
+      }).or_else(|err| cx.throw_error(&err.to_string()))?;
+      Ok(JsUndefined::new().upcast())
+    }
+
+    method setHistoryLimits(mut cx) {
+      let this = cx.this();
+      let max_entries = cx.argument::<JsValue>(0)?.downcast::<JsNumber>().ok().map(|n| n.value() as usize);
+      let max_bytes = cx.argument::<JsValue>(1)?.downcast::<JsNumber>().ok().map(|n| n.value() as usize);
+      let limits = HistoryLimits { max_entries, max_bytes };
+      cx.borrow(&this, |emitter| emitter.receiver.send(UserEvent::SetHistoryLimits(limits))).or_else(|err| cx.throw_error(&err.to_string()))?;
+      Ok(JsUndefined::new().upcast())
+    }
+
     method shutdown(mut cx) {
       let this = cx.this();
       cx.borrow(&this, |emitter| emitter.receiver.send(UserEvent::Shutdown)).or_else(|err| cx.throw_error(&err.to_string()))?;
@@ -138,7 +197,7 @@ declare_types! {
   }
 }
 
-static CONSTANTS : [(&'static str, u32); 16] = [
+static CONSTANTS : [(&'static str, u32); 29] = [
   ("EVENT_TYPE_NONE", 0),
   ("EVENT_TYPE_INSERTED_POINT", 1),
   ("EVENT_TYPE_INSERTED_LINE", 2),
@@ -155,6 +214,19 @@ static CONSTANTS : [(&'static str, u32); 16] = [
   ("EVENT_TYPE_REMOVED_ENTITY", 13),
   ("EVENT_TYPE_SELECTED_ENTITY", 14),
   ("EVENT_TYPE_DESELECTED_ENTITY", 15),
+  ("EVENT_TYPE_INSERTED_POLYGON", 16),
+  ("EVENT_TYPE_UPDATED_POLYGON", 17),
+  ("EVENT_TYPE_UPDATED_POLYGON_STYLE", 18),
+  ("EVENT_TYPE_INSERTED_LABEL", 19),
+  ("EVENT_TYPE_UPDATED_LABEL", 20),
+  ("EVENT_TYPE_REMOVED_LABEL", 21),
+  ("EVENT_TYPE_UPDATED_GRID", 22),
+  ("EVENT_TYPE_INSERTED_LOCUS", 23),
+  ("EVENT_TYPE_UPDATED_LOCUS", 24),
+  ("EVENT_TYPE_INSERTED_ARC", 25),
+  ("EVENT_TYPE_UPDATED_ARC", 26),
+  ("EVENT_TYPE_INSERTED_CONIC", 27),
+  ("EVENT_TYPE_UPDATED_CONIC", 28),
 ];
 
 register_module!(mut cx, {
@@ -1,5 +1,6 @@
 use std::time::SystemTime;
 use specs::prelude::*;
+use core_lib::{events::*, resources::History};
 use core_ui::{events::*, resources::*};
 use crate::events::*;
 
@@ -14,12 +15,18 @@ impl<'a> System<'a> for ReceiverSystem {
     Write<'a, InputState>,
     Write<'a, MouseEventChannel>,
     Write<'a, ExitEventChannel>,
+    Write<'a, CommandEventChannel>,
+    Write<'a, FileEventChannel>,
+    Write<'a, History>,
   );
 
   fn run(&mut self, (
     mut input_state,
     mut mouse_event_channel,
     mut exit_event_channel,
+    mut command_event_channel,
+    mut file_event_channel,
+    mut history,
   ): Self::SystemData) {
     input_state.reset_relative_data();
     loop {
@@ -47,6 +54,9 @@ impl<'a> System<'a> for ReceiverSystem {
               MotionEvent::MouseScroll(rel_scroll) => {
                 input_state.rel_scroll = input_state.rel_scroll + rel_scroll;
               },
+              MotionEvent::Pinch(rel_pinch) => {
+                input_state.rel_pinch += rel_pinch;
+              },
             },
             InputEvent::Button(button_state, button) => {
               let is_pressed = button_state == ButtonState::Press;
@@ -81,6 +91,18 @@ impl<'a> System<'a> for ReceiverSystem {
               }
             },
           },
+          UserEvent::RenameSelected(text) => {
+            command_event_channel.single_write(CommandEvent {
+              command: Command::Label(LabelEvent::RenameSelected(text)),
+              event_id: None,
+            });
+          },
+          UserEvent::Recover(path) => {
+            file_event_channel.single_write(FileEvent::Recover(path));
+          },
+          UserEvent::SetHistoryLimits(limits) => {
+            history.set_limits(limits);
+          },
           UserEvent::Shutdown => {
             exit_event_channel.single_write(ExitEvent);
           },
@@ -17,7 +17,14 @@ pub struct SenderSystem {
   circle_style_update_reader: Option<ReaderId<ComponentEvent>>,
   scrn_rect_update_reader: Option<ReaderId<ComponentEvent>>,
   rect_style_update_reader: Option<ReaderId<ComponentEvent>>,
+  scrn_polygon_update_reader: Option<ReaderId<ComponentEvent>>,
+  polygon_style_update_reader: Option<ReaderId<ComponentEvent>>,
+  scrn_label_update_reader: Option<ReaderId<ComponentEvent>>,
+  scrn_locus_update_reader: Option<ReaderId<ComponentEvent>>,
+  scrn_arc_update_reader: Option<ReaderId<ComponentEvent>>,
+  scrn_conic_update_reader: Option<ReaderId<ComponentEvent>>,
   marker_event_reader: Option<MarkerEventReader>,
+  grid_lines: Option<GridLines>, // The grid lines last sent
 }
 
 impl SenderSystem {
@@ -32,7 +39,14 @@ impl SenderSystem {
       circle_style_update_reader: None,
       scrn_rect_update_reader: None,
       rect_style_update_reader: None,
+      scrn_polygon_update_reader: None,
+      polygon_style_update_reader: None,
+      scrn_label_update_reader: None,
+      scrn_locus_update_reader: None,
+      scrn_arc_update_reader: None,
+      scrn_conic_update_reader: None,
       marker_event_reader: None,
+      grid_lines: None,
     }
   }
 }
@@ -41,6 +55,7 @@ impl<'a> System<'a> for SenderSystem {
   type SystemData = (
     Entities<'a>,
     Read<'a, Viewport>,
+    Read<'a, Grid>,
     Read<'a, MarkerEventChannel>,
     ReadStorage<'a, ScreenPoint>,
     ReadStorage<'a, PointStyle>,
@@ -50,6 +65,12 @@ impl<'a> System<'a> for SenderSystem {
     ReadStorage<'a, CircleStyle>,
     ReadStorage<'a, ScreenRectangle>,
     ReadStorage<'a, RectangleStyle>,
+    ReadStorage<'a, ScreenPolygon>,
+    ReadStorage<'a, PolygonStyle>,
+    ReadStorage<'a, ScreenLabel>,
+    ReadStorage<'a, ScreenLocus>,
+    ReadStorage<'a, ScreenArc>,
+    ReadStorage<'a, ScreenConic>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -62,12 +83,19 @@ impl<'a> System<'a> for SenderSystem {
     self.circle_style_update_reader = Some(WriteStorage::<CircleStyle>::fetch(&world).register_reader());
     self.scrn_rect_update_reader = Some(WriteStorage::<ScreenRectangle>::fetch(&world).register_reader());
     self.rect_style_update_reader = Some(WriteStorage::<RectangleStyle>::fetch(&world).register_reader());
+    self.scrn_polygon_update_reader = Some(WriteStorage::<ScreenPolygon>::fetch(&world).register_reader());
+    self.polygon_style_update_reader = Some(WriteStorage::<PolygonStyle>::fetch(&world).register_reader());
+    self.scrn_label_update_reader = Some(WriteStorage::<ScreenLabel>::fetch(&world).register_reader());
+    self.scrn_locus_update_reader = Some(WriteStorage::<ScreenLocus>::fetch(&world).register_reader());
+    self.scrn_arc_update_reader = Some(WriteStorage::<ScreenArc>::fetch(&world).register_reader());
+    self.scrn_conic_update_reader = Some(WriteStorage::<ScreenConic>::fetch(&world).register_reader());
     self.marker_event_reader = Some(world.fetch_mut::<MarkerEventChannel>().register_reader());
   }
 
   fn run(&mut self, (
     entities,
     viewport,
+    grid,
     marker_event_channel,
     scrn_points,
     point_styles,
@@ -77,6 +105,12 @@ impl<'a> System<'a> for SenderSystem {
     circle_styles,
     scrn_rects,
     rect_styles,
+    scrn_polygons,
+    polygon_styles,
+    scrn_labels,
+    scrn_loci,
+    scrn_arcs,
+    scrn_conics,
   ): Self::SystemData) {
 
     // First deal with geometry update
@@ -92,6 +126,18 @@ impl<'a> System<'a> for SenderSystem {
     let mut inserted_rects = BitSet::new();
     let mut modified_rects = BitSet::new();
     let mut modified_rect_styles = BitSet::new();
+    let mut inserted_polygons = BitSet::new();
+    let mut modified_polygons = BitSet::new();
+    let mut modified_polygon_styles = BitSet::new();
+    let mut inserted_labels = BitSet::new();
+    let mut modified_labels = BitSet::new();
+    let mut removed_labels = BitSet::new();
+    let mut inserted_loci = BitSet::new();
+    let mut modified_loci = BitSet::new();
+    let mut inserted_arcs = BitSet::new();
+    let mut modified_arcs = BitSet::new();
+    let mut inserted_conics = BitSet::new();
+    let mut modified_conics = BitSet::new();
     let mut removed : BitSet = BitSet::new();
 
     // Screen point updates
@@ -171,6 +217,69 @@ impl<'a> System<'a> for SenderSystem {
       }
     }
 
+    if let Some(reader) = &mut self.scrn_polygon_update_reader {
+      for event in scrn_polygons.channel().read(reader) {
+        match event {
+          ComponentEvent::Inserted(id) => { inserted_polygons.add(*id); },
+          ComponentEvent::Modified(id) => { modified_polygons.add(*id); },
+          ComponentEvent::Removed(id) => { removed.add(*id); },
+        }
+      }
+    }
+
+    if let Some(reader) = &mut self.polygon_style_update_reader {
+      for event in polygon_styles.channel().read(reader) {
+        match event {
+          ComponentEvent::Modified(id) => { modified_polygon_styles.add(*id); },
+          _ => (),
+        }
+      }
+    }
+
+    // Loci share the line styles, which are read above
+    if let Some(reader) = &mut self.scrn_locus_update_reader {
+      for event in scrn_loci.channel().read(reader) {
+        match event {
+          ComponentEvent::Inserted(id) => { inserted_loci.add(*id); },
+          ComponentEvent::Modified(id) => { modified_loci.add(*id); },
+          ComponentEvent::Removed(id) => { removed.add(*id); },
+        }
+      }
+    }
+
+    // Arcs share the polygon styles, which are read above
+    if let Some(reader) = &mut self.scrn_arc_update_reader {
+      for event in scrn_arcs.channel().read(reader) {
+        match event {
+          ComponentEvent::Inserted(id) => { inserted_arcs.add(*id); },
+          ComponentEvent::Modified(id) => { modified_arcs.add(*id); },
+          ComponentEvent::Removed(id) => { removed.add(*id); },
+        }
+      }
+    }
+
+    // Conics share the line styles as well
+    if let Some(reader) = &mut self.scrn_conic_update_reader {
+      for event in scrn_conics.channel().read(reader) {
+        match event {
+          ComponentEvent::Inserted(id) => { inserted_conics.add(*id); },
+          ComponentEvent::Modified(id) => { modified_conics.add(*id); },
+          ComponentEvent::Removed(id) => { removed.add(*id); },
+        }
+      }
+    }
+
+    // Labels are separate from the shapes, so removing a label does not remove the entity
+    if let Some(reader) = &mut self.scrn_label_update_reader {
+      for event in scrn_labels.channel().read(reader) {
+        match event {
+          ComponentEvent::Inserted(id) => { inserted_labels.add(*id); },
+          ComponentEvent::Modified(id) => { modified_labels.add(*id); },
+          ComponentEvent::Removed(id) => { removed_labels.add(*id); },
+        }
+      }
+    }
+
     // Do all the insert
     for (ent, scrn_point, point_style, _) in (&entities, &scrn_points, &point_styles, &inserted_points).join() {
       if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedPoint(ent, *scrn_point, *point_style)) { panic!(err) }
@@ -189,6 +298,26 @@ impl<'a> System<'a> for SenderSystem {
       if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedRectangle(ent, *scrn_rect, *rect_style)) { panic!(err) }
     }
 
+    for (ent, scrn_polygon, polygon_style, _) in (&entities, &scrn_polygons, &polygon_styles, &inserted_polygons).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedPolygon(ent, scrn_polygon.clone(), *polygon_style)) { panic!(err) }
+    }
+
+    for (ent, scrn_locus, line_style, _) in (&entities, &scrn_loci, &line_styles, &inserted_loci).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedLocus(ent, scrn_locus.clone(), *line_style)) { panic!(err) }
+    }
+
+    for (ent, scrn_arc, polygon_style, _) in (&entities, &scrn_arcs, &polygon_styles, &inserted_arcs).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedArc(ent, *scrn_arc, *polygon_style)) { panic!(err) }
+    }
+
+    for (ent, scrn_conic, line_style, _) in (&entities, &scrn_conics, &line_styles, &inserted_conics).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedConic(ent, scrn_conic.clone(), *line_style)) { panic!(err) }
+    }
+
+    for (ent, scrn_label, _) in (&entities, &scrn_labels, &inserted_labels).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::InsertedLabel(ent, scrn_label.clone())) { panic!(err) }
+    }
+
     // Do all the modify
     for (ent, scrn_point, _) in (&entities, &scrn_points, &modified_points).join() {
       if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedPoint(ent, *scrn_point)) { panic!(err) }
@@ -219,11 +348,45 @@ impl<'a> System<'a> for SenderSystem {
       if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedRectangleStyle(ent, *rect_style)) { panic!(err) }
     }
 
+    for (ent, scrn_polygon, _) in (&entities, &scrn_polygons, &modified_polygons).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedPolygon(ent, scrn_polygon.clone())) { panic!(err) }
+    }
+    for (ent, polygon_style, _) in (&entities, &polygon_styles, &modified_polygon_styles).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedPolygonStyle(ent, *polygon_style)) { panic!(err) }
+    }
+
+    for (ent, scrn_locus, _) in (&entities, &scrn_loci, &modified_loci).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedLocus(ent, scrn_locus.clone())) { panic!(err) }
+    }
+
+    for (ent, scrn_arc, _) in (&entities, &scrn_arcs, &modified_arcs).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedArc(ent, *scrn_arc)) { panic!(err) }
+    }
+
+    for (ent, scrn_conic, _) in (&entities, &scrn_conics, &modified_conics).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedConic(ent, scrn_conic.clone())) { panic!(err) }
+    }
+
+    for (ent, scrn_label, _) in (&entities, &scrn_labels, &modified_labels).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedLabel(ent, scrn_label.clone())) { panic!(err) }
+    }
+
     // Do all the removals
+    for (ent, _) in (&entities, &removed_labels).join() {
+      if let Err(err) = self.sender.send(RenderUpdateEvent::RemovedLabel(ent)) { panic!(err) }
+    }
     for (ent, _) in (&entities, &removed).join() {
       if let Err(err) = self.sender.send(RenderUpdateEvent::RemovedEntity(ent)) { panic!(err) }
     }
 
+    // The grid is not made of entities, so it is sent again whenever its lines change
+    let grid_lines = if grid.visible { Some(Grid::lines(&viewport)) } else { None };
+    if grid_lines != self.grid_lines {
+      let ticks = grid_lines.as_ref().map_or(vec![], |lines| lines.tick_labels(viewport.screen_size));
+      if let Err(err) = self.sender.send(RenderUpdateEvent::UpdatedGrid(grid_lines.clone(), ticks)) { panic!(err) }
+      self.grid_lines = grid_lines;
+    }
+
     // Then deal with select update
     if let Some(reader) = &mut self.marker_event_reader {
       for event in marker_event_channel.read(reader) {
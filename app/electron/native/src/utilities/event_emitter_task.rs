@@ -6,9 +6,9 @@ use neon::context::{Context, TaskContext};
 use neon::object::Object;
 use neon::result::JsResult;
 use neon::task::Task;
-use neon::types::{JsUndefined, JsValue};
+use neon::types::{JsNull, JsUndefined, JsValue};
 
-use core_lib::{math::*, utilities::*, components::styles::*};
+use core_lib::{math::*, utilities::*, components::{screen_shapes::*, styles::*}, resources::GridLines};
 use crate::events::*;
 use super::*;
 
@@ -103,14 +103,26 @@ impl Task for EventEmitterTask {
 
     macro_rules! line_style {
       ($line_style: expr) => {{
-        let LineStyle { color, width } = $line_style;
+        let LineStyle { color, width, dash } = $line_style;
         let rgb = cx.number(color_to_hex(color));
         let alpha = cx.number(color.a);
         let width = cx.number(width);
+        let dash = match dash {
+          Some(Dash { on, off }) => {
+            let dash = cx.empty_object();
+            let on = cx.number(on);
+            let off = cx.number(off);
+            dash.set(&mut cx, "on", on)?;
+            dash.set(&mut cx, "off", off)?;
+            dash.upcast::<JsValue>()
+          }
+          None => JsNull::new().upcast::<JsValue>(),
+        };
         let style = cx.empty_object();
         style.set(&mut cx, "color", rgb)?;
         style.set(&mut cx, "alpha", alpha)?;
         style.set(&mut cx, "width", width)?;
+        style.set(&mut cx, "dash", dash)?;
         style
       }};
     }
@@ -171,6 +183,123 @@ impl Task for EventEmitterTask {
       }};
     }
 
+    macro_rules! polygon {
+      ($polygon: expr) => {{
+        let ScreenPolygon { points } = $polygon;
+        let polygon = cx.empty_array();
+        for (i, point) in points.into_iter().enumerate() {
+          let point = position!(point);
+          polygon.set(&mut cx, i as u32, point)?;
+        }
+        polygon
+      }};
+    }
+
+    // Loci and conics are both drawn as separate runs of points
+    macro_rules! paths {
+      ($paths: expr) => {{
+        let paths: Vec<Vec<ScreenPosition>> = $paths;
+        let runs = cx.empty_array();
+        for (i, path) in paths.into_iter().enumerate() {
+          let points = cx.empty_array();
+          for (j, point) in path.into_iter().enumerate() {
+            let point = position!(point);
+            points.set(&mut cx, j as u32, point)?;
+          }
+          runs.set(&mut cx, i as u32, points)?;
+        }
+        runs
+      }};
+    }
+
+    macro_rules! arc {
+      ($arc: expr) => {{
+        let ScreenArc { center, radius, start, sweep, kind } = $arc;
+        let arc = cx.empty_object();
+        let center = position!(center);
+        let radius = cx.number(radius);
+        let start = cx.number(start);
+        let sweep = cx.number(sweep);
+        let kind = cx.number(match kind {
+          ArcKind::Arc => 0,
+          ArcKind::Sector => 1,
+          ArcKind::Segment => 2,
+        });
+        arc.set(&mut cx, "center", center)?;
+        arc.set(&mut cx, "radius", radius)?;
+        arc.set(&mut cx, "start", start)?;
+        arc.set(&mut cx, "sweep", sweep)?;
+        arc.set(&mut cx, "kind", kind)?;
+        arc
+      }};
+    }
+
+    macro_rules! polygon_style {
+      ($polygon_style: expr) => {{
+        let PolygonStyle { fill, border } = $polygon_style;
+        let polygon_style = cx.empty_object();
+        let fill_rgb = cx.number(color_to_hex(fill));
+        let fill_alpha = cx.number(fill.a);
+        let border = line_style!(border);
+        polygon_style.set(&mut cx, "fill", fill_rgb)?;
+        polygon_style.set(&mut cx, "fillAlpha", fill_alpha)?;
+        polygon_style.set(&mut cx, "border", border)?;
+        polygon_style
+      }};
+    }
+
+    macro_rules! label {
+      ($label: expr) => {{
+        let ScreenLabel { text, rect } = $label;
+        let label = cx.empty_object();
+        let text = cx.string(text);
+        let rect = rect!(rect);
+        label.set(&mut cx, "text", text)?;
+        label.set(&mut cx, "rect", rect)?;
+        label
+      }};
+    }
+
+    macro_rules! grid {
+      ($lines: expr, $ticks: expr) => {{
+        let GridLines { vertical, horizontal, x_axis, y_axis, .. } = $lines;
+        let grid = cx.empty_object();
+        let event_vertical = cx.empty_array();
+        for (i, line) in vertical.iter().enumerate() {
+          let position = cx.number(line.position);
+          event_vertical.set(&mut cx, i as u32, position)?;
+        }
+        let event_horizontal = cx.empty_array();
+        for (i, line) in horizontal.iter().enumerate() {
+          let position = cx.number(line.position);
+          event_horizontal.set(&mut cx, i as u32, position)?;
+        }
+        let x_axis = match x_axis {
+          Some(y) => cx.number(y).upcast::<JsValue>(),
+          None => JsNull::new().upcast::<JsValue>(),
+        };
+        let y_axis = match y_axis {
+          Some(x) => cx.number(x).upcast::<JsValue>(),
+          None => JsNull::new().upcast::<JsValue>(),
+        };
+        let event_ticks = cx.empty_array();
+        for (i, (text, position)) in $ticks.into_iter().enumerate() {
+          let tick = cx.empty_object();
+          let text = cx.string(text);
+          let position = position!(ScreenPosition(position));
+          tick.set(&mut cx, "text", text)?;
+          tick.set(&mut cx, "position", position)?;
+          event_ticks.set(&mut cx, i as u32, tick)?;
+        }
+        grid.set(&mut cx, "vertical", event_vertical)?;
+        grid.set(&mut cx, "horizontal", event_horizontal)?;
+        grid.set(&mut cx, "xAxis", x_axis)?;
+        grid.set(&mut cx, "yAxis", y_axis)?;
+        grid.set(&mut cx, "ticks", event_ticks)?;
+        grid
+      }};
+    }
+
     match event {
       RenderUpdateEvent::None => (),
       RenderUpdateEvent::InsertedPoint(ent, scrn_point, point_style) => {
@@ -205,6 +334,38 @@ impl Task for EventEmitterTask {
         let style = rect_style!(rect_style);
         o.set(&mut cx, "style", style)?;
       },
+      RenderUpdateEvent::InsertedPolygon(ent, scrn_polygon, polygon_style) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let polygon = polygon!(scrn_polygon);
+        o.set(&mut cx, "polygon", polygon)?;
+        let style = polygon_style!(polygon_style);
+        o.set(&mut cx, "style", style)?;
+      },
+      RenderUpdateEvent::InsertedLocus(ent, scrn_locus, line_style) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let locus = paths!(scrn_locus.paths);
+        o.set(&mut cx, "locus", locus)?;
+        let style = line_style!(line_style);
+        o.set(&mut cx, "style", style)?;
+      },
+      RenderUpdateEvent::InsertedArc(ent, scrn_arc, polygon_style) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let arc = arc!(scrn_arc);
+        o.set(&mut cx, "arc", arc)?;
+        let style = polygon_style!(polygon_style);
+        o.set(&mut cx, "style", style)?;
+      },
+      RenderUpdateEvent::InsertedConic(ent, scrn_conic, line_style) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let conic = paths!(scrn_conic.paths);
+        o.set(&mut cx, "conic", conic)?;
+        let style = line_style!(line_style);
+        o.set(&mut cx, "style", style)?;
+      },
       RenderUpdateEvent::UpdatedPoint(ent, scrn_point) => {
         let entity = entity!(ent);
         o.set(&mut cx, "entity", entity)?;
@@ -229,6 +390,30 @@ impl Task for EventEmitterTask {
         let rect = rect!(scrn_rect);
         o.set(&mut cx, "rect", rect)?;
       },
+      RenderUpdateEvent::UpdatedPolygon(ent, scrn_polygon) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let polygon = polygon!(scrn_polygon);
+        o.set(&mut cx, "polygon", polygon)?;
+      },
+      RenderUpdateEvent::UpdatedLocus(ent, scrn_locus) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let locus = paths!(scrn_locus.paths);
+        o.set(&mut cx, "locus", locus)?;
+      },
+      RenderUpdateEvent::UpdatedArc(ent, scrn_arc) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let arc = arc!(scrn_arc);
+        o.set(&mut cx, "arc", arc)?;
+      },
+      RenderUpdateEvent::UpdatedConic(ent, scrn_conic) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let conic = paths!(scrn_conic.paths);
+        o.set(&mut cx, "conic", conic)?;
+      },
       RenderUpdateEvent::UpdatedPointStyle(ent, point_style) => {
         let entity = entity!(ent);
         o.set(&mut cx, "entity", entity)?;
@@ -253,6 +438,29 @@ impl Task for EventEmitterTask {
         let style = rect_style!(rect_style);
         o.set(&mut cx, "style", style)?;
       },
+      RenderUpdateEvent::UpdatedPolygonStyle(ent, polygon_style) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let style = polygon_style!(polygon_style);
+        o.set(&mut cx, "style", style)?;
+      },
+      RenderUpdateEvent::InsertedLabel(ent, scrn_label) | RenderUpdateEvent::UpdatedLabel(ent, scrn_label) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+        let label = label!(scrn_label);
+        o.set(&mut cx, "label", label)?;
+      },
+      RenderUpdateEvent::RemovedLabel(ent) => {
+        let entity = entity!(ent);
+        o.set(&mut cx, "entity", entity)?;
+      },
+      RenderUpdateEvent::UpdatedGrid(lines, ticks) => {
+        let grid = match lines {
+          Some(lines) => grid!(lines, ticks).upcast::<JsValue>(),
+          None => JsNull::new().upcast::<JsValue>(),
+        };
+        o.set(&mut cx, "grid", grid)?;
+      },
       RenderUpdateEvent::SelectedEntity(ent) => {
         let entity = entity!(ent);
         o.set(&mut cx, "entity", entity)?;
@@ -10,9 +10,18 @@ mod window_system;
 use piston_window::*;
 pub use window_system::WindowSystem as PistonWindowSystem;
 
+/// Fonts tried in order for drawing the labels. Labels are not drawn when none of them exists
+static LABEL_FONTS: [&str; 4] = [
+  "/System/Library/Fonts/Supplemental/Arial.ttf",
+  "/Library/Fonts/Arial.ttf",
+  "C:\\Windows\\Fonts\\arial.ttf",
+  "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+];
+
 pub fn new_piston_window() -> PistonWindowSystem {
-  let window: PistonWindow = WindowSettings::new("Geometry Sketchpad", core_lib::resources::WINDOW_SIZE)
+  let mut window: PistonWindow = WindowSettings::new("Geometry Sketchpad", core_lib::resources::WINDOW_SIZE)
     .build()
     .unwrap();
-  window_system::WindowSystem { window }
+  let glyphs = LABEL_FONTS.iter().find_map(|path| window.load_font(path).ok());
+  window_system::WindowSystem { window, glyphs }
 }
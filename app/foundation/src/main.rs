@@ -6,9 +6,11 @@ extern crate specs;
 // Foundation library providing "new_piston_window"
 extern crate geopad_foundation;
 
+use core_lib::{events::*, utilities::*};
 use core_ui::{resources::*, setup_core_ui};
 use geopad_foundation::new_piston_window;
 use specs::prelude::*;
+use std::io::{self, BufRead, Write};
 
 fn main() {
   let mut world = World::new();
@@ -23,7 +25,30 @@ fn main() {
   // Build the dispatcher
   let mut dispatcher = builder.build();
   dispatcher.setup(&mut world);
+
+  // Journal the session, and offer to bring back the one that did not quit properly
+  let journal_dir = session_journal_dir();
+  let journal_path = session_journal_path(&journal_dir);
+  let previous = HistoryJournal::take_previous(&journal_dir);
+  HistoryJournal::begin_session(&mut world, &journal_path);
+  if let Some(previous) = previous {
+    if ask_to_recover() {
+      world.fetch_mut::<FileEventChannel>().single_write(FileEvent::Recover(previous));
+    }
+  }
+
   while !world.fetch::<ExitState>().is_exiting() {
     dispatcher.dispatch(&mut world);
   }
+  HistoryJournal::end_session(&mut world, &journal_path);
+}
+
+fn ask_to_recover() -> bool {
+  print!("The last session did not quit properly. Recover it? [y/N] ");
+  io::stdout().flush().ok();
+  let mut answer = String::new();
+  match io::stdin().lock().read_line(&mut answer) {
+    Ok(_) => answer.trim().eq_ignore_ascii_case("y"),
+    Err(_) => false,
+  }
 }
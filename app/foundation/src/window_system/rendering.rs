@@ -1,11 +1,12 @@
 use core_lib::{
-  components::{markers::*, screen_shapes::*, styles::*},
+  components::{labels::*, markers::*, screen_shapes::*, styles::*},
   math::*,
-  resources::Viewport,
+  resources::{Grid, Viewport},
   utilities::*,
 };
 use piston_window::{
-  circle_arc, clear, ellipse, line_from_to, rectangle, Context, Event as PistonEvent, G2d, PistonWindow,
+  circle_arc, clear, ellipse, line_from_to, polygon, rectangle, text::Text, Context, Event as PistonEvent, G2d, Glyphs,
+  PistonWindow, Transformed,
 };
 use specs::prelude::*;
 
@@ -13,29 +14,58 @@ pub fn render<'a>(
   window: &mut PistonWindow,
   event: &PistonEvent,
   viewport: &Viewport,
+  grid: &Grid,
   scrn_points: &ReadStorage<'a, ScreenPoint>,
   scrn_lines: &ReadStorage<'a, ScreenLine>,
   scrn_circles: &ReadStorage<'a, ScreenCircle>,
+  scrn_polygons: &ReadStorage<'a, ScreenPolygon>,
+  scrn_arcs: &ReadStorage<'a, ScreenArc>,
+  scrn_loci: &ReadStorage<'a, ScreenLocus>,
+  scrn_conics: &ReadStorage<'a, ScreenConic>,
+  scrn_labels: &ReadStorage<'a, ScreenLabel>,
   scrn_rects: &ReadStorage<'a, ScreenRectangle>,
   point_styles: &ReadStorage<'a, PointStyle>,
   line_styles: &ReadStorage<'a, LineStyle>,
   circle_styles: &ReadStorage<'a, CircleStyle>,
+  polygon_styles: &ReadStorage<'a, PolygonStyle>,
   rect_styles: &ReadStorage<'a, RectangleStyle>,
   selecteds: &ReadStorage<'a, Selected>,
   hiddens: &ReadStorage<'a, Hidden>,
+  mut glyphs: Option<&mut Glyphs>,
 ) {
-  window.draw_2d(event, |context, graphics, _device| {
+  window.draw_2d(event, |context, graphics, device| {
     // Clean the screen first
     clear(Color::white().into(), graphics);
 
     // NOTE: The later we draw, the higher the shape will be in the layers
     // i.e. The later we draw, the shape will be more on top of other shapes
-    // Therefore we first draw circle, then line, then point, then rectangles
-    // As circle should be at the bottom, line next, and point should be on the top
+    // Therefore we first draw polygon, then circle, then line, then point, then rectangles
+    // As polygon should be at the bottom, circle and line next, and point should be on the top
     // Note that currently we only have select rectangles so we draw rectangles on the most
     // top.
 
-    // First draw the circles
+    // The grid goes below everything else
+    if grid.visible {
+      render_grid(viewport, glyphs.as_deref_mut(), context, graphics);
+    }
+
+    // First draw the polygons
+    for (polygon, style, _, _) in (scrn_polygons, polygon_styles, !selecteds, !hiddens).join() {
+      render_polygon(polygon, style, false, context, graphics);
+    }
+    for (polygon, style, _, _) in (scrn_polygons, polygon_styles, selecteds, !hiddens).join() {
+      render_polygon(polygon, style, true, context, graphics);
+    }
+
+    // Arcs, sectors and circular segments are filled like polygons
+    for (arc, style, _, _) in (scrn_arcs, polygon_styles, !selecteds, !hiddens).join() {
+      render_arc(arc, style, false, context, graphics);
+    }
+    for (arc, style, _, _) in (scrn_arcs, polygon_styles, selecteds, !hiddens).join() {
+      render_arc(arc, style, true, context, graphics);
+    }
+
+    // Then the circles
     for (circle, style, _, _) in (scrn_circles, circle_styles, !selecteds, !hiddens).join() {
       render_circle(circle, style, false, context, graphics);
     }
@@ -51,6 +81,20 @@ pub fn render<'a>(
       render_line(line, style, true, viewport, context, graphics);
     }
 
+    // Loci and conics are drawn with the lines
+    for (locus, style, _, _) in (scrn_loci, line_styles, !selecteds, !hiddens).join() {
+      render_paths(&locus.paths, style, false, context, graphics);
+    }
+    for (locus, style, _, _) in (scrn_loci, line_styles, selecteds, !hiddens).join() {
+      render_paths(&locus.paths, style, true, context, graphics);
+    }
+    for (conic, style, _, _) in (scrn_conics, line_styles, !selecteds, !hiddens).join() {
+      render_paths(&conic.paths, style, false, context, graphics);
+    }
+    for (conic, style, _, _) in (scrn_conics, line_styles, selecteds, !hiddens).join() {
+      render_paths(&conic.paths, style, true, context, graphics);
+    }
+
     // Lastly, draw the points
     for (point, style, _, _) in (scrn_points, point_styles, !selecteds, !hiddens).join() {
       render_point(point, style, false, context, graphics);
@@ -59,6 +103,14 @@ pub fn render<'a>(
       render_point(point, style, true, context, graphics);
     }
 
+    // The labels go above all the shapes. They are skipped when no font could be loaded
+    if let Some(glyphs) = glyphs {
+      for (label, _) in (scrn_labels, !hiddens).join() {
+        render_label(label, glyphs, context, graphics);
+      }
+      glyphs.factory.encoder.flush(device);
+    }
+
     // Additionally, draw rectangles
     for (rect, style) in (scrn_rects, rect_styles).join() {
       render_rectangle(rect, style, context, graphics);
@@ -105,6 +157,69 @@ fn render_point(
   );
 }
 
+fn render_grid(viewport: &Viewport, glyphs: Option<&mut Glyphs>, context: Context, graphics: &mut G2d) {
+  let lines = Grid::lines(viewport);
+  let Vector2 { x: width, y: height } = viewport.screen_size;
+  for line in &lines.vertical {
+    line_from_to(
+      Color::light_gray().into(),
+      0.5,
+      [line.position, 0.0],
+      [line.position, height],
+      context.transform,
+      graphics,
+    );
+  }
+  for line in &lines.horizontal {
+    line_from_to(
+      Color::light_gray().into(),
+      0.5,
+      [0.0, line.position],
+      [width, line.position],
+      context.transform,
+      graphics,
+    );
+  }
+  if let Some(y) = lines.x_axis {
+    line_from_to(
+      Color::gray().into(),
+      1.0,
+      [0.0, y],
+      [width, y],
+      context.transform,
+      graphics,
+    );
+  }
+  if let Some(x) = lines.y_axis {
+    line_from_to(
+      Color::gray().into(),
+      1.0,
+      [x, 0.0],
+      [x, height],
+      context.transform,
+      graphics,
+    );
+  }
+  if let Some(glyphs) = glyphs {
+    let text = Text::new_color(Color::gray().into(), LABEL_FONT_SIZE as u32);
+    for (tick, Vector2 { x, y }) in lines.tick_labels(viewport.screen_size) {
+      let transform = context.transform.trans(x, y + LABEL_FONT_SIZE);
+      if let Err(err) = text.draw(&tick, glyphs, &context.draw_state, transform, graphics) {
+        eprintln!("[rendering] Cannot draw grid tick {}: {:?}", tick, err);
+      }
+    }
+  }
+}
+
+fn render_label(label: &ScreenLabel, glyphs: &mut Glyphs, context: Context, graphics: &mut G2d) {
+  // Text is drawn from its baseline, which is at the bottom of the first line
+  let transform = context.transform.trans(label.rect.x, label.rect.y + LABEL_FONT_SIZE);
+  let text = Text::new_color(Color::black().into(), LABEL_FONT_SIZE as u32);
+  if let Err(err) = text.draw(&label.text, glyphs, &context.draw_state, transform, graphics) {
+    eprintln!("[rendering] Cannot draw label {}: {:?}", label.text, err);
+  }
+}
+
 fn render_line(
   l: &ScreenLine,
   style: &LineStyle,
@@ -113,8 +228,16 @@ fn render_line(
   context: Context,
   graphics: &mut G2d,
 ) {
-  if let Some((from, to)) = Into::<Line>::into(*l).intersect(viewport.screen_aabb()) {
-    line_from_to(style.color.into(), style.width, from, to, context.transform, graphics);
+  let line: Line = (*l).into();
+  if let Some((from, to)) = line.intersect(viewport.screen_aabb()) {
+    match style.dash {
+      Some(dash) => {
+        for (from, to) in dash.split_line(line, from, to) {
+          line_from_to(style.color.into(), style.width, from, to, context.transform, graphics);
+        }
+      }
+      None => line_from_to(style.color.into(), style.width, from, to, context.transform, graphics),
+    }
     if selected {
       let Vector2 { x: dx, y: dy } = (to - from).normalized();
       let perp_dir = vec2![-dy, dx] * (style.width / 2.0 + 3.0);
@@ -149,15 +272,21 @@ fn render_circle(
   let radius: f64 = Into::<f64>::into(*radius);
   let rect = [center.x - radius, center.y - radius, radius * 2.0, radius * 2.0];
   ellipse(style.fill.into(), rect, context.transform, graphics);
-  circle_arc(
-    style.border.color.into(),
-    style.border.width,
-    0.0,
-    std::f64::consts::PI * 1.999999999,
-    rect,
-    context.transform,
-    graphics,
-  );
+  let arcs = match style.border.dash {
+    Some(dash) => dash.split_circle(radius),
+    None => vec![(0.0, std::f64::consts::PI * 1.999999999)],
+  };
+  for (start, end) in arcs {
+    circle_arc(
+      style.border.color.into(),
+      style.border.width,
+      start,
+      end,
+      rect,
+      context.transform,
+      graphics,
+    );
+  }
   if selected {
     let inner_radius = radius - style.border.width / 2.0 - 3.0;
     let outer_radius = radius + style.border.width / 2.0 + 3.0;
@@ -192,6 +321,162 @@ fn render_circle(
   }
 }
 
+fn render_polygon(
+  ScreenPolygon { points }: &ScreenPolygon,
+  style: &PolygonStyle,
+  selected: bool,
+  context: Context,
+  graphics: &mut G2d,
+) {
+  if points.is_empty() {
+    return;
+  }
+  let points: Vec<Vector2> = points.iter().map(|p| (*p).into()).collect();
+
+  // Piston only fills convex polygons, so fill it as a fan of triangles around the centroid.
+  // Polygons made from a selection are ordered around their centroid, so this is exact for them
+  let centroid = points.iter().fold(Vector2::zero(), |sum, p| sum + *p) / points.len() as f64;
+  for (i, from) in points.iter().enumerate() {
+    let to = points[(i + 1) % points.len()];
+    polygon(
+      style.fill.into(),
+      &[centroid.into(), (*from).into(), to.into()],
+      context.transform,
+      graphics,
+    );
+  }
+  // The dash pattern goes on around the polygon from its first point
+  let mut offset = 0.0;
+  for (i, from) in points.iter().enumerate() {
+    let to = points[(i + 1) % points.len()];
+    stroke_segment(&style.border, *from, to, offset, context, graphics);
+    offset += (to - *from).magnitude();
+    if selected && *from != to {
+      let Vector2 { x: dx, y: dy } = (to - *from).normalized();
+      let perp_dir = vec2![-dy, dx] * (style.border.width / 2.0 + 3.0);
+      line_from_to(
+        Color::magenta().into(),
+        0.5,
+        *from - perp_dir,
+        to - perp_dir,
+        context.transform,
+        graphics,
+      );
+      line_from_to(
+        Color::magenta().into(),
+        0.5,
+        *from + perp_dir,
+        to + perp_dir,
+        context.transform,
+        graphics,
+      );
+    }
+  }
+}
+
+fn render_arc(arc: &ScreenArc, style: &PolygonStyle, selected: bool, context: Context, graphics: &mut G2d) {
+  let arc: Arc = (*arc).into();
+  let steps = (arc.sweep * arc.radius / 2.0).ceil().max(1.0) as usize;
+  let points: Vec<Vector2> = (0..=steps)
+    .map(|i| arc.point_at_fraction(i as f64 / steps as f64))
+    .collect();
+
+  // A sector is fanned out from its center. A circular segment is convex, so it's fanned out
+  // from the start of the arc
+  let pivot = match arc.kind {
+    ArcKind::Arc => None,
+    ArcKind::Sector => Some(arc.center),
+    ArcKind::Segment => Some(arc.start_point()),
+  };
+  if let Some(pivot) = pivot {
+    for pair in points.windows(2) {
+      polygon(
+        style.fill.into(),
+        &[pivot.into(), pair[0].into(), pair[1].into()],
+        context.transform,
+        graphics,
+      );
+    }
+  }
+
+  // The outline goes along the arc, then back through the center or along the chord
+  let mut outline = points;
+  match arc.kind {
+    ArcKind::Arc => (),
+    ArcKind::Sector => outline.extend(vec![arc.center, arc.start_point()]),
+    ArcKind::Segment => outline.push(arc.start_point()),
+  }
+  let mut offset = 0.0;
+  for pair in outline.windows(2) {
+    stroke_segment(&style.border, pair[0], pair[1], offset, context, graphics);
+    offset += (pair[1] - pair[0]).magnitude();
+  }
+
+  if selected {
+    for radius in &[
+      arc.radius - style.border.width / 2.0 - 3.0,
+      arc.radius + style.border.width / 2.0 + 3.0,
+    ] {
+      circle_arc(
+        Color::magenta().into(),
+        0.5,
+        arc.start,
+        arc.start + arc.sweep,
+        [arc.center.x - radius, arc.center.y - radius, radius * 2.0, radius * 2.0],
+        context.transform,
+        graphics,
+      );
+    }
+  }
+}
+
+fn render_paths(paths: &[Vec<ScreenPosition>], style: &LineStyle, selected: bool, context: Context, graphics: &mut G2d) {
+  for path in paths {
+    let points: Vec<Vector2> = path.iter().map(|p| (*p).into()).collect();
+
+    // The dash pattern goes on along each path from its first point
+    let mut offset = 0.0;
+    for pair in points.windows(2) {
+      let (from, to) = (pair[0], pair[1]);
+      stroke_segment(style, from, to, offset, context, graphics);
+      offset += (to - from).magnitude();
+      if selected && from != to {
+        let Vector2 { x: dx, y: dy } = (to - from).normalized();
+        let perp_dir = vec2![-dy, dx] * (style.width / 2.0 + 3.0);
+        line_from_to(
+          Color::magenta().into(),
+          0.5,
+          from - perp_dir,
+          to - perp_dir,
+          context.transform,
+          graphics,
+        );
+        line_from_to(
+          Color::magenta().into(),
+          0.5,
+          from + perp_dir,
+          to + perp_dir,
+          context.transform,
+          graphics,
+        );
+      }
+    }
+  }
+}
+
+/// Stroke from `from` to `to`, dashed when the style has a dash pattern. `offset` is how far
+/// along the pattern `from` is
+fn stroke_segment(style: &LineStyle, from: Vector2, to: Vector2, offset: f64, context: Context, graphics: &mut G2d) {
+  match style.dash {
+    Some(dash) => {
+      for (from, to) in dash.split_segment(from, to, offset) {
+        line_from_to(style.color.into(), style.width, from, to, context.transform, graphics);
+      }
+    }
+    None => line_from_to(style.color.into(), style.width, from, to, context.transform, graphics),
+  }
+}
+
 fn render_rectangle(rect: &AABB, style: &RectangleStyle, context: Context, graphics: &mut G2d) {
   line_from_to(
     style.border.color.into(),
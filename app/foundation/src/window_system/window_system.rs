@@ -1,7 +1,7 @@
 use core_lib::{
   components::{markers::*, screen_shapes::*, styles::*},
   events::*,
-  resources::Viewport,
+  resources::{Grid, Viewport},
 };
 use core_ui::{events::*, resources::*};
 use piston_window::{Event as PistonEvent, *};
@@ -11,12 +11,14 @@ use super::{event_handling::*, rendering::*};
 
 pub struct WindowSystem {
   pub window: PistonWindow,
+  pub glyphs: Option<Glyphs>,
 }
 
 impl<'a> System<'a> for WindowSystem {
   type SystemData = (
     // Resources
     Read<'a, Viewport>,
+    Read<'a, Grid>,
     Write<'a, ExitEventChannel>,
     Write<'a, MouseEventChannel>,
     Write<'a, ViewportEventChannel>,
@@ -26,10 +28,16 @@ impl<'a> System<'a> for WindowSystem {
     ReadStorage<'a, ScreenPoint>,
     ReadStorage<'a, ScreenLine>,
     ReadStorage<'a, ScreenCircle>,
+    ReadStorage<'a, ScreenPolygon>,
+    ReadStorage<'a, ScreenArc>,
+    ReadStorage<'a, ScreenLocus>,
+    ReadStorage<'a, ScreenConic>,
+    ReadStorage<'a, ScreenLabel>,
     ReadStorage<'a, ScreenRectangle>,
     ReadStorage<'a, PointStyle>,
     ReadStorage<'a, LineStyle>,
     ReadStorage<'a, CircleStyle>,
+    ReadStorage<'a, PolygonStyle>,
     ReadStorage<'a, RectangleStyle>,
     ReadStorage<'a, Selected>,
     ReadStorage<'a, Hidden>,
@@ -39,6 +47,7 @@ impl<'a> System<'a> for WindowSystem {
     &mut self,
     (
       viewport,
+      grid,
       mut exit_event_channel,
       mut mouse_event_channel,
       mut viewport_event_channel,
@@ -47,10 +56,16 @@ impl<'a> System<'a> for WindowSystem {
       scrn_points,
       scrn_lines,
       scrn_circles,
+      scrn_polygons,
+      scrn_arcs,
+      scrn_loci,
+      scrn_conics,
+      scrn_labels,
       scrn_rects,
       point_styles,
       line_styles,
       circle_styles,
+      polygon_styles,
       rect_styles,
       selecteds,
       hiddens,
@@ -73,16 +88,24 @@ impl<'a> System<'a> for WindowSystem {
                 &mut self.window,
                 &event,
                 &*viewport,
+                &*grid,
                 &scrn_points,
                 &scrn_lines,
                 &scrn_circles,
+                &scrn_polygons,
+                &scrn_arcs,
+                &scrn_loci,
+                &scrn_conics,
+                &scrn_labels,
                 &scrn_rects,
                 &point_styles,
                 &line_styles,
                 &circle_styles,
+                &polygon_styles,
                 &rect_styles,
                 &selecteds,
                 &hiddens,
+                self.glyphs.as_mut(),
               );
               break;
             }
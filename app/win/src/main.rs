@@ -21,6 +21,7 @@ mod win_gui;
 
 #[cfg(target_os = "windows")]
 fn main() {
+  use core_lib::{events::*, utilities::*};
   use core_ui::{resources::*, setup_core_ui};
   use geopad_foundation::new_piston_window;
   use specs::prelude::*;
@@ -38,9 +39,42 @@ fn main() {
   // Build the dispatcher
   let mut dispatcher = builder.build();
   dispatcher.setup(&mut world);
+
+  // Journal the session, and offer to bring back the one that did not quit properly
+  let journal_dir = session_journal_dir();
+  let journal_path = session_journal_path(&journal_dir);
+  let previous = HistoryJournal::take_previous(&journal_dir);
+  HistoryJournal::begin_session(&mut world, &journal_path);
+  if let Some(previous) = previous {
+    if ask_to_recover() {
+      world.fetch_mut::<FileEventChannel>().single_write(FileEvent::Recover(previous));
+    }
+  }
+
   while !world.fetch::<ExitState>().is_exiting() {
     dispatcher.dispatch(&mut world);
   }
+  HistoryJournal::end_session(&mut world, &journal_path);
+}
+
+#[cfg(target_os = "windows")]
+fn ask_to_recover() -> bool {
+  use std::ffi::OsStr;
+  use std::os::windows::ffi::OsStrExt;
+  use user32::MessageBoxW;
+  use winapi::{IDYES, MB_ICONQUESTION, MB_YESNO};
+
+  let wide = |s: &str| OsStr::new(s).encode_wide().chain(Some(0)).collect::<Vec<_>>();
+  let text = wide("The last session did not quit properly. Recover it?");
+  let caption = wide("Geometry Sketchpad");
+  unsafe {
+    MessageBoxW(
+      std::ptr::null_mut(),
+      text.as_ptr(),
+      caption.as_ptr(),
+      MB_YESNO | MB_ICONQUESTION,
+    ) == IDYES
+  }
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -1,16 +1,18 @@
 use crate::core_lib::events::*;
-use crate::core_lib::math::LineType;
+use crate::core_lib::math::{ArcKind, LineType};
+use crate::core_lib::utilities::GEOPAD_FILE_EXTENSION;
 use crate::core_ui::events::{ExitEvent, ExitEventChannel, ToolChangeEvent, ToolChangeEventChannel};
 use crate::core_ui::resources::{InputState, Tool};
 use nwg::{Event, Ui};
 use shrev::{EventChannel, ReaderId};
 use specs::prelude::*;
-use std::sync::Mutex;
+use std::{path::PathBuf, sync::Mutex};
 
 enum GuiSystemAction {
   ToolChange(Tool),
   History(HistoryEvent),
   Command(CommandEvent),
+  File(FileEvent),
   Resize,
   Exit,
 }
@@ -38,6 +40,19 @@ pub enum AppId {
   MenuCreateMid,
   MenuCreateParallel,
   MenuCreatePerpendicular,
+  MenuCreatePolygon,
+  MenuCreateArc,
+  MenuCreateSector,
+  MenuCreateSegment,
+  MenuCreateConic,
+  MenuCreateCentroid,
+  MenuCreateCircumcenter,
+  MenuCreateOrthocenter,
+  MenuCreateIncenter,
+  MenuCreateNinePointCenter,
+  MenuCreateIncircle,
+  MenuCreateExcircles,
+  MenuCreateNinePointCircle,
   MenuDisplay,
   MenuDisplayHide,
   MenuDisplayUnhiddenAll,
@@ -45,6 +60,7 @@ pub enum AppId {
   MenuHelpIssue,
   MenuHelpAbout,
   OpenFileDialog,
+  SaveFileDialog,
 
   // tool buttons
   SelectToolBtn,
@@ -57,6 +73,7 @@ pub enum AppId {
 
   // events
   FileOpenEvent,
+  FileSaveEvent,
   FileExitEvent,
   EditUndoEvent,
   EditRedoEvent,
@@ -65,6 +82,19 @@ pub enum AppId {
   CreateMidEvent,
   CreateParallelEvent,
   CreatePerpendicularEvent,
+  CreatePolygonEvent,
+  CreateArcEvent,
+  CreateSectorEvent,
+  CreateSegmentEvent,
+  CreateConicEvent,
+  CreateCentroidEvent,
+  CreateCircumcenterEvent,
+  CreateOrthocenterEvent,
+  CreateIncenterEvent,
+  CreateNinePointCenterEvent,
+  CreateIncircleEvent,
+  CreateExcirclesEvent,
+  CreateNinePointCircleEvent,
   DisplayHideEvent,
   DisplayUnhiddenAllEvent,
   HelpIssueEvent,
@@ -102,13 +132,11 @@ nwg_template!(
     )),
         (MenuFileOpen, nwg_menuitem!(
               parent=MenuFile;
-              text="&Open...\tCtrl+O";
-              disabled=true
+              text="&Open...\tCtrl+O"
         )),
         (MenuFileSave, nwg_menuitem!(
               parent=MenuFile;
-              text="&Save\tCtrl+S";
-              disabled=true
+              text="&Save\tCtrl+S"
         )),
         (MenuFileExit, nwg_menuitem!(
               parent=MenuFile;
@@ -156,6 +184,58 @@ nwg_template!(
               parent=MenuCreate;
               text="&Perpendicular Line\tCtrl+Shift+\\"
         )),
+        (MenuCreatePolygon, nwg_menuitem!(
+              parent=MenuCreate;
+              text="P&olygon\tCtrl+G"
+        )),
+        (MenuCreateArc, nwg_menuitem!(
+              parent=MenuCreate;
+              text="&Arc\tCtrl+F"
+        )),
+        (MenuCreateSector, nwg_menuitem!(
+              parent=MenuCreate;
+              text="&Sector\tCtrl+Shift+F"
+        )),
+        (MenuCreateSegment, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Circular Se&gment\tCtrl+Y"
+        )),
+        (MenuCreateConic, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Co&nic\tCtrl+N"
+        )),
+        (MenuCreateCentroid, nwg_menuitem!(
+              parent=MenuCreate;
+              text="&Centroid\tCtrl+1"
+        )),
+        (MenuCreateCircumcenter, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Circu&mcenter\tCtrl+2"
+        )),
+        (MenuCreateOrthocenter, nwg_menuitem!(
+              parent=MenuCreate;
+              text="&Orthocenter\tCtrl+3"
+        )),
+        (MenuCreateIncenter, nwg_menuitem!(
+              parent=MenuCreate;
+              text="&Incenter\tCtrl+4"
+        )),
+        (MenuCreateNinePointCenter, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Nine-Point C&enter\tCtrl+5"
+        )),
+        (MenuCreateIncircle, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Incirc&le\tCtrl+6"
+        )),
+        (MenuCreateExcircles, nwg_menuitem!(
+              parent=MenuCreate;
+              text="E&xcircles\tCtrl+7"
+        )),
+        (MenuCreateNinePointCircle, nwg_menuitem!(
+              parent=MenuCreate;
+              text="Nine-Point Ci&rcle\tCtrl+8"
+        )),
 
     (MenuDisplay, nwg_menu!(
           parent=MainWindow;
@@ -187,7 +267,12 @@ nwg_template!(
           action=nwg::constants::FileDialogAction::Open;
           title="Open...";
           multiselect=false;
-          filters=Some("Rust Geometry Sketchpad File(*.rgsp)"))),
+          filters=Some("Geometry Sketchpad File(*.geopad)"))),
+    (SaveFileDialog, nwg_filedialog!(
+          parent=Some(MainWindow);
+          action=nwg::constants::FileDialogAction::Save;
+          title="Save...";
+          filters=Some("Geometry Sketchpad File(*.geopad)"))),
     (SelectToolBtn, nwg_button!(
           parent=MainWindow;
           text="select";
@@ -222,12 +307,28 @@ nwg_template!(
       if let Ok(file_dialog) = ui.get::<nwg::FileDialog>(&OpenFileDialog) {
         if file_dialog.run() {
           if let Ok(filename) = file_dialog.get_selected_item() {
-            println!("File selected ok {}", filename);
+            let path = PathBuf::from(filename);
+            (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::File(FileEvent::Load(path)));
+          } else {
+            panic!()
+          }
+        }
+      } else {
+        panic!()
+      }
+    }),
+    (MenuFileSave, FileSaveEvent, Event::Triggered, |ui,_,_,_| {
+      if let Ok(file_dialog) = ui.get::<nwg::FileDialog>(&SaveFileDialog) {
+        if file_dialog.run() {
+          if let Ok(filename) = file_dialog.get_selected_item() {
+            let mut path = PathBuf::from(filename);
+            if path.extension().is_none() {
+              path.set_extension(GEOPAD_FILE_EXTENSION);
+            }
+            (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::File(FileEvent::Save(path)));
           } else {
             panic!()
           }
-        } else {
-          println!("File open failed");
         }
       } else {
         panic!()
@@ -260,6 +361,84 @@ nwg_template!(
     (MenuCreatePerpendicular, CreatePerpendicularEvent, Event::Triggered, |_ui,_,_,_| {
       (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent::LineInsert(InsertLineEvent::InsertPerpendicularFromSelection)));
     }),
+    (MenuCreatePolygon, CreatePolygonEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PolygonInsert(InsertPolygonEvent::InsertPolygonFromSelection),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateArc, CreateArcEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::ArcInsert(InsertArcEvent::InsertArcFromSelection(ArcKind::Arc)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateSector, CreateSectorEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::ArcInsert(InsertArcEvent::InsertArcFromSelection(ArcKind::Sector)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateSegment, CreateSegmentEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::ArcInsert(InsertArcEvent::InsertArcFromSelection(ArcKind::Segment)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateConic, CreateConicEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::ConicInsert(InsertConicEvent::InsertConicFromSelection),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateCentroid, CreateCentroidEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(TriangleCenter::Centroid)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateCircumcenter, CreateCircumcenterEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(TriangleCenter::Circumcenter)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateOrthocenter, CreateOrthocenterEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(TriangleCenter::Orthocenter)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateIncenter, CreateIncenterEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(TriangleCenter::Incenter)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateNinePointCenter, CreateNinePointCenterEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(TriangleCenter::NinePointCenter)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateIncircle, CreateIncircleEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertTriangleCircleFromSelection(TriangleCircle::Incircle)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateExcircles, CreateExcirclesEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertTriangleCircleFromSelection(TriangleCircle::Excircles)),
+        event_id: None,
+      }));
+    }),
+    (MenuCreateNinePointCircle, CreateNinePointCircleEvent, Event::Triggered, |_ui,_,_,_| {
+      (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertTriangleCircleFromSelection(TriangleCircle::NinePointCircle)),
+        event_id: None,
+      }));
+    }),
     (MenuDisplayHide, DisplayHideEvent, Event::Triggered, |_ui,_,_,_| {
       (*GUI_ACTION_CHANNEL).lock().unwrap().single_write(GuiSystemAction::Command(CommandEvent::Hide(HideEvent::HideSelected)));
     }),
@@ -380,6 +559,7 @@ impl<'a> System<'a> for GuiSystem {
     Write<'a, ExitEventChannel>,
     Write<'a, HistoryEventChannel>,
     Write<'a, CommandEventChannel>,
+    Write<'a, FileEventChannel>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -389,7 +569,14 @@ impl<'a> System<'a> for GuiSystem {
 
   fn run(
     &mut self,
-    (_input_state, mut tool_change_events, mut exit_events, mut history_events, mut command_events): Self::SystemData,
+    (
+      _input_state,
+      mut tool_change_events,
+      mut exit_events,
+      mut history_events,
+      mut command_events,
+      mut file_events,
+    ): Self::SystemData,
   ) {
     unsafe {
       use user32::{
@@ -448,6 +635,9 @@ impl<'a> System<'a> for GuiSystem {
           GuiSystemAction::Command(event) => {
             command_events.single_write(*event);
           }
+          GuiSystemAction::File(event) => {
+            file_events.single_write(event.clone());
+          }
         }
       }
     }
@@ -0,0 +1,29 @@
+use core_lib::{events::*, resources::*, setup_core_lib, utilities::*};
+use specs::prelude::*;
+use std::path::Path;
+
+/// Build a world containing the sketch stored in a `.geopad` file, without opening any window.
+/// The viewport, including the screen size, is the one saved in the file.
+pub fn load_world(path: &Path) -> Result<World, GeopadFileError> {
+  let file = GeopadFile::load(path)?;
+  file.check_references()?;
+
+  let mut world = World::new();
+  let mut builder = DispatcherBuilder::new();
+  setup_core_lib(&mut builder);
+  let mut dispatcher = builder.build();
+  dispatcher.setup(&mut world);
+
+  let FileViewport {
+    virtual_center,
+    virtual_size,
+    screen_size,
+  } = file.viewport;
+  world.insert(Viewport::new(virtual_center, virtual_size, screen_size));
+  world
+    .fetch_mut::<FileEventChannel>()
+    .single_write(FileEvent::Load(path.to_path_buf()));
+  dispatcher.dispatch(&world);
+  world.maintain();
+  Ok(world)
+}
@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate core_lib;
+extern crate specs;
+extern crate tiny_skia;
+
+mod headless;
+pub mod raster;
+pub mod svg;
+
+pub use headless::*;
@@ -0,0 +1,39 @@
+extern crate core_export;
+
+use core_export::{load_world, raster::*, svg::*};
+use std::{env, fs, path::Path, process};
+
+fn main() {
+  let args = env::args().collect::<Vec<_>>();
+  if args.len() != 3 {
+    eprintln!("Usage: geopad-export <input.geopad> <output.svg|output.png>");
+    process::exit(1);
+  }
+  let (input, output) = (Path::new(&args[1]), Path::new(&args[2]));
+
+  let mut world = match load_world(input) {
+    Ok(world) => world,
+    Err(err) => {
+      eprintln!("Cannot load {}: {}", input.display(), err);
+      process::exit(1);
+    }
+  };
+  let content = match output.extension().and_then(|ext| ext.to_str()) {
+    Some("svg") => export_svg(&world, &SvgExportOptions::default()).into_bytes(),
+    Some("png") => match export_png(&mut world) {
+      Ok(bytes) => bytes,
+      Err(err) => {
+        eprintln!("Cannot render {}: {}", input.display(), err);
+        process::exit(1);
+      }
+    },
+    _ => {
+      eprintln!("Unknown output format for {}, expecting .svg or .png", output.display());
+      process::exit(1);
+    }
+  };
+  if let Err(err) = fs::write(output, content) {
+    eprintln!("Cannot write {}: {}", output.display(), err);
+    process::exit(1);
+  }
+}
@@ -0,0 +1,518 @@
+use core_lib::{
+  components::{markers::*, screen_shapes::*, styles::*},
+  math::{self, *},
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+use std::fmt;
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+#[derive(Debug)]
+pub enum RasterExportError {
+  EmptyViewport,
+  Encode(String),
+}
+
+impl fmt::Display for RasterExportError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      RasterExportError::EmptyViewport => write!(f, "the viewport has no area to render"),
+      RasterExportError::Encode(err) => write!(f, "cannot encode png: {}", err),
+    }
+  }
+}
+
+type RenderData<'a> = (
+  Read<'a, Viewport>,
+  ReadStorage<'a, ScreenPoint>,
+  ReadStorage<'a, ScreenLine>,
+  ReadStorage<'a, ScreenCircle>,
+  ReadStorage<'a, ScreenPolygon>,
+  ReadStorage<'a, ScreenLocus>,
+  ReadStorage<'a, ScreenArc>,
+  ReadStorage<'a, ScreenConic>,
+  ReadStorage<'a, ScreenRectangle>,
+  ReadStorage<'a, PointStyle>,
+  ReadStorage<'a, LineStyle>,
+  ReadStorage<'a, CircleStyle>,
+  ReadStorage<'a, PolygonStyle>,
+  ReadStorage<'a, RectangleStyle>,
+  ReadStorage<'a, Selected>,
+  ReadStorage<'a, Hidden>,
+);
+
+/// Render the screen shapes of the world into a pixmap the size of the viewport, the same
+/// way the piston window renders them. Labels are left out since there is no font to draw
+/// them with, use the SVG export to keep them
+pub fn render_pixmap(world: &mut World) -> Result<Pixmap, RasterExportError> {
+  RenderData::setup(world);
+  let (
+    viewport,
+    scrn_points,
+    scrn_lines,
+    scrn_circles,
+    scrn_polygons,
+    scrn_loci,
+    scrn_arcs,
+    scrn_conics,
+    scrn_rects,
+    point_styles,
+    line_styles,
+    circle_styles,
+    polygon_styles,
+    rect_styles,
+    selecteds,
+    hiddens,
+  ) = world.system_data::<RenderData>();
+
+  let (width, height) = (viewport.screen_width().round(), viewport.screen_height().round());
+  let mut pixmap = Pixmap::new(width as u32, height as u32).ok_or(RasterExportError::EmptyViewport)?;
+
+  // Clean the screen first
+  pixmap.fill(to_skia_color(Color::white()));
+
+  // Same layering as `render()`: polygons and arcs at the bottom, circles, lines, loci and conics,
+  // points, and rectangles on the top. Within each kind, the selected ones go above the others
+  for (polygon, style, _, _) in (&scrn_polygons, &polygon_styles, !&selecteds, !&hiddens).join() {
+    render_polygon(&mut pixmap, polygon, style, false);
+  }
+  for (polygon, style, _, _) in (&scrn_polygons, &polygon_styles, &selecteds, !&hiddens).join() {
+    render_polygon(&mut pixmap, polygon, style, true);
+  }
+  for (arc, style, _, _) in (&scrn_arcs, &polygon_styles, !&selecteds, !&hiddens).join() {
+    render_arc(&mut pixmap, arc, style, false);
+  }
+  for (arc, style, _, _) in (&scrn_arcs, &polygon_styles, &selecteds, !&hiddens).join() {
+    render_arc(&mut pixmap, arc, style, true);
+  }
+  for (circle, style, _, _) in (&scrn_circles, &circle_styles, !&selecteds, !&hiddens).join() {
+    render_circle(&mut pixmap, circle, style, false);
+  }
+  for (circle, style, _, _) in (&scrn_circles, &circle_styles, &selecteds, !&hiddens).join() {
+    render_circle(&mut pixmap, circle, style, true);
+  }
+  for (line, style, _, _) in (&scrn_lines, &line_styles, !&selecteds, !&hiddens).join() {
+    render_line(&mut pixmap, line, style, false, &viewport);
+  }
+  for (line, style, _, _) in (&scrn_lines, &line_styles, &selecteds, !&hiddens).join() {
+    render_line(&mut pixmap, line, style, true, &viewport);
+  }
+  for (locus, style, _, _) in (&scrn_loci, &line_styles, !&selecteds, !&hiddens).join() {
+    render_polyline(&mut pixmap, locus.clone().into(), style, false);
+  }
+  for (locus, style, _, _) in (&scrn_loci, &line_styles, &selecteds, !&hiddens).join() {
+    render_polyline(&mut pixmap, locus.clone().into(), style, true);
+  }
+  for (conic, style, _, _) in (&scrn_conics, &line_styles, !&selecteds, !&hiddens).join() {
+    render_polyline(&mut pixmap, conic.clone().into(), style, false);
+  }
+  for (conic, style, _, _) in (&scrn_conics, &line_styles, &selecteds, !&hiddens).join() {
+    render_polyline(&mut pixmap, conic.clone().into(), style, true);
+  }
+  for (point, style, _, _) in (&scrn_points, &point_styles, !&selecteds, !&hiddens).join() {
+    render_point(&mut pixmap, point, style, false);
+  }
+  for (point, style, _, _) in (&scrn_points, &point_styles, &selecteds, !&hiddens).join() {
+    render_point(&mut pixmap, point, style, true);
+  }
+  for (rect, style) in (&scrn_rects, &rect_styles).join() {
+    render_rectangle(&mut pixmap, rect, style);
+  }
+
+  Ok(pixmap)
+}
+
+/// Render the world and encode it as a PNG
+pub fn export_png(world: &mut World) -> Result<Vec<u8>, RasterExportError> {
+  let pixmap = render_pixmap(world)?;
+  pixmap
+    .encode_png()
+    .map_err(|err| RasterExportError::Encode(err.to_string()))
+}
+
+fn render_point(
+  pixmap: &mut Pixmap,
+  ScreenPosition(Vector2 { x, y }): &ScreenPoint,
+  style: &PointStyle,
+  selected: bool,
+) {
+  if selected {
+    stroke_circle(pixmap, vec2![*x, *y], style.radius + 3.0, Color::magenta(), 0.5);
+  }
+  fill_circle(
+    pixmap,
+    vec2![*x, *y],
+    style.radius,
+    style.border_color.apply_alpha(style.color.a),
+  );
+  fill_circle(pixmap, vec2![*x, *y], style.radius - style.border_width, style.color);
+}
+
+fn render_line(pixmap: &mut Pixmap, l: &ScreenLine, style: &LineStyle, selected: bool, viewport: &Viewport) {
+  let line: math::Line = (*l).into();
+  if let Some((from, to)) = line.intersect(viewport.screen_aabb()) {
+    match style.dash {
+      Some(dash) => {
+        for (from, to) in dash.split_line(line, from, to) {
+          stroke_line(pixmap, from, to, style.color, style.width);
+        }
+      }
+      None => stroke_line(pixmap, from, to, style.color, style.width),
+    }
+    if selected {
+      let Vector2 { x: dx, y: dy } = (to - from).normalized();
+      let perp_dir = vec2![-dy, dx] * (style.width / 2.0 + 3.0);
+      stroke_line(pixmap, from - perp_dir, to - perp_dir, Color::magenta(), 0.5);
+      stroke_line(pixmap, from + perp_dir, to + perp_dir, Color::magenta(), 0.5);
+    }
+  }
+}
+
+fn render_circle(
+  pixmap: &mut Pixmap,
+  ScreenCircle { center, radius }: &ScreenCircle,
+  style: &CircleStyle,
+  selected: bool,
+) {
+  let (center, radius) = (center.0, radius.0);
+  fill_circle(pixmap, center, radius, style.fill);
+  match style.border.dash {
+    Some(dash) => {
+      for (start, end) in dash.split_circle(radius) {
+        stroke_arc(
+          pixmap,
+          center,
+          radius,
+          start,
+          end,
+          style.border.color,
+          style.border.width,
+        );
+      }
+    }
+    None => stroke_circle(pixmap, center, radius, style.border.color, style.border.width),
+  }
+  if selected {
+    let offset = style.border.width / 2.0 + 3.0;
+    stroke_circle(pixmap, center, radius - offset, Color::magenta(), 0.5);
+    stroke_circle(pixmap, center, radius + offset, Color::magenta(), 0.5);
+  }
+}
+
+fn render_polygon(pixmap: &mut Pixmap, polygon: &ScreenPolygon, style: &PolygonStyle, selected: bool) {
+  let polygon: Polygon = polygon.clone().into();
+  let mut builder = PathBuilder::new();
+  for (i, p) in polygon.points.iter().enumerate() {
+    if i == 0 {
+      builder.move_to(p.x as f32, p.y as f32);
+    } else {
+      builder.line_to(p.x as f32, p.y as f32);
+    }
+  }
+  builder.close();
+  if let Some(path) = builder.finish() {
+    pixmap.fill_path(
+      &path,
+      &paint(style.fill),
+      FillRule::EvenOdd,
+      Transform::identity(),
+      None,
+    );
+    if style.border.dash.is_none() {
+      pixmap.stroke_path(
+        &path,
+        &paint(style.border.color),
+        &stroke(style.border.width),
+        Transform::identity(),
+        None,
+      );
+    }
+  }
+  if let Some(dash) = style.border.dash {
+    // The dash pattern goes on around the polygon from its first point
+    let mut offset = 0.0;
+    for edge in polygon.edges() {
+      for (from, to) in dash.split_segment(edge.from, edge.to, offset) {
+        stroke_line(pixmap, from, to, style.border.color, style.border.width);
+      }
+      offset += edge.from_to_length();
+    }
+  }
+  if selected {
+    for edge in polygon.edges() {
+      if edge.from != edge.to {
+        let Vector2 { x: dx, y: dy } = edge.direction();
+        let perp_dir = vec2![-dy, dx] * (style.border.width / 2.0 + 3.0);
+        stroke_line(pixmap, edge.from - perp_dir, edge.to - perp_dir, Color::magenta(), 0.5);
+        stroke_line(pixmap, edge.from + perp_dir, edge.to + perp_dir, Color::magenta(), 0.5);
+      }
+    }
+  }
+}
+
+fn render_arc(pixmap: &mut Pixmap, arc: &ScreenArc, style: &PolygonStyle, selected: bool) {
+  let arc: math::Arc = (*arc).into();
+  let end = arc.start + arc.sweep;
+
+  // The outline goes along the arc, then back through the center for a sector or straight
+  // along the chord for a circular segment
+  let steps = (arc.sweep * arc.radius / 2.0).ceil().max(1.0) as usize;
+  let mut outline = (0..=steps)
+    .map(|i| arc.point_at_fraction(i as f64 / steps as f64))
+    .collect::<Vec<_>>();
+  match arc.kind {
+    ArcKind::Arc => (),
+    ArcKind::Sector => outline.extend(vec![arc.center, arc.start_point()]),
+    ArcKind::Segment => outline.push(arc.start_point()),
+  }
+  let mut builder = PathBuilder::new();
+  for (i, p) in outline.iter().enumerate() {
+    if i == 0 {
+      builder.move_to(p.x as f32, p.y as f32);
+    } else {
+      builder.line_to(p.x as f32, p.y as f32);
+    }
+  }
+  if let Some(path) = builder.finish() {
+    if arc.kind != ArcKind::Arc {
+      pixmap.fill_path(
+        &path,
+        &paint(style.fill),
+        FillRule::Winding,
+        Transform::identity(),
+        None,
+      );
+    }
+    if style.border.dash.is_none() {
+      pixmap.stroke_path(
+        &path,
+        &paint(style.border.color),
+        &stroke(style.border.width),
+        Transform::identity(),
+        None,
+      );
+    }
+  }
+  if let Some(dash) = style.border.dash {
+    // The dash pattern goes on along the outline from the start of the arc
+    let mut offset = 0.0;
+    for pair in outline.windows(2) {
+      for (from, to) in dash.split_segment(pair[0], pair[1], offset) {
+        stroke_line(pixmap, from, to, style.border.color, style.border.width);
+      }
+      offset += (pair[1] - pair[0]).magnitude();
+    }
+  }
+  if selected {
+    let offset = style.border.width / 2.0 + 3.0;
+    stroke_arc(
+      pixmap,
+      arc.center,
+      arc.radius - offset,
+      arc.start,
+      end,
+      Color::magenta(),
+      0.5,
+    );
+    stroke_arc(
+      pixmap,
+      arc.center,
+      arc.radius + offset,
+      arc.start,
+      end,
+      Color::magenta(),
+      0.5,
+    );
+  }
+}
+
+fn render_polyline(pixmap: &mut Pixmap, polyline: Polyline, style: &LineStyle, selected: bool) {
+  for path in &polyline.paths {
+    // The dash pattern goes on along each path from its first point
+    let mut offset = 0.0;
+    for pair in path.windows(2) {
+      match style.dash {
+        Some(dash) => {
+          for (from, to) in dash.split_segment(pair[0], pair[1], offset) {
+            stroke_line(pixmap, from, to, style.color, style.width);
+          }
+        }
+        None => stroke_line(pixmap, pair[0], pair[1], style.color, style.width),
+      }
+      offset += (pair[1] - pair[0]).magnitude();
+    }
+  }
+  if selected {
+    for segment in polyline.segments() {
+      if segment.from != segment.to {
+        let Vector2 { x: dx, y: dy } = segment.direction();
+        let perp_dir = vec2![-dy, dx] * (style.width / 2.0 + 3.0);
+        stroke_line(
+          pixmap,
+          segment.from - perp_dir,
+          segment.to - perp_dir,
+          Color::magenta(),
+          0.5,
+        );
+        stroke_line(
+          pixmap,
+          segment.from + perp_dir,
+          segment.to + perp_dir,
+          Color::magenta(),
+          0.5,
+        );
+      }
+    }
+  }
+}
+
+fn render_rectangle(pixmap: &mut Pixmap, rect: &AABB, style: &RectangleStyle) {
+  if let Some(skia_rect) = Rect::from_xywh(rect.x as f32, rect.y as f32, rect.width as f32, rect.height as f32) {
+    pixmap.fill_rect(skia_rect, &paint(style.fill), Transform::identity(), None);
+  }
+  let corners = [
+    rect.min(),
+    vec2![rect.x_max(), rect.y],
+    rect.max(),
+    vec2![rect.x, rect.y_max()],
+  ];
+  for i in 0..4 {
+    stroke_line(
+      pixmap,
+      corners[i],
+      corners[(i + 1) % 4],
+      style.border.color,
+      style.border.width,
+    );
+  }
+}
+
+fn fill_circle(pixmap: &mut Pixmap, center: Vector2, radius: f64, color: Color) {
+  if radius <= 0.0 {
+    return;
+  }
+  if let Some(path) = PathBuilder::from_circle(center.x as f32, center.y as f32, radius as f32) {
+    pixmap.fill_path(&path, &paint(color), FillRule::Winding, Transform::identity(), None);
+  }
+}
+
+fn stroke_circle(pixmap: &mut Pixmap, center: Vector2, radius: f64, color: Color, width: f64) {
+  if radius <= 0.0 {
+    return;
+  }
+  if let Some(path) = PathBuilder::from_circle(center.x as f32, center.y as f32, radius as f32) {
+    pixmap.stroke_path(&path, &paint(color), &stroke(width), Transform::identity(), None);
+  }
+}
+
+/// Stroke the arc of the circle between the `start` and `end` angles, in radians
+fn stroke_arc(pixmap: &mut Pixmap, center: Vector2, radius: f64, start: f64, end: f64, color: Color, width: f64) {
+  // Approximate the arc with segments short enough not to be noticed
+  let steps = ((end - start) * radius / 2.0).ceil().max(1.0) as usize;
+  let mut builder = PathBuilder::new();
+  for i in 0..=steps {
+    let angle = start + (end - start) * i as f64 / steps as f64;
+    let p = center + vec2![angle.cos(), angle.sin()] * radius;
+    if i == 0 {
+      builder.move_to(p.x as f32, p.y as f32);
+    } else {
+      builder.line_to(p.x as f32, p.y as f32);
+    }
+  }
+  if let Some(path) = builder.finish() {
+    pixmap.stroke_path(&path, &paint(color), &stroke(width), Transform::identity(), None);
+  }
+}
+
+fn stroke_line(pixmap: &mut Pixmap, from: Vector2, to: Vector2, color: Color, width: f64) {
+  let mut builder = PathBuilder::new();
+  builder.move_to(from.x as f32, from.y as f32);
+  builder.line_to(to.x as f32, to.y as f32);
+  if let Some(path) = builder.finish() {
+    pixmap.stroke_path(&path, &paint(color), &stroke(width), Transform::identity(), None);
+  }
+}
+
+fn paint(color: Color) -> Paint<'static> {
+  let mut paint = Paint::default();
+  paint.set_color(to_skia_color(color));
+  paint.anti_alias = true;
+  paint
+}
+
+fn stroke(width: f64) -> Stroke {
+  Stroke {
+    width: width as f32,
+    ..Stroke::default()
+  }
+}
+
+fn to_skia_color(Color { r, g, b, a }: Color) -> tiny_skia::Color {
+  let clamp = |c: f32| c.clamp(0.0, 1.0);
+  tiny_skia::Color::from_rgba(clamp(r), clamp(g), clamp(b), clamp(a)).unwrap_or(tiny_skia::Color::BLACK)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_render_pixmap() {
+    let mut world = World::new();
+    world.insert(Viewport::new(vec2![0., 0.], vec2![20., 15.], vec2![200., 150.]));
+    RenderData::setup(&mut world);
+
+    let point_style = DefaultPointStyle::default().get();
+    world
+      .create_entity()
+      .with(ScreenPoint::from(vec2![50., 50.]))
+      .with(point_style)
+      .build();
+    world
+      .create_entity()
+      .with(ScreenPoint::from(vec2![150., 50.]))
+      .with(point_style)
+      .with(Hidden)
+      .build();
+    world
+      .create_entity()
+      .with(ScreenPoint::from(vec2![100., 100.]))
+      .with(point_style)
+      .with(Selected)
+      .build();
+    world
+      .create_entity()
+      .with(ScreenLine {
+        from: vec2![0., 130.].into(),
+        to: vec2![10., 130.].into(),
+        line_type: LineType::Straight,
+      })
+      .with(LineStyle {
+        color: Color::black(),
+        width: 2.0,
+        dash: Some(Dash { on: 8.0, off: 6.0 }),
+      })
+      .build();
+
+    let pixmap = render_pixmap(&mut world).unwrap();
+    assert_eq!((pixmap.width(), pixmap.height()), (200, 150));
+
+    let rgba = |x, y| {
+      let c = pixmap.pixel(x, y).unwrap().demultiply();
+      (c.red(), c.green(), c.blue(), c.alpha())
+    };
+    assert_eq!(rgba(50, 50), (255, 0, 0, 255));
+    assert_eq!(rgba(150, 50), (255, 255, 255, 255));
+    assert_eq!(rgba(0, 0), (255, 255, 255, 255));
+
+    // The dashed line is drawn in the dashes and not in the gaps
+    assert_eq!(rgba(4, 130), (0, 0, 0, 255));
+    assert_eq!(rgba(11, 130), (255, 255, 255, 255));
+    assert_eq!(rgba(18, 130), (0, 0, 0, 255));
+
+    // The selected point gets a magenta ring around it
+    let (r, g, b, _) = rgba(100, 92);
+    assert!(r > 200 && g < 200 && b > 200, "{:?}", (r, g, b));
+  }
+}
@@ -0,0 +1,367 @@
+use core_lib::{
+  components::{labels::*, markers::*, screen_shapes::*, styles::*, virtual_shapes::*},
+  math::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+use std::{f64::consts::PI, fmt::Write};
+
+pub struct SvgExportOptions {
+  /// The region to export in virtual space. Defaults to what the viewport is currently showing
+  pub region: Option<AABB>,
+  pub background: Option<Color>,
+}
+
+impl Default for SvgExportOptions {
+  fn default() -> Self {
+    Self {
+      region: None,
+      background: Some(Color::white()),
+    }
+  }
+}
+
+/// Export all the non-hidden geometries of the world to an SVG document. Selection is not
+/// exported. Points and widths keep the size they have on screen.
+pub fn export_svg(world: &World, options: &SvgExportOptions) -> String {
+  let viewport = export_viewport(&world.read_resource::<Viewport>(), options.region);
+  let (virt_points, virt_lines, virt_circles) = (
+    world.read_storage::<VirtualPoint>(),
+    world.read_storage::<VirtualLine>(),
+    world.read_storage::<VirtualCircle>(),
+  );
+  let (point_styles, line_styles, circle_styles) = (
+    world.read_storage::<PointStyle>(),
+    world.read_storage::<LineStyle>(),
+    world.read_storage::<CircleStyle>(),
+  );
+  let (virt_polygons, polygon_styles) = (
+    world.read_storage::<VirtualPolygon>(),
+    world.read_storage::<PolygonStyle>(),
+  );
+  let virt_loci = world.read_storage::<VirtualLocus>();
+  let virt_arcs = world.read_storage::<VirtualArc>();
+  let virt_conics = world.read_storage::<VirtualConic>();
+  let (entities, labels) = (world.entities(), world.read_storage::<Label>());
+  let hiddens = world.read_storage::<Hidden>();
+
+  let mut svg = String::new();
+  let (width, height) = (viewport.screen_width(), viewport.screen_height());
+  writeln!(
+    svg,
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+    w = num(width),
+    h = num(height)
+  )
+  .unwrap();
+  if let Some(background) = options.background {
+    writeln!(
+      svg,
+      r#"  <rect width="100%" height="100%" {}/>"#,
+      paint("fill", background)
+    )
+    .unwrap();
+  }
+
+  // Same layering as the renderer: polygons and arcs at the bottom, then circles, lines, loci and
+  // conics, and points
+  for (polygon, style, _) in (&virt_polygons, &polygon_styles, !&hiddens).join() {
+    let ScreenPolygon { points } = polygon.clone().to_screen(&viewport);
+    let points = points
+      .iter()
+      .map(|ScreenPosition(Vector2 { x, y })| format!("{},{}", num(*x), num(*y)))
+      .collect::<Vec<_>>();
+    writeln!(
+      svg,
+      r#"  <polygon points="{}" {} {} stroke-width="{}"{} fill-rule="evenodd"/>"#,
+      points.join(" "),
+      paint("fill", style.fill),
+      paint("stroke", style.border.color),
+      num(style.border.width),
+      dash(style.border, 0.0),
+    )
+    .unwrap();
+  }
+  for (arc, style, _) in (&virt_arcs, &polygon_styles, !&hiddens).join() {
+    let arc: Arc = arc.to_screen(&viewport).into();
+    let (from, to) = (arc.start_point(), arc.end_point());
+
+    // Screen angles grow clockwise, which is the positive sweep direction of SVG
+    let curve = format!(
+      "A {r} {r} 0 {} 1 {} {}",
+      if arc.sweep > PI { 1 } else { 0 },
+      num(to.x),
+      num(to.y),
+      r = num(arc.radius),
+    );
+    let (path, fill) = match arc.kind {
+      ArcKind::Arc => (
+        format!("M {} {} {}", num(from.x), num(from.y), curve),
+        "fill=\"none\"".to_string(),
+      ),
+      ArcKind::Sector => (
+        format!(
+          "M {} {} L {} {} {} Z",
+          num(arc.center.x),
+          num(arc.center.y),
+          num(from.x),
+          num(from.y),
+          curve
+        ),
+        paint("fill", style.fill),
+      ),
+      ArcKind::Segment => (
+        format!("M {} {} {} Z", num(from.x), num(from.y), curve),
+        paint("fill", style.fill),
+      ),
+    };
+    writeln!(
+      svg,
+      r#"  <path d="{}" {} {} stroke-width="{}"{}/>"#,
+      path,
+      fill,
+      paint("stroke", style.border.color),
+      num(style.border.width),
+      dash(style.border, 0.0),
+    )
+    .unwrap();
+  }
+  for (circle, style, _) in (&virt_circles, &circle_styles, !&hiddens).join() {
+    let ScreenCircle { center, radius } = circle.to_screen(&viewport);
+    writeln!(
+      svg,
+      r#"  <circle cx="{}" cy="{}" r="{}" {} {} stroke-width="{}"{}/>"#,
+      num(center.0.x),
+      num(center.0.y),
+      num(radius.0),
+      paint("fill", style.fill),
+      paint("stroke", style.border.color),
+      num(style.border.width),
+      dash(style.border, 0.0),
+    )
+    .unwrap();
+  }
+  for (line, style, _) in (&virt_lines, &line_styles, !&hiddens).join() {
+    let line: Line = line.to_screen(&viewport).into();
+    if let Some((from, to)) = line.intersect(viewport.screen_aabb()) {
+      // Go the same way as the line so the dash pattern starts from its first point
+      let (from, to) = if line.t_of_point(from) <= line.t_of_point(to) {
+        (from, to)
+      } else {
+        (to, from)
+      };
+      writeln!(
+        svg,
+        r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" {} stroke-width="{}"{}/>"#,
+        num(from.x),
+        num(from.y),
+        num(to.x),
+        num(to.y),
+        paint("stroke", style.color),
+        num(style.width),
+        dash(*style, line.t_of_point(from)),
+      )
+      .unwrap();
+    }
+  }
+  for (locus, style, _) in (&virt_loci, &line_styles, !&hiddens).join() {
+    let ScreenLocus { paths } = locus.clone().to_screen(&viewport);
+    write_paths(&mut svg, &paths, *style);
+  }
+  for (conic, style, _) in (&virt_conics, &line_styles, !&hiddens).join() {
+    let ScreenConic { paths, .. } = conic.to_screen(&viewport);
+    write_paths(&mut svg, &paths, *style);
+  }
+  for (point, style, _) in (&virt_points, &point_styles, !&hiddens).join() {
+    let ScreenPosition(Vector2 { x, y }) = point.to_screen(&viewport);
+    writeln!(
+      svg,
+      r#"  <circle cx="{}" cy="{}" r="{}" {}/>"#,
+      num(x),
+      num(y),
+      num(style.radius),
+      paint("fill", style.border_color.apply_alpha(style.color.a)),
+    )
+    .unwrap();
+    writeln!(
+      svg,
+      r#"  <circle cx="{}" cy="{}" r="{}" {}/>"#,
+      num(x),
+      num(y),
+      num((style.radius - style.border_width).max(0.0)),
+      paint("fill", style.color),
+    )
+    .unwrap();
+  }
+
+  // The labels go above everything, with the text starting from its baseline
+  for (ent, label, _) in (&entities, &labels, !&hiddens).join() {
+    let anchor = if let Some(point) = virt_points.get(ent) {
+      point.to_screen(&viewport).label_anchor()
+    } else if let Some(line) = virt_lines.get(ent) {
+      line.to_screen(&viewport).label_anchor()
+    } else if let Some(circle) = virt_circles.get(ent) {
+      circle.to_screen(&viewport).label_anchor()
+    } else {
+      continue;
+    };
+    let ScreenLabel { text, rect } = ScreenLabel::new(label, anchor);
+    writeln!(
+      svg,
+      r#"  <text x="{}" y="{}" font-family="sans-serif" font-size="{}" {}>{}</text>"#,
+      num(rect.x),
+      num(rect.y + LABEL_FONT_SIZE),
+      num(LABEL_FONT_SIZE),
+      paint("fill", Color::black()),
+      escape(&text),
+    )
+    .unwrap();
+  }
+
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/// A viewport looking at `region` with the same virtual to screen scale as `viewport`
+pub fn export_viewport(viewport: &Viewport, region: Option<AABB>) -> Viewport {
+  match region {
+    Some(aabb) => {
+      let size = vec2![aabb.width, aabb.height];
+      let center = aabb.min() + size / 2.0;
+      Viewport::new(center, size, size / viewport.virtual_to_screen_scale())
+    }
+    None => *viewport,
+  }
+}
+
+/// The samples of a curve, as one polyline for each run of them
+fn write_paths(svg: &mut String, paths: &[Vec<ScreenPosition>], style: LineStyle) {
+  for path in paths {
+    let points = path
+      .iter()
+      .map(|ScreenPosition(Vector2 { x, y })| format!("{},{}", num(*x), num(*y)))
+      .collect::<Vec<_>>();
+    writeln!(
+      svg,
+      r#"  <polyline points="{}" fill="none" {} stroke-width="{}"{}/>"#,
+      points.join(" "),
+      paint("stroke", style.color),
+      num(style.width),
+      dash(style, 0.0),
+    )
+    .unwrap();
+  }
+}
+
+fn paint(attr: &str, color: Color) -> String {
+  let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+  format!(
+    r#"{attr}="rgb({},{},{})" {attr}-opacity="{}""#,
+    channel(color.r),
+    channel(color.g),
+    channel(color.b),
+    num(color.a as f64),
+    attr = attr
+  )
+}
+
+/// The dash attributes of a stroke, if any. `offset` is how far along the pattern the stroke
+/// starts. Circles start at their rightmost point and go clockwise, like `Dash::split_circle`
+fn dash(style: LineStyle, offset: f64) -> String {
+  match style.dash {
+    Some(dash) => format!(
+      r#" stroke-dasharray="{} {}" stroke-dashoffset="{}""#,
+      num(dash.on),
+      num(dash.off),
+      num(offset.rem_euclid(dash.period()))
+    ),
+    None => String::new(),
+  }
+}
+
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn num(f: f64) -> String {
+  let s = format!("{:.3}", f);
+  let s = s.trim_end_matches('0').trim_end_matches('.');
+  if s == "-0" {
+    "0".to_string()
+  } else {
+    s.to_string()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_export_svg() {
+    let mut world = World::new();
+    world.register::<VirtualPoint>();
+    world.register::<VirtualLine>();
+    world.register::<VirtualCircle>();
+    world.register::<PointStyle>();
+    world.register::<LineStyle>();
+    world.register::<CircleStyle>();
+    world.register::<VirtualPolygon>();
+    world.register::<PolygonStyle>();
+    world.register::<VirtualLocus>();
+    world.register::<VirtualArc>();
+    world.register::<VirtualConic>();
+    world.register::<Hidden>();
+    world.register::<Label>();
+    world.insert(Viewport::default());
+
+    let line = VirtualLine {
+      from: vec2![0.0, 0.0].into(),
+      to: vec2![1.0, 1.0].into(),
+      line_type: LineType::Straight,
+    };
+    world
+      .create_entity()
+      .with(line)
+      .with(DefaultLineStyle::default().get())
+      .build();
+    world
+      .create_entity()
+      .with(VirtualPoint::from(vec2![0.0, 0.0]))
+      .with(DefaultPointStyle::default().get())
+      .with(Hidden)
+      .build();
+
+    let svg = export_svg(&world, &SvgExportOptions::default());
+    assert!(svg.starts_with("<svg"));
+    assert_eq!(svg.matches("<circle").count(), 0);
+
+    // The straight line passes the center and gets clipped by the bottom and top edges
+    let expected = r#"<line x1="120" y1="720" x2="840" y2="0""#;
+    assert!(svg.contains(expected), "{}", svg);
+    assert!(!svg.contains("stroke-dasharray"));
+
+    world
+      .create_entity()
+      .with(line)
+      .with(LineStyle {
+        dash: Some(Dash::dashed()),
+        ..DefaultLineStyle::default().get()
+      })
+      .build();
+    let svg = export_svg(&world, &SvgExportOptions::default());
+    assert!(svg.contains(r#"stroke-dasharray="8 6""#), "{}", svg);
+
+    // Labels are exported as text, escaped
+    world
+      .create_entity()
+      .with(VirtualPoint::from(vec2![0.0, 0.0]))
+      .with(DefaultPointStyle::default().get())
+      .with(Label::new("A<B".to_string()))
+      .build();
+    let svg = export_svg(&world, &SvgExportOptions::default());
+    assert_eq!(svg.matches("<text").count(), 1);
+    assert!(svg.contains(">A&lt;B</text>"), "{}", svg);
+  }
+}
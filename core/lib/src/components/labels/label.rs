@@ -0,0 +1,63 @@
+use crate::math::*;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+/// The size of the label text, in pixels
+pub static LABEL_FONT_SIZE: f64 = 14.0;
+
+/// Where the top left corner of a new label goes, relative to its anchor. Right above the point
+pub static DEFAULT_LABEL_OFFSET: Vector2 = Vector2 { x: 6.0, y: -22.0 };
+
+/// Where the value of a measurement goes, relative to its anchor. Right below, so that it doesn't
+/// cover the name of the point
+pub static MEASUREMENT_LABEL_OFFSET: Vector2 = Vector2 { x: 6.0, y: 6.0 };
+
+/// The name of a point, line or circle. The label is drawn at `offset` pixels from the anchor
+/// of the element, e.g. the point itself, so that it can be dragged around.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Label {
+  pub text: String,
+  pub offset: Vector2,
+}
+
+impl Component for Label {
+  type Storage = VecStorage<Self>;
+}
+
+impl Label {
+  pub fn new(text: String) -> Self {
+    Self {
+      text,
+      offset: DEFAULT_LABEL_OFFSET,
+    }
+  }
+
+  /// The size the text takes on screen. There's no font at this level so this is an estimate
+  /// which is good enough for hit testing
+  pub fn size(&self) -> Vector2 {
+    let chars = self.text.chars().count() as f64;
+    vec2![chars * LABEL_FONT_SIZE * 0.6, LABEL_FONT_SIZE * 1.2]
+  }
+
+  /// The `index`th automatic name, going A, B, ..., Z, then A1, ..., Z1, A2, ...
+  pub fn auto_name(index: usize) -> String {
+    let letter = (b'A' + (index % 26) as u8) as char;
+    match index / 26 {
+      0 => letter.to_string(),
+      round => format!("{}{}", letter, round),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_auto_name() {
+    assert_eq!(Label::auto_name(0), "A");
+    assert_eq!(Label::auto_name(25), "Z");
+    assert_eq!(Label::auto_name(26), "A1");
+    assert_eq!(Label::auto_name(53), "B2");
+  }
+}
@@ -1,7 +1,9 @@
 mod element;
 mod hidden;
 mod selected;
+mod undefined;
 
 pub use element::*;
 pub use hidden::*;
 pub use selected::*;
+pub use undefined::*;
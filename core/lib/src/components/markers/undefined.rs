@@ -0,0 +1,11 @@
+use specs::prelude::*;
+
+/// Marks an element whose shape can't be solved from its current inputs, like the intersection
+/// of two parallel lines. It has no virtual or screen shape while marked, and the marker goes
+/// away by itself once the inputs make the element defined again
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Undefined;
+
+impl Component for Undefined {
+  type Storage = NullStorage<Self>;
+}
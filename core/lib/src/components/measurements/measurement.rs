@@ -0,0 +1,44 @@
+use crate::components::symbolics::SymbolicMeasurement;
+use specs::prelude::*;
+
+/// The current value of a measurement, in virtual units. Angles are in radians, between 0 and
+/// pi. There's no value when the measured geometry is undefined.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Measurement(pub f64);
+
+impl Component for Measurement {
+  type Storage = FlaggedStorage<Self, VecStorage<Self>>;
+}
+
+impl Measurement {
+  /// The value as shown next to the measured geometry, with angles in degrees
+  pub fn text(self, sym: &SymbolicMeasurement) -> String {
+    match sym {
+      SymbolicMeasurement::Distance(_, _) | SymbolicMeasurement::Length(_) => format!("{:.2}", self.0),
+      SymbolicMeasurement::Angle(_, _, _) => format!("{:.1}°", self.0.to_degrees()),
+      SymbolicMeasurement::Area(_) => format!("Area {:.2}", self.0),
+      SymbolicMeasurement::Perimeter(_) => format!("Perimeter {:.2}", self.0),
+      SymbolicMeasurement::Ratio(_, _) => format!("Ratio {:.3}", self.0),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_text() {
+    let mut world = World::new();
+    let (a, b) = (world.create_entity().build(), world.create_entity().build());
+    assert_eq!(Measurement(5.0).text(&SymbolicMeasurement::Distance(a, b)), "5.00");
+    assert_eq!(
+      Measurement(std::f64::consts::FRAC_PI_2).text(&SymbolicMeasurement::Angle(a, b, a)),
+      "90.0°"
+    );
+    assert_eq!(
+      Measurement(2.0 / 3.0).text(&SymbolicMeasurement::Ratio(a, b)),
+      "Ratio 0.667"
+    );
+  }
+}
@@ -0,0 +1,3 @@
+mod measurement;
+
+pub use measurement::*;
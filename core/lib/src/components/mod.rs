@@ -1,4 +1,6 @@
+pub mod labels;
 pub mod markers;
+pub mod measurements;
 pub mod screen_shapes;
 pub mod styles;
 pub mod symbolics;
@@ -0,0 +1,59 @@
+use super::{ScreenCircle, ScreenLine, ScreenPoint, ScreenPolygon};
+use crate::{components::labels::Label, math::*};
+use specs::prelude::*;
+
+/// A label placed on screen, with `rect` being the area taken by the text
+#[derive(Debug, Clone)]
+pub struct ScreenLabel {
+  pub text: String,
+  pub rect: AABB,
+}
+
+impl Component for ScreenLabel {
+  type Storage = FlaggedStorage<Self, VecStorage<Self>>;
+}
+
+impl ScreenLabel {
+  pub fn new(label: &Label, anchor: Vector2) -> Self {
+    let top_left = anchor + label.offset;
+    let size = label.size();
+    Self {
+      text: label.text.clone(),
+      rect: AABB::new(top_left.x, top_left.y, size.x, size.y),
+    }
+  }
+}
+
+/// The point on screen a label is attached to
+pub trait LabelAnchor {
+  fn label_anchor(&self) -> Vector2;
+}
+
+impl LabelAnchor for ScreenPoint {
+  fn label_anchor(&self) -> Vector2 {
+    self.0
+  }
+}
+
+/// The middle of the two points defining the line
+impl LabelAnchor for ScreenLine {
+  fn label_anchor(&self) -> Vector2 {
+    (self.from.0 + self.to.0) / 2.0
+  }
+}
+
+/// The top right of the circle
+impl LabelAnchor for ScreenCircle {
+  fn label_anchor(&self) -> Vector2 {
+    let diagonal = std::f64::consts::FRAC_1_SQRT_2 * self.radius.0;
+    self.center.0 + vec2![diagonal, -diagonal]
+  }
+}
+
+/// The mean of the vertices of the polygon
+impl LabelAnchor for ScreenPolygon {
+  fn label_anchor(&self) -> Vector2 {
+    let sum = self.points.iter().fold(vec2![0., 0.], |sum, point| sum + point.0);
+    sum / self.points.len().max(1) as f64
+  }
+}
@@ -0,0 +1,7 @@
+use specs::prelude::*;
+
+pub use crate::utilities::ScreenLocus;
+
+impl Component for ScreenLocus {
+  type Storage = FlaggedStorage<Self, VecStorage<Self>>;
+}
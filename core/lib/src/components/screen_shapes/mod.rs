@@ -1,9 +1,21 @@
+mod arc;
 mod circle;
+mod conic;
+mod label;
 mod line;
+mod locus;
 mod point;
+mod polygon;
 mod rectangle;
+mod storages;
 
+pub use arc::*;
 pub use circle::*;
+pub use conic::*;
+pub use label::*;
 pub use line::*;
+pub use locus::*;
 pub use point::*;
+pub use polygon::*;
 pub use rectangle::*;
+pub use storages::*;
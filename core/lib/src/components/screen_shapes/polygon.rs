@@ -0,0 +1,7 @@
+use specs::prelude::*;
+
+pub use crate::utilities::ScreenPolygon;
+
+impl Component for ScreenPolygon {
+  type Storage = FlaggedStorage<Self, VecStorage<Self>>;
+}
@@ -0,0 +1,17 @@
+use super::*;
+use crate::system_data;
+use specs::prelude::*;
+
+system_data! {
+  /// The shapes of every kind on screen, along with the labels
+  pub struct ScreenShapes<'a> {
+    pub points: ReadStorage<'a, ScreenPoint>,
+    pub lines: ReadStorage<'a, ScreenLine>,
+    pub circles: ReadStorage<'a, ScreenCircle>,
+    pub polygons: ReadStorage<'a, ScreenPolygon>,
+    pub loci: ReadStorage<'a, ScreenLocus>,
+    pub arcs: ReadStorage<'a, ScreenArc>,
+    pub conics: ReadStorage<'a, ScreenConic>,
+    pub labels: ReadStorage<'a, ScreenLabel>,
+  }
+}
@@ -1,8 +1,9 @@
 use super::LineStyle;
 use crate::math::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct CircleStyle {
   pub fill: Color,
   pub border: LineStyle,
@@ -1,10 +1,13 @@
 use crate::math::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct LineStyle {
   pub color: Color,
   pub width: f64,
+  #[serde(default)]
+  pub dash: Option<Dash>, // Solid when there's none
 }
 
 impl Component for LineStyle {
@@ -16,6 +19,7 @@ impl LineStyle {
     Self {
       color: self.color.apply_alpha(a),
       width: self.width,
+      dash: self.dash,
     }
   }
 }
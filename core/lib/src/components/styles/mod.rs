@@ -1,9 +1,11 @@
 mod circle_style;
 mod line_style;
 mod point_style;
+mod polygon_style;
 mod rectangle_style;
 
 pub use circle_style::*;
 pub use line_style::*;
 pub use point_style::*;
+pub use polygon_style::*;
 pub use rectangle_style::*;
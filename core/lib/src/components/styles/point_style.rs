@@ -1,7 +1,8 @@
 use crate::math::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct PointStyle {
   pub color: Color,
   pub radius: f64,
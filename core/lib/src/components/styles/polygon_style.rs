@@ -0,0 +1,23 @@
+use super::LineStyle;
+use crate::math::*;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct PolygonStyle {
+  pub fill: Color,
+  pub border: LineStyle,
+}
+
+impl Component for PolygonStyle {
+  type Storage = FlaggedStorage<Self, VecStorage<Self>>;
+}
+
+impl PolygonStyle {
+  pub fn apply_alpha(self, a: f32) -> Self {
+    Self {
+      fill: self.fill.apply_alpha(a),
+      border: self.border.apply_alpha(a),
+    }
+  }
+}
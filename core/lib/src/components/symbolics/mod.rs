@@ -1,7 +1,21 @@
+mod storages;
+mod symbolic_arc;
 mod symbolic_circle;
+mod symbolic_conic;
 mod symbolic_line;
+mod symbolic_locus;
+mod symbolic_measurement;
 mod symbolic_point;
+mod symbolic_polygon;
+mod transformation;
 
+pub use storages::*;
+pub use symbolic_arc::*;
 pub use symbolic_circle::*;
+pub use symbolic_conic::*;
 pub use symbolic_line::*;
+pub use symbolic_locus::*;
+pub use symbolic_measurement::*;
 pub use symbolic_point::*;
+pub use symbolic_polygon::*;
+pub use transformation::*;
@@ -0,0 +1,40 @@
+use super::*;
+use crate::{system_data, utilities::GeometrySymbol};
+use specs::prelude::*;
+
+system_data! {
+  /// The symbolic elements of every kind
+  pub struct SymbolicShapes<'a> {
+    pub points: ReadStorage<'a, SymbolicPoint>,
+    pub lines: ReadStorage<'a, SymbolicLine>,
+    pub circles: ReadStorage<'a, SymbolicCircle>,
+    pub polygons: ReadStorage<'a, SymbolicPolygon>,
+    pub measurements: ReadStorage<'a, SymbolicMeasurement>,
+    pub loci: ReadStorage<'a, SymbolicLocus>,
+    pub arcs: ReadStorage<'a, SymbolicArc>,
+    pub conics: ReadStorage<'a, SymbolicConic>,
+  }
+}
+
+impl<'a> SymbolicShapes<'a> {
+  /// The symbol of the element, whatever its kind
+  pub fn get(&self, ent: Entity) -> Option<GeometrySymbol> {
+    if let Some(sym_point) = self.points.get(ent) {
+      Some(GeometrySymbol::Point(*sym_point))
+    } else if let Some(sym_line) = self.lines.get(ent) {
+      Some(GeometrySymbol::Line(*sym_line))
+    } else if let Some(sym_circle) = self.circles.get(ent) {
+      Some(GeometrySymbol::Circle(*sym_circle))
+    } else if let Some(sym_polygon) = self.polygons.get(ent) {
+      Some(GeometrySymbol::Polygon(sym_polygon.clone()))
+    } else if let Some(sym_measurement) = self.measurements.get(ent) {
+      Some(GeometrySymbol::Measurement(*sym_measurement))
+    } else if let Some(sym_locus) = self.loci.get(ent) {
+      Some(GeometrySymbol::Locus(*sym_locus))
+    } else if let Some(sym_arc) = self.arcs.get(ent) {
+      Some(GeometrySymbol::Arc(*sym_arc))
+    } else {
+      self.conics.get(ent).map(|sym_conic| GeometrySymbol::Conic(*sym_conic))
+    }
+  }
+}
@@ -0,0 +1,26 @@
+use crate::math::ArcKind;
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone)]
+pub enum SymbolicArc {
+  ThreePoints(Entity, Entity, Entity, ArcKind), // (From point entity, Through point entity, To point entity, Kind)
+  CenterFromTo(Entity, Entity, Entity, ArcKind), // (Center point entity, From point entity, Towards point entity, Kind)
+}
+
+impl SymbolicArc {
+  pub fn points(&self) -> [Entity; 3] {
+    match *self {
+      SymbolicArc::ThreePoints(p1, p2, p3, _) | SymbolicArc::CenterFromTo(p1, p2, p3, _) => [p1, p2, p3],
+    }
+  }
+
+  pub fn kind(&self) -> ArcKind {
+    match *self {
+      SymbolicArc::ThreePoints(_, _, _, kind) | SymbolicArc::CenterFromTo(_, _, _, kind) => kind,
+    }
+  }
+}
+
+impl Component for SymbolicArc {
+  type Storage = VecStorage<Self>;
+}
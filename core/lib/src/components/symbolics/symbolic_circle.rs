@@ -1,8 +1,15 @@
+use super::Transformation;
 use specs::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
 pub enum SymbolicCircle {
-  CenterRadius(Entity, Entity),
+  CenterRadius(Entity, Entity),            // (Center point entity, Point on circle entity)
+  ThreePoints(Entity, Entity, Entity),     // (Point entity, Point entity, Point entity)
+  Compass(Entity, Entity, Entity),         // (Center point entity, Radius from point entity, Radius to point entity)
+  Transformed(Entity, Transformation),     // (Circle entity, Transformation)
+  Incircle(Entity, Entity, Entity),        // (Point entity, Point entity, Point entity) of the triangle
+  Excircle(Entity, Entity, Entity),        // (Opposite vertex entity, Point entity, Point entity) of the triangle
+  NinePointCircle(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
 }
 
 impl Component for SymbolicCircle {
@@ -0,0 +1,23 @@
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone)]
+pub enum SymbolicConic {
+  FivePoints(Entity, Entity, Entity, Entity, Entity), // (Point entities the conic goes through)
+  FociPoint(Entity, Entity, Entity), // (Focus point entity, Focus point entity, Point entity on the ellipse)
+  FocusDirectrix(Entity, Entity),    // (Focus point entity, Directrix line entity)
+}
+
+impl SymbolicConic {
+  /// The entities the conic is built from
+  pub fn dependencies(&self) -> Vec<Entity> {
+    match *self {
+      SymbolicConic::FivePoints(p1, p2, p3, p4, p5) => vec![p1, p2, p3, p4, p5],
+      SymbolicConic::FociPoint(f1, f2, p) => vec![f1, f2, p],
+      SymbolicConic::FocusDirectrix(focus, directrix) => vec![focus, directrix],
+    }
+  }
+}
+
+impl Component for SymbolicConic {
+  type Storage = VecStorage<Self>;
+}
@@ -1,12 +1,18 @@
+use super::{CircleIntersectId, Transformation};
 use specs::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
 pub enum SymbolicLine {
-  Straight(Entity, Entity),      // (Point Entity, Point Entity)
-  Ray(Entity, Entity),           // (Point Entity, Point Entity)
-  Segment(Entity, Entity),       // (Point Entity, Point Entity)
-  Parallel(Entity, Entity),      // (Line Entity, Point Entity)
-  Perpendicular(Entity, Entity), // (Line Entity, Point Entity)
+  Straight(Entity, Entity),                            // (Point Entity, Point Entity)
+  Ray(Entity, Entity),                                 // (Point Entity, Point Entity)
+  Segment(Entity, Entity),                             // (Point Entity, Point Entity)
+  Parallel(Entity, Entity),                            // (Line Entity, Point Entity)
+  Perpendicular(Entity, Entity),                       // (Line Entity, Point Entity)
+  AngleBisector(Entity, Entity, Entity),               // (Point Entity, Vertex Point Entity, Point Entity)
+  PerpendicularBisector(Entity, Entity),               // (Point Entity, Point Entity)
+  TangentAt(Entity, Entity),                           // (Circle Entity, Point Entity on the circle)
+  TangentFromPoint(Entity, Entity, CircleIntersectId), // (Circle Entity, Point Entity, Id)
+  Transformed(Entity, Transformation),                 // (Line Entity, Transformation)
 }
 
 impl Component for SymbolicLine {
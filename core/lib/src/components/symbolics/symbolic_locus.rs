@@ -0,0 +1,24 @@
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone)]
+pub enum SymbolicLocus {
+  Traced(Entity, Entity), // (Driver point entity on a line, a circle or an arc, Traced point entity)
+}
+
+impl SymbolicLocus {
+  pub fn driver(&self) -> Entity {
+    match *self {
+      SymbolicLocus::Traced(driver_ent, _) => driver_ent,
+    }
+  }
+
+  pub fn traced(&self) -> Entity {
+    match *self {
+      SymbolicLocus::Traced(_, traced_ent) => traced_ent,
+    }
+  }
+}
+
+impl Component for SymbolicLocus {
+  type Storage = VecStorage<Self>;
+}
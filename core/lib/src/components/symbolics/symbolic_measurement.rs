@@ -0,0 +1,29 @@
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone)]
+pub enum SymbolicMeasurement {
+  Distance(Entity, Entity),      // (Point Entity, Point Entity)
+  Length(Entity),                // (Segment Entity)
+  Angle(Entity, Entity, Entity), // (Point Entity, Vertex Point Entity, Point Entity)
+  Area(Entity),                  // (Circle or Polygon Entity)
+  Perimeter(Entity),             // (Circle or Polygon Entity)
+  Ratio(Entity, Entity),         // (Measurement Entity, Measurement Entity)
+}
+
+impl SymbolicMeasurement {
+  /// The entities the measurement is taken on
+  pub fn entities(&self) -> Vec<Entity> {
+    match *self {
+      SymbolicMeasurement::Distance(p1_ent, p2_ent) => vec![p1_ent, p2_ent],
+      SymbolicMeasurement::Length(l_ent) => vec![l_ent],
+      SymbolicMeasurement::Angle(p1_ent, v_ent, p2_ent) => vec![p1_ent, v_ent, p2_ent],
+      SymbolicMeasurement::Area(ent) => vec![ent],
+      SymbolicMeasurement::Perimeter(ent) => vec![ent],
+      SymbolicMeasurement::Ratio(m1_ent, m2_ent) => vec![m1_ent, m2_ent],
+    }
+  }
+}
+
+impl Component for SymbolicMeasurement {
+  type Storage = VecStorage<Self>;
+}
@@ -1,4 +1,6 @@
+use super::Transformation;
 use crate::utilities::*;
+use serde::{Deserialize, Serialize};
 use specs::prelude::*;
 
 #[derive(Debug, Copy, Clone)]
@@ -11,9 +13,19 @@ pub enum SymbolicPoint {
   OnCircle(Entity, f64),                                    // (Circle entity, theta)
   CircleLineIntersect(Entity, Entity, CircleIntersectId),   // (Circle entity, Line entity, Id)
   CircleCircleIntersect(Entity, Entity, CircleIntersectId), // (Circle entity, Circle entity, Id)
+  Transformed(Entity, Transformation),                      // (Point entity, Transformation)
+  OnArc(Entity, f64), // (Arc entity, fraction of the way from its start to its end)
+  ArcLineIntersect(Entity, Entity, CircleIntersectId), // (Arc entity, Line entity, Id on the circle of the arc)
+  ArcCircleIntersect(Entity, Entity, CircleIntersectId), // (Arc entity, Circle entity, Id on the circle of the arc)
+  ConicLineIntersect(Entity, Entity, CircleIntersectId), // (Conic entity, Line entity, Id in the order along the line)
+  Centroid(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
+  Circumcenter(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
+  Orthocenter(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
+  Incenter(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
+  NinePointCenter(Entity, Entity, Entity), // (Point entity, Point entity, Point entity) of the triangle
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum CircleIntersectId {
   First,
   Second,
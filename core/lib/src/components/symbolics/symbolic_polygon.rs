@@ -0,0 +1,18 @@
+use specs::prelude::*;
+
+#[derive(Debug, Clone)]
+pub enum SymbolicPolygon {
+  Vertices(Vec<Entity>), // (Point entities, in order around the polygon)
+}
+
+impl SymbolicPolygon {
+  pub fn vertices(&self) -> &[Entity] {
+    match self {
+      SymbolicPolygon::Vertices(vertices) => vertices,
+    }
+  }
+}
+
+impl Component for SymbolicPolygon {
+  type Storage = VecStorage<Self>;
+}
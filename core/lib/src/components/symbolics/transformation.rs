@@ -0,0 +1,23 @@
+use specs::prelude::*;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Transformation {
+  Reflect(Entity),                               // (Mirror line entity)
+  Rotate(Entity, f64),                           // (Center point entity, Angle in radians)
+  RotateByAngle(Entity, Entity, Entity, Entity), // (Center point entity, Point entity, Vertex point entity, Point entity)
+  Translate(Entity, Entity),                     // (From point entity, To point entity)
+  Dilate(Entity, f64),                           // (Center point entity, Factor)
+}
+
+impl Transformation {
+  /// The entities the transformation itself depends on
+  pub fn entities(&self) -> Vec<Entity> {
+    match *self {
+      Transformation::Reflect(l_ent) => vec![l_ent],
+      Transformation::Rotate(c_ent, _) => vec![c_ent],
+      Transformation::RotateByAngle(c_ent, p1_ent, v_ent, p2_ent) => vec![c_ent, p1_ent, v_ent, p2_ent],
+      Transformation::Translate(p1_ent, p2_ent) => vec![p1_ent, p2_ent],
+      Transformation::Dilate(c_ent, _) => vec![c_ent],
+    }
+  }
+}
@@ -0,0 +1,7 @@
+use specs::prelude::*;
+
+pub use crate::utilities::VirtualLocus;
+
+impl Component for VirtualLocus {
+  type Storage = VecStorage<Self>;
+}
@@ -1,7 +1,17 @@
+mod arc;
 mod circle;
+mod conic;
 mod line;
+mod locus;
 mod point;
+mod polygon;
+mod storages;
 
+pub use arc::*;
 pub use circle::*;
+pub use conic::*;
 pub use line::*;
+pub use locus::*;
 pub use point::*;
+pub use polygon::*;
+pub use storages::*;
@@ -0,0 +1,16 @@
+use super::*;
+use crate::system_data;
+use specs::prelude::*;
+
+system_data! {
+  /// The virtual shapes of every kind
+  pub struct VirtualShapes<'a> {
+    pub points: ReadStorage<'a, VirtualPoint>,
+    pub lines: ReadStorage<'a, VirtualLine>,
+    pub circles: ReadStorage<'a, VirtualCircle>,
+    pub polygons: ReadStorage<'a, VirtualPolygon>,
+    pub loci: ReadStorage<'a, VirtualLocus>,
+    pub arcs: ReadStorage<'a, VirtualArc>,
+    pub conics: ReadStorage<'a, VirtualConic>,
+  }
+}
@@ -1,48 +1,137 @@
-use crate::components::{styles::*, symbolics::*};
+use crate::{
+  components::{labels::*, styles::*, symbolics::*},
+  math::*,
+  utilities::*,
+};
 use shrev::*;
 use specs::prelude::*;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CommandEvent {
   pub command: Command,
   pub event_id: Option<usize>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Command {
   PointInsert(InsertPointEvent),
   LineInsert(InsertLineEvent),
   CircleInsert(InsertCircleEvent),
+  PolygonInsert(InsertPolygonEvent),
+  MeasurementInsert(InsertMeasurementEvent),
+  LocusInsert(InsertLocusEvent),
+  ArcInsert(InsertArcEvent),
+  ConicInsert(InsertConicEvent),
   Remove(RemoveEvent),
   Update(UpdateEvent),
+  UpdateStyle(UpdateStyleEvent),
+  Label(LabelEvent),
   Select(SelectEvent),
   Hide(HideEvent),
+  Transform(TransformEvent),
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum InsertPointEvent {
   InsertPoint(SymbolicPoint),
   InsertMidPointFromSelection,
+  InsertTriangleCenterFromSelection(TriangleCenter),
   InsertPointWithStyle(SymbolicPoint, PointStyle),
+  InsertPointWithEntity(Entity, SymbolicPoint, PointStyle), // Into an entity created beforehand
   InsertPointByHistory(Entity, SymbolicPoint, PointStyle),
 }
 
+/// The centers of the triangle of the three selected points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriangleCenter {
+  Centroid,
+  Circumcenter,
+  Orthocenter,
+  Incenter,
+  NinePointCenter,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum InsertLineEvent {
   InsertLine(SymbolicLine),
   InsertParallelFromSelection,
   InsertPerpendicularFromSelection,
+  InsertAngleBisectorFromSelection,
+  InsertPerpendicularBisectorFromSelection,
+  InsertTangentsFromSelection,
   InsertLineWithStyle(SymbolicLine, LineStyle),
+  InsertLineWithEntity(Entity, SymbolicLine, LineStyle), // Into an entity created beforehand
   InsertLineByHistory(Entity, SymbolicLine, LineStyle),
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum InsertCircleEvent {
   InsertCircle(SymbolicCircle),
+  InsertThreePointCircleFromSelection,
+  InsertCompassCircleFromSelection,
+  InsertTriangleCircleFromSelection(TriangleCircle),
   InsertCircleWithStyle(SymbolicCircle, CircleStyle),
+  InsertCircleWithEntity(Entity, SymbolicCircle, CircleStyle), // Into an entity created beforehand
   InsertCircleByHistory(Entity, SymbolicCircle, CircleStyle),
 }
 
+/// The circles of the triangle of the three selected points. All three excircles are inserted
+/// at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriangleCircle {
+  Incircle,
+  Excircles,
+  NinePointCircle,
+}
+
+#[derive(Debug, Clone)]
+pub enum InsertPolygonEvent {
+  InsertPolygon(SymbolicPolygon),
+  InsertPolygonFromSelection,
+  InsertPolygonWithStyle(SymbolicPolygon, PolygonStyle),
+  InsertPolygonWithEntity(Entity, SymbolicPolygon, PolygonStyle), // Into an entity created beforehand
+  InsertPolygonByHistory(Entity, SymbolicPolygon, PolygonStyle),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum InsertMeasurementEvent {
+  InsertMeasurement(SymbolicMeasurement),
+  InsertDistanceFromSelection,
+  InsertAngleFromSelection,
+  InsertAreaFromSelection,
+  InsertPerimeterFromSelection,
+  InsertRatioFromSelection,
+  InsertMeasurementWithEntity(Entity, SymbolicMeasurement), // Into an entity created beforehand
+  InsertMeasurementByHistory(Entity, SymbolicMeasurement),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum InsertLocusEvent {
+  InsertLocus(SymbolicLocus),
+  InsertLocusFromSelection,
+  InsertLocusWithStyle(SymbolicLocus, LineStyle),
+  InsertLocusWithEntity(Entity, SymbolicLocus, LineStyle), // Into an entity created beforehand
+  InsertLocusByHistory(Entity, SymbolicLocus, LineStyle),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum InsertArcEvent {
+  InsertArc(SymbolicArc),
+  InsertArcFromSelection(ArcKind),
+  InsertArcWithStyle(SymbolicArc, PolygonStyle),
+  InsertArcWithEntity(Entity, SymbolicArc, PolygonStyle), // Into an entity created beforehand
+  InsertArcByHistory(Entity, SymbolicArc, PolygonStyle),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum InsertConicEvent {
+  InsertConic(SymbolicConic),
+  InsertConicFromSelection,
+  InsertConicWithStyle(SymbolicConic, LineStyle),
+  InsertConicWithEntity(Entity, SymbolicConic, LineStyle), // Into an entity created beforehand
+  InsertConicByHistory(Entity, SymbolicConic, LineStyle),
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum RemoveEvent {
   Remove(Entity),
@@ -58,6 +147,24 @@ pub enum UpdateEvent {
   UpdatePointByHistory(Entity, SymbolicPoint, SymbolicPoint), // Entity, before, after
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum UpdateStyleEvent {
+  UpdateStyle(Entity, StyleUpdate),
+  UpdateSelectedStyle(StyleUpdate),
+  SetStyle(Entity, Style),
+  SetStyleByHistory(Entity, Style),
+}
+
+/// Label changes are not recorded in the history, so they can't be undone. Undoing the removal
+/// of an element brings its label back though, as the label stays with the removed entity
+#[derive(Debug, Clone)]
+pub enum LabelEvent {
+  Rename(Entity, String),     // An empty name removes the label
+  RenameSelected(String),     // Only when exactly one point, line or circle is selected
+  MoveLabel(Entity, Vector2), // Entity, new offset from the anchor
+  SetLabel(Entity, Label),
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum SelectEvent {
   Select(Entity),
@@ -76,6 +183,11 @@ pub enum HideEvent {
   UnhideAll,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum TransformEvent {
+  TransformSelection(Transformation),
+}
+
 pub type CommandEventChannel = EventChannel<CommandEvent>;
 
 pub type CommandEventReader = ReaderId<CommandEvent>;
@@ -0,0 +1,13 @@
+use shrev::*;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub enum FileEvent {
+  Save(PathBuf),
+  Load(PathBuf),
+  Recover(PathBuf), // Replay a history journal
+}
+
+pub type FileEventChannel = EventChannel<FileEvent>;
+
+pub type FileEventReader = ReaderId<FileEvent>;
@@ -1,12 +1,18 @@
-use crate::{components::symbolics::SymbolicPoint, utilities::Geometry};
+use crate::{
+  components::symbolics::SymbolicPoint,
+  utilities::{Geometry, Style},
+};
 use shrev::{EventChannel, ReaderId};
 use specs::prelude::*;
 
+#[derive(Debug, Clone)]
 pub enum GeometryEvent {
   Inserted(Entity, Geometry, bool),
   Removed(Entity, Geometry, bool),
   PointUpdated(Entity, SymbolicPoint, SymbolicPoint, bool),
   PointUpdateFinished(Entity, SymbolicPoint, SymbolicPoint, bool),
+  StyleUpdated(Entity, Style, Style, bool), // Entity, old, new, is done by history
+  LabelUpdated(Entity),
 }
 
 pub type GeometryEventChannel = EventChannel<GeometryEvent>;
@@ -49,4 +55,12 @@ impl GeometryEvent {
   ) -> Self {
     GeometryEvent::PointUpdateFinished(entity, old_sym_point, new_sym_point, true)
   }
+
+  pub fn style_updated(entity: Entity, old_style: Style, new_style: Style) -> Self {
+    GeometryEvent::StyleUpdated(entity, old_style, new_style, false)
+  }
+
+  pub fn style_updated_by_history(entity: Entity, old_style: Style, new_style: Style) -> Self {
+    GeometryEvent::StyleUpdated(entity, old_style, new_style, true)
+  }
 }
@@ -5,6 +5,8 @@ pub enum HistoryEvent {
   Redo,
   Undo,
   Clear,
+  BeginTransaction,  // The modifications from this frame on are undone as one
+  CommitTransaction, // Up to this frame, included
 }
 
 pub type HistoryEventChannel = EventChannel<HistoryEvent>;
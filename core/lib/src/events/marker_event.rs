@@ -1,6 +1,7 @@
 use shrev::*;
 use specs::prelude::*;
 
+#[derive(Debug, Clone)]
 pub enum MarkerEvent {
   Select(Entity),
   Deselect(Entity),
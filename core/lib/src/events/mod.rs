@@ -1,10 +1,12 @@
 mod command_event;
+mod file_event;
 mod geometry_event;
 mod history_event;
 mod marker_event;
 mod viewport_event;
 
 pub use command_event::*;
+pub use file_event::*;
 pub use geometry_event::*;
 pub use history_event::*;
 pub use marker_event::*;
@@ -1,10 +1,12 @@
-use crate::math::*;
+use crate::{math::*, utilities::*};
 use shrev::{EventChannel, ReaderId};
 
 pub enum ViewportEvent {
-  Move(Vector2),   // Virtual Center
-  Scale(f64),      // Change in pixel
-  Resize(Vector2), // Screen Size
+  Move(Vector2),             // Virtual Center
+  Scale(f64),                // Change in pixel
+  Resize(Vector2),           // Screen Size
+  Zoom(f64, ScreenPosition), // Zoom factor, and the screen position that stays still
+  ZoomToFit,                 // Show all the elements that are not hidden
 }
 
 pub type ViewportEventChannel = EventChannel<ViewportEvent>;
@@ -14,15 +14,16 @@ use systems::*;
 
 pub fn setup_core_lib<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
   builder.add_barrier();
+  builder.add(event_handlers::FileEventHandler::default(), "file_event_handler", &[]);
   builder.add(
     event_handlers::ViewportEventHandler::default(),
     "viewport_event_handler",
-    &[],
+    &["file_event_handler"],
   );
   builder.add(
     event_handlers::HistoryEventHandler::default(),
     "history_event_handler",
-    &[],
+    &["file_event_handler"],
   );
   builder.add(
     command_handlers::RemoveHandler::default(),
@@ -44,11 +45,51 @@ pub fn setup_core_lib<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
     "insert_circle_handler",
     &["history_event_handler"],
   );
+  builder.add(
+    command_handlers::InsertPolygonHandler::default(),
+    "insert_polygon_handler",
+    &["history_event_handler"],
+  );
+  builder.add(
+    command_handlers::InsertMeasurementHandler::default(),
+    "insert_measurement_handler",
+    &["history_event_handler"],
+  );
+  builder.add(
+    command_handlers::InsertLocusHandler::default(),
+    "insert_locus_handler",
+    &["history_event_handler"],
+  );
+  builder.add(
+    command_handlers::InsertArcHandler::default(),
+    "insert_arc_handler",
+    &["history_event_handler"],
+  );
+  builder.add(
+    command_handlers::InsertConicHandler::default(),
+    "insert_conic_handler",
+    &["history_event_handler"],
+  );
   builder.add(
     command_handlers::UpdatePointHandler::default(),
     "update_point_handler",
     &["history_event_handler"],
   );
+  builder.add(
+    command_handlers::UpdateStyleHandler::default(),
+    "update_style_handler",
+    &["history_event_handler"],
+  );
+  builder.add(
+    command_handlers::LabelHandler::default(),
+    "label_handler",
+    &["insert_point_handler", "insert_line_handler", "insert_circle_handler"],
+  );
+  builder.add(
+    command_handlers::TransformHandler::default(),
+    "transform_handler",
+    &["history_event_handler"],
+  );
   builder.add(
     command_handlers::HideHandler::default(),
     "hide_handler",
@@ -67,7 +108,14 @@ pub fn setup_core_lib<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
       "insert_point_handler",
       "insert_line_handler",
       "insert_circle_handler",
+      "insert_polygon_handler",
+      "insert_measurement_handler",
+      "insert_locus_handler",
+      "insert_arc_handler",
+      "insert_conic_handler",
       "update_point_handler",
+      "update_style_handler",
+      "transform_handler",
       "hide_handler",
     ],
   );
@@ -79,7 +127,37 @@ pub fn setup_core_lib<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
       "insert_point_handler",
       "insert_line_handler",
       "insert_circle_handler",
+      "insert_polygon_handler",
+      "insert_measurement_handler",
+      "insert_locus_handler",
+      "insert_arc_handler",
+      "insert_conic_handler",
       "update_point_handler",
+      "transform_handler",
+    ],
+  );
+  builder.add(
+    data_managers::CreationOrderManager::default(),
+    "creation_order_manager",
+    &[
+      "insert_point_handler",
+      "insert_line_handler",
+      "insert_circle_handler",
+      "insert_polygon_handler",
+      "insert_measurement_handler",
+      "insert_locus_handler",
+      "insert_arc_handler",
+      "insert_conic_handler",
+    ],
+  );
+  builder.add(
+    data_managers::LabelManager::default(),
+    "label_manager",
+    &[
+      "remove_handler",
+      "insert_point_handler",
+      "label_handler",
+      "transform_handler",
     ],
   );
   builder.add(
@@ -90,7 +168,7 @@ pub fn setup_core_lib<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
   builder.add(
     solvers::ScreenShapeSolver::default(),
     "screen_shape_solver",
-    &["virtual_shape_solver", "viewport_event_handler"],
+    &["virtual_shape_solver", "viewport_event_handler", "label_manager"],
   );
   builder.add(
     data_managers::SpatialEntityMapManager::default(),
@@ -23,6 +23,14 @@ impl AABB {
     }
   }
 
+  /// The smallest AABB containing both
+  pub fn union(&self, other: AABB) -> Self {
+    Self::two_points(
+      vec2![self.x_min().min(other.x_min()), self.y_min().min(other.y_min())],
+      vec2![self.x_max().max(other.x_max()), self.y_max().max(other.y_max())],
+    )
+  }
+
   pub fn min(&self) -> Vector2 {
     vec2![self.x, self.y]
   }
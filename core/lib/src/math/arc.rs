@@ -0,0 +1,242 @@
+use super::{Circle, CircleIntersect, Vector2, AABB};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+static ARC_ANGLE_THRESHOLD: f64 = 1e-9;
+static ARC_DEGENERATE_THRESHOLD: f64 = 1e-10;
+
+/// What is filled along with the arc. A sector is closed by the two radii, a circular segment
+/// by the chord. A plain arc is never filled
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArcKind {
+  Arc,
+  Sector,
+  Segment,
+}
+
+/// The part of a circle swept counterclockwise from the `start` angle by `sweep` radians, with
+/// `sweep` within `[0, 2π)`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Arc {
+  pub center: Vector2,
+  pub radius: f64,
+  pub start: f64,
+  pub sweep: f64,
+  pub kind: ArcKind,
+}
+
+/// The angle within `[0, 2π)`
+fn normalize_angle(theta: f64) -> f64 {
+  theta.rem_euclid(2.0 * PI)
+}
+
+impl Arc {
+  /// The arc from `a` to `c` going through `b`, in whichever direction that takes. There's no
+  /// such arc when the points are collinear
+  pub fn from_three_points(a: Vector2, b: Vector2, c: Vector2, kind: ArcKind) -> Option<Self> {
+    let Circle { center, radius } = Circle::from_three_points(a, b, c)?;
+    let angle_of = |p: Vector2| (p.y - center.y).atan2(p.x - center.x);
+    let (theta_a, theta_b, theta_c) = (angle_of(a), angle_of(b), angle_of(c));
+    let sweep = normalize_angle(theta_c - theta_a);
+    let (start, sweep) = if normalize_angle(theta_b - theta_a) <= sweep {
+      (theta_a, sweep)
+    } else {
+      (theta_c, 2.0 * PI - sweep)
+    };
+    Some(Self {
+      center,
+      radius,
+      start,
+      sweep,
+      kind,
+    })
+  }
+
+  /// The arc around `center` going counterclockwise from `from` up to the ray towards `to`.
+  /// The radius is the distance to `from`, so `to` doesn't need to be on the arc
+  pub fn from_center(center: Vector2, from: Vector2, to: Vector2, kind: ArcKind) -> Option<Self> {
+    let (from_diff, to_diff) = (from - center, to - center);
+    if from_diff.magnitude() < ARC_DEGENERATE_THRESHOLD || to_diff.magnitude() < ARC_DEGENERATE_THRESHOLD {
+      return None;
+    }
+    let start = from_diff.y.atan2(from_diff.x);
+    Some(Self {
+      center,
+      radius: from_diff.magnitude(),
+      start,
+      sweep: normalize_angle(to_diff.y.atan2(to_diff.x) - start),
+      kind,
+    })
+  }
+
+  pub fn is_degenerate(&self) -> bool {
+    self.circle().is_degenerate() || !self.start.is_finite() || !self.sweep.is_finite()
+  }
+
+  pub fn circle(&self) -> Circle {
+    Circle {
+      center: self.center,
+      radius: self.radius,
+    }
+  }
+
+  pub fn point_at_angle(&self, theta: f64) -> Vector2 {
+    self.center + vec2![theta.cos(), theta.sin()] * self.radius
+  }
+
+  pub fn start_point(&self) -> Vector2 {
+    self.point_at_angle(self.start)
+  }
+
+  pub fn end_point(&self) -> Vector2 {
+    self.point_at_angle(self.start + self.sweep)
+  }
+
+  /// The point at `fraction` of the way along the arc, from `0` at its start to `1` at its end
+  pub fn point_at_fraction(&self, fraction: f64) -> Vector2 {
+    self.point_at_angle(self.start + self.sweep * fraction)
+  }
+
+  pub fn angle_of(&self, p: Vector2) -> f64 {
+    (p.y - self.center.y).atan2(p.x - self.center.x)
+  }
+
+  /// Whether the ray from the center at `theta` goes through the arc
+  pub fn contains_angle(&self, theta: f64) -> bool {
+    let from_start = normalize_angle(theta - self.start);
+    from_start <= self.sweep + ARC_ANGLE_THRESHOLD || from_start >= 2.0 * PI - ARC_ANGLE_THRESHOLD
+  }
+
+  /// How far along the arc the point closest to `p` is, from `0` to `1`
+  pub fn fraction_of(&self, p: Vector2) -> f64 {
+    let from_start = normalize_angle(self.angle_of(p) - self.start);
+    if from_start <= self.sweep {
+      if self.sweep > 0.0 {
+        from_start / self.sweep
+      } else {
+        0.0
+      }
+    } else if from_start - self.sweep < 2.0 * PI - from_start {
+      1.0
+    } else {
+      0.0
+    }
+  }
+
+  pub fn closest_point(&self, p: Vector2) -> Vector2 {
+    self.point_at_fraction(self.fraction_of(p))
+  }
+
+  /// The distance from `p` to the arc itself, without the radii or the chord
+  pub fn distance(&self, p: Vector2) -> f64 {
+    (p - self.closest_point(p)).magnitude()
+  }
+
+  /// The distance from `p` to the outline of the filled shape, which is only the arc when it's
+  /// not filled
+  pub fn border_distance(&self, p: Vector2) -> f64 {
+    let to_segment = |a: Vector2, b: Vector2| {
+      let ab = b - a;
+      let len_sq = ab.x * ab.x + ab.y * ab.y;
+      if len_sq == 0.0 {
+        (p - a).magnitude()
+      } else {
+        let t = (((p - a).x * ab.x + (p - a).y * ab.y) / len_sq).clamp(0.0, 1.0);
+        (p - (a + ab * t)).magnitude()
+      }
+    };
+    let (from, to) = (self.start_point(), self.end_point());
+    let arc_dist = self.distance(p);
+    match self.kind {
+      ArcKind::Arc => arc_dist,
+      ArcKind::Sector => arc_dist
+        .min(to_segment(self.center, from))
+        .min(to_segment(self.center, to)),
+      ArcKind::Segment => arc_dist.min(to_segment(from, to)),
+    }
+  }
+
+  /// Whether `p` is in the filled shape. A plain arc contains nothing
+  pub fn contains(&self, p: Vector2) -> bool {
+    if (p - self.center).magnitude() > self.radius {
+      return false;
+    }
+    match self.kind {
+      ArcKind::Arc => false,
+      ArcKind::Sector => self.contains_angle(self.angle_of(p)),
+      ArcKind::Segment => {
+        // The chord splits the disk, and the segment is on the side of the middle of the arc
+        let (from, to, mid) = (self.start_point(), self.end_point(), self.point_at_fraction(0.5));
+        let side = |q: Vector2| (to - from).x * (q - from).y - (to - from).y * (q - from).x;
+        side(p) * side(mid) >= 0.0
+      }
+    }
+  }
+
+  /// Only keep the intersections with the circle of the arc that are on the arc
+  pub fn keep_on_arc(&self, itsct: CircleIntersect) -> CircleIntersect {
+    let on_arc = |p: Vector2| self.contains_angle(self.angle_of(p));
+    match itsct {
+      CircleIntersect::TwoPoints(p1, p2) => match (on_arc(p1), on_arc(p2)) {
+        (true, true) => CircleIntersect::TwoPoints(p1, p2),
+        (true, false) => CircleIntersect::OnePoint(p1),
+        (false, true) => CircleIntersect::OnePoint(p2),
+        (false, false) => CircleIntersect::None,
+      },
+      CircleIntersect::OnePoint(p) if on_arc(p) => CircleIntersect::OnePoint(p),
+      _ => CircleIntersect::None,
+    }
+  }
+
+  pub fn bounding_box(&self) -> AABB {
+    let mut points = vec![self.start_point(), self.end_point()];
+    if self.kind == ArcKind::Sector {
+      points.push(self.center);
+    }
+    for quarter in 0..4 {
+      let theta = f64::from(quarter) * PI / 2.0;
+      if self.contains_angle(theta) {
+        points.push(self.point_at_angle(theta));
+      }
+    }
+    let first = points[0];
+    let (min, max) = points.iter().fold((first, first), |(min, max), p| {
+      (
+        vec2![min.x.min(p.x), min.y.min(p.y)],
+        vec2![max.x.max(p.x), max.y.max(p.y)],
+      )
+    });
+    AABB::two_points(min, max)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_arc_from_points() {
+    // From (1, 0) through (0, -1) to (-1, 0) goes clockwise, so it starts at (-1, 0)
+    let arc = Arc::from_three_points(vec2![1., 0.], vec2![0., -1.], vec2![-1., 0.], ArcKind::Arc).unwrap();
+    assert!((arc.center - vec2![0., 0.]).magnitude() < 1e-10);
+    assert!((arc.start_point() - vec2![-1., 0.]).magnitude() < 1e-10);
+    assert!((arc.sweep - PI).abs() < 1e-10);
+    assert!(arc.contains_angle(-PI / 2.0) && !arc.contains_angle(PI / 2.0));
+    assert!((arc.closest_point(vec2![0.5, 2.]) - vec2![1., 0.]).magnitude() < 1e-10);
+
+    // A quarter, from the center, and only the quarter is filled
+    let sector = Arc::from_center(vec2![0., 0.], vec2![2., 0.], vec2![0., 5.], ArcKind::Sector).unwrap();
+    assert!((sector.radius - 2.0).abs() < 1e-10 && (sector.sweep - PI / 2.0).abs() < 1e-10);
+    assert!(sector.contains(vec2![0.5, 0.5]) && !sector.contains(vec2![-0.5, 0.5]));
+    assert!((sector.border_distance(vec2![1., -1.]) - 1.0).abs() < 1e-10);
+    let aabb = sector.bounding_box();
+    assert_eq!((aabb.x, aabb.y), (0., 0.));
+    assert!((aabb.width - 2.0).abs() < 1e-10 && (aabb.height - 2.0).abs() < 1e-10);
+
+    let segment = Arc {
+      kind: ArcKind::Segment,
+      ..sector
+    };
+    assert!(segment.contains(vec2![1.2, 1.2]) && !segment.contains(vec2![0.5, 0.5]));
+  }
+}
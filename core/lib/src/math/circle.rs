@@ -1,6 +1,95 @@
-use super::Vector2;
+use super::{CircleIntersect, Vector2};
+
+static CIRCLE_TANGENT_THRESHOLD: f64 = 1e-5;
+static CIRCLE_DEGENERATE_THRESHOLD: f64 = 1e-10;
 
 pub struct Circle {
   pub center: Vector2,
   pub radius: f64,
 }
+
+impl Circle {
+  /// A circle of zero radius is only its center
+  pub fn is_degenerate(&self) -> bool {
+    !self.center.is_finite() || !self.radius.is_finite() || self.radius < CIRCLE_DEGENERATE_THRESHOLD
+  }
+
+  /// The circumcircle of the three points. There's no such circle when the points are collinear
+  pub fn from_three_points(a: Vector2, b: Vector2, c: Vector2) -> Option<Self> {
+    let (ab, ac) = (b - a, c - a);
+    let d = 2.0 * (ab.x * ac.y - ab.y * ac.x);
+    if d.abs() <= 1e-12 * ab.magnitude() * ac.magnitude() {
+      None
+    } else {
+      let (ab_sq, ac_sq) = (ab.x * ab.x + ab.y * ab.y, ac.x * ac.x + ac.y * ac.y);
+      let rel_center = vec2![(ac.y * ab_sq - ab.y * ac_sq) / d, (ab.x * ac_sq - ac.x * ab_sq) / d];
+      Some(Self {
+        center: a + rel_center,
+        radius: rel_center.magnitude(),
+      })
+    }
+  }
+
+  /// The points where the lines through `p` touch the circle. There are two of them when `p`
+  /// is outside of the circle, only `p` itself when it's on the circle, and none when inside
+  pub fn tangent_points(&self, p: Vector2) -> CircleIntersect {
+    let diff = p - self.center;
+    let dist = diff.magnitude();
+    if dist < self.radius - CIRCLE_TANGENT_THRESHOLD {
+      CircleIntersect::None
+    } else if dist < self.radius + CIRCLE_TANGENT_THRESHOLD {
+      if dist == 0.0 {
+        CircleIntersect::None
+      } else {
+        CircleIntersect::OnePoint(self.center + diff / dist * self.radius)
+      }
+    } else {
+      let dir = diff / dist;
+      let (cos, sin) = (self.radius / dist, (1.0 - (self.radius / dist).powi(2)).sqrt());
+      let p1 = self.center + vec2![dir.x * cos - dir.y * sin, dir.x * sin + dir.y * cos] * self.radius;
+      let p2 = self.center + vec2![dir.x * cos + dir.y * sin, dir.y * cos - dir.x * sin] * self.radius;
+      CircleIntersect::TwoPoints(p1, p2)
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_circle_from_three_points() {
+    let c = Circle::from_three_points(vec2![1.0, 0.0], vec2![0.0, 1.0], vec2![-1.0, 0.0]).unwrap();
+    assert!((c.center - vec2![0.0, 0.0]).magnitude() < 1e-10);
+    assert!((c.radius - 1.0).abs() < 1e-10);
+
+    let c = Circle::from_three_points(vec2![3.0, 1.0], vec2![5.0, 3.0], vec2![7.0, 1.0]).unwrap();
+    assert!((c.center - vec2![5.0, 1.0]).magnitude() < 1e-10);
+    assert!((c.radius - 2.0).abs() < 1e-10);
+
+    assert!(Circle::from_three_points(vec2![0.0, 0.0], vec2![1.0, 1.0], vec2![2.0, 2.0]).is_none());
+    assert!(Circle::from_three_points(vec2![1.0, 1.0], vec2![1.0, 1.0], vec2![2.0, 0.0]).is_none());
+  }
+
+  #[test]
+  fn test_circle_tangent_points() {
+    let c = Circle {
+      center: vec2![0.0, 0.0],
+      radius: 1.0,
+    };
+    match c.tangent_points(vec2![2.0, 0.0]) {
+      CircleIntersect::TwoPoints(p1, p2) => {
+        let s = 3.0f64.sqrt() / 2.0;
+        assert!((p1 - vec2![0.5, s]).magnitude() < 1e-10);
+        assert!((p2 - vec2![0.5, -s]).magnitude() < 1e-10);
+      }
+      _ => panic!("Expected two tangent points"),
+    }
+    match c.tangent_points(vec2![0.0, 1.0]) {
+      CircleIntersect::OnePoint(p) => assert!((p - vec2![0.0, 1.0]).magnitude() < 1e-10),
+      _ => panic!("Expected one tangent point"),
+    }
+    assert!(matches!(c.tangent_points(vec2![0.5, 0.0]), CircleIntersect::None));
+    assert!(matches!(c.tangent_points(vec2![0.0, 0.0]), CircleIntersect::None));
+  }
+}
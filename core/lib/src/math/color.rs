@@ -1,4 +1,6 @@
-#[derive(Debug, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Color {
   pub r: f32,
   pub g: f32,
@@ -81,6 +83,14 @@ impl Color {
   pub fn magenta() -> Self {
     rgb!(1.0, 0.0, 1.0)
   }
+
+  pub fn gray() -> Self {
+    rgb!(0.5, 0.5, 0.5)
+  }
+
+  pub fn light_gray() -> Self {
+    rgb!(0.85, 0.85, 0.85)
+  }
 }
 
 impl Into<[f32; 4]> for Color {
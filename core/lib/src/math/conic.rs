@@ -0,0 +1,492 @@
+use super::{DotProduct, Line, Polyline, Transform, Vector2, AABB};
+
+static CONIC_DEGENERATE_THRESHOLD: f64 = 1e-9;
+static CONIC_RANK_THRESHOLD: f64 = 1e-10;
+static CONIC_SAMPLES: usize = 64;
+static CONIC_MAX_STEP: f64 = 4.0;
+static CONIC_MAX_DEPTH: u32 = 20;
+
+/// The points where `a x² + b xy + c y² + d x + e y + f` is zero. The coefficients are only
+/// defined up to a common factor
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Conic {
+  pub a: f64,
+  pub b: f64,
+  pub c: f64,
+  pub d: f64,
+  pub e: f64,
+  pub f: f64,
+}
+
+/// A conic placed in the plane: its points are `rotation(angle) * (x, y) + center` for the
+/// `(x, y)` of its standard form. The ellipse is `x²/rx² + y²/ry² = 1`, the hyperbola is
+/// `x²/rx² - y²/ry² = 1` and the parabola is `4 focal x = y²`, with its vertex as the center
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConicShape {
+  Ellipse {
+    center: Vector2,
+    angle: f64,
+    rx: f64,
+    ry: f64,
+  },
+  Hyperbola {
+    center: Vector2,
+    angle: f64,
+    rx: f64,
+    ry: f64,
+  },
+  Parabola {
+    center: Vector2,
+    angle: f64,
+    focal: f64,
+  },
+}
+
+impl Conic {
+  /// The conic through the five points. There's none when four of them are collinear or two of
+  /// them are the same, since then many conics go through them
+  pub fn from_five_points(points: [Vector2; 5]) -> Option<Self> {
+    // Solve in coordinates around the centroid and scaled to a unit spread, which keeps the
+    // system well conditioned wherever the points are
+    let centroid = points.iter().fold(Vector2::zero(), |sum, p| sum + *p) / 5.0;
+    let spread = points.iter().map(|p| (*p - centroid).magnitude()).sum::<f64>() / 5.0;
+    if spread < CONIC_RANK_THRESHOLD {
+      return None;
+    }
+    let normalize = Transform {
+      matrix: [[1.0 / spread, 0.0], [0.0, 1.0 / spread]],
+      offset: -centroid / spread,
+    };
+    let mut rows: Vec<[f64; 6]> = points
+      .iter()
+      .map(|p| {
+        let Vector2 { x, y } = normalize.apply(*p);
+        [x * x, x * y, y * y, x, y, 1.0]
+      })
+      .collect();
+
+    // Gauss-Jordan elimination with full pivoting. The column left without a pivot spans the
+    // null space
+    let mut cols = [0, 1, 2, 3, 4, 5];
+    for i in 0..5 {
+      let mut pivot = (i, i, 0.0);
+      for (r, row) in rows.iter().enumerate().skip(i) {
+        for (c, col) in cols.iter().enumerate().skip(i) {
+          if row[*col].abs() > pivot.2 {
+            pivot = (r, c, row[*col].abs());
+          }
+        }
+      }
+      if pivot.2 < CONIC_RANK_THRESHOLD {
+        return None;
+      }
+      rows.swap(i, pivot.0);
+      cols.swap(i, pivot.1);
+      let pivot_row = rows[i];
+      for (r, row) in rows.iter_mut().enumerate() {
+        if r != i {
+          let factor = row[cols[i]] / pivot_row[cols[i]];
+          for (value, pivot_value) in row.iter_mut().zip(pivot_row.iter()) {
+            *value -= factor * pivot_value;
+          }
+        }
+      }
+    }
+    let mut v = [0.0; 6];
+    v[cols[5]] = 1.0;
+    for (i, row) in rows.iter().enumerate() {
+      v[cols[i]] = -row[cols[5]] / row[cols[i]];
+    }
+    let [a, b, c, d, e, f] = v;
+    Some(Self { a, b, c, d, e, f }.preimage(&normalize))
+  }
+
+  /// The ellipse with the two foci going through `p`. There's none when `p` is between the foci,
+  /// where the ellipse would be flattened into a segment
+  pub fn ellipse_from_foci(f1: Vector2, f2: Vector2, p: Vector2) -> Option<Self> {
+    let major = (p - f1).magnitude() + (p - f2).magnitude();
+    let focal = (f2 - f1).magnitude();
+    if major - focal <= CONIC_DEGENERATE_THRESHOLD * major {
+      return None;
+    }
+    let rx = major / 2.0;
+    let ry = (rx * rx - focal * focal / 4.0).sqrt();
+    let axis = f2 - f1;
+    Some(
+      ConicShape::Ellipse {
+        center: (f1 + f2) / 2.0,
+        angle: axis.y.atan2(axis.x),
+        rx,
+        ry,
+      }
+      .conic(),
+    )
+  }
+
+  /// The parabola of the points as far from `focus` as from the directrix, which is taken as a
+  /// straight line. There's none when the focus is on the directrix
+  pub fn parabola_from_focus(focus: Vector2, directrix: Line) -> Option<Self> {
+    if directrix.is_degenerate() {
+      return None;
+    }
+    let dir = directrix.direction();
+    let normal = vec2![-dir.y, dir.x];
+    let height = (focus - directrix.from).dot(normal);
+    if height.abs() <= CONIC_DEGENERATE_THRESHOLD * (focus.magnitude() + directrix.from.magnitude() + 1.0) {
+      return None;
+    }
+    // The parabola opens towards the focus, and its vertex is halfway to the directrix
+    let opening = normal * height.signum();
+    Some(
+      ConicShape::Parabola {
+        center: focus - normal * (height / 2.0),
+        angle: opening.y.atan2(opening.x),
+        focal: height.abs() / 2.0,
+      }
+      .conic(),
+    )
+  }
+
+  /// The value of the equation at `p`, which is zero on the conic
+  pub fn evaluate(&self, p: Vector2) -> f64 {
+    let Vector2 { x, y } = p;
+    self.a * x * x + self.b * x * y + self.c * y * y + self.d * x + self.e * y + self.f
+  }
+
+  /// The conic of the points `p` such that `transform.apply(p)` is on this one. Pulling back
+  /// by the inverse of a transformation moves the conic along with it
+  pub fn preimage(&self, transform: &Transform) -> Self {
+    let [[m11, m12], [m21, m22]] = transform.matrix;
+    let Vector2 { x: ox, y: oy } = transform.offset;
+    let (a, b, c) = (self.a, self.b / 2.0, self.c);
+    // The quadratic part is Mᵀ Q M with Q the symmetric matrix [[a, b], [b, c]]
+    let q11 = a * m11 * m11 + 2.0 * b * m11 * m21 + c * m21 * m21;
+    let q12 = a * m11 * m12 + b * (m11 * m22 + m12 * m21) + c * m21 * m22;
+    let q22 = a * m12 * m12 + 2.0 * b * m12 * m22 + c * m22 * m22;
+    // The linear part is Mᵀ (2 Q o + g) with g = (d, e)
+    let gx = 2.0 * (a * ox + b * oy) + self.d;
+    let gy = 2.0 * (b * ox + c * oy) + self.e;
+    Self {
+      a: q11,
+      b: 2.0 * q12,
+      c: q22,
+      d: m11 * gx + m21 * gy,
+      e: m12 * gx + m22 * gy,
+      f: self.evaluate(transform.offset),
+    }
+  }
+
+  /// Where the conic is and what it looks like. Degenerate conics, like pairs of lines or
+  /// single points, and conics without any real point have no shape
+  pub fn shape(&self) -> Option<ConicShape> {
+    let scale = self.a.abs().max(self.b.abs()).max(self.c.abs());
+    let coefficients = [self.a, self.b, self.c, self.d, self.e, self.f];
+    if scale == 0.0 || coefficients.iter().any(|k| !k.is_finite()) {
+      return None;
+    }
+    let [a, b, c, d, e, f] = coefficients.map(|k| k / scale);
+
+    // Turn the axes of the conic along x and y, where the xy term vanishes
+    let mut angle = 0.5 * b.atan2(a - c);
+    let (sin, cos) = angle.sin_cos();
+    let mut qx = a * cos * cos + b * sin * cos + c * sin * sin;
+    let mut qy = a * sin * sin - b * sin * cos + c * cos * cos;
+    let mut lx = d * cos + e * sin;
+    let mut ly = -d * sin + e * cos;
+    let rotate = |angle: f64, p: Vector2| {
+      let (sin, cos) = angle.sin_cos();
+      vec2![cos * p.x - sin * p.y, sin * p.x + cos * p.y]
+    };
+
+    let q_max = qx.abs().max(qy.abs());
+    if qx.abs() > CONIC_DEGENERATE_THRESHOLD * q_max && qy.abs() > CONIC_DEGENERATE_THRESHOLD * q_max {
+      // Central conics: complete the squares around the center
+      let (x0, y0) = (-lx / (2.0 * qx), -ly / (2.0 * qy));
+      let constant = f - qx * x0 * x0 - qy * y0 * y0;
+      let magnitude = f.abs() + (qx * x0 * x0).abs() + (qy * y0 * y0).abs();
+      if constant.abs() <= CONIC_DEGENERATE_THRESHOLD * magnitude {
+        return None;
+      }
+      let center = rotate(angle, vec2![x0, y0]);
+      let (rx_sq, ry_sq) = (-constant / qx, -constant / qy);
+      if rx_sq > 0.0 && ry_sq > 0.0 {
+        Some(ConicShape::Ellipse {
+          center,
+          angle,
+          rx: rx_sq.sqrt(),
+          ry: ry_sq.sqrt(),
+        })
+      } else if rx_sq > 0.0 {
+        Some(ConicShape::Hyperbola {
+          center,
+          angle,
+          rx: rx_sq.sqrt(),
+          ry: (-ry_sq).sqrt(),
+        })
+      } else if ry_sq > 0.0 {
+        // The branches open along y, so turn a quarter more to have them along x
+        Some(ConicShape::Hyperbola {
+          center,
+          angle: angle + std::f64::consts::FRAC_PI_2,
+          rx: ry_sq.sqrt(),
+          ry: (-rx_sq).sqrt(),
+        })
+      } else {
+        None
+      }
+    } else {
+      // Parabolas: keep the vanishing square along x by turning a quarter if needed
+      if qy.abs() < qx.abs() {
+        angle += std::f64::consts::FRAC_PI_2;
+        std::mem::swap(&mut qx, &mut qy);
+        let (new_lx, new_ly) = (ly, -lx);
+        lx = new_lx;
+        ly = new_ly;
+      }
+      // What's left is qy y² + lx x + ly y + f = 0
+      let y0 = -ly / (2.0 * qy);
+      let x0 = -(f - qy * y0 * y0) / lx;
+      let focal = -lx / (4.0 * qy);
+      if !x0.is_finite() || focal.abs() <= CONIC_DEGENERATE_THRESHOLD * (x0.abs() + y0.abs()) {
+        return None;
+      }
+      let center = rotate(angle, vec2![x0, y0]);
+      if focal < 0.0 {
+        angle += std::f64::consts::PI;
+      }
+      Some(ConicShape::Parabola {
+        center,
+        angle,
+        focal: focal.abs(),
+      })
+    }
+  }
+
+  /// Whether the conic has no shape to draw
+  pub fn is_degenerate(&self) -> bool {
+    self.shape().is_none()
+  }
+
+  /// Sample the conic around `aabb` as the paths to draw. The samples are closest together
+  /// within the box, and unbounded conics stop a bit beyond it
+  pub fn tessellate(&self, aabb: AABB) -> Polyline {
+    match self.shape() {
+      Some(shape) => shape.tessellate(aabb),
+      None => Polyline { paths: vec![] },
+    }
+  }
+}
+
+impl ConicShape {
+  fn center(&self) -> Vector2 {
+    match *self {
+      ConicShape::Ellipse { center, .. } => center,
+      ConicShape::Hyperbola { center, .. } => center,
+      ConicShape::Parabola { center, .. } => center,
+    }
+  }
+
+  fn angle(&self) -> f64 {
+    match *self {
+      ConicShape::Ellipse { angle, .. } => angle,
+      ConicShape::Hyperbola { angle, .. } => angle,
+      ConicShape::Parabola { angle, .. } => angle,
+    }
+  }
+
+  /// The map from the standard form to the plane
+  fn placement(&self) -> Transform {
+    let mut placement = Transform::rotate(Vector2::zero(), self.angle());
+    placement.offset = self.center();
+    placement
+  }
+
+  /// The equation of the shape
+  pub fn conic(&self) -> Conic {
+    let standard = match *self {
+      ConicShape::Ellipse { rx, ry, .. } => Conic {
+        a: 1.0 / (rx * rx),
+        b: 0.0,
+        c: 1.0 / (ry * ry),
+        d: 0.0,
+        e: 0.0,
+        f: -1.0,
+      },
+      ConicShape::Hyperbola { rx, ry, .. } => Conic {
+        a: 1.0 / (rx * rx),
+        b: 0.0,
+        c: -1.0 / (ry * ry),
+        d: 0.0,
+        e: 0.0,
+        f: -1.0,
+      },
+      ConicShape::Parabola { focal, .. } => Conic {
+        a: 0.0,
+        b: 0.0,
+        c: 1.0,
+        d: -4.0 * focal,
+        e: 0.0,
+        f: 0.0,
+      },
+    };
+    // The inverse of the placement takes the plane back to the standard form
+    let (sin, cos) = self.angle().sin_cos();
+    let center = self.center();
+    standard.preimage(&Transform {
+      matrix: [[cos, sin], [-sin, cos]],
+      offset: vec2![-cos * center.x - sin * center.y, sin * center.x - cos * center.y],
+    })
+  }
+
+  pub fn tessellate(&self, aabb: AABB) -> Polyline {
+    let placement = self.placement();
+    // How far from the center the unbounded conics need to go to leave the box
+    let reach = [
+      aabb.min(),
+      aabb.max(),
+      vec2![aabb.x_min(), aabb.y_max()],
+      vec2![aabb.x_max(), aabb.y_min()],
+    ]
+    .iter()
+    .map(|corner| (*corner - self.center()).magnitude())
+    .fold(0.0, f64::max)
+      + CONIC_MAX_STEP;
+    let paths = match *self {
+      ConicShape::Ellipse { rx, ry, .. } => {
+        let point = |t: f64| placement.apply(vec2![rx * t.cos(), ry * t.sin()]);
+        vec![sample_curve(&point, 0.0, 2.0 * std::f64::consts::PI, aabb)]
+      }
+      ConicShape::Hyperbola { rx, ry, .. } => {
+        let end = (reach / rx.min(ry)).asinh();
+        let right = |t: f64| placement.apply(vec2![rx * t.cosh(), ry * t.sinh()]);
+        let left = |t: f64| placement.apply(vec2![-rx * t.cosh(), ry * t.sinh()]);
+        vec![
+          sample_curve(&right, -end, end, aabb),
+          sample_curve(&left, -end, end, aabb),
+        ]
+      }
+      ConicShape::Parabola { focal, .. } => {
+        let point = |t: f64| placement.apply(vec2![t * t / (4.0 * focal), t]);
+        vec![sample_curve(&point, -reach, reach, aabb)]
+      }
+    };
+    Polyline { paths }
+  }
+}
+
+/// Sample the curve from `from` to `to`, evenly in the parameter and then halving the steps that
+/// are long on screen and close to the box
+fn sample_curve(point: &dyn Fn(f64) -> Vector2, from: f64, to: f64, aabb: AABB) -> Vec<Vector2> {
+  let mut samples = vec![point(from)];
+  let step = (to - from) / CONIC_SAMPLES as f64;
+  for i in 0..CONIC_SAMPLES {
+    subdivide(
+      point,
+      from + step * i as f64,
+      from + step * (i + 1) as f64,
+      aabb,
+      CONIC_MAX_DEPTH,
+      &mut samples,
+    );
+  }
+  samples
+}
+
+/// Push the samples after the one at `t0` up to the one at `t1`
+fn subdivide(point: &dyn Fn(f64) -> Vector2, t0: f64, t1: f64, aabb: AABB, depth: u32, samples: &mut Vec<Vector2>) {
+  let p0 = *samples.last().unwrap();
+  let mid = (t0 + t1) / 2.0;
+  let (pm, p1) = (point(mid), point(t1));
+  let length = (pm - p0).magnitude() + (p1 - pm).magnitude();
+  let near = |p: Vector2| {
+    p.x > aabb.x_min() - length
+      && p.x < aabb.x_max() + length
+      && p.y > aabb.y_min() - length
+      && p.y < aabb.y_max() + length
+  };
+  if depth > 0 && length > CONIC_MAX_STEP && (near(p0) || near(pm) || near(p1)) {
+    subdivide(point, t0, mid, aabb, depth - 1, samples);
+    subdivide(point, mid, t1, aabb, depth - 1, samples);
+  } else {
+    samples.push(p1);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::math::LineType;
+
+  fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-8, "{} != {}", a, b);
+  }
+
+  #[test]
+  fn test_conic_shapes() {
+    // A circle of radius 2 around (1, 1)
+    let on_circle = |t: f64| vec2![1.0 + 2.0 * t.cos(), 1.0 + 2.0 * t.sin()];
+    let circle = Conic::from_five_points([
+      on_circle(0.1),
+      on_circle(1.0),
+      on_circle(2.0),
+      on_circle(3.5),
+      on_circle(5.0),
+    ])
+    .unwrap();
+    match circle.shape() {
+      Some(ConicShape::Ellipse { center, rx, ry, .. }) => {
+        assert_close(center.x, 1.0);
+        assert_close(center.y, 1.0);
+        assert_close(rx, 2.0);
+        assert_close(ry, 2.0);
+      }
+      shape => panic!("Expected an ellipse, got {:?}", shape),
+    }
+    let collinear = [
+      vec2![0.0, 0.0],
+      vec2![1.0, 1.0],
+      vec2![2.0, 2.0],
+      vec2![3.0, 3.0],
+      vec2![0.0, 1.0],
+    ];
+    assert!(Conic::from_five_points(collinear).is_none());
+
+    let ellipse = Conic::ellipse_from_foci(vec2![-3.0, 0.0], vec2![3.0, 0.0], vec2![0.0, 4.0]).unwrap();
+    assert_close(ellipse.evaluate(vec2![5.0, 0.0]), 0.0);
+    assert_close(ellipse.evaluate(vec2![0.0, -4.0]), 0.0);
+    assert!(Conic::ellipse_from_foci(vec2![-3.0, 0.0], vec2![3.0, 0.0], vec2![1.0, 0.0]).is_none());
+
+    let directrix = Line {
+      from: vec2![0.0, -1.0],
+      to: vec2![1.0, -1.0],
+      line_type: LineType::Segment,
+    };
+    let parabola = Conic::parabola_from_focus(vec2![0.0, 1.0], directrix).unwrap();
+    assert_close(parabola.evaluate(vec2![2.0, 1.0]), 0.0);
+    match parabola.shape() {
+      Some(ConicShape::Parabola { center, focal, .. }) => {
+        assert_close(center.magnitude(), 0.0);
+        assert_close(focal, 1.0);
+      }
+      shape => panic!("Expected a parabola, got {:?}", shape),
+    }
+    assert!(Conic::parabola_from_focus(vec2![5.0, -1.0], directrix).is_none());
+
+    let hyperbola = Conic {
+      a: 0.0,
+      b: 1.0,
+      c: 0.0,
+      d: 0.0,
+      e: 0.0,
+      f: -1.0,
+    };
+    assert!(matches!(hyperbola.shape(), Some(ConicShape::Hyperbola { .. })));
+    let polyline = hyperbola.tessellate(AABB::new(-10.0, -10.0, 20.0, 20.0));
+    assert_eq!(polyline.paths.len(), 2);
+    for p in polyline.paths.iter().flatten() {
+      assert_close(p.x * p.y, 1.0);
+    }
+    let line_pair = Conic { f: 0.0, ..hyperbola };
+    assert!(line_pair.shape().is_none());
+  }
+}
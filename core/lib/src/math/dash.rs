@@ -0,0 +1,109 @@
+use super::{Line, Vector2};
+use serde::{Deserialize, Serialize};
+
+/// Don't bother dashing when there would be more dashes than this, and draw a solid stroke
+const MAX_DASHES: f64 = 10000.0;
+
+/// A stroke pattern alternating `on` drawn lengths and `off` gaps, both in pixels.
+///
+/// The pattern always starts at a point fixed in virtual space, like the first point of a
+/// line or the rightmost point of a circle, so the dashes move along with the shape instead of
+/// sliding along it when the viewport pans.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dash {
+  pub on: f64,
+  pub off: f64,
+}
+
+impl Dash {
+  pub fn dashed() -> Self {
+    Self { on: 8.0, off: 6.0 }
+  }
+
+  pub fn dotted() -> Self {
+    Self { on: 2.0, off: 4.0 }
+  }
+
+  pub fn period(&self) -> f64 {
+    self.on + self.off
+  }
+
+  /// Split the segment into the parts to draw. `offset` is how far along the pattern `from`
+  /// is, e.g. its distance to the point where the pattern starts
+  pub fn split_segment(&self, from: Vector2, to: Vector2, offset: f64) -> Vec<(Vector2, Vector2)> {
+    let length = (to - from).magnitude();
+    if length == 0.0 {
+      return vec![];
+    }
+    let dir = (to - from) / length;
+    self
+      .split_length(length, offset)
+      .into_iter()
+      .map(|(start, end)| (from + dir * start, from + dir * end))
+      .collect()
+  }
+
+  /// Split the part of the line between `from` and `to`, both on the line, with the pattern
+  /// starting at the first point of the line
+  pub fn split_line(&self, line: Line, from: Vector2, to: Vector2) -> Vec<(Vector2, Vector2)> {
+    let (from, to) = if line.t_of_point(from) <= line.t_of_point(to) {
+      (from, to)
+    } else {
+      (to, from)
+    };
+    self.split_segment(from, to, line.t_of_point(from))
+  }
+
+  /// Split a circle into the arcs to draw, as `(start, end)` angles in radians. The pattern
+  /// starts at angle 0
+  pub fn split_circle(&self, radius: f64) -> Vec<(f64, f64)> {
+    if radius <= 0.0 {
+      return vec![];
+    }
+    self
+      .split_length(2.0 * std::f64::consts::PI * radius, 0.0)
+      .into_iter()
+      .map(|(start, end)| (start / radius, end / radius))
+      .collect()
+  }
+
+  /// The `(start, end)` distances of the drawn parts along a stroke of `length`
+  fn split_length(&self, length: f64, offset: f64) -> Vec<(f64, f64)> {
+    let period = self.period();
+    if self.on <= 0.0 || self.off <= 0.0 || length / period > MAX_DASHES {
+      return vec![(0.0, length)];
+    }
+    let mut dashes = vec![];
+    let mut start = -offset.rem_euclid(period);
+    while start < length {
+      let end = (start + self.on).min(length);
+      if end > 0.0 {
+        dashes.push((start.max(0.0), end));
+      }
+      start += period;
+    }
+    dashes
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_dash() {
+    let dash = Dash { on: 2.0, off: 1.0 };
+    let dashes = dash.split_segment(vec2![0.0, 0.0], vec2![7.0, 0.0], 0.0);
+    let xs: Vec<(f64, f64)> = dashes.iter().map(|(from, to)| (from.x, to.x)).collect();
+    assert_eq!(xs, vec![(0.0, 2.0), (3.0, 5.0), (6.0, 7.0)]);
+
+    // Starting half way through the first dash
+    let dashes = dash.split_segment(vec2![0.0, 0.0], vec2![0.0, 4.0], 1.0);
+    let ys: Vec<(f64, f64)> = dashes.iter().map(|(from, to)| (from.y, to.y)).collect();
+    assert_eq!(ys, vec![(0.0, 1.0), (2.0, 4.0)]);
+
+    let arcs = Dash { on: 1.0, off: 1.0 }.split_circle(2.0);
+    assert_eq!(arcs.len(), 7);
+    assert_eq!(arcs[1], (1.0, 1.5));
+  }
+}
@@ -1,5 +1,7 @@
 use super::{DotProduct, Project, Vector2};
 
+static LINE_DEGENERATE_THRESHOLD: f64 = 1e-10;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Line {
   pub from: Vector2,
@@ -23,6 +25,11 @@ impl Line {
     (self.to - self.from).magnitude()
   }
 
+  /// A line needs two distinct points to have a direction
+  pub fn is_degenerate(&self) -> bool {
+    !self.from.is_finite() || !self.to.is_finite() || self.from_to_length() < LINE_DEGENERATE_THRESHOLD
+  }
+
   pub fn t_of_point(&self, p: Vector2) -> f64 {
     (p - self.from).dot(self.direction())
   }
@@ -3,13 +3,27 @@ mod color;
 #[macro_use]
 mod vector2;
 mod aabb;
+mod arc;
 mod circle;
+mod conic;
+mod dash;
 mod line;
+mod polygon;
+mod polyline;
 mod traits;
+mod transform;
+mod triangle;
 
 pub use aabb::*;
+pub use arc::*;
 pub use circle::*;
 pub use color::*;
+pub use conic::*;
+pub use dash::*;
 pub use line::*;
+pub use polygon::*;
+pub use polyline::*;
 pub use traits::*;
+pub use transform::*;
+pub use triangle::*;
 pub use vector2::*;
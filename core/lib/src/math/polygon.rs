@@ -0,0 +1,111 @@
+use super::{Line, LineType, Project, Vector2, AABB};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon {
+  pub points: Vec<Vector2>,
+}
+
+impl Polygon {
+  /// The sides of the polygon as segments, the last one going back to the first point
+  pub fn edges(&self) -> Vec<Line> {
+    let n = self.points.len();
+    (0..n)
+      .map(|i| Line {
+        from: self.points[i],
+        to: self.points[(i + 1) % n],
+        line_type: LineType::Segment,
+      })
+      .collect()
+  }
+
+  /// Whether `p` is inside the polygon, using the even-odd rule
+  pub fn contains(&self, p: Vector2) -> bool {
+    let n = self.points.len();
+    let mut inside = false;
+    for i in 0..n {
+      let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+      if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+        inside = !inside;
+      }
+    }
+    inside
+  }
+
+  /// The distance from `p` to the closest side of the polygon
+  pub fn border_distance(&self, p: Vector2) -> f64 {
+    self
+      .edges()
+      .into_iter()
+      .map(|edge| {
+        if edge.from == edge.to {
+          (p - edge.from).magnitude()
+        } else {
+          let t = edge.t_of_point(p).max(0.0).min(edge.from_to_length());
+          (p - edge.point_at_t(t)).magnitude()
+        }
+      })
+      .fold(f64::INFINITY, f64::min)
+  }
+
+  /// The closest point on the border of the polygon
+  pub fn closest_border_point(&self, p: Vector2) -> Option<Vector2> {
+    self
+      .edges()
+      .into_iter()
+      .filter(|edge| edge.from != edge.to)
+      .map(|edge| p.project(edge))
+      .min_by(|a, b| (*a - p).magnitude().partial_cmp(&(*b - p).magnitude()).unwrap())
+  }
+
+  /// The enclosed area, using the shoelace formula. Self-intersecting polygons have parts
+  /// cancelling each other out
+  pub fn area(&self) -> f64 {
+    let n = self.points.len();
+    let twice_area = (0..n).fold(0.0, |sum, i| {
+      let (a, b) = (self.points[i], self.points[(i + 1) % n]);
+      sum + a.x * b.y - b.x * a.y
+    });
+    twice_area.abs() / 2.0
+  }
+
+  pub fn perimeter(&self) -> f64 {
+    self.edges().iter().map(|edge| edge.from_to_length()).sum()
+  }
+
+  pub fn bounding_box(&self) -> Option<AABB> {
+    let first = *self.points.first()?;
+    let (min, max) = self.points.iter().fold((first, first), |(min, max), p| {
+      (
+        vec2![min.x.min(p.x), min.y.min(p.y)],
+        vec2![max.x.max(p.x), max.y.max(p.y)],
+      )
+    });
+    Some(AABB {
+      x: min.x,
+      y: min.y,
+      width: max.x - min.x,
+      height: max.y - min.y,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_polygon() {
+    let square = Polygon {
+      points: vec![vec2![0.0, 0.0], vec2![2.0, 0.0], vec2![2.0, 2.0], vec2![0.0, 2.0]],
+    };
+    assert_eq!(square.edges().len(), 4);
+    assert!(square.contains(vec2![1.0, 1.0]));
+    assert!(!square.contains(vec2![3.0, 1.0]));
+    assert!((square.border_distance(vec2![1.0, 0.5]) - 0.5).abs() < 1e-10);
+    assert!((square.border_distance(vec2![3.0, 3.0]) - 2.0f64.sqrt()).abs() < 1e-10);
+    assert!((square.area() - 4.0).abs() < 1e-10);
+    assert!((square.perimeter() - 8.0).abs() < 1e-10);
+    let aabb = square.bounding_box().unwrap();
+    assert_eq!((aabb.x, aabb.y, aabb.width, aabb.height), (0.0, 0.0, 2.0, 2.0));
+  }
+}
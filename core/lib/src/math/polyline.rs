@@ -0,0 +1,122 @@
+use super::{Line, LineType, Vector2, AABB};
+
+static POLYLINE_JUMP_RATIO: f64 = 20.0;
+static POLYLINE_STEP_THRESHOLD: f64 = 1e-10;
+
+/// Runs of connected points. Each run is drawn as the segments between its consecutive points,
+/// with a gap between one run and the next
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polyline {
+  pub paths: Vec<Vec<Vector2>>,
+}
+
+impl Polyline {
+  /// Connect the samples of a curve in order. The curve breaks where a sample is missing, and
+  /// where it jumps much further than its typical step, like a hyperbola switching branches.
+  /// Runs of a single point are dropped since there's nothing to draw
+  pub fn from_samples(samples: &[Option<Vector2>]) -> Self {
+    let mut steps: Vec<f64> = samples
+      .windows(2)
+      .filter_map(|pair| match (pair[0], pair[1]) {
+        (Some(a), Some(b)) => Some((b - a).magnitude()),
+        _ => None,
+      })
+      .collect();
+    steps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let max_step = match steps.get(steps.len() / 2) {
+      Some(&median) if median > POLYLINE_STEP_THRESHOLD => median * POLYLINE_JUMP_RATIO,
+      _ => f64::INFINITY,
+    };
+
+    let mut paths = vec![];
+    let mut path: Vec<Vector2> = vec![];
+    for sample in samples {
+      match (sample, path.last()) {
+        (Some(p), Some(&last)) if (*p - last).magnitude() > max_step => {
+          paths.push(std::mem::replace(&mut path, vec![*p]));
+        }
+        (Some(p), _) => path.push(*p),
+        (None, _) => paths.push(std::mem::take(&mut path)),
+      }
+    }
+    paths.push(path);
+    paths.retain(|path| path.len() > 1);
+    Self { paths }
+  }
+
+  pub fn segments(&self) -> Vec<Line> {
+    self
+      .paths
+      .iter()
+      .flat_map(|path| {
+        path.windows(2).map(|pair| Line {
+          from: pair[0],
+          to: pair[1],
+          line_type: LineType::Segment,
+        })
+      })
+      .collect()
+  }
+
+  /// The distance from `p` to the closest segment
+  pub fn distance(&self, p: Vector2) -> f64 {
+    self
+      .segments()
+      .into_iter()
+      .map(|segment| {
+        if segment.from == segment.to {
+          (p - segment.from).magnitude()
+        } else {
+          (p - segment.get_closest_point(p)).magnitude()
+        }
+      })
+      .fold(f64::INFINITY, f64::min)
+  }
+
+  pub fn bounding_box(&self) -> Option<AABB> {
+    let mut points = self.paths.iter().flatten();
+    let first = *points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), p| {
+      (
+        vec2![min.x.min(p.x), min.y.min(p.y)],
+        vec2![max.x.max(p.x), max.y.max(p.y)],
+      )
+    });
+    Some(AABB {
+      x: min.x,
+      y: min.y,
+      width: max.x - min.x,
+      height: max.y - min.y,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_polyline_from_samples() {
+    let samples = vec![
+      Some(vec2![0.0, 0.0]),
+      Some(vec2![1.0, 0.0]),
+      Some(vec2![2.0, 0.0]),
+      None,
+      Some(vec2![3.0, 0.0]),
+      Some(vec2![4.0, 0.0]),
+      Some(vec2![100.0, 0.0]),
+      Some(vec2![101.0, 0.0]),
+      None,
+      Some(vec2![102.0, 0.0]),
+    ];
+    let polyline = Polyline::from_samples(&samples);
+    assert_eq!(polyline.paths.len(), 3);
+    assert_eq!(polyline.paths[0].len(), 3);
+    assert_eq!(polyline.paths[1], vec![vec2![3.0, 0.0], vec2![4.0, 0.0]]);
+    assert_eq!(polyline.segments().len(), 4);
+    assert!((polyline.distance(vec2![1.5, 2.0]) - 2.0).abs() < 1e-10);
+    assert!((polyline.distance(vec2![50.0, 0.0]) - 46.0).abs() < 1e-10);
+    let aabb = polyline.bounding_box().unwrap();
+    assert_eq!((aabb.x, aabb.width, aabb.height), (0.0, 101.0, 0.0));
+  }
+}
@@ -20,12 +20,18 @@ impl Intersect<Line> for Line {
   type Output = Option<Vector2>;
 
   fn intersect(self, other: Self) -> Self::Output {
+    if self.is_degenerate() || other.is_degenerate() {
+      return None;
+    }
     let Vector2 { x: sox, y: soy } = self.from;
     let Vector2 { x: oox, y: ooy } = other.from;
     let Vector2 { x: sdx, y: sdy } = self.direction();
     let Vector2 { x: odx, y: ody } = other.direction();
+
+    // The directions are normalized, so this is the sine of the angle between the lines. Parallel
+    // lines meet nowhere or everywhere, which are both no intersection point
     let det = sdx * ody - sdy * odx;
-    if det == 0. {
+    if det.abs() < LINE_ITSCT_THRESHOLD {
       None
     } else {
       let x2 = sox + sdx;
@@ -180,6 +186,9 @@ impl Intersect<Line> for Circle {
   type Output = CircleIntersect;
 
   fn intersect(self, line: Line) -> Self::Output {
+    if line.is_degenerate() {
+      return CircleIntersect::None;
+    }
     let proj = self.center.project(line);
     let dist = (proj - self.center).magnitude();
     if dist < self.radius - CIRCLE_ITSCT_THRESHOLD {
@@ -225,19 +234,157 @@ impl Intersect<Circle> for Circle {
     };
     let center_diff = c2.center - c1.center;
     let d = center_diff.magnitude();
-    if d < c1.radius + c2.radius - CIRCLE_ITSCT_THRESHOLD {
-      let center_theta = center_diff.y.atan2(center_diff.x);
+    let outer = c1.radius + c2.radius;
+    let inner = (c1.radius - c2.radius).abs();
+
+    // Concentric circles meet everywhere or nowhere, and the others meet nowhere when one is
+    // too far from or too deep inside of the other
+    if d < CIRCLE_ITSCT_THRESHOLD || d > outer + CIRCLE_ITSCT_THRESHOLD || d < inner - CIRCLE_ITSCT_THRESHOLD {
+      CircleIntersect::None
+    } else {
+      // The signed distance from the center of c1 to the chord through the intersections
       let d1 = (d * d - c2.radius * c2.radius + c1.radius * c1.radius) / (2.0 * d);
-      let theta = (d1 / c1.radius).acos();
-      let theta_1 = center_theta - theta;
-      let theta_2 = center_theta + theta;
-      let p1 = c1.center + vec2![c1.radius * theta_1.cos(), c1.radius * theta_1.sin()];
-      let p2 = c1.center + vec2![c1.radius * theta_2.cos(), c1.radius * theta_2.sin()];
-      CircleIntersect::TwoPoints(p1, p2)
-    } else if d < c1.radius + c2.radius + CIRCLE_ITSCT_THRESHOLD {
-      CircleIntersect::OnePoint(c1.center + center_diff / d * c1.radius)
+      if d > outer - CIRCLE_ITSCT_THRESHOLD || d < inner + CIRCLE_ITSCT_THRESHOLD {
+        // Touching from outside or from inside
+        CircleIntersect::OnePoint(c1.center + center_diff / d * d1)
+      } else {
+        let center_theta = center_diff.y.atan2(center_diff.x);
+        let theta = (d1 / c1.radius).clamp(-1.0, 1.0).acos();
+        let theta_1 = center_theta - theta;
+        let theta_2 = center_theta + theta;
+        let p1 = c1.center + vec2![c1.radius * theta_1.cos(), c1.radius * theta_1.sin()];
+        let p2 = c1.center + vec2![c1.radius * theta_2.cos(), c1.radius * theta_2.sin()];
+        CircleIntersect::TwoPoints(p1, p2)
+      }
+    }
+  }
+}
+
+impl Intersect<Line> for Arc {
+  type Output = CircleIntersect;
+
+  fn intersect(self, line: Line) -> Self::Output {
+    self.keep_on_arc(self.circle().intersect(line))
+  }
+}
+
+impl Intersect<Arc> for Line {
+  type Output = CircleIntersect;
+
+  fn intersect(self, arc: Arc) -> Self::Output {
+    arc.intersect(self)
+  }
+}
+
+impl Intersect<Circle> for Arc {
+  type Output = CircleIntersect;
+
+  fn intersect(self, circle: Circle) -> Self::Output {
+    self.keep_on_arc(self.circle().intersect(circle))
+  }
+}
+
+impl Intersect<Arc> for Circle {
+  type Output = CircleIntersect;
+
+  fn intersect(self, arc: Arc) -> Self::Output {
+    arc.intersect(self)
+  }
+}
+
+static CONIC_LINEAR_THRESHOLD: f64 = 1e-12;
+
+/// The intersections are ordered along the line, like those with circles
+impl Intersect<Line> for Conic {
+  type Output = CircleIntersect;
+
+  fn intersect(self, line: Line) -> Self::Output {
+    if line.is_degenerate() || self.is_degenerate() {
+      return CircleIntersect::None;
+    }
+    // The conic along the line is the quadratic alpha t² + beta t + gamma
+    let (p, u) = (line.from, line.direction());
+    let alpha = self.a * u.x * u.x + self.b * u.x * u.y + self.c * u.y * u.y;
+    let beta = 2.0 * self.a * p.x * u.x
+      + self.b * (p.x * u.y + p.y * u.x)
+      + 2.0 * self.c * p.y * u.y
+      + self.d * u.x
+      + self.e * u.y;
+    let gamma = self.evaluate(p);
+    let scale = self.a.abs().max(self.b.abs()).max(self.c.abs());
+    let ts = if alpha.abs() < CONIC_LINEAR_THRESHOLD * scale {
+      // Lines along an asymptote or the axis of a parabola cross only once
+      if beta == 0.0 {
+        vec![]
+      } else {
+        vec![-gamma / beta]
+      }
     } else {
-      CircleIntersect::None
+      let t_mid = -beta / (2.0 * alpha);
+      let discriminant = beta * beta - 4.0 * alpha * gamma;
+      let half_chord = discriminant.abs().sqrt() / (2.0 * alpha.abs());
+      if half_chord < CIRCLE_ITSCT_THRESHOLD {
+        vec![t_mid]
+      } else if discriminant > 0.0 {
+        vec![t_mid - half_chord, t_mid + half_chord]
+      } else {
+        vec![]
+      }
+    };
+    let points: Vec<Vector2> = ts
+      .into_iter()
+      .map(|t| line.point_at_t(t))
+      .filter(|itsct| itsct_line_is_not_none(line, *itsct))
+      .collect();
+    match points[..] {
+      [p1, p2] => CircleIntersect::TwoPoints(p1, p2),
+      [p] => CircleIntersect::OnePoint(p),
+      _ => CircleIntersect::None,
+    }
+  }
+}
+
+impl Intersect<Conic> for Line {
+  type Output = CircleIntersect;
+
+  fn intersect(self, conic: Conic) -> Self::Output {
+    conic.intersect(self)
+  }
+}
+
+/// Whether the outline of the arc crosses the rectangle, or the rectangle is inside the filled
+/// arc
+impl Intersect<AABB> for Arc {
+  type Output = Option<()>;
+
+  fn intersect(self, aabb: AABB) -> Self::Output {
+    let segment = |from: Vector2, to: Vector2| Line {
+      from,
+      to,
+      line_type: LineType::Segment,
+    };
+    let (min, max) = (aabb.min(), aabb.max());
+    let (top_right, bottom_left) = (vec2![max.x, min.y], vec2![min.x, max.y]);
+    let edges = [
+      segment(min, top_right),
+      segment(top_right, max),
+      segment(max, bottom_left),
+      segment(bottom_left, min),
+    ];
+    let crosses_edge = edges
+      .iter()
+      .any(|edge| !matches!(self.intersect(*edge), CircleIntersect::None));
+    let (from, to) = (self.start_point(), self.end_point());
+    let closing = match self.kind {
+      ArcKind::Arc => vec![],
+      ArcKind::Sector => vec![segment(self.center, from), segment(self.center, to)],
+      ArcKind::Segment => vec![segment(from, to)],
+    };
+    let crosses_closing = closing.into_iter().any(|l| l.intersect(aabb).is_some());
+    if aabb.contains(from) || crosses_edge || crosses_closing || self.contains(min) {
+      Some(())
+    } else {
+      None
     }
   }
 }
@@ -282,4 +429,104 @@ mod test {
     };
     assert!(l.intersect(aabb) == Some((vec2![-0.5, 0.0], vec2![0.5, 0.0])));
   }
+
+  #[test]
+  fn test_degenerate_intersect() {
+    let line = |from: Vector2, to: Vector2| Line {
+      from,
+      to,
+      line_type: LineType::Straight,
+    };
+    let circle = |center: Vector2, radius: f64| Circle { center, radius };
+
+    // Parallel, coincident and zero length lines
+    assert!(line(vec2![0., 0.], vec2![1., 0.])
+      .intersect(line(vec2![0., 1.], vec2![2., 1.]))
+      .is_none());
+    assert!(line(vec2![0., 0.], vec2![1., 0.])
+      .intersect(line(vec2![3., 0.], vec2![2., 0.]))
+      .is_none());
+    assert!(line(vec2![1., 1.], vec2![1., 1.])
+      .intersect(line(vec2![0., 0.], vec2![0., 1.]))
+      .is_none());
+    match circle(vec2![0., 0.], 1.).intersect(line(vec2![0., 0.], vec2![0., 0.])) {
+      CircleIntersect::None => (),
+      itsct => panic!("expected no intersection, found {:?}", itsct),
+    }
+
+    // Concentric circles, a circle inside of another, and circles touching from inside
+    match circle(vec2![0., 0.], 1.).intersect(circle(vec2![0., 0.], 1.)) {
+      CircleIntersect::None => (),
+      itsct => panic!("expected no intersection, found {:?}", itsct),
+    }
+    match circle(vec2![0., 0.], 3.).intersect(circle(vec2![1., 0.], 1.)) {
+      CircleIntersect::None => (),
+      itsct => panic!("expected no intersection, found {:?}", itsct),
+    }
+    match circle(vec2![0., 0.], 3.).intersect(circle(vec2![2., 0.], 1.)) {
+      CircleIntersect::OnePoint(p) => assert!((p - vec2![3., 0.]).magnitude() < 1e-10),
+      itsct => panic!("expected one point, found {:?}", itsct),
+    }
+  }
+
+  #[test]
+  fn test_arc_intersect() {
+    // The upper half of the unit circle
+    let arc = Arc::from_center(vec2![0., 0.], vec2![1., 0.], vec2![-1., 0.], ArcKind::Arc).unwrap();
+    let line = |from: Vector2, to: Vector2| Line {
+      from,
+      to,
+      line_type: LineType::Straight,
+    };
+    match arc.intersect(line(vec2![0., -2.], vec2![0., 2.])) {
+      CircleIntersect::OnePoint(p) => assert!((p - vec2![0., 1.]).magnitude() < 1e-10),
+      itsct => panic!("expected one point, found {:?}", itsct),
+    }
+    match line(vec2![-2., 0.5], vec2![2., 0.5]).intersect(arc) {
+      CircleIntersect::TwoPoints(_, _) => (),
+      itsct => panic!("expected two points, found {:?}", itsct),
+    }
+    let below = Circle {
+      center: vec2![0., -1.],
+      radius: 0.5,
+    };
+    assert!(matches!(arc.intersect(below), CircleIntersect::None));
+  }
+
+  #[test]
+  fn test_conic_intersect() {
+    // The parabola y = x², crossed by a horizontal line, its axis and a tangent
+    let parabola = Conic {
+      a: 1.,
+      b: 0.,
+      c: 0.,
+      d: 0.,
+      e: -1.,
+      f: 0.,
+    };
+    let line = |from: Vector2, to: Vector2| Line {
+      from,
+      to,
+      line_type: LineType::Straight,
+    };
+    match line(vec2![3., 4.], vec2![-3., 4.]).intersect(parabola) {
+      CircleIntersect::TwoPoints(p1, p2) => {
+        assert!((p1 - vec2![2., 4.]).magnitude() < 1e-10);
+        assert!((p2 - vec2![-2., 4.]).magnitude() < 1e-10);
+      }
+      itsct => panic!("expected two points, found {:?}", itsct),
+    }
+    match parabola.intersect(line(vec2![0., -2.], vec2![0., 2.])) {
+      CircleIntersect::OnePoint(p) => assert!(p.magnitude() < 1e-10),
+      itsct => panic!("expected one point, found {:?}", itsct),
+    }
+    match parabola.intersect(line(vec2![-1., 0.], vec2![1., 0.])) {
+      CircleIntersect::OnePoint(p) => assert!(p.magnitude() < 1e-10),
+      itsct => panic!("expected one point, found {:?}", itsct),
+    }
+    assert!(matches!(
+      parabola.intersect(line(vec2![-1., -1.], vec2![1., -1.])),
+      CircleIntersect::None
+    ));
+  }
 }
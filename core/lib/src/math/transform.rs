@@ -0,0 +1,121 @@
+use super::{Circle, Line, Vector2};
+
+/// A similarity transformation of the plane, mapping `p` to `matrix * p + offset`
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+  pub matrix: [[f64; 2]; 2],
+  pub offset: Vector2,
+}
+
+impl Transform {
+  /// Reflection across the line. There's no such reflection when the line has no direction
+  pub fn reflect(line: Line) -> Option<Self> {
+    let dir = line.to - line.from;
+    if dir.is_zero() {
+      None
+    } else {
+      let Vector2 { x: dx, y: dy } = dir.normalized();
+      let matrix = [
+        [2.0 * dx * dx - 1.0, 2.0 * dx * dy],
+        [2.0 * dx * dy, 2.0 * dy * dy - 1.0],
+      ];
+      Some(Self::fixing(line.from, matrix))
+    }
+  }
+
+  /// Counter-clockwise rotation of `angle` radians about `center`
+  pub fn rotate(center: Vector2, angle: f64) -> Self {
+    let (sin, cos) = angle.sin_cos();
+    Self::fixing(center, [[cos, -sin], [sin, cos]])
+  }
+
+  pub fn translate(by: Vector2) -> Self {
+    Self {
+      matrix: [[1.0, 0.0], [0.0, 1.0]],
+      offset: by,
+    }
+  }
+
+  /// Scaling by `factor` about `center`. A negative factor also flips through the center
+  pub fn dilate(center: Vector2, factor: f64) -> Self {
+    Self::fixing(center, [[factor, 0.0], [0.0, factor]])
+  }
+
+  /// The transformation applying `matrix` while keeping `fixed` where it is
+  fn fixing(fixed: Vector2, matrix: [[f64; 2]; 2]) -> Self {
+    let linear = Self {
+      matrix,
+      offset: vec2![0.0, 0.0],
+    };
+    Self {
+      matrix,
+      offset: fixed - linear.apply(fixed),
+    }
+  }
+
+  pub fn apply(&self, p: Vector2) -> Vector2 {
+    let [[a, b], [c, d]] = self.matrix;
+    vec2![a * p.x + b * p.y, c * p.x + d * p.y] + self.offset
+  }
+
+  /// How much the transformation scales the lengths
+  pub fn scale(&self) -> f64 {
+    let [[a, b], [c, d]] = self.matrix;
+    (a * d - b * c).abs().sqrt()
+  }
+
+  pub fn apply_line(&self, line: Line) -> Line {
+    Line {
+      from: self.apply(line.from),
+      to: self.apply(line.to),
+      line_type: line.line_type,
+    }
+  }
+
+  pub fn apply_circle(&self, circle: Circle) -> Circle {
+    Circle {
+      center: self.apply(circle.center),
+      radius: circle.radius * self.scale(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::math::LineType;
+
+  fn assert_close(a: Vector2, b: Vector2) {
+    assert!((a - b).magnitude() < 1e-10, "{:?} != {:?}", a, b);
+  }
+
+  #[test]
+  fn test_transform() {
+    let line = Line {
+      from: vec2![0.0, 1.0],
+      to: vec2![1.0, 2.0],
+      line_type: LineType::Straight,
+    };
+    let reflect = Transform::reflect(line).unwrap();
+    assert_close(reflect.apply(vec2![1.0, 0.0]), vec2![-1.0, 2.0]);
+    assert_close(reflect.apply(vec2![2.0, 3.0]), vec2![2.0, 3.0]);
+
+    let rotate = Transform::rotate(vec2![1.0, 1.0], std::f64::consts::FRAC_PI_2);
+    assert_close(rotate.apply(vec2![2.0, 1.0]), vec2![1.0, 2.0]);
+
+    let translate = Transform::translate(vec2![3.0, -1.0]);
+    assert_close(translate.apply(vec2![1.0, 1.0]), vec2![4.0, 0.0]);
+
+    let dilate = Transform::dilate(vec2![1.0, 1.0], -2.0);
+    assert_close(dilate.apply(vec2![2.0, 1.0]), vec2![-1.0, 1.0]);
+    let circle = dilate.apply_circle(Circle {
+      center: vec2![1.0, 2.0],
+      radius: 1.5,
+    });
+    assert_close(circle.center, vec2![1.0, -1.0]);
+    assert!((circle.radius - 3.0).abs() < 1e-10);
+
+    let degenerate = Line { to: line.from, ..line };
+    assert!(Transform::reflect(degenerate).is_none());
+  }
+}
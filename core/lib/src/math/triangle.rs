@@ -0,0 +1,142 @@
+use super::*;
+
+static TRIANGLE_DEGENERATE_THRESHOLD: f64 = 1e-12;
+
+/// The triangle with vertices `a`, `b` and `c`. Apart from the centroid, its centers and circles
+/// only exist when the vertices are not on one line
+#[derive(Debug, Copy, Clone)]
+pub struct Triangle {
+  pub a: Vector2,
+  pub b: Vector2,
+  pub c: Vector2,
+}
+
+impl Triangle {
+  pub fn new(a: Vector2, b: Vector2, c: Vector2) -> Self {
+    Self { a, b, c }
+  }
+
+  /// Whether the vertices are on one line, including when two of them coincide
+  pub fn is_degenerate(&self) -> bool {
+    let (ab, ac) = (self.b - self.a, self.c - self.a);
+    self.doubled_area() <= TRIANGLE_DEGENERATE_THRESHOLD * ab.magnitude() * ac.magnitude()
+  }
+
+  pub fn centroid(&self) -> Vector2 {
+    (self.a + self.b + self.c) / 3.0
+  }
+
+  pub fn circumcenter(&self) -> Option<Vector2> {
+    self.circumcircle().map(|c| c.center)
+  }
+
+  /// Where the altitudes meet, which is `3G - 2O` by the Euler line
+  pub fn orthocenter(&self) -> Option<Vector2> {
+    self.circumcenter().map(|o| self.a + self.b + self.c - o * 2.0)
+  }
+
+  pub fn incenter(&self) -> Option<Vector2> {
+    self.incircle().map(|c| c.center)
+  }
+
+  /// The middle of the circumcenter and the orthocenter
+  pub fn nine_point_center(&self) -> Option<Vector2> {
+    self.nine_point_circle().map(|c| c.center)
+  }
+
+  pub fn circumcircle(&self) -> Option<Circle> {
+    if self.is_degenerate() {
+      None
+    } else {
+      Circle::from_three_points(self.a, self.b, self.c)
+    }
+  }
+
+  /// The circle inside the triangle touching its three sides
+  pub fn incircle(&self) -> Option<Circle> {
+    if self.is_degenerate() {
+      return None;
+    }
+    let (la, lb, lc) = self.side_lengths();
+    let perimeter = la + lb + lc;
+    Some(Circle {
+      center: (self.a * la + self.b * lb + self.c * lc) / perimeter,
+      radius: self.doubled_area() / perimeter,
+    })
+  }
+
+  /// The circle outside the triangle touching the side opposite to `a` and the extensions of the
+  /// two other sides
+  pub fn excircle(&self) -> Option<Circle> {
+    if self.is_degenerate() {
+      return None;
+    }
+    let (la, lb, lc) = self.side_lengths();
+    // Any side is shorter than the two others together, so the weight is positive
+    let weight = lb + lc - la;
+    Some(Circle {
+      center: (self.b * lb + self.c * lc - self.a * la) / weight,
+      radius: self.doubled_area() / weight,
+    })
+  }
+
+  /// The circle through the midpoints of the sides and the feet of the altitudes, half the size
+  /// of the circumcircle
+  pub fn nine_point_circle(&self) -> Option<Circle> {
+    self.circumcircle().map(|o| Circle {
+      center: ((self.a + self.b + self.c) - o.center) / 2.0,
+      radius: o.radius / 2.0,
+    })
+  }
+
+  /// The lengths of the sides opposite to `a`, `b` and `c`
+  fn side_lengths(&self) -> (f64, f64, f64) {
+    (
+      (self.c - self.b).magnitude(),
+      (self.a - self.c).magnitude(),
+      (self.b - self.a).magnitude(),
+    )
+  }
+
+  fn doubled_area(&self) -> f64 {
+    let (ab, ac) = (self.b - self.a, self.c - self.a);
+    (ab.x * ac.y - ab.y * ac.x).abs()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_triangle_centers() {
+    // The right triangle with sides 3, 4 and 5
+    let t = Triangle::new(vec2![0.0, 0.0], vec2![4.0, 0.0], vec2![0.0, 3.0]);
+    assert!((t.centroid() - vec2![4.0 / 3.0, 1.0]).magnitude() < 1e-10);
+    assert!((t.circumcenter().unwrap() - vec2![2.0, 1.5]).magnitude() < 1e-10);
+    assert!((t.orthocenter().unwrap() - vec2![0.0, 0.0]).magnitude() < 1e-10);
+    assert!((t.nine_point_center().unwrap() - vec2![1.0, 0.75]).magnitude() < 1e-10);
+
+    let incircle = t.incircle().unwrap();
+    assert!((incircle.center - vec2![1.0, 1.0]).magnitude() < 1e-10);
+    assert!((incircle.radius - 1.0).abs() < 1e-10);
+
+    // Opposite to the right angle, the radius is the semiperimeter
+    let excircle = t.excircle().unwrap();
+    assert!((excircle.center - vec2![6.0, 6.0]).magnitude() < 1e-10);
+    assert!((excircle.radius - 6.0).abs() < 1e-10);
+
+    let nine_point = t.nine_point_circle().unwrap();
+    assert!((nine_point.radius - 1.25).abs() < 1e-10);
+    assert!(((vec2![2.0, 0.0] - nine_point.center).magnitude() - 1.25).abs() < 1e-10);
+
+    let flat = Triangle::new(vec2![0.0, 0.0], vec2![1.0, 1.0], vec2![3.0, 3.0]);
+    assert!((flat.centroid() - vec2![4.0 / 3.0, 4.0 / 3.0]).magnitude() < 1e-10);
+    assert!(flat.orthocenter().is_none());
+    assert!(flat.incircle().is_none());
+    assert!(flat.excircle().is_none());
+    assert!(Triangle::new(vec2![1.0, 1.0], vec2![1.0, 1.0], vec2![2.0, 0.0])
+      .nine_point_circle()
+      .is_none());
+  }
+}
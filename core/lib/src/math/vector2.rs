@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Vector2 {
   pub x: f64,
   pub y: f64,
@@ -35,6 +36,9 @@ impl Vector2 {
   pub fn is_zero(&self) -> bool {
     self.x == 0.0 && self.y == 0.0
   }
+  pub fn is_finite(&self) -> bool {
+    self.x.is_finite() && self.y.is_finite()
+  }
 }
 
 impl Default for Vector2 {
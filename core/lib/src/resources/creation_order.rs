@@ -0,0 +1,63 @@
+use specs::prelude::*;
+use std::collections::HashMap;
+
+/// The order in which the elements were created. An element put back by undo or redo keeps
+/// its place, since its entity is never deleted
+pub struct CreationOrder {
+  orders: HashMap<Entity, usize>,
+  next: usize,
+}
+
+impl Default for CreationOrder {
+  fn default() -> Self {
+    Self {
+      orders: HashMap::new(),
+      next: 0,
+    }
+  }
+}
+
+impl CreationOrder {
+  pub fn insert(&mut self, ent: &Entity) {
+    if !self.orders.contains_key(ent) {
+      self.orders.insert(*ent, self.next);
+      self.next += 1;
+    }
+  }
+
+  /// Elements that are not known yet come last
+  pub fn get(&self, ent: &Entity) -> usize {
+    self.orders.get(ent).copied().unwrap_or(usize::MAX)
+  }
+
+  /// Sort the entities from the first created to the last one
+  pub fn sort(&self, ents: &mut [Entity]) {
+    ents.sort_by_key(|ent| (self.get(ent), ent.id()));
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_sort() {
+    let mut world = World::new();
+    let ents = (0..4).map(|_| world.create_entity().build()).collect::<Vec<_>>();
+    let mut creation_order = CreationOrder::default();
+
+    // The order does not follow the entity ids
+    creation_order.insert(&ents[2]);
+    creation_order.insert(&ents[0]);
+    creation_order.insert(&ents[1]);
+    let mut sorted = vec![ents[3], ents[1], ents[0], ents[2]];
+    creation_order.sort(&mut sorted);
+    assert_eq!(sorted, vec![ents[2], ents[0], ents[1], ents[3]]);
+
+    // Inserting again, by redo for instance, keeps the place
+    creation_order.insert(&ents[2]);
+    creation_order.insert(&ents[3]);
+    creation_order.sort(&mut sorted);
+    assert_eq!(sorted, vec![ents[2], ents[0], ents[1], ents[3]]);
+  }
+}
@@ -0,0 +1,179 @@
+use super::{ToScreen, Viewport};
+use crate::{components::labels::LABEL_FONT_SIZE, math::*, utilities::*};
+
+/// Grid lines are never closer than this on screen, in pixels
+static MIN_GRID_SPACING: f64 = 40.0;
+
+/// The Cartesian grid drawn below all the shapes. `snap` makes the new points go on the grid
+/// lattice, and works even when the grid is not visible
+#[derive(Debug, Copy, Clone)]
+pub struct Grid {
+  pub visible: bool,
+  pub snap: bool,
+}
+
+impl Default for Grid {
+  fn default() -> Self {
+    Self {
+      visible: false,
+      snap: false,
+    }
+  }
+}
+
+/// A grid line on screen. `position` is the screen x of a vertical line, or the screen y of a
+/// horizontal line, and `value` the virtual coordinate it stands for
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GridLine {
+  pub position: f64,
+  pub value: f64,
+}
+
+/// All the grid lines visible in a viewport. The axes are `None` when out of the screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLines {
+  pub spacing: f64,
+  pub vertical: Vec<GridLine>,
+  pub horizontal: Vec<GridLine>,
+  pub x_axis: Option<f64>, // Screen y of the x axis
+  pub y_axis: Option<f64>, // Screen x of the y axis
+}
+
+impl GridLines {
+  /// The text of every tick and the top left corner where it goes on screen. The ticks follow
+  /// the axes, or the screen edges when the axes are out of the screen. The origin is only
+  /// labelled once
+  pub fn tick_labels(&self, screen_size: Vector2) -> Vec<(String, Vector2)> {
+    let margin = 3.0;
+    let tick_y = self
+      .x_axis
+      .unwrap_or(0.0)
+      .max(0.0)
+      .min(screen_size.y - LABEL_FONT_SIZE - 2.0 * margin);
+    let tick_x = self
+      .y_axis
+      .unwrap_or(0.0)
+      .max(0.0)
+      .min(screen_size.x - 4.0 * LABEL_FONT_SIZE);
+    let vertical = self.vertical.iter().map(|line| {
+      (
+        Grid::tick_label(line.value, self.spacing),
+        vec2![line.position + margin, tick_y + margin],
+      )
+    });
+    let horizontal = self.horizontal.iter().filter(|line| line.value != 0.0).map(|line| {
+      (
+        Grid::tick_label(line.value, self.spacing),
+        vec2![tick_x + margin, line.position + margin],
+      )
+    });
+    vertical.chain(horizontal).collect()
+  }
+}
+
+impl Grid {
+  /// The virtual distance between two grid lines. It goes 1, 2, 5, 10, 20... so that the lines
+  /// stay at least `MIN_GRID_SPACING` pixels apart at any zoom
+  pub fn spacing(viewport: &Viewport) -> f64 {
+    let min_spacing = MIN_GRID_SPACING * viewport.virtual_to_screen_scale();
+    let magnitude = 10.0f64.powf(min_spacing.log10().floor());
+    for step in &[1.0, 2.0, 5.0] {
+      if step * magnitude >= min_spacing {
+        return step * magnitude;
+      }
+    }
+    10.0 * magnitude
+  }
+
+  /// The multiples of `spacing` between `min` and `max`
+  pub fn ticks(min: f64, max: f64, spacing: f64) -> Vec<f64> {
+    let (from, to) = ((min / spacing).ceil() as i64, (max / spacing).floor() as i64);
+    (from..=to).map(|i| i as f64 * spacing).collect()
+  }
+
+  pub fn lines(viewport: &Viewport) -> GridLines {
+    let spacing = Self::spacing(viewport);
+    let to_screen = |x: f64, y: f64| VirtualPosition(vec2![x, y]).to_screen(viewport).0;
+    let vertical = Self::ticks(viewport.x_min(), viewport.x_max(), spacing)
+      .into_iter()
+      .map(|x| GridLine {
+        position: to_screen(x, 0.0).x,
+        value: x,
+      })
+      .collect();
+    let horizontal = Self::ticks(viewport.y_min(), viewport.y_max(), spacing)
+      .into_iter()
+      .map(|y| GridLine {
+        position: to_screen(0.0, y).y,
+        value: y,
+      })
+      .collect();
+    let origin = to_screen(0.0, 0.0);
+    GridLines {
+      spacing,
+      vertical,
+      horizontal,
+      x_axis: if viewport.y_min() <= 0.0 && 0.0 <= viewport.y_max() {
+        Some(origin.y)
+      } else {
+        None
+      },
+      y_axis: if viewport.x_min() <= 0.0 && 0.0 <= viewport.x_max() {
+        Some(origin.x)
+      } else {
+        None
+      },
+    }
+  }
+
+  /// The lattice point closest to `position`
+  pub fn snap_position(position: VirtualPosition, viewport: &Viewport) -> VirtualPosition {
+    let spacing = Self::spacing(viewport);
+    let Vector2 { x, y } = position.0;
+    VirtualPosition(vec2![(x / spacing).round() * spacing, (y / spacing).round() * spacing])
+  }
+
+  /// The text of the tick at `value`, with just enough decimals for the spacing
+  pub fn tick_label(value: f64, spacing: f64) -> String {
+    let decimals = (-spacing.log10() - 1e-9).ceil().max(0.0) as usize;
+    let label = format!("{:.*}", decimals, value);
+    if label.starts_with('-') && label.trim_start_matches(&['-', '0', '.'][..]).is_empty() {
+      label[1..].to_string()
+    } else {
+      label
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_grid() {
+    // The default viewport shows 20 units on 960 pixels, so 40 pixels is 0.83 unit
+    let viewport = Viewport::default();
+    assert_eq!(Grid::spacing(&viewport), 1.0);
+    let zoomed_out = Viewport::new(vec2![0., 0.], vec2![200., 150.], viewport.screen_size);
+    assert_eq!(Grid::spacing(&zoomed_out), 10.0);
+    let zoomed_in = Viewport::new(vec2![0., 0.], vec2![3., 2.], viewport.screen_size);
+    assert!((Grid::spacing(&zoomed_in) - 0.2).abs() < 1e-12);
+
+    assert_eq!(Grid::ticks(-2.5, 1.0, 1.0), vec![-2.0, -1.0, 0.0, 1.0]);
+
+    let lines = Grid::lines(&viewport);
+    assert_eq!(lines.vertical.len(), 21);
+    assert_eq!(lines.x_axis, Some(360.0));
+    assert_eq!(lines.y_axis, Some(480.0));
+    let ticks = lines.tick_labels(viewport.screen_size);
+    assert_eq!(ticks.len(), 21 + 14); // 15 horizontal lines, without the origin
+    assert_eq!(ticks.iter().filter(|(text, _)| text == "0").count(), 1);
+
+    let snapped = Grid::snap_position(VirtualPosition(vec2![1.4, -0.6]), &viewport);
+    assert_eq!(snapped.0, vec2![1.0, -1.0]);
+
+    assert_eq!(Grid::tick_label(3.0, 1.0), "3");
+    assert_eq!(Grid::tick_label(0.4, 0.2), "0.4");
+    assert_eq!(Grid::tick_label(-0.0, 0.05), "0.00");
+  }
+}
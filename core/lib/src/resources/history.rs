@@ -1,19 +1,70 @@
-use crate::{components::symbolics::SymbolicPoint, utilities::Geometry};
+use crate::{
+  components::symbolics::{SymbolicPoint, SymbolicPolygon},
+  utilities::{Geometry, GeopadFile, HistoryJournal, JournalEntry, JournalReplay, Style},
+};
 use specs::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::{
+  collections::{HashMap, HashSet},
+  mem::size_of,
+};
 
 pub enum Modification {
   RemoveMany(HashMap<Entity, Geometry>),
   InsertMany(HashMap<Entity, Geometry>),
   UpdatePoint(Entity, SymbolicPoint, SymbolicPoint), // Entity, old, new
+  UpdateStyles(HashMap<Entity, (Style, Style)>),     // Entity -> (old, new)
   HideMany(HashSet<Entity>),
   UnhideMany(HashSet<Entity>),
+  Group(Vec<Modification>), // Undone and redone as a whole, in order
+}
+
+impl Modification {
+  /// A rough number of bytes taken by the modification, used to bound the history
+  pub fn estimated_size(&self) -> usize {
+    size_of::<Modification>()
+      + match self {
+        Modification::RemoveMany(geometries) | Modification::InsertMany(geometries) => geometries
+          .values()
+          .map(|geometry| match geometry {
+            Geometry::Polygon(SymbolicPolygon::Vertices(ps), _) => ps.len() * size_of::<Entity>(),
+            _ => 0,
+          })
+          .sum::<usize>()
+          .saturating_add(geometries.len() * size_of::<(Entity, Geometry)>()),
+        Modification::UpdatePoint(_, _, _) => 0,
+        Modification::UpdateStyles(updates) => updates.len() * size_of::<(Entity, (Style, Style))>(),
+        Modification::HideMany(entities) | Modification::UnhideMany(entities) => entities.len() * size_of::<Entity>(),
+        Modification::Group(modifications) => modifications.iter().map(Modification::estimated_size).sum(),
+      }
+  }
+}
+
+/// How much the history keeps. When either limit is exceeded the oldest entries are dropped
+/// first, but the last modification can always be undone
+#[derive(Debug, Copy, Clone)]
+pub struct HistoryLimits {
+  pub max_entries: Option<usize>,
+  pub max_bytes: Option<usize>, // As estimated by `Modification::estimated_size`
+}
+
+impl Default for HistoryLimits {
+  fn default() -> Self {
+    Self {
+      max_entries: Some(1000),
+      max_bytes: None,
+    }
+  }
 }
 
 pub struct History {
   history: Vec<Modification>,
   cursor: usize,
   head: usize,
+  size: usize, // Estimated size of all the entries
+  limits: HistoryLimits,
+  transaction: Vec<Modification>,
+  transaction_depth: usize,
+  journal: Option<HistoryJournal>,
 }
 
 impl Default for History {
@@ -22,6 +73,11 @@ impl Default for History {
       history: Vec::new(),
       cursor: 0,
       head: 0,
+      size: 0,
+      limits: HistoryLimits::default(),
+      transaction: Vec::new(),
+      transaction_depth: 0,
+      journal: None,
     }
   }
 }
@@ -31,11 +87,93 @@ impl History {
     self.history.clear();
     self.cursor = 0;
     self.head = 0;
+    self.size = 0;
+    self.transaction.clear();
+    self.transaction_depth = 0;
+    if let Some(journal) = &mut self.journal {
+      journal.record_clear();
+    }
+  }
+
+  pub fn limits(&self) -> HistoryLimits {
+    self.limits
+  }
+
+  pub fn set_limits(&mut self, limits: HistoryLimits) {
+    self.limits = limits;
+    self.drop_oldest();
+  }
+
+  pub fn len(&self) -> usize {
+    self.head
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.head == 0
+  }
+
+  /// Record every change of the history from now on, so that the session can be replayed
+  /// if the application crashes. `None` stops recording
+  pub fn set_journal(&mut self, journal: Option<HistoryJournal>) {
+    self.journal = journal;
   }
 
+  /// Tell the journal that the content was replaced, by loading a file for instance. The
+  /// elements of the file belong to `entities`, in the same order
+  pub fn record_base(&mut self, file: &GeopadFile, entities: &[Entity]) {
+    if let Some(journal) = &mut self.journal {
+      journal.record_base(file, entities);
+    }
+  }
+
+  /// Tell the journal that the content and the history were recovered from the journal
+  /// `entries`. `empty` is a file without elements, for the content to start again from
+  pub fn record_replay(&mut self, empty: &GeopadFile, entries: &[JournalEntry], replay: &JournalReplay) {
+    if let Some(journal) = &mut self.journal {
+      journal.record_replay(empty, entries, replay);
+    }
+  }
+
+  /// Take the entries of another history, a replayed one for instance. The limits and the
+  /// journal stay the same
+  pub fn replace_entries(&mut self, other: History) {
+    self.history = other.history;
+    self.cursor = other.cursor;
+    self.head = other.head;
+    self.size = other.size;
+    self.transaction.clear();
+    self.transaction_depth = 0;
+    self.drop_oldest();
+  }
+
+  /// Start grouping the pushed modifications into one. Transactions can be nested, and only
+  /// the outermost commit pushes the group
+  pub fn begin_transaction(&mut self) {
+    self.transaction_depth += 1;
+  }
+
+  pub fn commit_transaction(&mut self) {
+    if self.transaction_depth > 0 {
+      self.transaction_depth -= 1;
+      if self.transaction_depth == 0 {
+        self.flush_transaction();
+      }
+    }
+  }
+
+  pub fn in_transaction(&self) -> bool {
+    self.transaction_depth > 0
+  }
+
+  /// Undoing in the middle of a transaction commits it first, so that a group is never split
   pub fn undo(&mut self) -> Option<&Modification> {
+    self.transaction_depth = 0;
+    self.flush_transaction();
     if self.cursor > 0 {
       self.cursor -= 1;
+      if let Some(journal) = &mut self.journal {
+        journal.record_undo();
+      }
       Some(&self.history[self.cursor])
     } else {
       None
@@ -43,8 +181,13 @@ impl History {
   }
 
   pub fn redo(&mut self) -> Option<&Modification> {
+    self.transaction_depth = 0;
+    self.flush_transaction();
     if self.cursor < self.head {
       self.cursor += 1;
+      if let Some(journal) = &mut self.journal {
+        journal.record_redo();
+      }
       Some(&self.history[self.cursor - 1])
     } else {
       None
@@ -52,12 +195,143 @@ impl History {
   }
 
   pub fn push(&mut self, event: Modification) {
-    if self.cursor < self.history.len() {
-      self.history[self.cursor] = event;
+    if self.in_transaction() {
+      self.transaction.push(event);
     } else {
-      self.history.push(event);
+      self.push_entry(event);
+    }
+  }
+
+  fn flush_transaction(&mut self) {
+    let mut group = std::mem::take(&mut self.transaction);
+    match group.len() {
+      0 => (),
+      1 => self.push_entry(group.remove(0)),
+      _ => self.push_entry(Modification::Group(group)),
+    }
+  }
+
+  /// Pushing an entry drops the ones that could be redone
+  fn push_entry(&mut self, event: Modification) {
+    if let Some(journal) = &mut self.journal {
+      journal.record_push(&event);
     }
+    self.size -= self.history[self.cursor..]
+      .iter()
+      .map(Modification::estimated_size)
+      .sum::<usize>();
+    self.history.truncate(self.cursor);
+    self.size += event.estimated_size();
+    self.history.push(event);
     self.cursor += 1;
     self.head = self.cursor;
+    self.drop_oldest();
+  }
+
+  /// Drop the oldest entries until the history fits in its limits. Only the entries before
+  /// the cursor are dropped, and the last of them is always kept
+  fn drop_oldest(&mut self) {
+    let mut dropped = 0;
+    let mut size = self.size;
+    while self.cursor - dropped > 1 && self.exceeds_limits(self.history.len() - dropped, size) {
+      size -= self.history[dropped].estimated_size();
+      dropped += 1;
+    }
+    self.history.drain(..dropped);
+    self.size = size;
+    self.cursor -= dropped;
+    self.head -= dropped;
+  }
+
+  fn exceeds_limits(&self, entries: usize, size: usize) -> bool {
+    matches!(self.limits.max_entries, Some(max) if entries > max)
+      || matches!(self.limits.max_bytes, Some(max) if size > max)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn hide(world: &mut World) -> Modification {
+    let mut entities = HashSet::new();
+    entities.insert(world.create_entity().build());
+    Modification::HideMany(entities)
+  }
+
+  #[test]
+  fn test_transaction() {
+    let mut world = World::new();
+    let mut history = History::default();
+
+    history.begin_transaction();
+    history.push(hide(&mut world));
+    history.begin_transaction();
+    history.push(hide(&mut world));
+    history.commit_transaction();
+    assert!(history.in_transaction());
+    history.push(hide(&mut world));
+    history.commit_transaction();
+    assert!(!history.in_transaction());
+
+    // The three modifications are undone at once
+    match history.undo() {
+      Some(Modification::Group(group)) => assert_eq!(group.len(), 3),
+      _ => panic!("Expected a group"),
+    }
+    assert!(history.undo().is_none());
+    assert!(history.redo().is_some());
+
+    // A transaction with a single modification does not need a group
+    history.begin_transaction();
+    history.push(hide(&mut world));
+    history.commit_transaction();
+    match history.undo() {
+      Some(Modification::HideMany(_)) => (),
+      _ => panic!("Expected a single modification"),
+    }
+
+    // Undoing commits what was pushed so far
+    history.begin_transaction();
+    history.push(hide(&mut world));
+    history.push(hide(&mut world));
+    match history.undo() {
+      Some(Modification::Group(group)) => assert_eq!(group.len(), 2),
+      _ => panic!("Expected a group"),
+    }
+    assert!(!history.in_transaction());
+  }
+
+  #[test]
+  fn test_limits() {
+    let mut world = World::new();
+    let mut history = History::default();
+    history.set_limits(HistoryLimits {
+      max_entries: Some(3),
+      max_bytes: None,
+    });
+    for _ in 0..5 {
+      history.push(hide(&mut world));
+    }
+    assert_eq!(history.len(), 3);
+    for _ in 0..3 {
+      assert!(history.undo().is_some());
+    }
+    assert!(history.undo().is_none());
+
+    // Pushing after undoing drops what could be redone
+    history.redo();
+    history.push(hide(&mut world));
+    assert_eq!(history.len(), 2);
+    assert!(history.redo().is_none());
+
+    // The last modification is kept even when it is bigger than the limit
+    history.set_limits(HistoryLimits {
+      max_entries: None,
+      max_bytes: Some(1),
+    });
+    assert_eq!(history.len(), 1);
+    assert!(history.undo().is_some());
+    assert!(history.undo().is_none());
   }
 }
@@ -1,10 +1,14 @@
+mod creation_order;
 mod dependency_graph;
+mod grid;
 mod history;
 mod spatial_entity_map;
 mod styles;
 mod viewport;
 
+pub use creation_order::*;
 pub use dependency_graph::*;
+pub use grid::*;
 pub use history::*;
 pub use spatial_entity_map::*;
 pub use styles::*;
@@ -11,6 +11,7 @@ impl Default for DefaultCircleStyle {
       border: LineStyle {
         color: rgb!(0.0, 0.6, 0.0),
         width: 2.0,
+        dash: None,
       },
     })
   }
@@ -20,4 +21,8 @@ impl DefaultCircleStyle {
   pub fn get(&self) -> CircleStyle {
     self.0
   }
+
+  pub fn set(&mut self, style: CircleStyle) {
+    self.0 = style;
+  }
 }
@@ -9,6 +9,7 @@ impl Default for DefaultLineStyle {
     Self(LineStyle {
       color: Color::blue(),
       width: 2.0,
+      dash: None,
     })
   }
 }
@@ -17,4 +18,8 @@ impl DefaultLineStyle {
   pub fn get(&self) -> LineStyle {
     self.0
   }
+
+  pub fn set(&mut self, style: LineStyle) {
+    self.0 = style;
+  }
 }
@@ -19,4 +19,8 @@ impl DefaultPointStyle {
   pub fn get(&self) -> PointStyle {
     self.0
   }
+
+  pub fn set(&mut self, style: PointStyle) {
+    self.0 = style;
+  }
 }
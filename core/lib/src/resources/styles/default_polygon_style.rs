@@ -0,0 +1,28 @@
+use crate::components::styles::*;
+use crate::math::*;
+
+#[derive(Debug, Copy, Clone)]
+pub struct DefaultPolygonStyle(PolygonStyle);
+
+impl Default for DefaultPolygonStyle {
+  fn default() -> Self {
+    Self(PolygonStyle {
+      fill: rgba!(1.0, 0.6, 0.0, 0.3),
+      border: LineStyle {
+        color: rgb!(0.8, 0.4, 0.0),
+        width: 2.0,
+        dash: None,
+      },
+    })
+  }
+}
+
+impl DefaultPolygonStyle {
+  pub fn get(&self) -> PolygonStyle {
+    self.0
+  }
+
+  pub fn set(&mut self, style: PolygonStyle) {
+    self.0 = style;
+  }
+}
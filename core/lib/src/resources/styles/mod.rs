@@ -1,7 +1,9 @@
 mod default_circle_style;
 mod default_line_style;
 mod default_point_style;
+mod default_polygon_style;
 
 pub use default_circle_style::*;
 pub use default_line_style::*;
 pub use default_point_style::*;
+pub use default_polygon_style::*;
@@ -100,6 +100,146 @@ impl ToScreen for VirtualCircle {
   }
 }
 
+impl ToVirtual for ScreenArc {
+  type Output = VirtualArc;
+
+  fn to_virtual(self, vp: &Viewport) -> Self::Output {
+    let Self {
+      center,
+      radius,
+      start,
+      sweep,
+      kind,
+    } = self;
+    Self::Output {
+      center: center.to_virtual(vp),
+      radius: radius.to_virtual(vp),
+      start: -(start + sweep),
+      sweep,
+      kind,
+    }
+  }
+}
+
+impl ToScreen for VirtualArc {
+  type Output = ScreenArc;
+
+  fn to_screen(self, vp: &Viewport) -> Self::Output {
+    let Self {
+      center,
+      radius,
+      start,
+      sweep,
+      kind,
+    } = self;
+
+    // Flipping the y axis turns the arc around, so it starts at its end
+    Self::Output {
+      center: center.to_screen(vp),
+      radius: radius.to_screen(vp),
+      start: -(start + sweep),
+      sweep,
+      kind,
+    }
+  }
+}
+
+impl ToVirtual for ScreenPolygon {
+  type Output = VirtualPolygon;
+
+  fn to_virtual(self, vp: &Viewport) -> Self::Output {
+    Self::Output {
+      points: self.points.into_iter().map(|p| p.to_virtual(vp)).collect(),
+    }
+  }
+}
+
+impl ToScreen for VirtualPolygon {
+  type Output = ScreenPolygon;
+
+  fn to_screen(self, vp: &Viewport) -> Self::Output {
+    Self::Output {
+      points: self.points.into_iter().map(|p| p.to_screen(vp)).collect(),
+    }
+  }
+}
+
+impl ToVirtual for ScreenLocus {
+  type Output = VirtualLocus;
+
+  fn to_virtual(self, vp: &Viewport) -> Self::Output {
+    Self::Output {
+      paths: self
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(|p| p.to_virtual(vp)).collect())
+        .collect(),
+    }
+  }
+}
+
+impl ToScreen for VirtualLocus {
+  type Output = ScreenLocus;
+
+  fn to_screen(self, vp: &Viewport) -> Self::Output {
+    Self::Output {
+      paths: self
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(|p| p.to_screen(vp)).collect())
+        .collect(),
+    }
+  }
+}
+
+/// The map from screen to virtual positions, as a transformation of the plane
+fn screen_to_virtual(vp: &Viewport) -> Transform {
+  let origin = ScreenPosition(vec2![0.0, 0.0]).to_virtual(vp).0;
+  let x = ScreenPosition(vec2![1.0, 0.0]).to_virtual(vp).0 - origin;
+  let y = ScreenPosition(vec2![0.0, 1.0]).to_virtual(vp).0 - origin;
+  Transform {
+    matrix: [[x.x, y.x], [x.y, y.y]],
+    offset: origin,
+  }
+}
+
+/// The map from virtual to screen positions, as a transformation of the plane
+fn virtual_to_screen(vp: &Viewport) -> Transform {
+  let origin = VirtualPosition(vec2![0.0, 0.0]).to_screen(vp).0;
+  let x = VirtualPosition(vec2![1.0, 0.0]).to_screen(vp).0 - origin;
+  let y = VirtualPosition(vec2![0.0, 1.0]).to_screen(vp).0 - origin;
+  Transform {
+    matrix: [[x.x, y.x], [x.y, y.y]],
+    offset: origin,
+  }
+}
+
+impl ToVirtual for ScreenConic {
+  type Output = VirtualConic;
+
+  fn to_virtual(self, vp: &Viewport) -> Self::Output {
+    // A virtual position is on the conic when its screen position is
+    VirtualConic(self.conic.preimage(&virtual_to_screen(vp)))
+  }
+}
+
+impl ToScreen for VirtualConic {
+  type Output = ScreenConic;
+
+  fn to_screen(self, vp: &Viewport) -> Self::Output {
+    let conic = self.0.preimage(&screen_to_virtual(vp));
+    Self::Output {
+      conic,
+      paths: conic
+        .tessellate(vp.screen_aabb())
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -1,7 +1,16 @@
-use crate::math::*;
+use super::*;
+use crate::{math::*, utilities::*};
 
 pub static WINDOW_SIZE: [f64; 2] = [960., 720.];
 
+/// The virtual width is kept between these so that the viewport can neither collapse nor
+/// go so far out that the floating points stop making sense
+pub static MIN_VIRTUAL_WIDTH: f64 = 0.01;
+pub static MAX_VIRTUAL_WIDTH: f64 = 100_000.0;
+
+/// The space left around the elements when zooming to fit them, relative to their size
+static ZOOM_TO_FIT_MARGIN: f64 = 0.1;
+
 #[derive(Debug, Clone, Copy)]
 pub struct Viewport {
   pub virtual_center: Vector2,
@@ -36,11 +45,31 @@ impl Viewport {
   }
 
   pub fn set_virtual_size_x(&mut self, virtual_size_x: f64) {
+    let virtual_size_x = virtual_size_x.max(MIN_VIRTUAL_WIDTH).min(MAX_VIRTUAL_WIDTH);
     self.virtual_size.x = virtual_size_x;
     self.virtual_size.y = virtual_size_x / self.screen_size.x * self.screen_size.y;
     self.half_virtual_size = self.virtual_size / 2.0;
   }
 
+  /// Zoom in by `factor`, or out when it is less than 1. The virtual position under `anchor`
+  /// stays under it, unless the zoom gets clamped
+  pub fn zoom(&mut self, factor: f64, anchor: ScreenPosition) {
+    let fixed = anchor.to_virtual(self).0;
+    self.set_virtual_size_x(self.virtual_size.x / factor);
+    let moved = anchor.to_virtual(self).0;
+    self.virtual_center = self.virtual_center + fixed - moved;
+  }
+
+  /// Center on `aabb` and zoom so that all of it is visible. An empty `aabb`, like the one of
+  /// a single point, only moves the viewport
+  pub fn zoom_to_fit(&mut self, aabb: AABB) {
+    self.virtual_center = vec2![aabb.x + aabb.width / 2.0, aabb.y + aabb.height / 2.0];
+    let width = aabb.width.max(aabb.height / self.aspect_ratio());
+    if width > 0.0 {
+      self.set_virtual_size_x(width * (1.0 + 2.0 * ZOOM_TO_FIT_MARGIN));
+    }
+  }
+
   pub fn aspect_ratio(&self) -> f64 {
     self.screen_size.y / self.screen_size.x
   }
@@ -105,3 +134,31 @@ impl Viewport {
     AABB::new(0., 0., self.screen_width(), self.screen_height())
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_zoom() {
+    let mut viewport = Viewport::default();
+    let anchor = ScreenPosition(vec2![720., 180.]);
+    let fixed = anchor.to_virtual(&viewport).0;
+    viewport.zoom(2.0, anchor);
+    assert!((viewport.virtual_width() - 10.0).abs() < 1e-9);
+    assert!((anchor.to_virtual(&viewport).0 - fixed).magnitude() < 1e-9);
+
+    // Zooming is clamped
+    viewport.zoom(1e9, anchor);
+    assert_eq!(viewport.virtual_width(), MIN_VIRTUAL_WIDTH);
+    viewport.zoom(1e-12, anchor);
+    assert_eq!(viewport.virtual_width(), MAX_VIRTUAL_WIDTH);
+
+    // A tall box sets the height, with the margin on both sides
+    viewport.zoom_to_fit(AABB::new(1.0, 2.0, 3.0, 6.0));
+    assert_eq!(viewport.virtual_center, vec2![2.5, 5.0]);
+    assert!((viewport.virtual_height() - 6.0 * 1.2).abs() < 1e-9);
+    assert!(viewport.virtual_aabb().contains(vec2![1.0, 2.0]));
+    assert!(viewport.virtual_aabb().contains(vec2![4.0, 8.0]));
+  }
+}
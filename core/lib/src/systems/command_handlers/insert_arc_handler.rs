@@ -0,0 +1,190 @@
+use crate::{
+  components::{markers::*, styles::*, symbolics::*},
+  events::*,
+  math::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct InsertArcHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for InsertArcHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for InsertArcHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, MarkerEventChannel>,
+    Read<'a, DefaultPolygonStyle>,
+    Read<'a, CreationOrder>,
+    ReadStorage<'a, SymbolicPoint>,
+    WriteStorage<'a, SymbolicArc>,
+    WriteStorage<'a, PolygonStyle>,
+    WriteStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut marker_event_channel,
+      default_polygon_style,
+      creation_order,
+      sym_points,
+      mut sym_arcs,
+      mut polygon_styles,
+      mut selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        match &event.command {
+          Command::ArcInsert(insert_arc_event) => match insert_arc_event {
+            InsertArcEvent::InsertArc(sym_arc) => {
+              let ent = entities.create();
+              let polygon_style = default_polygon_style.get();
+              let (ent, geom) = insert(
+                ent,
+                *sym_arc,
+                polygon_style,
+                &mut sym_arcs,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertArcEvent::InsertArcFromSelection(kind) => {
+              if let Some(sym_arc) =
+                create_arc_from_selection(*kind, &entities, &creation_order, &sym_points, &selecteds)
+              {
+                let ent = entities.create();
+                let polygon_style = default_polygon_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_arc,
+                  polygon_style,
+                  &mut sym_arcs,
+                  &mut polygon_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertArcEvent::InsertArcWithStyle(sym_arc, polygon_style) => {
+              let ent = entities.create();
+              let (ent, geom) = insert(
+                ent,
+                *sym_arc,
+                *polygon_style,
+                &mut sym_arcs,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertArcEvent::InsertArcWithEntity(ent, sym_arc, polygon_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_arc,
+                *polygon_style,
+                &mut sym_arcs,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertArcEvent::InsertArcByHistory(ent, sym_arc, polygon_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_arc,
+                *polygon_style,
+                &mut sym_arcs,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted_by_history(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+fn insert<'a>(
+  ent: Entity,
+  sym_arc: SymbolicArc,
+  polygon_style: PolygonStyle,
+  sym_arcs: &mut WriteStorage<'a, SymbolicArc>,
+  polygon_styles: &mut WriteStorage<'a, PolygonStyle>,
+  selecteds: &mut WriteStorage<'a, Selected>,
+  elements: &mut WriteStorage<'a, Element>,
+) -> (Entity, Geometry) {
+  if let Err(err) = sym_arcs.insert(ent, sym_arc) {
+    panic!(err)
+  }
+  if let Err(err) = polygon_styles.insert(ent, polygon_style) {
+    panic!(err)
+  }
+  if let Err(err) = selecteds.insert(ent, Selected) {
+    panic!(err)
+  }
+  if let Err(err) = elements.insert(ent, Element) {
+    panic!(err)
+  }
+  (ent, Geometry::Arc(sym_arc, polygon_style))
+}
+
+/// We need exactly three points, and nothing else, in selection. The arc goes from the first
+/// created point through the second one to the third one
+pub fn create_arc_from_selection<'a>(
+  kind: ArcKind,
+  entities: &Entities<'a>,
+  creation_order: &CreationOrder,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<SymbolicArc> {
+  let mut point_ents = Vec::with_capacity(3);
+  for (ent, _) in (entities, selecteds).join() {
+    if sym_points.get(ent).is_some() && point_ents.len() < 3 {
+      point_ents.push(ent);
+    } else {
+      return None;
+    }
+  }
+  creation_order.sort(&mut point_ents);
+  match point_ents[..] {
+    [p1, p2, p3] => Some(SymbolicArc::ThreePoints(p1, p2, p3, kind)),
+    _ => None,
+  }
+}
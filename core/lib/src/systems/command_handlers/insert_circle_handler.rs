@@ -24,7 +24,11 @@ impl<'a> System<'a> for InsertCircleHandler {
     Read<'a, CommandEventChannel>,
     Write<'a, GeometryEventChannel>,
     Write<'a, MarkerEventChannel>,
+    Write<'a, HistoryEventChannel>,
     Read<'a, DefaultCircleStyle>,
+    Read<'a, CreationOrder>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, SymbolicLine>,
     WriteStorage<'a, SymbolicCircle>,
     WriteStorage<'a, CircleStyle>,
     WriteStorage<'a, Selected>,
@@ -43,7 +47,11 @@ impl<'a> System<'a> for InsertCircleHandler {
       command_event_channel,
       mut geometry_event_channel,
       mut marker_event_channel,
+      mut history_event_channel,
       default_circle_style,
+      creation_order,
+      sym_points,
+      sym_lines,
       mut sym_circles,
       mut circle_styles,
       mut selecteds,
@@ -69,6 +77,67 @@ impl<'a> System<'a> for InsertCircleHandler {
               geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
               marker_event_channel.single_write(MarkerEvent::Select(ent));
             }
+            InsertCircleEvent::InsertThreePointCircleFromSelection => {
+              if let Some(sym_circle) = create_three_point_circle_from_selection(&entities, &sym_points, &selecteds) {
+                let ent = entities.create();
+                let circle_style = default_circle_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_circle,
+                  circle_style,
+                  &mut sym_circles,
+                  &mut circle_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertCircleEvent::InsertTriangleCircleFromSelection(circle) => {
+              // The three excircles are undone at once
+              history_event_channel.single_write(HistoryEvent::BeginTransaction);
+              for sym_circle in create_triangle_circles_from_selection(&entities, &sym_points, &selecteds, circle) {
+                let ent = entities.create();
+                let circle_style = default_circle_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_circle,
+                  circle_style,
+                  &mut sym_circles,
+                  &mut circle_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+              history_event_channel.single_write(HistoryEvent::CommitTransaction);
+            }
+            InsertCircleEvent::InsertCompassCircleFromSelection => {
+              if let Some((p1_ent, p2_ent, center_ents)) =
+                check_compass_selection(&entities, &creation_order, &sym_points, &sym_lines, &selecteds)
+              {
+                history_event_channel.single_write(HistoryEvent::BeginTransaction);
+                for center_ent in center_ents {
+                  let sym_circle = SymbolicCircle::Compass(center_ent, p1_ent, p2_ent);
+                  let ent = entities.create();
+                  let circle_style = default_circle_style.get();
+                  let (ent, geom) = insert(
+                    ent,
+                    sym_circle,
+                    circle_style,
+                    &mut sym_circles,
+                    &mut circle_styles,
+                    &mut selecteds,
+                    &mut elements,
+                  );
+                  geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                  marker_event_channel.single_write(MarkerEvent::Select(ent));
+                }
+                history_event_channel.single_write(HistoryEvent::CommitTransaction);
+              }
+            }
             InsertCircleEvent::InsertCircleWithStyle(sym_circle, circle_style) => {
               let ent = entities.create();
               let (ent, geom) = insert(
@@ -83,6 +152,19 @@ impl<'a> System<'a> for InsertCircleHandler {
               geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
               marker_event_channel.single_write(MarkerEvent::Select(ent));
             }
+            InsertCircleEvent::InsertCircleWithEntity(ent, sym_circle, circle_style) => {
+              let (ent, geom) = insert(
+                ent,
+                sym_circle,
+                circle_style,
+                &mut sym_circles,
+                &mut circle_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
             InsertCircleEvent::InsertCircleByHistory(ent, sym_circle, circle_style) => {
               let (ent, geom) = insert(
                 ent,
@@ -127,3 +209,87 @@ fn insert<'a>(
   }
   (ent, Geometry::Circle(sym_circle, circle_style))
 }
+
+/// We need exactly three points, and nothing else, in selection
+pub fn create_three_point_circle_from_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<SymbolicCircle> {
+  let mut point_ents = Vec::with_capacity(3);
+  for (ent, _) in (entities, selecteds).join() {
+    if sym_points.get(ent).is_some() && point_ents.len() < 3 {
+      point_ents.push(ent);
+    } else {
+      return None;
+    }
+  }
+  match point_ents[..] {
+    [p1, p2, p3] => Some(SymbolicCircle::ThreePoints(p1, p2, p3)),
+    _ => None,
+  }
+}
+
+/// We need exactly three points, and nothing else, in selection. There's one excircle opposite
+/// to each of them, the other circles come alone
+pub fn create_triangle_circles_from_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+  circle: TriangleCircle,
+) -> Vec<SymbolicCircle> {
+  let mut point_ents = Vec::with_capacity(3);
+  for (ent, _) in (entities, selecteds).join() {
+    if sym_points.get(ent).is_some() && point_ents.len() < 3 {
+      point_ents.push(ent);
+    } else {
+      return vec![];
+    }
+  }
+  match point_ents[..] {
+    [p1, p2, p3] => match circle {
+      TriangleCircle::Incircle => vec![SymbolicCircle::Incircle(p1, p2, p3)],
+      TriangleCircle::Excircles => vec![
+        SymbolicCircle::Excircle(p1, p2, p3),
+        SymbolicCircle::Excircle(p2, p3, p1),
+        SymbolicCircle::Excircle(p3, p1, p2),
+      ],
+      TriangleCircle::NinePointCircle => vec![SymbolicCircle::NinePointCircle(p1, p2, p3)],
+    },
+    _ => vec![],
+  }
+}
+
+/// We can have, in selection, a single segment giving the radius, and lots of points as centers.
+/// Without a segment, exactly three points make a compass transfer: the radius goes from the
+/// first created point to the second one, and the circle is centered at the third one. Returns
+/// the two ends of the radius along with the centers
+pub fn check_compass_selection<'a>(
+  entities: &Entities<'a>,
+  creation_order: &CreationOrder,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  sym_lines: &ReadStorage<'a, SymbolicLine>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<(Entity, Entity, Vec<Entity>)> {
+  let mut maybe_segment = None;
+  let mut point_ents = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    if let Some(&SymbolicLine::Segment(p1_ent, p2_ent)) = sym_lines.get(entity) {
+      if maybe_segment.is_none() {
+        maybe_segment = Some((p1_ent, p2_ent));
+      } else {
+        return None;
+      }
+    } else if sym_points.get(entity).is_some() {
+      point_ents.push(entity);
+    } else {
+      return None;
+    }
+  }
+  creation_order.sort(&mut point_ents);
+  match (maybe_segment, &point_ents[..]) {
+    (Some((p1_ent, p2_ent)), _) if !point_ents.is_empty() => Some((p1_ent, p2_ent, point_ents)),
+    (None, &[p1_ent, p2_ent, center_ent]) => Some((p1_ent, p2_ent, vec![center_ent])),
+    _ => None,
+  }
+}
@@ -0,0 +1,197 @@
+use crate::{
+  components::{markers::*, styles::*, symbolics::*},
+  events::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct InsertConicHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for InsertConicHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for InsertConicHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, MarkerEventChannel>,
+    Read<'a, DefaultLineStyle>,
+    Read<'a, CreationOrder>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, SymbolicLine>,
+    WriteStorage<'a, SymbolicConic>,
+    WriteStorage<'a, LineStyle>,
+    WriteStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut marker_event_channel,
+      default_line_style,
+      creation_order,
+      sym_points,
+      sym_lines,
+      mut sym_conics,
+      mut line_styles,
+      mut selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        match &event.command {
+          Command::ConicInsert(insert_conic_event) => match insert_conic_event {
+            InsertConicEvent::InsertConic(sym_conic) => {
+              let ent = entities.create();
+              let line_style = default_line_style.get();
+              let (ent, geom) = insert(
+                ent,
+                *sym_conic,
+                line_style,
+                &mut sym_conics,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertConicEvent::InsertConicFromSelection => {
+              if let Some(sym_conic) =
+                create_conic_from_selection(&entities, &creation_order, &sym_points, &sym_lines, &selecteds)
+              {
+                let ent = entities.create();
+                let line_style = default_line_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_conic,
+                  line_style,
+                  &mut sym_conics,
+                  &mut line_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertConicEvent::InsertConicWithStyle(sym_conic, line_style) => {
+              let ent = entities.create();
+              let (ent, geom) = insert(
+                ent,
+                *sym_conic,
+                *line_style,
+                &mut sym_conics,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertConicEvent::InsertConicWithEntity(ent, sym_conic, line_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_conic,
+                *line_style,
+                &mut sym_conics,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertConicEvent::InsertConicByHistory(ent, sym_conic, line_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_conic,
+                *line_style,
+                &mut sym_conics,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted_by_history(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+fn insert<'a>(
+  ent: Entity,
+  sym_conic: SymbolicConic,
+  line_style: LineStyle,
+  sym_conics: &mut WriteStorage<'a, SymbolicConic>,
+  line_styles: &mut WriteStorage<'a, LineStyle>,
+  selecteds: &mut WriteStorage<'a, Selected>,
+  elements: &mut WriteStorage<'a, Element>,
+) -> (Entity, Geometry) {
+  if let Err(err) = sym_conics.insert(ent, sym_conic) {
+    panic!(err)
+  }
+  if let Err(err) = line_styles.insert(ent, line_style) {
+    panic!(err)
+  }
+  if let Err(err) = selecteds.insert(ent, Selected) {
+    panic!(err)
+  }
+  if let Err(err) = elements.insert(ent, Element) {
+    panic!(err)
+  }
+  (ent, Geometry::Conic(sym_conic, line_style))
+}
+
+/// The selection decides how the conic is built: five points for the conic through them, three
+/// points for the ellipse with the first two created ones as foci, or a point and a line for the
+/// parabola with that focus and directrix. Nothing else may be selected
+pub fn create_conic_from_selection<'a>(
+  entities: &Entities<'a>,
+  creation_order: &CreationOrder,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  sym_lines: &ReadStorage<'a, SymbolicLine>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<SymbolicConic> {
+  let mut point_ents = Vec::with_capacity(5);
+  let mut line_ents = Vec::with_capacity(1);
+  for (ent, _) in (entities, selecteds).join() {
+    if sym_points.get(ent).is_some() && point_ents.len() < 5 {
+      point_ents.push(ent);
+    } else if sym_lines.get(ent).is_some() && line_ents.is_empty() {
+      line_ents.push(ent);
+    } else {
+      return None;
+    }
+  }
+  creation_order.sort(&mut point_ents);
+  match (&point_ents[..], &line_ents[..]) {
+    ([p1, p2, p3, p4, p5], []) => Some(SymbolicConic::FivePoints(*p1, *p2, *p3, *p4, *p5)),
+    ([f1, f2, p], []) => Some(SymbolicConic::FociPoint(*f1, *f2, *p)),
+    ([focus], [directrix]) => Some(SymbolicConic::FocusDirectrix(*focus, *directrix)),
+    _ => None,
+  }
+}
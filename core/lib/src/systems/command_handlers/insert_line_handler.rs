@@ -24,9 +24,11 @@ impl<'a> System<'a> for InsertLineHandler {
     Read<'a, CommandEventChannel>,
     Write<'a, GeometryEventChannel>,
     Write<'a, MarkerEventChannel>,
+    Write<'a, HistoryEventChannel>,
     Read<'a, DefaultLineStyle>,
     ReadStorage<'a, SymbolicPoint>,
     WriteStorage<'a, SymbolicLine>,
+    ReadStorage<'a, SymbolicCircle>,
     WriteStorage<'a, LineStyle>,
     WriteStorage<'a, Selected>,
     WriteStorage<'a, Element>,
@@ -44,9 +46,11 @@ impl<'a> System<'a> for InsertLineHandler {
       command_event_channel,
       mut geometry_event_channel,
       mut marker_event_channel,
+      mut history_event_channel,
       default_line_style,
       sym_points,
       mut sym_lines,
+      sym_circles,
       mut line_styles,
       mut selecteds,
       mut elements,
@@ -73,6 +77,8 @@ impl<'a> System<'a> for InsertLineHandler {
             }
             InsertLineEvent::InsertParallelFromSelection => {
               if let Some((l_ent, p_ents)) = check_perp_para_selection(&entities, &sym_points, &sym_lines, &selecteds) {
+                // The lines through all the points are undone at once
+                history_event_channel.single_write(HistoryEvent::BeginTransaction);
                 for p_ent in p_ents {
                   let sym_line = SymbolicLine::Parallel(l_ent, p_ent);
                   let ent = entities.create();
@@ -89,10 +95,12 @@ impl<'a> System<'a> for InsertLineHandler {
                   geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
                   marker_event_channel.single_write(MarkerEvent::Select(ent));
                 }
+                history_event_channel.single_write(HistoryEvent::CommitTransaction);
               }
             }
             InsertLineEvent::InsertPerpendicularFromSelection => {
               if let Some((l_ent, p_ents)) = check_perp_para_selection(&entities, &sym_points, &sym_lines, &selecteds) {
+                history_event_channel.single_write(HistoryEvent::BeginTransaction);
                 for p_ent in p_ents {
                   let sym_line = SymbolicLine::Perpendicular(l_ent, p_ent);
                   let ent = entities.create();
@@ -109,6 +117,80 @@ impl<'a> System<'a> for InsertLineHandler {
                   geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
                   marker_event_channel.single_write(MarkerEvent::Select(ent));
                 }
+                history_event_channel.single_write(HistoryEvent::CommitTransaction);
+              }
+            }
+            InsertLineEvent::InsertAngleBisectorFromSelection => {
+              if let Some((p1_ent, v_ent, p2_ent)) = check_angle_bisector_selection(&entities, &sym_lines, &selecteds) {
+                let sym_line = SymbolicLine::AngleBisector(p1_ent, v_ent, p2_ent);
+                let ent = entities.create();
+                let line_style = default_line_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_line,
+                  line_style,
+                  &mut sym_lines,
+                  &mut line_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertLineEvent::InsertPerpendicularBisectorFromSelection => {
+              if let Some(pairs) = check_perp_bisector_selection(&entities, &sym_points, &sym_lines, &selecteds) {
+                history_event_channel.single_write(HistoryEvent::BeginTransaction);
+                for (p1_ent, p2_ent) in pairs {
+                  let sym_line = SymbolicLine::PerpendicularBisector(p1_ent, p2_ent);
+                  let ent = entities.create();
+                  let line_style = default_line_style.get();
+                  let (ent, geom) = insert(
+                    ent,
+                    sym_line,
+                    line_style,
+                    &mut sym_lines,
+                    &mut line_styles,
+                    &mut selecteds,
+                    &mut elements,
+                  );
+                  geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                  marker_event_channel.single_write(MarkerEvent::Select(ent));
+                }
+                history_event_channel.single_write(HistoryEvent::CommitTransaction);
+              }
+            }
+            InsertLineEvent::InsertTangentsFromSelection => {
+              if let Some((c_ent, p_ents)) = check_tangent_selection(&entities, &sym_points, &sym_circles, &selecteds) {
+                history_event_channel.single_write(HistoryEvent::BeginTransaction);
+                for p_ent in p_ents {
+                  // A point constrained on the circle only has one tangent, otherwise both get created
+                  let tangents = match sym_points.get(p_ent) {
+                    Some(&SymbolicPoint::OnCircle(on_c_ent, _)) if on_c_ent == c_ent => {
+                      vec![SymbolicLine::TangentAt(c_ent, p_ent)]
+                    }
+                    _ => vec![
+                      SymbolicLine::TangentFromPoint(c_ent, p_ent, CircleIntersectId::First),
+                      SymbolicLine::TangentFromPoint(c_ent, p_ent, CircleIntersectId::Second),
+                    ],
+                  };
+                  for sym_line in tangents {
+                    let ent = entities.create();
+                    let line_style = default_line_style.get();
+                    let (ent, geom) = insert(
+                      ent,
+                      sym_line,
+                      line_style,
+                      &mut sym_lines,
+                      &mut line_styles,
+                      &mut selecteds,
+                      &mut elements,
+                    );
+                    geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                    marker_event_channel.single_write(MarkerEvent::Select(ent));
+                  }
+                }
+                history_event_channel.single_write(HistoryEvent::CommitTransaction);
               }
             }
             InsertLineEvent::InsertLineWithStyle(sym_line, line_style) => {
@@ -125,6 +207,19 @@ impl<'a> System<'a> for InsertLineHandler {
               geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
               marker_event_channel.single_write(MarkerEvent::Select(ent));
             }
+            InsertLineEvent::InsertLineWithEntity(ent, sym_line, line_style) => {
+              let (ent, geom) = insert(
+                ent,
+                sym_line,
+                line_style,
+                &mut sym_lines,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
             InsertLineEvent::InsertLineByHistory(ent, sym_line, line_style) => {
               let (ent, geom) = insert(
                 ent,
@@ -200,3 +295,98 @@ pub fn check_perp_para_selection<'a>(
     None
   }
 }
+
+/// We can have, in selection, a single circle, and lots of points
+pub fn check_tangent_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  sym_circles: &ReadStorage<'a, SymbolicCircle>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<(Entity, Vec<Entity>)> {
+  let mut maybe_circle_ent = None;
+  let mut point_ents = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    if sym_circles.get(entity).is_some() {
+      if maybe_circle_ent.is_none() {
+        maybe_circle_ent = Some(entity);
+      } else {
+        return None;
+      }
+    } else if sym_points.get(entity).is_some() {
+      point_ents.push(entity);
+    }
+  }
+  match maybe_circle_ent {
+    Some(circle_ent) if !point_ents.is_empty() => Some((circle_ent, point_ents)),
+    _ => None,
+  }
+}
+
+/// The two sides of the angle need to be selected: exactly two lines going through two
+/// points each (straight lines, rays or segments) that share exactly one point. The shared
+/// point becomes the vertex. Returns `(point, vertex, point)`
+pub fn check_angle_bisector_selection<'a>(
+  entities: &Entities<'a>,
+  sym_lines: &WriteStorage<'a, SymbolicLine>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<(Entity, Entity, Entity)> {
+  let mut sides = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    match sym_lines.get(entity) {
+      Some(&SymbolicLine::Straight(p1, p2))
+      | Some(&SymbolicLine::Ray(p1, p2))
+      | Some(&SymbolicLine::Segment(p1, p2)) => sides.push((p1, p2)),
+      _ => return None,
+    }
+  }
+  if sides.len() != 2 {
+    return None;
+  }
+  angle_from_sides(sides[0], sides[1])
+}
+
+/// The angle between two sides given by their two points, when they share exactly one point.
+/// Returns `(point, vertex, point)`
+pub fn angle_from_sides((a1, a2): (Entity, Entity), (b1, b2): (Entity, Entity)) -> Option<(Entity, Entity, Entity)> {
+  if (a1 == b1 && a2 == b2) || (a1 == b2 && a2 == b1) {
+    None
+  } else if a1 == b1 {
+    Some((a2, a1, b2))
+  } else if a1 == b2 {
+    Some((a2, a1, b1))
+  } else if a2 == b1 {
+    Some((a1, a2, b2))
+  } else if a2 == b2 {
+    Some((a1, a2, b1))
+  } else {
+    None
+  }
+}
+
+/// We can have, in selection, either exactly two points, or whatever many segments and
+/// nothing else. Returns the pairs of points to draw a perpendicular bisector for
+pub fn check_perp_bisector_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  sym_lines: &WriteStorage<'a, SymbolicLine>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<Vec<(Entity, Entity)>> {
+  let mut point_ents = Vec::new();
+  let mut segments = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    if sym_points.get(entity).is_some() {
+      point_ents.push(entity);
+    } else if let Some(&SymbolicLine::Segment(p1_ent, p2_ent)) = sym_lines.get(entity) {
+      segments.push((p1_ent, p2_ent));
+    } else {
+      return None;
+    }
+  }
+  if point_ents.len() == 2 && segments.is_empty() {
+    Some(vec![(point_ents[0], point_ents[1])])
+  } else if point_ents.is_empty() && !segments.is_empty() {
+    Some(segments)
+  } else {
+    None
+  }
+}
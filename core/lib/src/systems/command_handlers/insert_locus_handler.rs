@@ -0,0 +1,198 @@
+use crate::{
+  components::{markers::*, styles::*, symbolics::*},
+  events::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct InsertLocusHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for InsertLocusHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for InsertLocusHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, MarkerEventChannel>,
+    Read<'a, DefaultLineStyle>,
+    Read<'a, DependencyGraph>,
+    ReadStorage<'a, SymbolicPoint>,
+    WriteStorage<'a, SymbolicLocus>,
+    WriteStorage<'a, LineStyle>,
+    WriteStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut marker_event_channel,
+      default_line_style,
+      dependency_graph,
+      sym_points,
+      mut sym_loci,
+      mut line_styles,
+      mut selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        match &event.command {
+          Command::LocusInsert(insert_locus_event) => match insert_locus_event {
+            InsertLocusEvent::InsertLocus(sym_locus) => {
+              let ent = entities.create();
+              let line_style = default_line_style.get();
+              let (ent, geom) = insert(
+                ent,
+                *sym_locus,
+                line_style,
+                &mut sym_loci,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertLocusEvent::InsertLocusFromSelection => {
+              if let Some(sym_locus) =
+                create_locus_from_selection(&entities, &dependency_graph, &sym_points, &selecteds)
+              {
+                let ent = entities.create();
+                let line_style = default_line_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_locus,
+                  line_style,
+                  &mut sym_loci,
+                  &mut line_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertLocusEvent::InsertLocusWithStyle(sym_locus, line_style) => {
+              let ent = entities.create();
+              let (ent, geom) = insert(
+                ent,
+                *sym_locus,
+                *line_style,
+                &mut sym_loci,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertLocusEvent::InsertLocusWithEntity(ent, sym_locus, line_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_locus,
+                *line_style,
+                &mut sym_loci,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertLocusEvent::InsertLocusByHistory(ent, sym_locus, line_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                *sym_locus,
+                *line_style,
+                &mut sym_loci,
+                &mut line_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted_by_history(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+fn insert<'a>(
+  ent: Entity,
+  sym_locus: SymbolicLocus,
+  line_style: LineStyle,
+  sym_loci: &mut WriteStorage<'a, SymbolicLocus>,
+  line_styles: &mut WriteStorage<'a, LineStyle>,
+  selecteds: &mut WriteStorage<'a, Selected>,
+  elements: &mut WriteStorage<'a, Element>,
+) -> (Entity, Geometry) {
+  if let Err(err) = sym_loci.insert(ent, sym_locus) {
+    panic!(err)
+  }
+  if let Err(err) = line_styles.insert(ent, line_style) {
+    panic!(err)
+  }
+  if let Err(err) = selecteds.insert(ent, Selected) {
+    panic!(err)
+  }
+  if let Err(err) = elements.insert(ent, Element) {
+    panic!(err)
+  }
+  (ent, Geometry::Locus(sym_locus, line_style))
+}
+
+/// We need exactly two points in selection: the driver, which is a point on a line, a circle
+/// or an arc, and a point depending on it to trace
+pub fn create_locus_from_selection<'a>(
+  entities: &Entities<'a>,
+  dependency_graph: &DependencyGraph,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<SymbolicLocus> {
+  let mut points = Vec::new();
+  for (ent, _) in (entities, selecteds).join() {
+    match sym_points.get(ent) {
+      Some(sym_point) => points.push((ent, *sym_point)),
+      None => return None,
+    }
+  }
+  if points.len() != 2 {
+    return None;
+  }
+  let is_driving = |(driver, sym_driver): (Entity, SymbolicPoint), traced: Entity| match sym_driver {
+    SymbolicPoint::OnLine(_, _) | SymbolicPoint::OnCircle(_, _) | SymbolicPoint::OnArc(_, _) => {
+      dependency_graph.get_all_dependents(&driver).contains(&traced)
+    }
+    _ => false,
+  };
+  if is_driving(points[0], points[1].0) {
+    Some(SymbolicLocus::Traced(points[0].0, points[1].0))
+  } else if is_driving(points[1], points[0].0) {
+    Some(SymbolicLocus::Traced(points[1].0, points[0].0))
+  } else {
+    None
+  }
+}
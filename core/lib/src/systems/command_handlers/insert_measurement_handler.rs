@@ -0,0 +1,246 @@
+use super::angle_from_sides;
+use crate::{
+  components::{markers::*, symbolics::*},
+  events::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct InsertMeasurementHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for InsertMeasurementHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for InsertMeasurementHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, HistoryEventChannel>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, SymbolicLine>,
+    ReadStorage<'a, SymbolicCircle>,
+    ReadStorage<'a, SymbolicPolygon>,
+    WriteStorage<'a, SymbolicMeasurement>,
+    ReadStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut history_event_channel,
+      sym_points,
+      sym_lines,
+      sym_circles,
+      sym_polygons,
+      mut sym_measurements,
+      selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        let insert_measurement_event = match &event.command {
+          Command::MeasurementInsert(insert_measurement_event) => *insert_measurement_event,
+          _ => continue,
+        };
+
+        // Several measurements may come from one selection, and they are undone at once
+        let from_selection = !matches!(
+          insert_measurement_event,
+          InsertMeasurementEvent::InsertMeasurement(_)
+            | InsertMeasurementEvent::InsertMeasurementWithEntity(_, _)
+            | InsertMeasurementEvent::InsertMeasurementByHistory(_, _)
+        );
+        if from_selection {
+          history_event_channel.single_write(HistoryEvent::BeginTransaction);
+        }
+        let to_insert = match insert_measurement_event {
+          InsertMeasurementEvent::InsertMeasurement(sym_measurement) => vec![sym_measurement],
+          InsertMeasurementEvent::InsertDistanceFromSelection => {
+            check_distance_selection(&entities, &sym_points, &sym_lines, &selecteds).unwrap_or_default()
+          }
+          InsertMeasurementEvent::InsertAngleFromSelection => {
+            match check_angle_selection(&entities, &sym_lines, &selecteds) {
+              Some((p1_ent, v_ent, p2_ent)) => vec![SymbolicMeasurement::Angle(p1_ent, v_ent, p2_ent)],
+              None => vec![],
+            }
+          }
+          InsertMeasurementEvent::InsertAreaFromSelection => {
+            match check_area_selection(&entities, &sym_circles, &sym_polygons, &selecteds) {
+              Some(ents) => ents.into_iter().map(SymbolicMeasurement::Area).collect(),
+              None => vec![],
+            }
+          }
+          InsertMeasurementEvent::InsertPerimeterFromSelection => {
+            match check_area_selection(&entities, &sym_circles, &sym_polygons, &selecteds) {
+              Some(ents) => ents.into_iter().map(SymbolicMeasurement::Perimeter).collect(),
+              None => vec![],
+            }
+          }
+          InsertMeasurementEvent::InsertRatioFromSelection => {
+            match check_ratio_selection(&entities, &sym_lines, &selecteds) {
+              Some((s1_ent, s2_ent)) => {
+                // The ratio is taken between the lengths of the two segments, which get measured too
+                let (m1_ent, m2_ent) = (entities.create(), entities.create());
+                for &(ent, sym_measurement) in &[
+                  (m1_ent, SymbolicMeasurement::Length(s1_ent)),
+                  (m2_ent, SymbolicMeasurement::Length(s2_ent)),
+                ] {
+                  let (ent, geom) = insert(ent, sym_measurement, &mut sym_measurements, &mut elements);
+                  geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                }
+                vec![SymbolicMeasurement::Ratio(m1_ent, m2_ent)]
+              }
+              None => vec![],
+            }
+          }
+          InsertMeasurementEvent::InsertMeasurementWithEntity(ent, sym_measurement) => {
+            let (ent, geom) = insert(ent, sym_measurement, &mut sym_measurements, &mut elements);
+            geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+            vec![]
+          }
+          InsertMeasurementEvent::InsertMeasurementByHistory(ent, sym_measurement) => {
+            let (ent, geom) = insert(ent, sym_measurement, &mut sym_measurements, &mut elements);
+            geometry_event_channel.single_write(GeometryEvent::inserted_by_history(ent, geom));
+            vec![]
+          }
+        };
+
+        // Measurements are only shown as their values, so unlike the other geometries they don't get
+        // selected when inserted. They would otherwise get in the way of the next command using the selection
+        for sym_measurement in to_insert {
+          let ent = entities.create();
+          let (ent, geom) = insert(ent, sym_measurement, &mut sym_measurements, &mut elements);
+          geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+        }
+        if from_selection {
+          history_event_channel.single_write(HistoryEvent::CommitTransaction);
+        }
+      }
+    }
+  }
+}
+
+fn insert<'a>(
+  ent: Entity,
+  sym_measurement: SymbolicMeasurement,
+  sym_measurements: &mut WriteStorage<'a, SymbolicMeasurement>,
+  elements: &mut WriteStorage<'a, Element>,
+) -> (Entity, Geometry) {
+  if let Err(err) = sym_measurements.insert(ent, sym_measurement) {
+    panic!(err)
+  }
+  if let Err(err) = elements.insert(ent, Element) {
+    panic!(err)
+  }
+  (ent, Geometry::Measurement(sym_measurement))
+}
+
+/// We can have, in selection, either exactly two points, or whatever many segments and
+/// nothing else. Two points give their distance, and segments give their own length
+pub fn check_distance_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  sym_lines: &ReadStorage<'a, SymbolicLine>,
+  selecteds: &ReadStorage<'a, Selected>,
+) -> Option<Vec<SymbolicMeasurement>> {
+  let mut point_ents = Vec::new();
+  let mut segment_ents = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    if sym_points.get(entity).is_some() {
+      point_ents.push(entity);
+    } else if let Some(SymbolicLine::Segment(_, _)) = sym_lines.get(entity) {
+      segment_ents.push(entity);
+    } else {
+      return None;
+    }
+  }
+  if point_ents.len() == 2 && segment_ents.is_empty() {
+    Some(vec![SymbolicMeasurement::Distance(point_ents[0], point_ents[1])])
+  } else if point_ents.is_empty() && !segment_ents.is_empty() {
+    Some(segment_ents.into_iter().map(SymbolicMeasurement::Length).collect())
+  } else {
+    None
+  }
+}
+
+/// The two sides of the angle need to be selected, the same way as for an angle bisector.
+/// Returns `(point, vertex, point)`
+pub fn check_angle_selection<'a>(
+  entities: &Entities<'a>,
+  sym_lines: &ReadStorage<'a, SymbolicLine>,
+  selecteds: &ReadStorage<'a, Selected>,
+) -> Option<(Entity, Entity, Entity)> {
+  let mut sides = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    match sym_lines.get(entity) {
+      Some(&SymbolicLine::Straight(p1, p2))
+      | Some(&SymbolicLine::Ray(p1, p2))
+      | Some(&SymbolicLine::Segment(p1, p2)) => sides.push((p1, p2)),
+      _ => return None,
+    }
+  }
+  if sides.len() != 2 {
+    return None;
+  }
+  angle_from_sides(sides[0], sides[1])
+}
+
+/// We need whatever many circles or polygons, and nothing else, in selection
+pub fn check_area_selection<'a>(
+  entities: &Entities<'a>,
+  sym_circles: &ReadStorage<'a, SymbolicCircle>,
+  sym_polygons: &ReadStorage<'a, SymbolicPolygon>,
+  selecteds: &ReadStorage<'a, Selected>,
+) -> Option<Vec<Entity>> {
+  let mut ents = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    if sym_circles.get(entity).is_some() || sym_polygons.get(entity).is_some() {
+      ents.push(entity);
+    } else {
+      return None;
+    }
+  }
+  if ents.is_empty() {
+    None
+  } else {
+    Some(ents)
+  }
+}
+
+/// We need exactly two segments, and nothing else, in selection
+pub fn check_ratio_selection<'a>(
+  entities: &Entities<'a>,
+  sym_lines: &ReadStorage<'a, SymbolicLine>,
+  selecteds: &ReadStorage<'a, Selected>,
+) -> Option<(Entity, Entity)> {
+  let mut segment_ents = Vec::new();
+  for (entity, _) in (entities, selecteds).join() {
+    match sym_lines.get(entity) {
+      Some(SymbolicLine::Segment(_, _)) => segment_ents.push(entity),
+      _ => return None,
+    }
+  }
+  if segment_ents.len() == 2 {
+    Some((segment_ents[0], segment_ents[1]))
+  } else {
+    None
+  }
+}
@@ -86,6 +86,24 @@ impl<'a> System<'a> for InsertPointHandler {
                 marker_event_channel.single_write(MarkerEvent::Select(ent));
               }
             }
+            InsertPointEvent::InsertTriangleCenterFromSelection(center) => {
+              if let Some(sym_point) = create_triangle_center_from_selection(&entities, &sym_points, &selecteds, center)
+              {
+                let ent = entities.create();
+                let point_style = default_point_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_point,
+                  point_style,
+                  &mut sym_points,
+                  &mut point_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
             InsertPointEvent::InsertPointWithStyle(sym_point, point_style) => {
               let ent = entities.create();
               let (ent, geom) = insert(
@@ -100,6 +118,19 @@ impl<'a> System<'a> for InsertPointHandler {
               geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
               marker_event_channel.single_write(MarkerEvent::Select(ent));
             }
+            InsertPointEvent::InsertPointWithEntity(ent, sym_point, point_style) => {
+              let (ent, geom) = insert(
+                ent,
+                sym_point,
+                point_style,
+                &mut sym_points,
+                &mut point_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
             InsertPointEvent::InsertPointByHistory(ent, sym_point, point_style) => {
               let (ent, geom) = insert(
                 ent,
@@ -169,3 +200,30 @@ pub fn create_midpoint_from_selection<'a>(
     _ => None,
   }
 }
+
+/// We need exactly three points, and nothing else, in selection
+pub fn create_triangle_center_from_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &WriteStorage<'a, SymbolicPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+  center: TriangleCenter,
+) -> Option<SymbolicPoint> {
+  let mut point_ents = Vec::with_capacity(3);
+  for (ent, _) in (entities, selecteds).join() {
+    if sym_points.get(ent).is_some() && point_ents.len() < 3 {
+      point_ents.push(ent);
+    } else {
+      return None;
+    }
+  }
+  match point_ents[..] {
+    [p1, p2, p3] => Some(match center {
+      TriangleCenter::Centroid => SymbolicPoint::Centroid(p1, p2, p3),
+      TriangleCenter::Circumcenter => SymbolicPoint::Circumcenter(p1, p2, p3),
+      TriangleCenter::Orthocenter => SymbolicPoint::Orthocenter(p1, p2, p3),
+      TriangleCenter::Incenter => SymbolicPoint::Incenter(p1, p2, p3),
+      TriangleCenter::NinePointCenter => SymbolicPoint::NinePointCenter(p1, p2, p3),
+    }),
+    _ => None,
+  }
+}
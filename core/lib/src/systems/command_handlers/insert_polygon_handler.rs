@@ -0,0 +1,192 @@
+use crate::{
+  components::{markers::*, styles::*, symbolics::*, virtual_shapes::*},
+  events::*,
+  math::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct InsertPolygonHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for InsertPolygonHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for InsertPolygonHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, MarkerEventChannel>,
+    Read<'a, DefaultPolygonStyle>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, VirtualPoint>,
+    WriteStorage<'a, SymbolicPolygon>,
+    WriteStorage<'a, PolygonStyle>,
+    WriteStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut marker_event_channel,
+      default_polygon_style,
+      sym_points,
+      virt_points,
+      mut sym_polygons,
+      mut polygon_styles,
+      mut selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        match &event.command {
+          Command::PolygonInsert(insert_polygon_event) => match insert_polygon_event {
+            InsertPolygonEvent::InsertPolygon(sym_polygon) => {
+              let ent = entities.create();
+              let polygon_style = default_polygon_style.get();
+              let (ent, geom) = insert(
+                ent,
+                sym_polygon.clone(),
+                polygon_style,
+                &mut sym_polygons,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertPolygonEvent::InsertPolygonFromSelection => {
+              if let Some(sym_polygon) = create_polygon_from_selection(&entities, &sym_points, &virt_points, &selecteds)
+              {
+                let ent = entities.create();
+                let polygon_style = default_polygon_style.get();
+                let (ent, geom) = insert(
+                  ent,
+                  sym_polygon,
+                  polygon_style,
+                  &mut sym_polygons,
+                  &mut polygon_styles,
+                  &mut selecteds,
+                  &mut elements,
+                );
+                geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+                marker_event_channel.single_write(MarkerEvent::Select(ent));
+              }
+            }
+            InsertPolygonEvent::InsertPolygonWithStyle(sym_polygon, polygon_style) => {
+              let ent = entities.create();
+              let (ent, geom) = insert(
+                ent,
+                sym_polygon.clone(),
+                *polygon_style,
+                &mut sym_polygons,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertPolygonEvent::InsertPolygonWithEntity(ent, sym_polygon, polygon_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                sym_polygon.clone(),
+                *polygon_style,
+                &mut sym_polygons,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            InsertPolygonEvent::InsertPolygonByHistory(ent, sym_polygon, polygon_style) => {
+              let (ent, geom) = insert(
+                *ent,
+                sym_polygon.clone(),
+                *polygon_style,
+                &mut sym_polygons,
+                &mut polygon_styles,
+                &mut selecteds,
+                &mut elements,
+              );
+              geometry_event_channel.single_write(GeometryEvent::inserted_by_history(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+fn insert<'a>(
+  ent: Entity,
+  sym_polygon: SymbolicPolygon,
+  polygon_style: PolygonStyle,
+  sym_polygons: &mut WriteStorage<'a, SymbolicPolygon>,
+  polygon_styles: &mut WriteStorage<'a, PolygonStyle>,
+  selecteds: &mut WriteStorage<'a, Selected>,
+  elements: &mut WriteStorage<'a, Element>,
+) -> (Entity, Geometry) {
+  if let Err(err) = sym_polygons.insert(ent, sym_polygon.clone()) {
+    panic!(err)
+  }
+  if let Err(err) = polygon_styles.insert(ent, polygon_style) {
+    panic!(err)
+  }
+  if let Err(err) = selecteds.insert(ent, Selected) {
+    panic!(err)
+  }
+  if let Err(err) = elements.insert(ent, Element) {
+    panic!(err)
+  }
+  (ent, Geometry::Polygon(sym_polygon, polygon_style))
+}
+
+/// We need at least three points, and nothing else, in selection. Since the selection carries
+/// no order, the vertices are ordered by their angle around the centroid of the points
+pub fn create_polygon_from_selection<'a>(
+  entities: &Entities<'a>,
+  sym_points: &ReadStorage<'a, SymbolicPoint>,
+  virt_points: &ReadStorage<'a, VirtualPoint>,
+  selecteds: &WriteStorage<'a, Selected>,
+) -> Option<SymbolicPolygon> {
+  let mut vertices = Vec::new();
+  for (ent, _) in (entities, selecteds).join() {
+    match (sym_points.get(ent), virt_points.get(ent)) {
+      (Some(_), Some(&virt_point)) => vertices.push((ent, virt_point.0)),
+      _ => return None,
+    }
+  }
+  if vertices.len() < 3 {
+    return None;
+  }
+  let sum = vertices.iter().fold(vec2![0.0, 0.0], |sum, &(_, p)| sum + p);
+  let centroid = sum / vertices.len() as f64;
+  let angle = |p: Vector2| (p.y - centroid.y).atan2(p.x - centroid.x);
+  vertices.sort_by(|&(_, p1), &(_, p2)| angle(p1).partial_cmp(&angle(p2)).unwrap());
+  Some(SymbolicPolygon::Vertices(
+    vertices.into_iter().map(|(ent, _)| ent).collect(),
+  ))
+}
@@ -0,0 +1,100 @@
+use crate::{
+  components::{labels::*, markers::*, symbolics::*},
+  events::*,
+};
+use specs::prelude::*;
+
+pub struct LabelHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for LabelHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for LabelHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, SymbolicLine>,
+    ReadStorage<'a, SymbolicCircle>,
+    ReadStorage<'a, Selected>,
+    WriteStorage<'a, Label>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      sym_points,
+      sym_lines,
+      sym_circles,
+      selecteds,
+      mut labels,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        // Only points, lines and circles can be labelled
+        let can_label =
+          |ent: Entity| sym_points.get(ent).is_some() || sym_lines.get(ent).is_some() || sym_circles.get(ent).is_some();
+        match &event.command {
+          Command::Label(label_event) => match label_event {
+            LabelEvent::Rename(ent, text) => {
+              if can_label(*ent) && rename(*ent, text, &mut labels) {
+                geometry_event_channel.single_write(GeometryEvent::LabelUpdated(*ent));
+              }
+            }
+            LabelEvent::RenameSelected(text) => {
+              let selected = (&entities, &selecteds).join().map(|(ent, _)| ent).collect::<Vec<_>>();
+              if let [ent] = selected[..] {
+                if can_label(ent) && rename(ent, text, &mut labels) {
+                  geometry_event_channel.single_write(GeometryEvent::LabelUpdated(ent));
+                }
+              }
+            }
+            LabelEvent::MoveLabel(ent, offset) => {
+              if let Some(label) = labels.get_mut(*ent) {
+                label.offset = *offset;
+                geometry_event_channel.single_write(GeometryEvent::LabelUpdated(*ent));
+              }
+            }
+            LabelEvent::SetLabel(ent, label) => {
+              if can_label(*ent) && labels.insert(*ent, label.clone()).is_ok() {
+                geometry_event_channel.single_write(GeometryEvent::LabelUpdated(*ent));
+              }
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+/// Change the text of the label, keeping where it is. Returns whether anything changed, which
+/// is not the case when the entity is already deleted. The change is not recorded in the history
+fn rename<'a>(ent: Entity, text: &str, labels: &mut WriteStorage<'a, Label>) -> bool {
+  let text = text.trim();
+  if text.is_empty() {
+    labels.remove(ent).is_some()
+  } else if let Some(label) = labels.get_mut(ent) {
+    label.text = text.to_string();
+    true
+  } else {
+    labels.insert(ent, Label::new(text.to_string())).is_ok()
+  }
+}
@@ -1,15 +1,31 @@
 mod hide_handler;
+mod insert_arc_handler;
 mod insert_circle_handler;
+mod insert_conic_handler;
 mod insert_line_handler;
+mod insert_locus_handler;
+mod insert_measurement_handler;
 mod insert_point_handler;
+mod insert_polygon_handler;
+mod label_handler;
 mod remove_handler;
 mod select_handler;
+mod transform_handler;
 mod update_point_handler;
+mod update_style_handler;
 
 pub use hide_handler::*;
+pub use insert_arc_handler::*;
 pub use insert_circle_handler::*;
+pub use insert_conic_handler::*;
 pub use insert_line_handler::*;
+pub use insert_locus_handler::*;
+pub use insert_measurement_handler::*;
 pub use insert_point_handler::*;
+pub use insert_polygon_handler::*;
+pub use label_handler::*;
 pub use remove_handler::*;
 pub use select_handler::*;
+pub use transform_handler::*;
 pub use update_point_handler::*;
+pub use update_style_handler::*;
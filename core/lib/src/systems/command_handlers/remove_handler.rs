@@ -1,12 +1,49 @@
 use crate::{
-  components::{markers::*, screen_shapes::*, styles::*, symbolics::*, virtual_shapes::*},
+  components::{markers::*, measurements::*, screen_shapes::*, styles::*, symbolics::*, virtual_shapes::*},
   events::*,
   resources::*,
+  system_data,
   utilities::*,
 };
 use specs::prelude::*;
 use std::collections::HashSet;
 
+system_data! {
+  /// Every storage an element can be in, all of them being cleared when it is removed
+  pub struct ElementStorages<'a> {
+    sym_points: WriteStorage<'a, SymbolicPoint>,
+    point_styles: WriteStorage<'a, PointStyle>,
+    virt_points: WriteStorage<'a, VirtualPoint>,
+    scrn_points: WriteStorage<'a, ScreenPoint>,
+    sym_lines: WriteStorage<'a, SymbolicLine>,
+    line_styles: WriteStorage<'a, LineStyle>,
+    virt_lines: WriteStorage<'a, VirtualLine>,
+    scrn_lines: WriteStorage<'a, ScreenLine>,
+    sym_circles: WriteStorage<'a, SymbolicCircle>,
+    circle_styles: WriteStorage<'a, CircleStyle>,
+    virt_circles: WriteStorage<'a, VirtualCircle>,
+    scrn_circles: WriteStorage<'a, ScreenCircle>,
+    sym_polygons: WriteStorage<'a, SymbolicPolygon>,
+    polygon_styles: WriteStorage<'a, PolygonStyle>,
+    virt_polygons: WriteStorage<'a, VirtualPolygon>,
+    scrn_polygons: WriteStorage<'a, ScreenPolygon>,
+    sym_measurements: WriteStorage<'a, SymbolicMeasurement>,
+    measurements: WriteStorage<'a, Measurement>,
+    sym_loci: WriteStorage<'a, SymbolicLocus>,
+    virt_loci: WriteStorage<'a, VirtualLocus>,
+    scrn_loci: WriteStorage<'a, ScreenLocus>,
+    sym_arcs: WriteStorage<'a, SymbolicArc>,
+    virt_arcs: WriteStorage<'a, VirtualArc>,
+    scrn_arcs: WriteStorage<'a, ScreenArc>,
+    sym_conics: WriteStorage<'a, SymbolicConic>,
+    virt_conics: WriteStorage<'a, VirtualConic>,
+    scrn_conics: WriteStorage<'a, ScreenConic>,
+    elements: WriteStorage<'a, Element>,
+    selecteds: WriteStorage<'a, Selected>,
+    hiddens: WriteStorage<'a, Hidden>,
+  }
+}
+
 pub struct RemoveHandler {
   command_event_reader: Option<CommandEventReader>,
 }
@@ -25,21 +62,7 @@ impl<'a> System<'a> for RemoveHandler {
     Read<'a, DependencyGraph>,
     Read<'a, CommandEventChannel>,
     Write<'a, GeometryEventChannel>,
-    WriteStorage<'a, SymbolicPoint>,
-    WriteStorage<'a, PointStyle>,
-    WriteStorage<'a, VirtualPoint>,
-    WriteStorage<'a, ScreenPoint>,
-    WriteStorage<'a, SymbolicLine>,
-    WriteStorage<'a, LineStyle>,
-    WriteStorage<'a, VirtualLine>,
-    WriteStorage<'a, ScreenLine>,
-    WriteStorage<'a, SymbolicCircle>,
-    WriteStorage<'a, CircleStyle>,
-    WriteStorage<'a, VirtualCircle>,
-    WriteStorage<'a, ScreenCircle>,
-    WriteStorage<'a, Element>,
-    WriteStorage<'a, Selected>,
-    WriteStorage<'a, Hidden>,
+    ElementStorages<'a>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -49,90 +72,48 @@ impl<'a> System<'a> for RemoveHandler {
 
   fn run(
     &mut self,
-    (
-      entities,
-      dependency_graph,
-      command_event_channel,
-      mut geometry_event_channel,
-      mut sym_points,
-      mut point_styles,
-      mut virt_points,
-      mut scrn_points,
-      mut sym_lines,
-      mut line_styles,
-      mut virt_lines,
-      mut scrn_lines,
-      mut sym_circles,
-      mut circle_styles,
-      mut virt_circles,
-      mut scrn_circles,
-      mut elements,
-      mut selecteds,
-      mut hiddens,
-    ): Self::SystemData,
+    (entities, dependency_graph, command_event_channel, mut geometry_event_channel, mut storages): Self::SystemData,
   ) {
     if let Some(reader) = &mut self.command_event_reader {
       for event in command_event_channel.read(reader) {
-        macro_rules! remove {
-          ($ent:expr) => {
-            remove_element(
-              $ent,
-              &mut sym_points,
-              &mut point_styles,
-              &mut virt_points,
-              &mut scrn_points,
-              &mut sym_lines,
-              &mut line_styles,
-              &mut virt_lines,
-              &mut scrn_lines,
-              &mut sym_circles,
-              &mut circle_styles,
-              &mut virt_circles,
-              &mut scrn_circles,
-              &mut elements,
-              &mut selecteds,
-              &mut hiddens,
-            )
-          };
-        }
         match event.command {
           Command::Remove(remove_event) => match remove_event {
             RemoveEvent::Remove(ent) => {
               for dep in dependency_graph.get_all_dependents(&ent) {
-                if let Some(geom) = remove!(&dep) {
+                if let Some(geom) = remove_element(&dep, &mut storages) {
                   geometry_event_channel.single_write(GeometryEvent::removed(dep, geom));
                 }
               }
             }
             RemoveEvent::RemoveByHistory(ent) => {
               for dep in dependency_graph.get_all_dependents(&ent) {
-                if let Some(geom) = remove!(&dep) {
+                if let Some(geom) = remove_element(&dep, &mut storages) {
                   geometry_event_channel.single_write(GeometryEvent::removed_by_history(dep, geom));
                 }
               }
             }
             RemoveEvent::RemoveSelected => {
               let mut set = HashSet::new();
-              for (ent, _) in (&entities, &selecteds).join() {
+              for (ent, _) in (&entities, &storages.selecteds).join() {
                 for dep in dependency_graph.get_all_dependents(&ent) {
                   set.insert(dep);
                 }
               }
               for ent in set {
-                if let Some(geom) = remove!(&ent) {
+                if let Some(geom) = remove_element(&ent, &mut storages) {
                   geometry_event_channel.single_write(GeometryEvent::removed(ent, geom));
                 }
               }
             }
             RemoveEvent::RemoveAll => {
               let mut set = HashSet::new();
-              for (ent, _) in (&entities, &elements).join() {
+              for (ent, _) in (&entities, &storages.elements).join() {
                 for dep in dependency_graph.get_all_dependents(&ent) {
                   set.insert(dep);
                 }
               }
               for ent in set {
-                if let Some(geom) = remove!(&ent) {
+                if let Some(geom) = remove_element(&ent, &mut storages) {
                   geometry_event_channel.single_write(GeometryEvent::removed(ent, geom));
                 }
               }
@@ -145,58 +126,72 @@ impl<'a> System<'a> for RemoveHandler {
   }
 }
 
-fn remove_element<'a>(
-  ent: &Entity,
-
-  sym_points: &mut WriteStorage<'a, SymbolicPoint>,
-  point_styles: &mut WriteStorage<'a, PointStyle>,
-  virt_points: &mut WriteStorage<'a, VirtualPoint>,
-  scrn_points: &mut WriteStorage<'a, ScreenPoint>,
-
-  sym_lines: &mut WriteStorage<'a, SymbolicLine>,
-  line_styles: &mut WriteStorage<'a, LineStyle>,
-  virt_lines: &mut WriteStorage<'a, VirtualLine>,
-  scrn_lines: &mut WriteStorage<'a, ScreenLine>,
-
-  sym_circles: &mut WriteStorage<'a, SymbolicCircle>,
-  circle_styles: &mut WriteStorage<'a, CircleStyle>,
-  virt_circles: &mut WriteStorage<'a, VirtualCircle>,
-  scrn_circles: &mut WriteStorage<'a, ScreenCircle>,
-
-  elements: &mut WriteStorage<'a, Element>,
-  selecteds: &mut WriteStorage<'a, Selected>,
-  hiddens: &mut WriteStorage<'a, Hidden>,
-) -> Option<Geometry> {
+fn remove_element<'a>(ent: &Entity, storages: &mut ElementStorages<'a>) -> Option<Geometry> {
   // Remove from markers
-  elements.remove(*ent);
-  selecteds.remove(*ent);
-  hiddens.remove(*ent);
+  storages.elements.remove(*ent);
+  storages.selecteds.remove(*ent);
+  storages.hiddens.remove(*ent);
 
   // Remove from geometry storages
-  if let Some(sym_point) = sym_points.remove(*ent) {
-    if let Some(point_style) = point_styles.remove(*ent) {
-      virt_points.remove(*ent);
-      scrn_points.remove(*ent);
+  if let Some(sym_point) = storages.sym_points.remove(*ent) {
+    if let Some(point_style) = storages.point_styles.remove(*ent) {
+      storages.virt_points.remove(*ent);
+      storages.scrn_points.remove(*ent);
       Some(Geometry::Point(sym_point, point_style))
     } else {
       None
     }
-  } else if let Some(sym_line) = sym_lines.remove(*ent) {
-    if let Some(line_style) = line_styles.remove(*ent) {
-      virt_lines.remove(*ent);
-      scrn_lines.remove(*ent);
+  } else if let Some(sym_line) = storages.sym_lines.remove(*ent) {
+    if let Some(line_style) = storages.line_styles.remove(*ent) {
+      storages.virt_lines.remove(*ent);
+      storages.scrn_lines.remove(*ent);
       Some(Geometry::Line(sym_line, line_style))
     } else {
       None
     }
-  } else if let Some(sym_circle) = sym_circles.remove(*ent) {
-    if let Some(circle_style) = circle_styles.remove(*ent) {
-      virt_circles.remove(*ent);
-      scrn_circles.remove(*ent);
+  } else if let Some(sym_circle) = storages.sym_circles.remove(*ent) {
+    if let Some(circle_style) = storages.circle_styles.remove(*ent) {
+      storages.virt_circles.remove(*ent);
+      storages.scrn_circles.remove(*ent);
       Some(Geometry::Circle(sym_circle, circle_style))
     } else {
       None
     }
+  } else if let Some(sym_polygon) = storages.sym_polygons.remove(*ent) {
+    if let Some(polygon_style) = storages.polygon_styles.remove(*ent) {
+      storages.virt_polygons.remove(*ent);
+      storages.scrn_polygons.remove(*ent);
+      Some(Geometry::Polygon(sym_polygon, polygon_style))
+    } else {
+      None
+    }
+  } else if let Some(sym_measurement) = storages.sym_measurements.remove(*ent) {
+    storages.measurements.remove(*ent);
+    Some(Geometry::Measurement(sym_measurement))
+  } else if let Some(sym_locus) = storages.sym_loci.remove(*ent) {
+    if let Some(line_style) = storages.line_styles.remove(*ent) {
+      storages.virt_loci.remove(*ent);
+      storages.scrn_loci.remove(*ent);
+      Some(Geometry::Locus(sym_locus, line_style))
+    } else {
+      None
+    }
+  } else if let Some(sym_arc) = storages.sym_arcs.remove(*ent) {
+    if let Some(polygon_style) = storages.polygon_styles.remove(*ent) {
+      storages.virt_arcs.remove(*ent);
+      storages.scrn_arcs.remove(*ent);
+      Some(Geometry::Arc(sym_arc, polygon_style))
+    } else {
+      None
+    }
+  } else if let Some(sym_conic) = storages.sym_conics.remove(*ent) {
+    if let Some(line_style) = storages.line_styles.remove(*ent) {
+      storages.virt_conics.remove(*ent);
+      storages.scrn_conics.remove(*ent);
+      Some(Geometry::Conic(sym_conic, line_style))
+    } else {
+      None
+    }
   } else {
     None
   }
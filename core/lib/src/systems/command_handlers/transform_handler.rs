@@ -0,0 +1,132 @@
+use crate::{
+  components::{markers::*, styles::*, symbolics::*},
+  events::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct TransformHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for TransformHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for TransformHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    Write<'a, MarkerEventChannel>,
+    Write<'a, HistoryEventChannel>,
+    WriteStorage<'a, SymbolicPoint>,
+    WriteStorage<'a, SymbolicLine>,
+    WriteStorage<'a, SymbolicCircle>,
+    WriteStorage<'a, PointStyle>,
+    WriteStorage<'a, LineStyle>,
+    WriteStorage<'a, CircleStyle>,
+    WriteStorage<'a, Selected>,
+    WriteStorage<'a, Element>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      mut marker_event_channel,
+      mut history_event_channel,
+      mut sym_points,
+      mut sym_lines,
+      mut sym_circles,
+      mut point_styles,
+      mut line_styles,
+      mut circle_styles,
+      mut selecteds,
+      mut elements,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        match event.command {
+          Command::Transform(TransformEvent::TransformSelection(trans)) => {
+            // The image of every selected element, except for the ones defining the transformation.
+            // Images keep the style of their source
+            let trans_ents = trans.entities();
+            let mut images = Vec::new();
+            for (ent, _) in (&entities, &selecteds).join() {
+              if trans_ents.contains(&ent) {
+                continue;
+              }
+              if let Some(style) = point_styles.get(ent) {
+                images.push(Geometry::Point(SymbolicPoint::Transformed(ent, trans), *style));
+              } else if let (Some(_), Some(style)) = (sym_lines.get(ent), line_styles.get(ent)) {
+                images.push(Geometry::Line(SymbolicLine::Transformed(ent, trans), *style));
+              } else if let Some(style) = circle_styles.get(ent) {
+                images.push(Geometry::Circle(SymbolicCircle::Transformed(ent, trans), *style));
+              }
+            }
+            // The images of all the elements are undone at once
+            history_event_channel.single_write(HistoryEvent::BeginTransaction);
+            for geom in images {
+              let ent = entities.create();
+              match geom {
+                Geometry::Point(sym_point, style) => {
+                  if let Err(err) = sym_points.insert(ent, sym_point) {
+                    panic!(err)
+                  }
+                  if let Err(err) = point_styles.insert(ent, style) {
+                    panic!(err)
+                  }
+                }
+                Geometry::Line(sym_line, style) => {
+                  if let Err(err) = sym_lines.insert(ent, sym_line) {
+                    panic!(err)
+                  }
+                  if let Err(err) = line_styles.insert(ent, style) {
+                    panic!(err)
+                  }
+                }
+                Geometry::Circle(sym_circle, style) => {
+                  if let Err(err) = sym_circles.insert(ent, sym_circle) {
+                    panic!(err)
+                  }
+                  if let Err(err) = circle_styles.insert(ent, style) {
+                    panic!(err)
+                  }
+                }
+                // Only points, lines and circles are transformed
+                Geometry::Polygon(_, _)
+                | Geometry::Measurement(_)
+                | Geometry::Locus(_, _)
+                | Geometry::Arc(_, _)
+                | Geometry::Conic(_, _) => continue,
+              }
+              if let Err(err) = selecteds.insert(ent, Selected) {
+                panic!(err)
+              }
+              if let Err(err) = elements.insert(ent, Element) {
+                panic!(err)
+              }
+              geometry_event_channel.single_write(GeometryEvent::inserted(ent, geom));
+              marker_event_channel.single_write(MarkerEvent::Select(ent));
+            }
+            history_event_channel.single_write(HistoryEvent::CommitTransaction);
+          }
+          _ => (),
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,154 @@
+use crate::{
+  components::{markers::*, styles::*},
+  events::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct UpdateStyleHandler {
+  command_event_reader: Option<CommandEventReader>,
+}
+
+impl Default for UpdateStyleHandler {
+  fn default() -> Self {
+    Self {
+      command_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for UpdateStyleHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, CommandEventChannel>,
+    Write<'a, GeometryEventChannel>,
+    ReadStorage<'a, Selected>,
+    WriteStorage<'a, PointStyle>,
+    WriteStorage<'a, LineStyle>,
+    WriteStorage<'a, CircleStyle>,
+    WriteStorage<'a, PolygonStyle>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.command_event_reader = Some(world.fetch_mut::<CommandEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      command_event_channel,
+      mut geometry_event_channel,
+      selecteds,
+      mut point_styles,
+      mut line_styles,
+      mut circle_styles,
+      mut polygon_styles,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.command_event_reader {
+      for event in command_event_channel.read(reader) {
+        macro_rules! get {
+          ($ent:expr) => {
+            get_style($ent, &point_styles, &line_styles, &circle_styles, &polygon_styles)
+          };
+        }
+        macro_rules! set {
+          ($ent:expr, $style:expr) => {
+            set_style(
+              $ent,
+              $style,
+              &mut point_styles,
+              &mut line_styles,
+              &mut circle_styles,
+              &mut polygon_styles,
+            )
+          };
+        }
+        match event.command {
+          Command::UpdateStyle(update_style_event) => match update_style_event {
+            UpdateStyleEvent::UpdateStyle(ent, update) => {
+              if let Some(old_style) = get!(ent) {
+                let new_style = old_style.updated(update);
+                set!(ent, new_style);
+                geometry_event_channel.single_write(GeometryEvent::style_updated(ent, old_style, new_style));
+              }
+            }
+            UpdateStyleEvent::UpdateSelectedStyle(update) => {
+              for (ent, _) in (&entities, &selecteds).join() {
+                if let Some(old_style) = get!(ent) {
+                  let new_style = old_style.updated(update);
+                  set!(ent, new_style);
+                  geometry_event_channel.single_write(GeometryEvent::style_updated(ent, old_style, new_style));
+                }
+              }
+            }
+            UpdateStyleEvent::SetStyle(ent, new_style) => {
+              if let Some(old_style) = get!(ent) {
+                if set!(ent, new_style) {
+                  geometry_event_channel.single_write(GeometryEvent::style_updated(ent, old_style, new_style));
+                }
+              }
+            }
+            UpdateStyleEvent::SetStyleByHistory(ent, new_style) => {
+              if let Some(old_style) = get!(ent) {
+                if set!(ent, new_style) {
+                  geometry_event_channel
+                    .single_write(GeometryEvent::style_updated_by_history(ent, old_style, new_style));
+                }
+              }
+            }
+          },
+          _ => (),
+        }
+      }
+    }
+  }
+}
+
+fn get_style<'a>(
+  ent: Entity,
+  point_styles: &WriteStorage<'a, PointStyle>,
+  line_styles: &WriteStorage<'a, LineStyle>,
+  circle_styles: &WriteStorage<'a, CircleStyle>,
+  polygon_styles: &WriteStorage<'a, PolygonStyle>,
+) -> Option<Style> {
+  point_styles
+    .get(ent)
+    .map(|style| Style::Point(*style))
+    .or_else(|| line_styles.get(ent).map(|style| Style::Line(*style)))
+    .or_else(|| circle_styles.get(ent).map(|style| Style::Circle(*style)))
+    .or_else(|| polygon_styles.get(ent).map(|style| Style::Polygon(*style)))
+}
+
+/// Replace the style of the element. Nothing happens, and false is returned, when the style
+/// is not of the same kind as the element
+fn set_style<'a>(
+  ent: Entity,
+  style: Style,
+  point_styles: &mut WriteStorage<'a, PointStyle>,
+  line_styles: &mut WriteStorage<'a, LineStyle>,
+  circle_styles: &mut WriteStorage<'a, CircleStyle>,
+  polygon_styles: &mut WriteStorage<'a, PolygonStyle>,
+) -> bool {
+  match style {
+    Style::Point(style) => match point_styles.get_mut(ent) {
+      Some(point_style) => *point_style = style,
+      None => return false,
+    },
+    Style::Line(style) => match line_styles.get_mut(ent) {
+      Some(line_style) => *line_style = style,
+      None => return false,
+    },
+    Style::Circle(style) => match circle_styles.get_mut(ent) {
+      Some(circle_style) => *circle_style = style,
+      None => return false,
+    },
+    Style::Polygon(style) => match polygon_styles.get_mut(ent) {
+      Some(polygon_style) => *polygon_style = style,
+      None => return false,
+    },
+  }
+  true
+}
@@ -0,0 +1,33 @@
+use crate::{events::*, resources::*};
+use specs::prelude::*;
+
+pub struct CreationOrderManager {
+  geometry_event_reader: Option<GeometryEventReader>,
+}
+
+impl Default for CreationOrderManager {
+  fn default() -> Self {
+    Self {
+      geometry_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for CreationOrderManager {
+  type SystemData = (Read<'a, GeometryEventChannel>, Write<'a, CreationOrder>);
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.geometry_event_reader = Some(world.fetch_mut::<GeometryEventChannel>().register_reader());
+  }
+
+  fn run(&mut self, (geometry_event_channel, mut creation_order): Self::SystemData) {
+    if let Some(reader) = &mut self.geometry_event_reader {
+      for event in geometry_event_channel.read(reader) {
+        if let GeometryEvent::Inserted(ent, _, _) = event {
+          creation_order.insert(ent);
+        }
+      }
+    }
+  }
+}
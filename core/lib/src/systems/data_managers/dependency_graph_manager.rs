@@ -29,6 +29,11 @@ impl<'a> System<'a> for DependencyGraphManager {
             Geometry::Point(sym_point, _) => insert_point(ent, sym_point, &mut *dependency_graph),
             Geometry::Line(sym_line, _) => insert_line(ent, sym_line, &mut *dependency_graph),
             Geometry::Circle(sym_circle, _) => insert_circle(ent, sym_circle, &mut *dependency_graph),
+            Geometry::Polygon(sym_polygon, _) => insert_polygon(ent, sym_polygon, &mut *dependency_graph),
+            Geometry::Measurement(sym_measurement) => insert_measurement(ent, sym_measurement, &mut *dependency_graph),
+            Geometry::Locus(sym_locus, _) => insert_locus(ent, sym_locus, &mut *dependency_graph),
+            Geometry::Arc(sym_arc, _) => insert_arc(ent, sym_arc, &mut *dependency_graph),
+            Geometry::Conic(sym_conic, _) => insert_conic(ent, sym_conic, &mut *dependency_graph),
           },
           GeometryEvent::Removed(ent, geom, _) => {
             dependency_graph.remove(ent);
@@ -36,6 +41,13 @@ impl<'a> System<'a> for DependencyGraphManager {
               Geometry::Point(sym_point, _) => remove_point(ent, sym_point, &mut *dependency_graph),
               Geometry::Line(sym_line, _) => remove_line(ent, sym_line, &mut *dependency_graph),
               Geometry::Circle(sym_circle, _) => remove_circle(ent, sym_circle, &mut *dependency_graph),
+              Geometry::Polygon(sym_polygon, _) => remove_polygon(ent, sym_polygon, &mut *dependency_graph),
+              Geometry::Measurement(sym_measurement) => {
+                remove_measurement(ent, sym_measurement, &mut *dependency_graph)
+              }
+              Geometry::Locus(sym_locus, _) => remove_locus(ent, sym_locus, &mut *dependency_graph),
+              Geometry::Arc(sym_arc, _) => remove_arc(ent, sym_arc, &mut *dependency_graph),
+              Geometry::Conic(sym_conic, _) => remove_conic(ent, sym_conic, &mut *dependency_graph),
             }
           }
           _ => (),
@@ -67,6 +79,34 @@ fn insert_point(ent: &Entity, sym_point: &SymbolicPoint, dependency_graph: &mut
       dependency_graph.add(c1_ent, ent);
       dependency_graph.add(c2_ent, ent);
     }
+    SymbolicPoint::Transformed(point_ent, trans) => {
+      dependency_graph.add(point_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.add(&trans_ent, ent);
+      }
+    }
+    SymbolicPoint::OnArc(arc_ent, _) => dependency_graph.add(arc_ent, ent),
+    SymbolicPoint::ArcLineIntersect(arc_ent, line_ent, _) => {
+      dependency_graph.add(arc_ent, ent);
+      dependency_graph.add(line_ent, ent);
+    }
+    SymbolicPoint::ArcCircleIntersect(arc_ent, circle_ent, _) => {
+      dependency_graph.add(arc_ent, ent);
+      dependency_graph.add(circle_ent, ent);
+    }
+    SymbolicPoint::ConicLineIntersect(conic_ent, line_ent, _) => {
+      dependency_graph.add(conic_ent, ent);
+      dependency_graph.add(line_ent, ent);
+    }
+    SymbolicPoint::Centroid(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Circumcenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Orthocenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Incenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::NinePointCenter(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+      dependency_graph.add(p3_ent, ent);
+    }
   }
 }
 
@@ -92,6 +132,29 @@ fn insert_line(ent: &Entity, sym_line: &SymbolicLine, dependency_graph: &mut Dep
       dependency_graph.add(line_ent, ent);
       dependency_graph.add(point_ent, ent);
     }
+    SymbolicLine::AngleBisector(p1_ent, vertex_ent, p2_ent) => {
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(vertex_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+    }
+    SymbolicLine::PerpendicularBisector(p1_ent, p2_ent) => {
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+    }
+    SymbolicLine::TangentAt(circle_ent, point_ent) => {
+      dependency_graph.add(circle_ent, ent);
+      dependency_graph.add(point_ent, ent);
+    }
+    SymbolicLine::TangentFromPoint(circle_ent, point_ent, _) => {
+      dependency_graph.add(circle_ent, ent);
+      dependency_graph.add(point_ent, ent);
+    }
+    SymbolicLine::Transformed(line_ent, trans) => {
+      dependency_graph.add(line_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.add(&trans_ent, ent);
+      }
+    }
   }
 }
 
@@ -101,6 +164,59 @@ fn insert_circle(ent: &Entity, sym_circle: &SymbolicCircle, dependency_graph: &m
       dependency_graph.add(p1_ent, ent);
       dependency_graph.add(p2_ent, ent);
     }
+    SymbolicCircle::ThreePoints(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+      dependency_graph.add(p3_ent, ent);
+    }
+    SymbolicCircle::Compass(c_ent, p1_ent, p2_ent) => {
+      dependency_graph.add(c_ent, ent);
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+    }
+    SymbolicCircle::Transformed(circle_ent, trans) => {
+      dependency_graph.add(circle_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.add(&trans_ent, ent);
+      }
+    }
+    SymbolicCircle::Incircle(p1_ent, p2_ent, p3_ent)
+    | SymbolicCircle::Excircle(p1_ent, p2_ent, p3_ent)
+    | SymbolicCircle::NinePointCircle(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.add(p1_ent, ent);
+      dependency_graph.add(p2_ent, ent);
+      dependency_graph.add(p3_ent, ent);
+    }
+  }
+}
+
+fn insert_polygon(ent: &Entity, sym_polygon: &SymbolicPolygon, dependency_graph: &mut DependencyGraph) {
+  for vertex_ent in sym_polygon.vertices() {
+    dependency_graph.add(vertex_ent, ent);
+  }
+}
+
+fn insert_measurement(ent: &Entity, sym_measurement: &SymbolicMeasurement, dependency_graph: &mut DependencyGraph) {
+  for measured_ent in sym_measurement.entities() {
+    dependency_graph.add(&measured_ent, ent);
+  }
+}
+
+/// The locus depends on the traced point, and so on everything the traced point depends on
+fn insert_locus(ent: &Entity, sym_locus: &SymbolicLocus, dependency_graph: &mut DependencyGraph) {
+  dependency_graph.add(&sym_locus.driver(), ent);
+  dependency_graph.add(&sym_locus.traced(), ent);
+}
+
+fn insert_arc(ent: &Entity, sym_arc: &SymbolicArc, dependency_graph: &mut DependencyGraph) {
+  for point_ent in sym_arc.points().iter() {
+    dependency_graph.add(point_ent, ent);
+  }
+}
+
+fn insert_conic(ent: &Entity, sym_conic: &SymbolicConic, dependency_graph: &mut DependencyGraph) {
+  for dep_ent in sym_conic.dependencies().iter() {
+    dependency_graph.add(dep_ent, ent);
   }
 }
 
@@ -126,6 +242,34 @@ fn remove_point(ent: &Entity, sym_point: &SymbolicPoint, dependency_graph: &mut
       dependency_graph.remove_dependent(c1_ent, ent);
       dependency_graph.remove_dependent(c2_ent, ent);
     }
+    SymbolicPoint::Transformed(point_ent, trans) => {
+      dependency_graph.remove_dependent(point_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.remove_dependent(&trans_ent, ent);
+      }
+    }
+    SymbolicPoint::OnArc(arc_ent, _) => dependency_graph.remove_dependent(arc_ent, ent),
+    SymbolicPoint::ArcLineIntersect(arc_ent, line_ent, _) => {
+      dependency_graph.remove_dependent(arc_ent, ent);
+      dependency_graph.remove_dependent(line_ent, ent);
+    }
+    SymbolicPoint::ArcCircleIntersect(arc_ent, circle_ent, _) => {
+      dependency_graph.remove_dependent(arc_ent, ent);
+      dependency_graph.remove_dependent(circle_ent, ent);
+    }
+    SymbolicPoint::ConicLineIntersect(conic_ent, line_ent, _) => {
+      dependency_graph.remove_dependent(conic_ent, ent);
+      dependency_graph.remove_dependent(line_ent, ent);
+    }
+    SymbolicPoint::Centroid(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Circumcenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Orthocenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::Incenter(p1_ent, p2_ent, p3_ent)
+    | SymbolicPoint::NinePointCenter(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+      dependency_graph.remove_dependent(p3_ent, ent);
+    }
   }
 }
 
@@ -151,6 +295,29 @@ fn remove_line(ent: &Entity, sym_line: &SymbolicLine, dependency_graph: &mut Dep
       dependency_graph.remove_dependent(line_ent, ent);
       dependency_graph.remove_dependent(point_ent, ent);
     }
+    SymbolicLine::AngleBisector(p1_ent, vertex_ent, p2_ent) => {
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(vertex_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+    }
+    SymbolicLine::PerpendicularBisector(p1_ent, p2_ent) => {
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+    }
+    SymbolicLine::TangentAt(circle_ent, point_ent) => {
+      dependency_graph.remove_dependent(circle_ent, ent);
+      dependency_graph.remove_dependent(point_ent, ent);
+    }
+    SymbolicLine::TangentFromPoint(circle_ent, point_ent, _) => {
+      dependency_graph.remove_dependent(circle_ent, ent);
+      dependency_graph.remove_dependent(point_ent, ent);
+    }
+    SymbolicLine::Transformed(line_ent, trans) => {
+      dependency_graph.remove_dependent(line_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.remove_dependent(&trans_ent, ent);
+      }
+    }
   }
 }
 
@@ -160,5 +327,57 @@ fn remove_circle(ent: &Entity, sym_circle: &SymbolicCircle, dependency_graph: &m
       dependency_graph.remove_dependent(p1_ent, ent);
       dependency_graph.remove_dependent(p2_ent, ent);
     }
+    SymbolicCircle::ThreePoints(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+      dependency_graph.remove_dependent(p3_ent, ent);
+    }
+    SymbolicCircle::Compass(c_ent, p1_ent, p2_ent) => {
+      dependency_graph.remove_dependent(c_ent, ent);
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+    }
+    SymbolicCircle::Transformed(circle_ent, trans) => {
+      dependency_graph.remove_dependent(circle_ent, ent);
+      for trans_ent in trans.entities() {
+        dependency_graph.remove_dependent(&trans_ent, ent);
+      }
+    }
+    SymbolicCircle::Incircle(p1_ent, p2_ent, p3_ent)
+    | SymbolicCircle::Excircle(p1_ent, p2_ent, p3_ent)
+    | SymbolicCircle::NinePointCircle(p1_ent, p2_ent, p3_ent) => {
+      dependency_graph.remove_dependent(p1_ent, ent);
+      dependency_graph.remove_dependent(p2_ent, ent);
+      dependency_graph.remove_dependent(p3_ent, ent);
+    }
+  }
+}
+
+fn remove_polygon(ent: &Entity, sym_polygon: &SymbolicPolygon, dependency_graph: &mut DependencyGraph) {
+  for vertex_ent in sym_polygon.vertices() {
+    dependency_graph.remove_dependent(vertex_ent, ent);
+  }
+}
+
+fn remove_measurement(ent: &Entity, sym_measurement: &SymbolicMeasurement, dependency_graph: &mut DependencyGraph) {
+  for measured_ent in sym_measurement.entities() {
+    dependency_graph.remove_dependent(&measured_ent, ent);
+  }
+}
+
+fn remove_locus(ent: &Entity, sym_locus: &SymbolicLocus, dependency_graph: &mut DependencyGraph) {
+  dependency_graph.remove_dependent(&sym_locus.driver(), ent);
+  dependency_graph.remove_dependent(&sym_locus.traced(), ent);
+}
+
+fn remove_arc(ent: &Entity, sym_arc: &SymbolicArc, dependency_graph: &mut DependencyGraph) {
+  for point_ent in sym_arc.points().iter() {
+    dependency_graph.remove_dependent(point_ent, ent);
+  }
+}
+
+fn remove_conic(ent: &Entity, sym_conic: &SymbolicConic, dependency_graph: &mut DependencyGraph) {
+  for dep_ent in sym_conic.dependencies().iter() {
+    dependency_graph.remove_dependent(dep_ent, ent);
   }
 }
@@ -7,6 +7,7 @@ enum Mod {
   Insert(HashMap<Entity, Geometry>),
   Remove(HashMap<Entity, Geometry>),
   UpdatePoint(Entity, SymbolicPoint, SymbolicPoint),
+  UpdateStyle(HashMap<Entity, (Style, Style)>),
   Hide(HashSet<Entity>),
   Unhide(HashSet<Entity>),
 }
@@ -14,6 +15,7 @@ enum Mod {
 pub struct HistoryManager {
   geometry_event_reader: Option<GeometryEventReader>,
   marker_event_reader: Option<MarkerEventReader>,
+  history_event_reader: Option<HistoryEventReader>,
 }
 
 impl Default for HistoryManager {
@@ -21,6 +23,7 @@ impl Default for HistoryManager {
     Self {
       geometry_event_reader: None,
       marker_event_reader: None,
+      history_event_reader: None,
     }
   }
 }
@@ -29,6 +32,7 @@ impl<'a> System<'a> for HistoryManager {
   type SystemData = (
     Read<'a, GeometryEventChannel>,
     Read<'a, MarkerEventChannel>,
+    Read<'a, HistoryEventChannel>,
     Write<'a, History>,
   );
 
@@ -36,12 +40,29 @@ impl<'a> System<'a> for HistoryManager {
     Self::SystemData::setup(world);
     self.geometry_event_reader = Some(world.fetch_mut::<GeometryEventChannel>().register_reader());
     self.marker_event_reader = Some(world.fetch_mut::<MarkerEventChannel>().register_reader());
+    self.history_event_reader = Some(world.fetch_mut::<HistoryEventChannel>().register_reader());
   }
 
-  fn run(&mut self, (geometry_event_channel, marker_event_channel, mut history): Self::SystemData) {
+  fn run(
+    &mut self,
+    (geometry_event_channel, marker_event_channel, history_event_channel, mut history): Self::SystemData,
+  ) {
     assert!(self.geometry_event_reader.is_some());
     assert!(self.marker_event_reader.is_some());
 
+    // Transactions are made of whole frames. The ones beginning in this frame take its
+    // modifications, and the ones committed in this frame are committed after them
+    let mut commit_count = 0;
+    if let Some(reader) = &mut self.history_event_reader {
+      for event in history_event_channel.read(reader) {
+        match event {
+          HistoryEvent::BeginTransaction => history.begin_transaction(),
+          HistoryEvent::CommitTransaction => commit_count += 1,
+          _ => (),
+        }
+      }
+    }
+
     // First do geometry events
     if let Some(reader_id) = &mut self.geometry_event_reader {
       let mut curr_event = Mod::None;
@@ -49,21 +70,21 @@ impl<'a> System<'a> for HistoryManager {
         match event {
           GeometryEvent::Inserted(entity, geom, false) => {
             if let Mod::Insert(insertions) = &mut curr_event {
-              insertions.insert(*entity, *geom);
+              insertions.insert(*entity, geom.clone());
             } else {
               push_event(curr_event, &mut history);
               let mut insertions = HashMap::new();
-              insertions.insert(*entity, *geom);
+              insertions.insert(*entity, geom.clone());
               curr_event = Mod::Insert(insertions);
             }
           }
           GeometryEvent::Removed(entity, geom, false) => {
             if let Mod::Remove(removals) = &mut curr_event {
-              removals.insert(*entity, *geom);
+              removals.insert(*entity, geom.clone());
             } else {
               push_event(curr_event, &mut history);
               let mut removals = HashMap::new();
-              removals.insert(*entity, *geom);
+              removals.insert(*entity, geom.clone());
               curr_event = Mod::Remove(removals);
             }
           }
@@ -71,6 +92,18 @@ impl<'a> System<'a> for HistoryManager {
             push_event(curr_event, &mut history);
             curr_event = Mod::UpdatePoint(*entity, *old_sym_point, *new_sym_point);
           }
+          GeometryEvent::StyleUpdated(entity, old_style, new_style, false) => {
+            if let Mod::UpdateStyle(updates) = &mut curr_event {
+              // Keep the oldest style when the same element gets updated more than once
+              let old_style = updates.get(entity).map_or(*old_style, |(old_style, _)| *old_style);
+              updates.insert(*entity, (old_style, *new_style));
+            } else {
+              push_event(curr_event, &mut history);
+              let mut updates = HashMap::new();
+              updates.insert(*entity, (*old_style, *new_style));
+              curr_event = Mod::UpdateStyle(updates);
+            }
+          }
           _ => (),
         }
       }
@@ -107,6 +140,10 @@ impl<'a> System<'a> for HistoryManager {
       }
       push_event(curr_event, &mut history);
     }
+
+    for _ in 0..commit_count {
+      history.commit_transaction();
+    }
   }
 }
 
@@ -118,6 +155,7 @@ fn push_event(event: Mod, history: &mut History) {
     Mod::UpdatePoint(ent, old_sym_point, new_sym_point) => {
       history.push(Modification::UpdatePoint(ent, old_sym_point, new_sym_point))
     }
+    Mod::UpdateStyle(updates) => history.push(Modification::UpdateStyles(updates)),
     Mod::Hide(entities) => history.push(Modification::HideMany(entities)),
     Mod::Unhide(entities) => history.push(Modification::UnhideMany(entities)),
   }
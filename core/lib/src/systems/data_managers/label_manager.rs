@@ -0,0 +1,64 @@
+use crate::{
+  components::{labels::*, markers::*},
+  events::*,
+  utilities::*,
+};
+use specs::prelude::*;
+use std::collections::HashSet;
+
+/// Names the newly inserted points A, B, C... skipping the names already taken. Points
+/// inserted by history keep the label they had, as removing an element leaves its label there
+pub struct LabelManager {
+  geometry_event_reader: Option<GeometryEventReader>,
+}
+
+impl Default for LabelManager {
+  fn default() -> Self {
+    Self {
+      geometry_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for LabelManager {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, GeometryEventChannel>,
+    ReadStorage<'a, Element>,
+    WriteStorage<'a, Label>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.geometry_event_reader = Some(world.fetch_mut::<GeometryEventChannel>().register_reader());
+  }
+
+  fn run(&mut self, (entities, geometry_event_channel, elements, mut labels): Self::SystemData) {
+    if let Some(reader) = &mut self.geometry_event_reader {
+      let mut taken: Option<HashSet<String>> = None;
+      let mut next_index = 0;
+      for event in geometry_event_channel.read(reader) {
+        if let GeometryEvent::Inserted(ent, Geometry::Point(_, _), false) = event {
+          if labels.get(*ent).is_some() {
+            continue;
+          }
+
+          // Only the labels of the elements still there are taken
+          let taken = taken.get_or_insert_with(|| {
+            (&entities, &elements, &labels)
+              .join()
+              .map(|(_, _, label)| label.text.clone())
+              .collect()
+          });
+          while taken.contains(&Label::auto_name(next_index)) {
+            next_index += 1;
+          }
+          let name = Label::auto_name(next_index);
+          taken.insert(name.clone());
+          // The point may already be deleted by a later command of the same frame
+          labels.insert(*ent, Label::new(name)).ok();
+        }
+      }
+    }
+  }
+}
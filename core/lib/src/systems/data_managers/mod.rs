@@ -1,7 +1,11 @@
+mod creation_order_manager;
 mod dependency_graph_manager;
 mod history_manager;
+mod label_manager;
 mod spatial_entity_map_manager;
 
+pub use creation_order_manager::*;
 pub use dependency_graph_manager::*;
 pub use history_manager::*;
+pub use label_manager::*;
 pub use spatial_entity_map_manager::*;
@@ -3,7 +3,6 @@ use crate::{
   events::*,
   math::*,
   resources::*,
-  utilities::*,
 };
 use specs::prelude::*;
 
@@ -31,9 +30,7 @@ impl<'a> System<'a> for SpatialEntityMapManager {
     Read<'a, MarkerEventChannel>,
     Read<'a, DependencyGraph>,
     Write<'a, SpatialEntityMap>,
-    ReadStorage<'a, ScreenPosition>,
-    ReadStorage<'a, ScreenLine>,
-    ReadStorage<'a, ScreenCircle>,
+    ScreenShapes<'a>,
     ReadStorage<'a, Hidden>,
   );
 
@@ -53,9 +50,7 @@ impl<'a> System<'a> for SpatialEntityMapManager {
       marker_event_channel,
       dependency_graph,
       mut spatial_entity_map,
-      screen_points,
-      screen_lines,
-      screen_circles,
+      screen_shapes,
       hiddens,
     ): Self::SystemData,
   ) {
@@ -66,20 +61,36 @@ impl<'a> System<'a> for SpatialEntityMapManager {
         match event {
           ViewportEvent::Move(_) => spatial_entity_map.clear(),
           ViewportEvent::Scale(_) => spatial_entity_map.clear(),
+          ViewportEvent::Zoom(_, _) | ViewportEvent::ZoomToFit => spatial_entity_map.clear(),
           ViewportEvent::Resize(Vector2 { x, y }) => spatial_entity_map.set_size(*x, *y),
         }
         need_add_all = true;
       }
       if need_add_all {
-        for (ent, screen_point, _) in (&entities, &screen_points, !&hiddens).join() {
+        for (ent, screen_point, _) in (&entities, &screen_shapes.points, !&hiddens).join() {
           spatial_entity_map.insert_point(ent, (*screen_point).into());
         }
-        for (ent, screen_line, _) in (&entities, &screen_lines, !&hiddens).join() {
+        for (ent, screen_line, _) in (&entities, &screen_shapes.lines, !&hiddens).join() {
           spatial_entity_map.insert_line(ent, (*screen_line).into());
         }
-        for (ent, screen_circle, _) in (&entities, &screen_circles, !&hiddens).join() {
+        for (ent, screen_circle, _) in (&entities, &screen_shapes.circles, !&hiddens).join() {
           spatial_entity_map.insert_circle(ent, (*screen_circle).into());
         }
+        for (ent, screen_polygon, _) in (&entities, &screen_shapes.polygons, !&hiddens).join() {
+          spatial_entity_map.insert_polygon(ent, screen_polygon.clone().into());
+        }
+        for (ent, screen_locus, _) in (&entities, &screen_shapes.loci, !&hiddens).join() {
+          spatial_entity_map.insert_polyline(ent, screen_locus.clone().into());
+        }
+        for (ent, screen_arc, _) in (&entities, &screen_shapes.arcs, !&hiddens).join() {
+          spatial_entity_map.insert_arc(ent, (*screen_arc).into());
+        }
+        for (ent, screen_conic, _) in (&entities, &screen_shapes.conics, !&hiddens).join() {
+          spatial_entity_map.insert_polyline(ent, screen_conic.clone().into());
+        }
+        for (ent, screen_label, _) in (&entities, &screen_shapes.labels, !&hiddens).join() {
+          spatial_entity_map.insert_aabb(ent, screen_label.rect);
+        }
       }
     }
 
@@ -88,13 +99,7 @@ impl<'a> System<'a> for SpatialEntityMapManager {
       for event in geometry_event_channel.read(reader) {
         match event {
           GeometryEvent::Inserted(ent, _, _) => {
-            insert(
-              ent,
-              &mut spatial_entity_map,
-              &screen_points,
-              &screen_lines,
-              &screen_circles,
-            );
+            insert(ent, &mut spatial_entity_map, &screen_shapes);
           }
           GeometryEvent::Removed(ent, _, _) => {
             spatial_entity_map.remove_from_all(*ent);
@@ -103,16 +108,14 @@ impl<'a> System<'a> for SpatialEntityMapManager {
             for dep in dependency_graph.get_all_dependents(ent) {
               if hiddens.get(dep).is_none() {
                 spatial_entity_map.remove_from_all(dep);
-                insert(
-                  &dep,
-                  &mut spatial_entity_map,
-                  &screen_points,
-                  &screen_lines,
-                  &screen_circles,
-                );
+                insert(&dep, &mut spatial_entity_map, &screen_shapes);
               }
             }
           }
+          GeometryEvent::LabelUpdated(ent) if hiddens.get(*ent).is_none() => {
+            spatial_entity_map.remove_from_all(*ent);
+            insert(ent, &mut spatial_entity_map, &screen_shapes);
+          }
           _ => (),
         }
       }
@@ -126,13 +129,7 @@ impl<'a> System<'a> for SpatialEntityMapManager {
             spatial_entity_map.remove_from_all(*ent);
           }
           MarkerEvent::Unhide(ent, _) => {
-            insert_without_geom(
-              ent,
-              &mut spatial_entity_map,
-              &screen_points,
-              &screen_lines,
-              &screen_circles,
-            );
+            insert_without_geom(ent, &mut spatial_entity_map, &screen_shapes);
           }
           _ => (), // Do nothing otherwise
         }
@@ -141,34 +138,44 @@ impl<'a> System<'a> for SpatialEntityMapManager {
   }
 }
 
-fn insert<'a>(
-  ent: &Entity,
-  spatial_entity_map: &mut SpatialEntityMap,
-  screen_points: &ReadStorage<'a, ScreenPoint>,
-  screen_lines: &ReadStorage<'a, ScreenLine>,
-  screen_circles: &ReadStorage<'a, ScreenCircle>,
-) {
-  if let Some(screen_point) = screen_points.get(*ent) {
+fn insert<'a>(ent: &Entity, spatial_entity_map: &mut SpatialEntityMap, screen_shapes: &ScreenShapes<'a>) {
+  if let Some(screen_point) = screen_shapes.points.get(*ent) {
     spatial_entity_map.insert_point(*ent, (*screen_point).into());
-  } else if let Some(screen_line) = screen_lines.get(*ent) {
+  } else if let Some(screen_line) = screen_shapes.lines.get(*ent) {
     spatial_entity_map.insert_line(*ent, (*screen_line).into());
-  } else if let Some(screen_circle) = screen_circles.get(*ent) {
+  } else if let Some(screen_circle) = screen_shapes.circles.get(*ent) {
     spatial_entity_map.insert_circle(*ent, (*screen_circle).into());
+  } else if let Some(screen_polygon) = screen_shapes.polygons.get(*ent) {
+    spatial_entity_map.insert_polygon(*ent, screen_polygon.clone().into());
+  } else if let Some(screen_locus) = screen_shapes.loci.get(*ent) {
+    spatial_entity_map.insert_polyline(*ent, screen_locus.clone().into());
+  } else if let Some(screen_arc) = screen_shapes.arcs.get(*ent) {
+    spatial_entity_map.insert_arc(*ent, (*screen_arc).into());
+  } else if let Some(screen_conic) = screen_shapes.conics.get(*ent) {
+    spatial_entity_map.insert_polyline(*ent, screen_conic.clone().into());
+  }
+  if let Some(screen_label) = screen_shapes.labels.get(*ent) {
+    spatial_entity_map.insert_aabb(*ent, screen_label.rect);
   }
 }
 
-fn insert_without_geom<'a>(
-  ent: &Entity,
-  spatial_entity_map: &mut SpatialEntityMap,
-  screen_points: &ReadStorage<'a, ScreenPoint>,
-  screen_lines: &ReadStorage<'a, ScreenLine>,
-  screen_circles: &ReadStorage<'a, ScreenCircle>,
-) {
-  if let Some(screen_point) = screen_points.get(*ent) {
+fn insert_without_geom<'a>(ent: &Entity, spatial_entity_map: &mut SpatialEntityMap, screen_shapes: &ScreenShapes<'a>) {
+  if let Some(screen_point) = screen_shapes.points.get(*ent) {
     spatial_entity_map.insert_point(*ent, (*screen_point).into());
-  } else if let Some(screen_line) = screen_lines.get(*ent) {
+  } else if let Some(screen_line) = screen_shapes.lines.get(*ent) {
     spatial_entity_map.insert_line(*ent, (*screen_line).into());
-  } else if let Some(screen_circle) = screen_circles.get(*ent) {
+  } else if let Some(screen_circle) = screen_shapes.circles.get(*ent) {
     spatial_entity_map.insert_circle(*ent, (*screen_circle).into());
+  } else if let Some(screen_polygon) = screen_shapes.polygons.get(*ent) {
+    spatial_entity_map.insert_polygon(*ent, screen_polygon.clone().into());
+  } else if let Some(screen_locus) = screen_shapes.loci.get(*ent) {
+    spatial_entity_map.insert_polyline(*ent, screen_locus.clone().into());
+  } else if let Some(screen_arc) = screen_shapes.arcs.get(*ent) {
+    spatial_entity_map.insert_arc(*ent, (*screen_arc).into());
+  } else if let Some(screen_conic) = screen_shapes.conics.get(*ent) {
+    spatial_entity_map.insert_polyline(*ent, screen_conic.clone().into());
+  }
+  if let Some(screen_label) = screen_shapes.labels.get(*ent) {
+    spatial_entity_map.insert_aabb(*ent, screen_label.rect);
   }
 }
@@ -0,0 +1,273 @@
+use crate::{
+  components::{labels::*, markers::*, styles::*, symbolics::*},
+  events::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct FileEventHandler {
+  file_event_reader: Option<FileEventReader>,
+}
+
+impl Default for FileEventHandler {
+  fn default() -> Self {
+    Self {
+      file_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for FileEventHandler {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, FileEventChannel>,
+    Write<'a, CommandEventChannel>,
+    Write<'a, History>,
+    Write<'a, ViewportEventChannel>,
+    Read<'a, Viewport>,
+    Write<'a, DefaultPointStyle>,
+    Write<'a, DefaultLineStyle>,
+    Write<'a, DefaultCircleStyle>,
+    Write<'a, DefaultPolygonStyle>,
+    ReadStorage<'a, Element>,
+    ReadStorage<'a, Hidden>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, SymbolicLine>,
+    ReadStorage<'a, SymbolicCircle>,
+    ReadStorage<'a, SymbolicPolygon>,
+    ReadStorage<'a, SymbolicMeasurement>,
+    ReadStorage<'a, SymbolicLocus>,
+    ReadStorage<'a, SymbolicArc>,
+    ReadStorage<'a, SymbolicConic>,
+    ReadStorage<'a, PointStyle>,
+    ReadStorage<'a, LineStyle>,
+    ReadStorage<'a, CircleStyle>,
+    ReadStorage<'a, PolygonStyle>,
+    ReadStorage<'a, Label>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.file_event_reader = Some(world.fetch_mut::<FileEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      entities,
+      file_event_channel,
+      mut command_event_channel,
+      mut history,
+      mut viewport_event_channel,
+      viewport,
+      mut default_point_style,
+      mut default_line_style,
+      mut default_circle_style,
+      mut default_polygon_style,
+      elements,
+      hiddens,
+      sym_points,
+      sym_lines,
+      sym_circles,
+      sym_polygons,
+      sym_measurements,
+      sym_loci,
+      sym_arcs,
+      sym_conics,
+      point_styles,
+      line_styles,
+      circle_styles,
+      polygon_styles,
+      labels,
+    ): Self::SystemData,
+  ) {
+    if let Some(reader) = &mut self.file_event_reader {
+      for event in file_event_channel.read(reader) {
+        match event {
+          FileEvent::Save(path) => {
+            let mut geometries = vec![];
+            for (ent, _) in (&entities, &elements).join() {
+              let geometry = if let (Some(sym), Some(style)) = (sym_points.get(ent), point_styles.get(ent)) {
+                Geometry::Point(*sym, *style)
+              } else if let (Some(sym), Some(style)) = (sym_lines.get(ent), line_styles.get(ent)) {
+                Geometry::Line(*sym, *style)
+              } else if let (Some(sym), Some(style)) = (sym_circles.get(ent), circle_styles.get(ent)) {
+                Geometry::Circle(*sym, *style)
+              } else if let (Some(sym), Some(style)) = (sym_polygons.get(ent), polygon_styles.get(ent)) {
+                Geometry::Polygon(sym.clone(), *style)
+              } else if let Some(sym) = sym_measurements.get(ent) {
+                Geometry::Measurement(*sym)
+              } else if let (Some(sym), Some(style)) = (sym_loci.get(ent), line_styles.get(ent)) {
+                Geometry::Locus(*sym, *style)
+              } else if let (Some(sym), Some(style)) = (sym_arcs.get(ent), polygon_styles.get(ent)) {
+                Geometry::Arc(*sym, *style)
+              } else if let (Some(sym), Some(style)) = (sym_conics.get(ent), line_styles.get(ent)) {
+                Geometry::Conic(*sym, *style)
+              } else {
+                continue;
+              };
+              geometries.push((ent, geometry, hiddens.get(ent).is_some()));
+            }
+            let default_styles = FileDefaultStyles {
+              point: default_point_style.get(),
+              line: default_line_style.get(),
+              circle: default_circle_style.get(),
+              polygon: default_polygon_style.get(),
+            };
+            let result = GeopadFile::new(&viewport, default_styles, &geometries).and_then(|mut file| {
+              // The elements are in the same order as the geometries
+              for (element, (ent, _, _)) in file.elements.iter_mut().zip(geometries.iter()) {
+                element.label = labels.get(*ent).cloned();
+              }
+              file.save(path)
+            });
+            if let Err(err) = result {
+              eprintln!("[file_event_handler] Cannot save {}: {}", path.display(), err);
+            }
+          }
+          FileEvent::Load(path) => {
+            let result = GeopadFile::load(path).and_then(|file| {
+              let geometries = file.geometries(|| entities.create())?;
+              Ok((file, geometries))
+            });
+            match result {
+              Ok((file, geometries)) => {
+                let current = (&entities, &elements).join().map(|(ent, _)| ent).collect::<Vec<_>>();
+                load(
+                  &file,
+                  &geometries,
+                  &current,
+                  &viewport,
+                  &mut command_event_channel,
+                  &mut viewport_event_channel,
+                );
+                history.clear();
+                history.record_base(&file, &geometries.iter().map(|(ent, _, _)| *ent).collect::<Vec<_>>());
+                default_point_style.set(file.default_styles.point);
+                default_line_style.set(file.default_styles.line);
+                default_circle_style.set(file.default_styles.circle);
+                default_polygon_style.set(file.default_styles.polygon);
+              }
+              Err(err) => eprintln!("[file_event_handler] Cannot load {}: {}", path.display(), err),
+            }
+          }
+          FileEvent::Recover(path) => match HistoryJournal::read(path) {
+            Ok(entries) => {
+              let replay = HistoryJournal::replay(&entries, || entities.create());
+              let current = (&entities, &elements).join().map(|(ent, _)| ent).collect::<Vec<_>>();
+              remove_all(&current, &mut command_event_channel);
+              insert_all(&replay.geometries, &replay.labels, &mut command_event_channel);
+
+              // The journal in use goes on with the recovered content and history
+              let default_styles = FileDefaultStyles {
+                point: default_point_style.get(),
+                line: default_line_style.get(),
+                circle: default_circle_style.get(),
+                polygon: default_polygon_style.get(),
+              };
+              if let Ok(empty) = GeopadFile::new(&viewport, default_styles, &[]) {
+                history.record_replay(&empty, &entries, &replay);
+              }
+              history.replace_entries(replay.history);
+            }
+            Err(err) => eprintln!("[file_event_handler] Cannot recover {}: {}", path.display(), err),
+          },
+        }
+      }
+    }
+  }
+}
+
+/// Replace the current content with the content of the file. Everything goes through the
+/// `ByHistory` commands so that none of it is recorded.
+fn load(
+  file: &GeopadFile,
+  geometries: &[(Entity, Geometry, bool)],
+  current: &[Entity],
+  viewport: &Viewport,
+  command_event_channel: &mut CommandEventChannel,
+  viewport_event_channel: &mut ViewportEventChannel,
+) {
+  remove_all(current, command_event_channel);
+
+  // Restore the viewport. The screen size belongs to the window so only the virtual part is restored
+  viewport_event_channel.single_write(ViewportEvent::Move(
+    file.viewport.virtual_center - viewport.virtual_center,
+  ));
+  viewport_event_channel.single_write(ViewportEvent::Scale(
+    file.viewport.virtual_size.x - viewport.virtual_size.x,
+  ));
+
+  let labels = file
+    .elements
+    .iter()
+    .map(|element| element.label.clone())
+    .collect::<Vec<_>>();
+  insert_all(geometries, &labels, command_event_channel);
+}
+
+fn remove_all(current: &[Entity], command_event_channel: &mut CommandEventChannel) {
+  for ent in current {
+    command_event_channel.single_write(CommandEvent {
+      command: Command::Remove(RemoveEvent::RemoveByHistory(*ent)),
+      event_id: None,
+    });
+  }
+}
+
+/// Insert the geometries with their labels, which are in the same order
+fn insert_all(
+  geometries: &[(Entity, Geometry, bool)],
+  labels: &[Option<Label>],
+  command_event_channel: &mut CommandEventChannel,
+) {
+  for ((ent, geometry, hidden), label) in geometries.iter().zip(labels.iter()) {
+    let command = match geometry.clone() {
+      Geometry::Point(sym_point, style) => {
+        Command::PointInsert(InsertPointEvent::InsertPointByHistory(*ent, sym_point, style))
+      }
+      Geometry::Line(sym_line, style) => {
+        Command::LineInsert(InsertLineEvent::InsertLineByHistory(*ent, sym_line, style))
+      }
+      Geometry::Circle(sym_circle, style) => {
+        Command::CircleInsert(InsertCircleEvent::InsertCircleByHistory(*ent, sym_circle, style))
+      }
+      Geometry::Polygon(sym_polygon, style) => {
+        Command::PolygonInsert(InsertPolygonEvent::InsertPolygonByHistory(*ent, sym_polygon, style))
+      }
+      Geometry::Measurement(sym_measurement) => Command::MeasurementInsert(
+        InsertMeasurementEvent::InsertMeasurementByHistory(*ent, sym_measurement),
+      ),
+      Geometry::Locus(sym_locus, style) => {
+        Command::LocusInsert(InsertLocusEvent::InsertLocusByHistory(*ent, sym_locus, style))
+      }
+      Geometry::Arc(sym_arc, style) => Command::ArcInsert(InsertArcEvent::InsertArcByHistory(*ent, sym_arc, style)),
+      Geometry::Conic(sym_conic, style) => {
+        Command::ConicInsert(InsertConicEvent::InsertConicByHistory(*ent, sym_conic, style))
+      }
+    };
+    command_event_channel.single_write(CommandEvent {
+      command,
+      event_id: None,
+    });
+    if *hidden {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::Hide(HideEvent::HideByHistory(*ent)),
+        event_id: None,
+      });
+    }
+    if let Some(label) = label {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::Label(LabelEvent::SetLabel(*ent, label.clone())),
+        event_id: None,
+      });
+    }
+  }
+
+  // Inserting by history selects the element, so deselect everything afterwards
+  command_event_channel.single_write(CommandEvent {
+    command: Command::Select(SelectEvent::DeselectAll),
+    event_id: None,
+  });
+}
@@ -35,36 +35,60 @@ impl<'a> System<'a> for HistoryEventHandler {
           }
           HistoryEvent::Undo => {
             if let Some(modification) = history.undo() {
-              match modification {
-                Modification::InsertMany(insertions) => write_remove_events(&mut command_event_channel, insertions),
-                Modification::RemoveMany(removals) => write_insert_events(&mut command_event_channel, removals),
-                Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => {
-                  write_update_event(&mut command_event_channel, ent, new_sym_point, old_sym_point)
-                }
-                Modification::HideMany(unhidden_ents) => write_unhide_events(&mut command_event_channel, unhidden_ents),
-                Modification::UnhideMany(hidden_ents) => write_hide_events(&mut command_event_channel, hidden_ents),
-              }
+              write_undo_events(&mut command_event_channel, modification);
             }
           }
           HistoryEvent::Redo => {
             if let Some(modification) = history.redo() {
-              match modification {
-                Modification::InsertMany(insertions) => write_insert_events(&mut command_event_channel, insertions),
-                Modification::RemoveMany(removals) => write_remove_events(&mut command_event_channel, removals),
-                Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => {
-                  write_update_event(&mut command_event_channel, ent, old_sym_point, new_sym_point)
-                }
-                Modification::HideMany(unhidden_ents) => write_hide_events(&mut command_event_channel, unhidden_ents),
-                Modification::UnhideMany(hidden_ents) => write_unhide_events(&mut command_event_channel, hidden_ents),
-              }
+              write_redo_events(&mut command_event_channel, modification);
             }
           }
+          // Transactions are opened and committed by the history manager, after the commands
+          // of the frame are handled
+          HistoryEvent::BeginTransaction | HistoryEvent::CommitTransaction => (),
         }
       }
     }
   }
 }
 
+fn write_undo_events(command_event_channel: &mut CommandEventChannel, modification: &Modification) {
+  match modification {
+    Modification::InsertMany(insertions) => write_remove_events(command_event_channel, insertions),
+    Modification::RemoveMany(removals) => write_insert_events(command_event_channel, removals),
+    Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => {
+      write_update_event(command_event_channel, ent, new_sym_point, old_sym_point)
+    }
+    Modification::UpdateStyles(updates) => write_style_events(command_event_channel, updates, true),
+    Modification::HideMany(unhidden_ents) => write_unhide_events(command_event_channel, unhidden_ents),
+    Modification::UnhideMany(hidden_ents) => write_hide_events(command_event_channel, hidden_ents),
+    Modification::Group(modifications) => {
+      // Undo the last modification first
+      for modification in modifications.iter().rev() {
+        write_undo_events(command_event_channel, modification);
+      }
+    }
+  }
+}
+
+fn write_redo_events(command_event_channel: &mut CommandEventChannel, modification: &Modification) {
+  match modification {
+    Modification::InsertMany(insertions) => write_insert_events(command_event_channel, insertions),
+    Modification::RemoveMany(removals) => write_remove_events(command_event_channel, removals),
+    Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => {
+      write_update_event(command_event_channel, ent, old_sym_point, new_sym_point)
+    }
+    Modification::UpdateStyles(updates) => write_style_events(command_event_channel, updates, false),
+    Modification::HideMany(unhidden_ents) => write_hide_events(command_event_channel, unhidden_ents),
+    Modification::UnhideMany(hidden_ents) => write_unhide_events(command_event_channel, hidden_ents),
+    Modification::Group(modifications) => {
+      for modification in modifications {
+        write_redo_events(command_event_channel, modification);
+      }
+    }
+  }
+}
+
 fn write_remove_events(command_event_channel: &mut CommandEventChannel, entities: &HashMap<Entity, Geometry>) {
   for (entity, _) in entities {
     command_event_channel.single_write(CommandEvent {
@@ -93,6 +117,33 @@ fn write_insert_events(command_event_channel: &mut CommandEventChannel, entities
         )),
         event_id: None,
       },
+      Geometry::Polygon(sym_polygon, polygon_style) => CommandEvent {
+        command: Command::PolygonInsert(InsertPolygonEvent::InsertPolygonByHistory(
+          *ent,
+          sym_polygon.clone(),
+          *polygon_style,
+        )),
+        event_id: None,
+      },
+      Geometry::Measurement(sym_measurement) => CommandEvent {
+        command: Command::MeasurementInsert(InsertMeasurementEvent::InsertMeasurementByHistory(
+          *ent,
+          *sym_measurement,
+        )),
+        event_id: None,
+      },
+      Geometry::Locus(sym_locus, line_style) => CommandEvent {
+        command: Command::LocusInsert(InsertLocusEvent::InsertLocusByHistory(*ent, *sym_locus, *line_style)),
+        event_id: None,
+      },
+      Geometry::Arc(sym_arc, polygon_style) => CommandEvent {
+        command: Command::ArcInsert(InsertArcEvent::InsertArcByHistory(*ent, *sym_arc, *polygon_style)),
+        event_id: None,
+      },
+      Geometry::Conic(sym_conic, line_style) => CommandEvent {
+        command: Command::ConicInsert(InsertConicEvent::InsertConicByHistory(*ent, *sym_conic, *line_style)),
+        event_id: None,
+      },
     };
     command_event_channel.single_write(command);
   }
@@ -112,6 +163,21 @@ fn write_update_event(
   });
 }
 
+/// Restore the old styles when undoing, or the new ones when redoing
+fn write_style_events(
+  command_event_channel: &mut CommandEventChannel,
+  updates: &HashMap<Entity, (Style, Style)>,
+  is_undo: bool,
+) {
+  for (ent, (old_style, new_style)) in updates {
+    let style = if is_undo { *old_style } else { *new_style };
+    command_event_channel.single_write(CommandEvent {
+      command: Command::UpdateStyle(UpdateStyleEvent::SetStyleByHistory(*ent, style)),
+      event_id: None,
+    });
+  }
+}
+
 fn write_hide_events(command_event_channel: &mut CommandEventChannel, entities: &HashSet<Entity>) {
   for entity in entities {
     command_event_channel.single_write(CommandEvent {
@@ -1,5 +1,7 @@
+mod file_event_handler;
 mod history_event_handler;
 mod viewport_event_handler;
 
+pub use file_event_handler::*;
 pub use history_event_handler::*;
 pub use viewport_event_handler::*;
@@ -1,4 +1,9 @@
-use crate::{events::*, resources::*};
+use crate::{
+  components::{markers::*, virtual_shapes::*},
+  events::*,
+  math::*,
+  resources::*,
+};
 use specs::prelude::*;
 
 pub struct ViewportEventHandler {
@@ -14,14 +19,24 @@ impl Default for ViewportEventHandler {
 }
 
 impl<'a> System<'a> for ViewportEventHandler {
-  type SystemData = (Read<'a, ViewportEventChannel>, Write<'a, Viewport>);
+  type SystemData = (
+    Read<'a, ViewportEventChannel>,
+    Write<'a, Viewport>,
+    ReadStorage<'a, VirtualPoint>,
+    ReadStorage<'a, VirtualLine>,
+    ReadStorage<'a, VirtualCircle>,
+    ReadStorage<'a, Hidden>,
+  );
 
   fn setup(&mut self, world: &mut World) {
     Self::SystemData::setup(world);
     self.viewport_event_reader = Some(world.fetch_mut::<ViewportEventChannel>().register_reader());
   }
 
-  fn run(&mut self, (viewport_event_channel, mut viewport): Self::SystemData) {
+  fn run(
+    &mut self,
+    (viewport_event_channel, mut viewport, virt_points, virt_lines, virt_circles, hiddens): Self::SystemData,
+  ) {
     if let Some(reader) = &mut self.viewport_event_reader {
       for event in viewport_event_channel.read(reader) {
         match event {
@@ -35,8 +50,41 @@ impl<'a> System<'a> for ViewportEventHandler {
           ViewportEvent::Resize(scrn_size) => {
             viewport.set_screen_size(*scrn_size);
           }
+          ViewportEvent::Zoom(factor, anchor) => {
+            viewport.zoom(*factor, *anchor);
+          }
+          ViewportEvent::ZoomToFit => {
+            if let Some(aabb) = elements_aabb(&virt_points, &virt_lines, &virt_circles, &hiddens) {
+              viewport.zoom_to_fit(aabb);
+            }
+          }
         }
       }
     }
   }
 }
+
+/// The bounding box of all the points, lines and circles that are not hidden. Lines are
+/// infinite, so only the two points defining them are taken
+fn elements_aabb(
+  virt_points: &ReadStorage<VirtualPoint>,
+  virt_lines: &ReadStorage<VirtualLine>,
+  virt_circles: &ReadStorage<VirtualCircle>,
+  hiddens: &ReadStorage<Hidden>,
+) -> Option<AABB> {
+  let points = (virt_points, !hiddens)
+    .join()
+    .map(|(point, _)| AABB::two_points(point.0, point.0));
+  let lines = (virt_lines, !hiddens)
+    .join()
+    .map(|(line, _)| AABB::two_points(line.from.0, line.to.0));
+  let circles = (virt_circles, !hiddens).join().map(|(circle, _)| {
+    let Vector2 { x, y } = circle.center.0;
+    let r = circle.radius.0;
+    AABB::new(x - r, y - r, 2.0 * r, 2.0 * r)
+  });
+  points.chain(lines).chain(circles).fold(None, |aabb, other| match aabb {
+    Some(aabb) => Some(aabb.union(other)),
+    None => Some(other),
+  })
+}
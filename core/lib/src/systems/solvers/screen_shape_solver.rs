@@ -1,10 +1,26 @@
 use crate::{
-  components::{screen_shapes::*, virtual_shapes::*},
+  components::{labels::*, measurements::*, screen_shapes::*, symbolics::SymbolicMeasurement, virtual_shapes::*},
   events::*,
+  math::*,
   resources::*,
+  system_data,
 };
 use specs::prelude::*;
 
+system_data! {
+  /// The shapes of every kind on screen, along with the labels, as the solver updates them
+  pub struct ScreenShapesMut<'a> {
+    points: WriteStorage<'a, ScreenPoint>,
+    lines: WriteStorage<'a, ScreenLine>,
+    circles: WriteStorage<'a, ScreenCircle>,
+    polygons: WriteStorage<'a, ScreenPolygon>,
+    loci: WriteStorage<'a, ScreenLocus>,
+    arcs: WriteStorage<'a, ScreenArc>,
+    conics: WriteStorage<'a, ScreenConic>,
+    labels: WriteStorage<'a, ScreenLabel>,
+  }
+}
+
 pub struct ScreenShapeSolver {
   viewport_event_reader: Option<ViewportEventReader>,
   geometry_event_reader: Option<GeometryEventReader>,
@@ -26,12 +42,11 @@ impl<'a> System<'a> for ScreenShapeSolver {
     Read<'a, DependencyGraph>,
     Read<'a, GeometryEventChannel>,
     Read<'a, ViewportEventChannel>,
-    ReadStorage<'a, VirtualPoint>,
-    ReadStorage<'a, VirtualLine>,
-    ReadStorage<'a, VirtualCircle>,
-    WriteStorage<'a, ScreenPoint>,
-    WriteStorage<'a, ScreenLine>,
-    WriteStorage<'a, ScreenCircle>,
+    VirtualShapes<'a>,
+    ReadStorage<'a, Label>,
+    ReadStorage<'a, SymbolicMeasurement>,
+    ReadStorage<'a, Measurement>,
+    ScreenShapesMut<'a>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -48,12 +63,11 @@ impl<'a> System<'a> for ScreenShapeSolver {
       dependency_graph,
       geometry_event_channel,
       viewport_event_channel,
-      virt_points,
-      virt_lines,
-      virt_circles,
-      mut scrn_points,
-      mut scrn_lines,
-      mut scrn_circles,
+      virt_shapes,
+      labels,
+      sym_measurements,
+      measurements,
+      mut scrn_shapes,
     ): Self::SystemData,
   ) {
     // Check if there's viewport event
@@ -68,20 +82,38 @@ impl<'a> System<'a> for ScreenShapeSolver {
     // Check if need update all
     if need_update_all {
       // Update all
-      for (ent, virt_point) in (&entities, &virt_points).join() {
-        if let Err(err) = scrn_points.insert(ent, virt_point.to_screen(&*viewport)) {
-          panic!(err)
-        }
+      for (ent, virt_point) in (&entities, &virt_shapes.points).join() {
+        scrn_shapes.points.insert(ent, virt_point.to_screen(&*viewport)).ok();
       }
-      for (ent, virt_line) in (&entities, &virt_lines).join() {
-        if let Err(err) = scrn_lines.insert(ent, virt_line.to_screen(&*viewport)) {
-          panic!(err)
-        }
+      for (ent, virt_line) in (&entities, &virt_shapes.lines).join() {
+        scrn_shapes.lines.insert(ent, virt_line.to_screen(&*viewport)).ok();
       }
-      for (ent, virt_circle) in (&entities, &virt_circles).join() {
-        if let Err(err) = scrn_circles.insert(ent, virt_circle.to_screen(&*viewport)) {
-          panic!(err)
-        }
+      for (ent, virt_circle) in (&entities, &virt_shapes.circles).join() {
+        scrn_shapes.circles.insert(ent, virt_circle.to_screen(&*viewport)).ok();
+      }
+      for (ent, virt_polygon) in (&entities, &virt_shapes.polygons).join() {
+        scrn_shapes
+          .polygons
+          .insert(ent, virt_polygon.clone().to_screen(&*viewport))
+          .ok();
+      }
+      for (ent, virt_locus) in (&entities, &virt_shapes.loci).join() {
+        scrn_shapes
+          .loci
+          .insert(ent, virt_locus.clone().to_screen(&*viewport))
+          .ok();
+      }
+      for (ent, virt_arc) in (&entities, &virt_shapes.arcs).join() {
+        scrn_shapes.arcs.insert(ent, virt_arc.to_screen(&*viewport)).ok();
+      }
+      for (ent, virt_conic) in (&entities, &virt_shapes.conics).join() {
+        scrn_shapes.conics.insert(ent, virt_conic.to_screen(&*viewport)).ok();
+      }
+      for (ent, _) in (&entities, &labels).join() {
+        calc_scrn_label(ent, &labels, &sym_measurements, &measurements, &mut scrn_shapes);
+      }
+      for (ent, _) in (&entities, &sym_measurements).join() {
+        calc_scrn_label(ent, &labels, &sym_measurements, &measurements, &mut scrn_shapes);
       }
     } else {
       // Only update what's needed
@@ -89,33 +121,28 @@ impl<'a> System<'a> for ScreenShapeSolver {
         for event in geometry_event_channel.read(reader) {
           match event {
             GeometryEvent::Inserted(ent, _, _) => {
-              calc_scrn_shape(
-                *ent,
-                &viewport,
-                &virt_points,
-                &virt_lines,
-                &virt_circles,
-                &mut scrn_points,
-                &mut scrn_lines,
-                &mut scrn_circles,
-              );
+              calc_scrn_shape(*ent, &viewport, &virt_shapes, &mut scrn_shapes);
+              calc_scrn_label(*ent, &labels, &sym_measurements, &measurements, &mut scrn_shapes);
+            }
+            GeometryEvent::Removed(ent, _, _) => {
+              scrn_shapes.labels.remove(*ent);
             }
-            GeometryEvent::Removed(_, _, _) => (),
             GeometryEvent::PointUpdated(ent, _, _, _) => {
-              for dep in dependency_graph.get_all_dependents(ent) {
-                calc_scrn_shape(
-                  dep,
-                  &viewport,
-                  &virt_points,
-                  &virt_lines,
-                  &virt_circles,
-                  &mut scrn_points,
-                  &mut scrn_lines,
-                  &mut scrn_circles,
-                );
+              // The labels of the measurements go by the shapes they measure, so all the shapes
+              // are placed first
+              let dependents = dependency_graph.get_all_dependents(ent);
+              for dep in &dependents {
+                calc_scrn_shape(*dep, &viewport, &virt_shapes, &mut scrn_shapes);
+              }
+              for dep in dependents {
+                calc_scrn_label(dep, &labels, &sym_measurements, &measurements, &mut scrn_shapes);
               }
             }
             GeometryEvent::PointUpdateFinished(_, _, _, _) => (),
+            GeometryEvent::StyleUpdated(_, _, _, _) => (),
+            GeometryEvent::LabelUpdated(ent) => {
+              calc_scrn_label(*ent, &labels, &sym_measurements, &measurements, &mut scrn_shapes);
+            }
           }
         }
       }
@@ -123,27 +150,96 @@ impl<'a> System<'a> for ScreenShapeSolver {
   }
 }
 
+/// Project the virtual shape of the element on screen. Storing only fails when the entity is
+/// already deleted, and then there's nothing left to draw
 fn calc_scrn_shape<'a>(
   ent: Entity,
   viewport: &Read<'a, Viewport>,
-  virt_points: &ReadStorage<'a, VirtualPoint>,
-  virt_lines: &ReadStorage<'a, VirtualLine>,
-  virt_circles: &ReadStorage<'a, VirtualCircle>,
-  scrn_points: &mut WriteStorage<'a, ScreenPoint>,
-  scrn_lines: &mut WriteStorage<'a, ScreenLine>,
-  scrn_circles: &mut WriteStorage<'a, ScreenCircle>,
+  virt: &VirtualShapes<'a>,
+  scrn: &mut ScreenShapesMut<'a>,
+) {
+  if let Some(virt_point) = virt.points.get(ent) {
+    scrn.points.insert(ent, virt_point.to_screen(&*viewport)).ok();
+  } else if let Some(virt_line) = virt.lines.get(ent) {
+    scrn.lines.insert(ent, virt_line.to_screen(&*viewport)).ok();
+  } else if let Some(virt_circle) = virt.circles.get(ent) {
+    scrn.circles.insert(ent, virt_circle.to_screen(&*viewport)).ok();
+  } else if let Some(virt_polygon) = virt.polygons.get(ent) {
+    scrn
+      .polygons
+      .insert(ent, virt_polygon.clone().to_screen(&*viewport))
+      .ok();
+  } else if let Some(virt_locus) = virt.loci.get(ent) {
+    scrn.loci.insert(ent, virt_locus.clone().to_screen(&*viewport)).ok();
+  } else if let Some(virt_arc) = virt.arcs.get(ent) {
+    scrn.arcs.insert(ent, virt_arc.to_screen(&*viewport)).ok();
+  } else if let Some(virt_conic) = virt.conics.get(ent) {
+    scrn.conics.insert(ent, virt_conic.to_screen(&*viewport)).ok();
+  } else {
+    // The virtual shape is undefined, so it should not be shown at all
+    scrn.points.remove(ent);
+    scrn.lines.remove(ent);
+    scrn.circles.remove(ent);
+    scrn.polygons.remove(ent);
+    scrn.loci.remove(ent);
+    scrn.arcs.remove(ent);
+    scrn.conics.remove(ent);
+  }
+}
+
+/// Place the label of the element next to its anchor, or remove it if the element has no label
+/// or is not on screen. Measurements are labelled with their value
+fn calc_scrn_label<'a>(
+  ent: Entity,
+  labels: &ReadStorage<'a, Label>,
+  sym_measurements: &ReadStorage<'a, SymbolicMeasurement>,
+  measurements: &ReadStorage<'a, Measurement>,
+  scrn: &mut ScreenShapesMut<'a>,
 ) {
-  if let Some(virt_point) = virt_points.get(ent) {
-    if let Err(err) = scrn_points.insert(ent, virt_point.to_screen(&*viewport)) {
-      panic!(err)
+  let label = match (labels.get(ent), sym_measurements.get(ent), measurements.get(ent)) {
+    (Some(label), _, _) => Some(label.clone()),
+    (None, Some(sym_measurement), Some(measurement)) => Some(Label {
+      text: measurement.text(sym_measurement),
+      offset: MEASUREMENT_LABEL_OFFSET,
+    }),
+    _ => None,
+  };
+  match (label, anchor_of(ent, sym_measurements, scrn)) {
+    (Some(label), Some(anchor)) => {
+      scrn.labels.insert(ent, ScreenLabel::new(&label, anchor)).ok();
     }
-  } else if let Some(virt_line) = virt_lines.get(ent) {
-    if let Err(err) = scrn_lines.insert(ent, virt_line.to_screen(&*viewport)) {
-      panic!(err)
+    _ => {
+      scrn.labels.remove(ent);
     }
-  } else if let Some(virt_circle) = virt_circles.get(ent) {
-    if let Err(err) = scrn_circles.insert(ent, virt_circle.to_screen(&*viewport)) {
-      panic!(err)
+  }
+}
+
+/// Where the label of the element is attached. The value of a measurement goes between the two
+/// points of a distance, at the vertex of an angle, between the two values of a ratio, and next
+/// to the measured shape otherwise
+fn anchor_of<'a>(
+  ent: Entity,
+  sym_measurements: &ReadStorage<'a, SymbolicMeasurement>,
+  scrn: &ScreenShapesMut<'a>,
+) -> Option<Vector2> {
+  let anchor = |ent| anchor_of(ent, sym_measurements, scrn);
+  if let Some(scrn_point) = scrn.points.get(ent) {
+    Some(scrn_point.label_anchor())
+  } else if let Some(scrn_line) = scrn.lines.get(ent) {
+    Some(scrn_line.label_anchor())
+  } else if let Some(scrn_circle) = scrn.circles.get(ent) {
+    Some(scrn_circle.label_anchor())
+  } else if let Some(scrn_polygon) = scrn.polygons.get(ent) {
+    Some(scrn_polygon.label_anchor())
+  } else {
+    match *sym_measurements.get(ent)? {
+      SymbolicMeasurement::Distance(ent1, ent2) | SymbolicMeasurement::Ratio(ent1, ent2) => {
+        Some((anchor(ent1)? + anchor(ent2)?) / 2.0)
+      }
+      SymbolicMeasurement::Angle(_, v_ent, _) => anchor(v_ent),
+      SymbolicMeasurement::Length(ent) | SymbolicMeasurement::Area(ent) | SymbolicMeasurement::Perimeter(ent) => {
+        anchor(ent)
+      }
     }
   }
 }
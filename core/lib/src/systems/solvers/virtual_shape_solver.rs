@@ -1,13 +1,27 @@
 use crate::{
-  components::{symbolics::*, virtual_shapes::*},
+  components::{markers::*, measurements::*, symbolics::*, virtual_shapes::*},
   events::*,
   math::*,
   resources::*,
+  system_data,
   utilities::*,
 };
 use specs::prelude::*;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+static LOCUS_SAMPLES: usize = 200;
+static LOCUS_LINE_EXTENT: f64 = 20.0;
+
+/// Computes the virtual shapes from the symbolic ones. An element that can't be computed, because
+/// its inputs are degenerate or because it depends on such an element, gets the `Undefined`
+/// marker instead of a shape. A line needs two distinct points, a circle needs a positive radius,
+/// and an intersection needs inputs meeting at isolated points, so parallel lines and concentric
+/// circles have none. Undefined elements are solved again with their dependencies, so they come
+/// back as soon as their inputs allow it.
+///
+/// A locus is solved by moving its driver along its line or circle, solving again everything
+/// depending on the driver for each sample. The samples are solved aside, the live shapes of the
+/// construction are only read
 pub struct VirtualShapeSolver {
   geometry_event_reader: Option<GeometryEventReader>,
 }
@@ -23,24 +37,180 @@ impl Default for VirtualShapeSolver {
 struct ToCompute(Entity, GeometrySymbol);
 
 enum SolveResult {
-  AlreadyComputed,             // Already Computed
-  SolvedPoint(VirtualPoint),   // The result of point
-  SolvedLine(VirtualLine),     // The result of line
-  SolvedCircle(VirtualCircle), // The result of circle
-  Request(Entity),             // Need other dependency
-  Undefined,                   // The result does not exist
+  AlreadyComputed,                // Already Computed
+  SolvedPoint(VirtualPoint),      // The result of point
+  SolvedLine(VirtualLine),        // The result of line
+  SolvedCircle(VirtualCircle),    // The result of circle
+  SolvedPolygon(VirtualPolygon),  // The result of polygon
+  SolvedMeasurement(Measurement), // The result of measurement
+  SolvedLocus(VirtualLocus),      // The result of locus
+  SolvedArc(VirtualArc),          // The result of arc
+  SolvedConic(VirtualConic),      // The result of conic
+  Request(Entity),                // Need other dependency
+  Undefined,                      // The result does not exist
+}
+
+/// The shapes the solver reads the inputs of an element from
+trait Shapes<T> {
+  fn get(&self, ent: Entity) -> Option<&T>;
+
+  fn contains(&self, ent: Entity) -> bool {
+    self.get(ent).is_some()
+  }
+}
+
+impl<'a, T: Component> Shapes<T> for WriteStorage<'a, T> {
+  fn get(&self, ent: Entity) -> Option<&T> {
+    Storage::get(self, ent)
+  }
+}
+
+/// The shapes of one sample of a locus. The elements depending on the driver are read from what
+/// was solved for this sample, and the other ones from the live storage
+struct SampleShapes<'s, 'a, T: Component> {
+  live: &'s WriteStorage<'a, T>,
+  stale: &'s HashSet<Entity>,
+  solved: HashMap<Entity, T>,
+}
+
+impl<'s, 'a, T: Component> SampleShapes<'s, 'a, T> {
+  fn new(live: &'s WriteStorage<'a, T>, stale: &'s HashSet<Entity>) -> Self {
+    Self {
+      live,
+      stale,
+      solved: HashMap::new(),
+    }
+  }
+}
+
+impl<'s, 'a, T: Component> Shapes<T> for SampleShapes<'s, 'a, T> {
+  fn get(&self, ent: Entity) -> Option<&T> {
+    if self.stale.contains(&ent) {
+      self.solved.get(&ent)
+    } else {
+      self.live.get(ent)
+    }
+  }
+}
+
+system_data! {
+  /// The shapes the solver computes, one storage for every kind of element
+  pub struct SolvedShapes<'a> {
+    points: WriteStorage<'a, VirtualPoint>,
+    lines: WriteStorage<'a, VirtualLine>,
+    circles: WriteStorage<'a, VirtualCircle>,
+    polygons: WriteStorage<'a, VirtualPolygon>,
+    measurements: WriteStorage<'a, Measurement>,
+    loci: WriteStorage<'a, VirtualLocus>,
+    arcs: WriteStorage<'a, VirtualArc>,
+    conics: WriteStorage<'a, VirtualConic>,
+  }
+}
+
+/// The shapes of every kind the inputs of an element are read from. Nothing is built on loci, so
+/// they are never an input
+trait Solved {
+  fn points(&self) -> &dyn Shapes<VirtualPoint>;
+  fn lines(&self) -> &dyn Shapes<VirtualLine>;
+  fn circles(&self) -> &dyn Shapes<VirtualCircle>;
+  fn polygons(&self) -> &dyn Shapes<VirtualPolygon>;
+  fn measurements(&self) -> &dyn Shapes<Measurement>;
+  fn arcs(&self) -> &dyn Shapes<VirtualArc>;
+  fn conics(&self) -> &dyn Shapes<VirtualConic>;
+}
+
+impl<'a> Solved for SolvedShapes<'a> {
+  fn points(&self) -> &dyn Shapes<VirtualPoint> {
+    &self.points
+  }
+
+  fn lines(&self) -> &dyn Shapes<VirtualLine> {
+    &self.lines
+  }
+
+  fn circles(&self) -> &dyn Shapes<VirtualCircle> {
+    &self.circles
+  }
+
+  fn polygons(&self) -> &dyn Shapes<VirtualPolygon> {
+    &self.polygons
+  }
+
+  fn measurements(&self) -> &dyn Shapes<Measurement> {
+    &self.measurements
+  }
+
+  fn arcs(&self) -> &dyn Shapes<VirtualArc> {
+    &self.arcs
+  }
+
+  fn conics(&self) -> &dyn Shapes<VirtualConic> {
+    &self.conics
+  }
+}
+
+/// All the shapes of one sample of a locus
+struct LocusSample<'s, 'a> {
+  points: SampleShapes<'s, 'a, VirtualPoint>,
+  lines: SampleShapes<'s, 'a, VirtualLine>,
+  circles: SampleShapes<'s, 'a, VirtualCircle>,
+  polygons: SampleShapes<'s, 'a, VirtualPolygon>,
+  measurements: SampleShapes<'s, 'a, Measurement>,
+  arcs: SampleShapes<'s, 'a, VirtualArc>,
+  conics: SampleShapes<'s, 'a, VirtualConic>,
+}
+
+impl<'s, 'a> LocusSample<'s, 'a> {
+  fn new(live: &'s SolvedShapes<'a>, stale: &'s HashSet<Entity>) -> Self {
+    Self {
+      points: SampleShapes::new(&live.points, stale),
+      lines: SampleShapes::new(&live.lines, stale),
+      circles: SampleShapes::new(&live.circles, stale),
+      polygons: SampleShapes::new(&live.polygons, stale),
+      measurements: SampleShapes::new(&live.measurements, stale),
+      arcs: SampleShapes::new(&live.arcs, stale),
+      conics: SampleShapes::new(&live.conics, stale),
+    }
+  }
+}
+
+impl<'s, 'a> Solved for LocusSample<'s, 'a> {
+  fn points(&self) -> &dyn Shapes<VirtualPoint> {
+    &self.points
+  }
+
+  fn lines(&self) -> &dyn Shapes<VirtualLine> {
+    &self.lines
+  }
+
+  fn circles(&self) -> &dyn Shapes<VirtualCircle> {
+    &self.circles
+  }
+
+  fn polygons(&self) -> &dyn Shapes<VirtualPolygon> {
+    &self.polygons
+  }
+
+  fn measurements(&self) -> &dyn Shapes<Measurement> {
+    &self.measurements
+  }
+
+  fn arcs(&self) -> &dyn Shapes<VirtualArc> {
+    &self.arcs
+  }
+
+  fn conics(&self) -> &dyn Shapes<VirtualConic> {
+    &self.conics
+  }
 }
 
 impl<'a> System<'a> for VirtualShapeSolver {
   type SystemData = (
     Read<'a, GeometryEventChannel>,
     Read<'a, DependencyGraph>,
-    ReadStorage<'a, SymbolicPoint>,
-    ReadStorage<'a, SymbolicLine>,
-    ReadStorage<'a, SymbolicCircle>,
-    WriteStorage<'a, VirtualPoint>,
-    WriteStorage<'a, VirtualLine>,
-    WriteStorage<'a, VirtualCircle>,
+    SymbolicShapes<'a>,
+    SolvedShapes<'a>,
+    WriteStorage<'a, Undefined>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -48,21 +218,10 @@ impl<'a> System<'a> for VirtualShapeSolver {
     self.geometry_event_reader = Some(world.fetch_mut::<GeometryEventChannel>().register_reader());
   }
 
-  fn run(
-    &mut self,
-    (
-      geometry_event_channel,
-      dependency_graph,
-      sym_points,
-      sym_lines,
-      sym_circles,
-      mut virt_points,
-      mut virt_lines,
-      mut virt_circles,
-    ): Self::SystemData,
-  ) {
+  fn run(&mut self, (geometry_event_channel, dependency_graph, symbols, mut solved, mut undefineds): Self::SystemData) {
     let mut to_process = Vec::new();
     let mut cannot_compute = HashSet::new();
+    let symbol_of = |ent| symbols.get(ent);
 
     // First get all the things to process
     if let Some(reader) = &mut self.geometry_event_reader {
@@ -71,13 +230,19 @@ impl<'a> System<'a> for VirtualShapeSolver {
           GeometryEvent::Inserted(ent, geom, _) => {
             to_process.push(ToCompute(*ent, geom.clone().into()));
           }
-          GeometryEvent::Removed(_, _, _) => (),
+          GeometryEvent::Removed(ent, _, _) => {
+            undefineds.remove(*ent);
+          }
           GeometryEvent::PointUpdated(ent, _, _, _) => {
             for dep in dependency_graph.get_all_dependents(ent) {
-              to_process.push(ToCompute(dep, get_symbol(dep, &sym_points, &sym_lines, &sym_circles)));
+              if let Some(sym) = symbol_of(dep) {
+                to_process.push(ToCompute(dep, sym));
+              }
             }
           }
           GeometryEvent::PointUpdateFinished(_, _, _, _) => (),
+          GeometryEvent::StyleUpdated(_, _, _, _) => (),
+          GeometryEvent::LabelUpdated(_) => (),
         }
       }
     }
@@ -86,13 +251,28 @@ impl<'a> System<'a> for VirtualShapeSolver {
     for elem in &to_process {
       match elem {
         ToCompute(ent, GeometrySymbol::Point(_)) => {
-          virt_points.remove(*ent);
+          solved.points.remove(*ent);
         }
         ToCompute(ent, GeometrySymbol::Line(_)) => {
-          virt_lines.remove(*ent);
+          solved.lines.remove(*ent);
         }
         ToCompute(ent, GeometrySymbol::Circle(_)) => {
-          virt_circles.remove(*ent);
+          solved.circles.remove(*ent);
+        }
+        ToCompute(ent, GeometrySymbol::Polygon(_)) => {
+          solved.polygons.remove(*ent);
+        }
+        ToCompute(ent, GeometrySymbol::Measurement(_)) => {
+          solved.measurements.remove(*ent);
+        }
+        ToCompute(ent, GeometrySymbol::Locus(_)) => {
+          solved.loci.remove(*ent);
+        }
+        ToCompute(ent, GeometrySymbol::Arc(_)) => {
+          solved.arcs.remove(*ent);
+        }
+        ToCompute(ent, GeometrySymbol::Conic(_)) => {
+          solved.conics.remove(*ent);
         }
       }
     }
@@ -102,33 +282,31 @@ impl<'a> System<'a> for VirtualShapeSolver {
       let to_comp = to_process.pop().unwrap(); // We can do this because we have checked it is not empty
       let ent = to_comp.0.clone();
       let sym = to_comp.1.clone();
-      match solve(ent, sym, &virt_points, &virt_lines, &virt_circles) {
+      let result = match sym {
+        GeometrySymbol::Locus(sym_locus) => solve_locus(ent, sym_locus, &dependency_graph, &symbol_of, &solved),
+        sym => solve(ent, sym, &solved),
+      };
+      match check_defined(result) {
         SolveResult::AlreadyComputed => (),
-        SolveResult::Undefined => {
-          cannot_compute.insert(ent);
-        }
-        SolveResult::SolvedPoint(vp) => {
-          if let Err(err) = virt_points.insert(ent, vp) {
-            panic!(err)
-          }
-        }
-        SolveResult::SolvedLine(vl) => {
-          if let Err(err) = virt_lines.insert(ent, vl) {
-            panic!(err)
-          }
-        }
-        SolveResult::SolvedCircle(vc) => {
-          if let Err(err) = virt_circles.insert(ent, vc) {
-            panic!(err)
-          }
-        }
+        SolveResult::Undefined => set_undefined(ent, &mut undefineds, &mut cannot_compute),
+        SolveResult::SolvedPoint(vp) => set_defined(ent, vp, &mut solved.points, &mut undefineds),
+        SolveResult::SolvedLine(vl) => set_defined(ent, vl, &mut solved.lines, &mut undefineds),
+        SolveResult::SolvedCircle(vc) => set_defined(ent, vc, &mut solved.circles, &mut undefineds),
+        SolveResult::SolvedPolygon(vp) => set_defined(ent, vp, &mut solved.polygons, &mut undefineds),
+        SolveResult::SolvedMeasurement(m) => set_defined(ent, m, &mut solved.measurements, &mut undefineds),
+        SolveResult::SolvedLocus(l) => set_defined(ent, l, &mut solved.loci, &mut undefineds),
+        SolveResult::SolvedArc(a) => set_defined(ent, a, &mut solved.arcs, &mut undefineds),
+        SolveResult::SolvedConic(c) => set_defined(ent, c, &mut solved.conics, &mut undefineds),
         SolveResult::Request(req_ent) => {
-          if !cannot_compute.contains(&req_ent) {
-            to_process.push(to_comp);
-            to_process.push(ToCompute(
-              req_ent,
-              get_symbol(req_ent, &sym_points, &sym_lines, &sym_circles),
-            ));
+          let req_sym = symbol_of(req_ent);
+          match req_sym {
+            Some(req_sym) if !cannot_compute.contains(&req_ent) => {
+              to_process.push(to_comp);
+              to_process.push(ToCompute(req_ent, req_sym));
+            }
+
+            // Depending on an undefined or a removed element makes this one undefined as well
+            _ => set_undefined(ent, &mut undefineds, &mut cannot_compute),
           }
         }
       }
@@ -136,43 +314,189 @@ impl<'a> System<'a> for VirtualShapeSolver {
   }
 }
 
-fn get_symbol<'a>(
+/// Store the solved shape of the element, which is defined from now on. Storing only fails when
+/// the entity is already deleted, and then there's nothing left to update
+fn set_defined<'a, T: Component>(
   ent: Entity,
-  sym_points: &ReadStorage<'a, SymbolicPoint>,
-  sym_lines: &ReadStorage<'a, SymbolicLine>,
-  sym_circles: &ReadStorage<'a, SymbolicCircle>,
-) -> GeometrySymbol {
-  if let Some(sym_point) = sym_points.get(ent) {
-    GeometrySymbol::Point(*sym_point)
-  } else if let Some(sym_line) = sym_lines.get(ent) {
-    GeometrySymbol::Line(*sym_line)
-  } else if let Some(sym_circle) = sym_circles.get(ent) {
-    GeometrySymbol::Circle(*sym_circle)
-  } else {
-    panic!("Cannot find symbol");
+  shape: T,
+  storage: &mut WriteStorage<'a, T>,
+  undefineds: &mut WriteStorage<'a, Undefined>,
+) {
+  if storage.insert(ent, shape).is_ok() {
+    undefineds.remove(ent);
+  }
+}
+
+fn set_undefined<'a>(ent: Entity, undefineds: &mut WriteStorage<'a, Undefined>, cannot_compute: &mut HashSet<Entity>) {
+  undefineds.insert(ent, Undefined).ok();
+  cannot_compute.insert(ent);
+}
+
+/// Turn the degenerate results into undefined ones, so that no shape with a zero length line, a
+/// zero radius circle or a non finite value is ever stored
+fn check_defined(result: SolveResult) -> SolveResult {
+  match result {
+    SolveResult::SolvedPoint(VirtualPosition(p)) if !p.is_finite() => SolveResult::Undefined,
+    SolveResult::SolvedLine(l) if l.is_degenerate() => SolveResult::Undefined,
+    SolveResult::SolvedCircle(c) if c.is_degenerate() => SolveResult::Undefined,
+    SolveResult::SolvedPolygon(p) if p.points.iter().any(|point| !point.0.is_finite()) => SolveResult::Undefined,
+    SolveResult::SolvedMeasurement(Measurement(m)) if !m.is_finite() => SolveResult::Undefined,
+    SolveResult::SolvedLocus(l) if l.paths.is_empty() => SolveResult::Undefined,
+    SolveResult::SolvedArc(a) if a.is_degenerate() => SolveResult::Undefined,
+    SolveResult::SolvedConic(c) if c.is_degenerate() => SolveResult::Undefined,
+    result => result,
+  }
+}
+
+fn solve(ent: Entity, sym: GeometrySymbol, shapes: &dyn Solved) -> SolveResult {
+  let (virt_points, virt_lines, virt_circles) = (shapes.points(), shapes.lines(), shapes.circles());
+  let (virt_polygons, measurements) = (shapes.polygons(), shapes.measurements());
+  let (virt_arcs, virt_conics) = (shapes.arcs(), shapes.conics());
+  match sym {
+    GeometrySymbol::Point(sym_point) => solve_point(
+      ent,
+      sym_point,
+      virt_points,
+      virt_lines,
+      virt_circles,
+      virt_arcs,
+      virt_conics,
+    ),
+    GeometrySymbol::Line(sym_line) => solve_line(ent, sym_line, virt_points, virt_lines, virt_circles),
+    GeometrySymbol::Circle(sym_circle) => solve_circle(ent, sym_circle, virt_points, virt_lines, virt_circles),
+    GeometrySymbol::Polygon(sym_polygon) => solve_polygon(ent, sym_polygon, virt_points, virt_polygons),
+    GeometrySymbol::Measurement(sym_measurement) => solve_measurement(
+      ent,
+      sym_measurement,
+      virt_points,
+      virt_lines,
+      virt_circles,
+      virt_polygons,
+      measurements,
+    ),
+    GeometrySymbol::Locus(_) => SolveResult::Undefined, // Loci are sampled by solve_locus, nothing is built on them
+    GeometrySymbol::Arc(sym_arc) => solve_arc(ent, sym_arc, virt_points, virt_arcs),
+    GeometrySymbol::Conic(sym_conic) => solve_conic(ent, sym_conic, virt_points, virt_lines, virt_conics),
   }
 }
 
-fn solve<'a>(
+fn solve_locus(
   ent: Entity,
-  sym: GeometrySymbol,
-  virt_points: &WriteStorage<'a, VirtualPoint>,
-  virt_lines: &WriteStorage<'a, VirtualLine>,
-  virt_circles: &WriteStorage<'a, VirtualCircle>,
+  sym_locus: SymbolicLocus,
+  dependency_graph: &DependencyGraph,
+  symbol_of: &dyn Fn(Entity) -> Option<GeometrySymbol>,
+  solved: &SolvedShapes,
 ) -> SolveResult {
-  match sym {
-    GeometrySymbol::Point(sym_point) => solve_point(ent, sym_point, &virt_points, &virt_lines, &virt_circles),
-    GeometrySymbol::Line(sym_line) => solve_line(ent, sym_line, &virt_points, &virt_lines, &virt_circles),
-    GeometrySymbol::Circle(sym_circle) => solve_circle(ent, sym_circle, &virt_points, &virt_lines, &virt_circles),
+  if solved.loci.contains(ent) {
+    return SolveResult::AlreadyComputed;
+  }
+  let (driver, traced) = (sym_locus.driver(), sym_locus.traced());
+  let stale = dependency_graph.get_all_dependents(&driver);
+  if driver == traced || !stale.contains(&traced) {
+    return SolveResult::Undefined; // A point not moving with the driver traces nothing
+  }
+
+  // Where the driver goes, from one end of its line to the other or once around its circle
+  let positions: Vec<VirtualPosition> = match symbol_of(driver) {
+    Some(GeometrySymbol::Point(SymbolicPoint::OnLine(l_ent, _))) => match solved.lines.get(l_ent) {
+      Some(&VirtualLine { from, to, line_type }) => {
+        let (start, end) = match line_type {
+          LineType::Segment => (0.0, 1.0),
+          LineType::Ray => (0.0, LOCUS_LINE_EXTENT),
+          LineType::Straight => (-LOCUS_LINE_EXTENT, LOCUS_LINE_EXTENT),
+        };
+        (0..=LOCUS_SAMPLES)
+          .map(|i| {
+            let t = start + (end - start) * i as f64 / LOCUS_SAMPLES as f64;
+            from + (to - from) * VirtualScalar(t)
+          })
+          .collect()
+      }
+      None => return SolveResult::Request(l_ent),
+    },
+    Some(GeometrySymbol::Point(SymbolicPoint::OnCircle(c_ent, _))) => match solved.circles.get(c_ent) {
+      Some(&c) => (0..=LOCUS_SAMPLES)
+        .map(|i| {
+          let theta = 2.0 * std::f64::consts::PI * i as f64 / LOCUS_SAMPLES as f64;
+          c.center + VirtualPosition(vec2![theta.cos(), theta.sin()]) * c.radius
+        })
+        .collect(),
+      None => return SolveResult::Request(c_ent),
+    },
+    Some(GeometrySymbol::Point(SymbolicPoint::OnArc(a_ent, _))) => match solved.arcs.get(a_ent) {
+      Some(&a) => (0..=LOCUS_SAMPLES)
+        .map(|i| a.point_at_fraction(i as f64 / LOCUS_SAMPLES as f64))
+        .collect(),
+      None => return SolveResult::Request(a_ent),
+    },
+    _ => return SolveResult::Undefined, // Only a point on a line, a circle or an arc can drive a locus
+  };
+
+  let mut samples = Vec::with_capacity(positions.len());
+  for position in positions {
+    let mut sample = LocusSample::new(solved, &stale);
+    let mut sample_undefineds = HashSet::new();
+    sample.points.solved.insert(driver, position);
+
+    let mut stack = vec![traced];
+    while let Some(&dep) = stack.last() {
+      let result = match symbol_of(dep) {
+        Some(sym) => check_defined(solve(dep, sym, &sample)),
+        None => SolveResult::Undefined,
+      };
+      match result {
+        SolveResult::AlreadyComputed => (),
+        SolveResult::Undefined | SolveResult::SolvedLocus(_) => {
+          sample_undefineds.insert(dep);
+        }
+        SolveResult::SolvedPoint(vp) => {
+          sample.points.solved.insert(dep, vp);
+        }
+        SolveResult::SolvedLine(vl) => {
+          sample.lines.solved.insert(dep, vl);
+        }
+        SolveResult::SolvedCircle(vc) => {
+          sample.circles.solved.insert(dep, vc);
+        }
+        SolveResult::SolvedPolygon(vp) => {
+          sample.polygons.solved.insert(dep, vp);
+        }
+        SolveResult::SolvedMeasurement(m) => {
+          sample.measurements.solved.insert(dep, m);
+        }
+        SolveResult::SolvedArc(va) => {
+          sample.arcs.solved.insert(dep, va);
+        }
+        SolveResult::SolvedConic(vc) => {
+          sample.conics.solved.insert(dep, vc);
+        }
+
+        // The inputs not moving with the driver come from the live construction, so they are
+        // solved first like for any other element
+        SolveResult::Request(req_ent) if !stale.contains(&req_ent) => return SolveResult::Request(req_ent),
+        SolveResult::Request(req_ent) if sample_undefineds.contains(&req_ent) => {
+          sample_undefineds.insert(dep);
+        }
+        SolveResult::Request(req_ent) => {
+          stack.push(req_ent);
+          continue;
+        }
+      }
+      stack.pop();
+    }
+    samples.push(sample.points.solved.get(&traced).map(|&VirtualPosition(p)| p));
   }
+  SolveResult::SolvedLocus(Polyline::from_samples(&samples).into())
 }
 
-fn solve_point<'a>(
+fn solve_point(
   ent: Entity,
   sym_point: SymbolicPoint,
-  virt_points: &WriteStorage<'a, VirtualPoint>,
-  virt_lines: &WriteStorage<'a, VirtualLine>,
-  virt_circles: &WriteStorage<'a, VirtualCircle>,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+  virt_circles: &dyn Shapes<VirtualCircle>,
+  virt_arcs: &dyn Shapes<VirtualArc>,
+  virt_conics: &dyn Shapes<VirtualConic>,
 ) -> SolveResult {
   if virt_points.contains(ent) {
     SolveResult::AlreadyComputed
@@ -233,16 +557,90 @@ fn solve_point<'a>(
         },
         None => SolveResult::Request(c1_ent),
       },
+      SymbolicPoint::Transformed(p_ent, trans) => match virt_points.get(p_ent) {
+        Some(&p) => match solve_transformation(trans, virt_points, virt_lines) {
+          Ok(t) => SolveResult::SolvedPoint(t.apply(p.into()).into()),
+          Err(result) => result,
+        },
+        None => SolveResult::Request(p_ent),
+      },
+      SymbolicPoint::OnArc(a_ent, fraction) => match virt_arcs.get(a_ent) {
+        Some(a) => SolveResult::SolvedPoint(a.point_at_fraction(fraction)),
+        None => SolveResult::Request(a_ent),
+      },
+
+      // The ids are the ones on the whole circle of the arc, so that a point keeps its id when
+      // the other one leaves the arc. Only the points on the arc are defined
+      SymbolicPoint::ArcLineIntersect(a_ent, l_ent, ity) => match virt_arcs.get(a_ent) {
+        Some(&a) => match virt_lines.get(l_ent) {
+          Some(&l) => on_arc(a, a.circle().intersect(l), ity),
+          None => SolveResult::Request(l_ent),
+        },
+        None => SolveResult::Request(a_ent),
+      },
+      SymbolicPoint::ArcCircleIntersect(a_ent, c_ent, ity) => match virt_arcs.get(a_ent) {
+        Some(&a) => match virt_circles.get(c_ent) {
+          Some(&c) => on_arc(a, a.circle().intersect(c), ity),
+          None => SolveResult::Request(c_ent),
+        },
+        None => SolveResult::Request(a_ent),
+      },
+      SymbolicPoint::ConicLineIntersect(c_ent, l_ent, ity) => match virt_conics.get(c_ent) {
+        Some(&c) => match virt_lines.get(l_ent) {
+          Some(&l) => match c.intersect(l) {
+            VirtualCircleIntersect::TwoPoints(p1, p2) => match ity {
+              CircleIntersectId::First => SolveResult::SolvedPoint(p1),
+              CircleIntersectId::Second => SolveResult::SolvedPoint(p2),
+            },
+            VirtualCircleIntersect::OnePoint(p) => SolveResult::SolvedPoint(p),
+            VirtualCircleIntersect::None => SolveResult::Undefined,
+          },
+          None => SolveResult::Request(l_ent),
+        },
+        None => SolveResult::Request(c_ent),
+      },
+      SymbolicPoint::Centroid(p1_ent, p2_ent, p3_ent) => solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| {
+        SolveResult::SolvedPoint(t.centroid().into())
+      }),
+      SymbolicPoint::Circumcenter(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| solved_point(t.circumcenter()))
+      }
+      SymbolicPoint::Orthocenter(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| solved_point(t.orthocenter()))
+      }
+      SymbolicPoint::Incenter(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| solved_point(t.incenter()))
+      }
+      SymbolicPoint::NinePointCenter(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| {
+          solved_point(t.nine_point_center())
+        })
+      }
     }
   }
 }
 
-fn solve_line<'a>(
+/// The intersection with the circle of the arc picked by `ity`, when it's on the arc
+fn on_arc(a: VirtualArc, itsct: VirtualCircleIntersect, ity: CircleIntersectId) -> SolveResult {
+  let p = match (itsct, ity) {
+    (VirtualCircleIntersect::TwoPoints(p1, _), CircleIntersectId::First) => p1,
+    (VirtualCircleIntersect::TwoPoints(_, p2), CircleIntersectId::Second) => p2,
+    (VirtualCircleIntersect::OnePoint(p), _) => p,
+    (VirtualCircleIntersect::None, _) => return SolveResult::Undefined,
+  };
+  if a.contains_point(p) {
+    SolveResult::SolvedPoint(p)
+  } else {
+    SolveResult::Undefined
+  }
+}
+
+fn solve_line(
   ent: Entity,
   sym_line: SymbolicLine,
-  virt_points: &WriteStorage<'a, VirtualPoint>,
-  virt_lines: &WriteStorage<'a, VirtualLine>,
-  _virt_circles: &WriteStorage<'a, VirtualCircle>, // Don't need circle for now
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+  virt_circles: &dyn Shapes<VirtualCircle>,
 ) -> SolveResult {
   if virt_lines.contains(ent) {
     SolveResult::AlreadyComputed
@@ -307,16 +705,116 @@ fn solve_line<'a>(
         },
         None => SolveResult::Request(l_ent),
       },
+      SymbolicLine::AngleBisector(p1_ent, v_ent, p2_ent) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(v_ent) {
+          Some(&v) => match virt_points.get(p2_ent) {
+            Some(&p2) => match angle_bisector_direction((p1 - v).into(), (p2 - v).into()) {
+              Some(dir) => SolveResult::SolvedLine(VirtualLine {
+                from: v,
+                to: v + dir.into(),
+                line_type: LineType::Ray,
+              }),
+              None => SolveResult::Undefined,
+            },
+            None => SolveResult::Request(p2_ent),
+          },
+          None => SolveResult::Request(v_ent),
+        },
+        None => SolveResult::Request(p1_ent),
+      },
+      SymbolicLine::PerpendicularBisector(p1_ent, p2_ent) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(p2_ent) {
+          Some(&p2) => {
+            let dir: Vector2 = (p2 - p1).into();
+            if dir.is_zero() {
+              SolveResult::Undefined
+            } else {
+              let mid = (p1 + p2) / 2.0.into();
+              let perp_dir: Vector2 = vec2![-dir.y, dir.x];
+              SolveResult::SolvedLine(VirtualLine {
+                from: mid,
+                to: mid + perp_dir.into(),
+                line_type: LineType::Straight,
+              })
+            }
+          }
+          None => SolveResult::Request(p2_ent),
+        },
+        None => SolveResult::Request(p1_ent),
+      },
+      SymbolicLine::TangentAt(c_ent, p_ent) => match virt_circles.get(c_ent) {
+        Some(&c) => match virt_points.get(p_ent) {
+          Some(&p) => match c.tangent_points(p) {
+            VirtualCircleIntersect::OnePoint(t) => SolveResult::SolvedLine(tangent_line_at(c, t)),
+            _ => SolveResult::Undefined,
+          },
+          None => SolveResult::Request(p_ent),
+        },
+        None => SolveResult::Request(c_ent),
+      },
+      SymbolicLine::TangentFromPoint(c_ent, p_ent, ity) => match virt_circles.get(c_ent) {
+        Some(&c) => match virt_points.get(p_ent) {
+          Some(&p) => match c.tangent_points(p) {
+            VirtualCircleIntersect::TwoPoints(t1, t2) => SolveResult::SolvedLine(VirtualLine {
+              from: p,
+              to: match ity {
+                CircleIntersectId::First => t1,
+                CircleIntersectId::Second => t2,
+              },
+              line_type: LineType::Straight,
+            }),
+            VirtualCircleIntersect::OnePoint(t) => SolveResult::SolvedLine(tangent_line_at(c, t)),
+            VirtualCircleIntersect::None => SolveResult::Undefined,
+          },
+          None => SolveResult::Request(p_ent),
+        },
+        None => SolveResult::Request(c_ent),
+      },
+      SymbolicLine::Transformed(l_ent, trans) => match virt_lines.get(l_ent) {
+        Some(&l) => match solve_transformation(trans, virt_points, virt_lines) {
+          Ok(t) => SolveResult::SolvedLine(t.apply_line(l.into()).into()),
+          Err(result) => result,
+        },
+        None => SolveResult::Request(l_ent),
+      },
     }
   }
 }
 
-fn solve_circle<'a>(
+/// The line touching the circle at `t`, which has to be on the circle
+fn tangent_line_at(c: VirtualCircle, t: VirtualPosition) -> VirtualLine {
+  let dir: Vector2 = (t - c.center).into();
+  let perp_dir: Vector2 = vec2![-dir.y, dir.x];
+  VirtualLine {
+    from: t,
+    to: t + perp_dir.into(),
+    line_type: LineType::Straight,
+  }
+}
+
+/// The unit direction splitting the angle between `d1` and `d2` in half. When the two sides
+/// point in opposite directions the bisector is perpendicular to them. There's no angle when
+/// one of the sides has zero length.
+fn angle_bisector_direction(d1: Vector2, d2: Vector2) -> Option<Vector2> {
+  if d1.is_zero() || d2.is_zero() {
+    None
+  } else {
+    let (d1, d2) = (d1.normalized(), d2.normalized());
+    let sum = d1 + d2;
+    if sum.magnitude() < 1e-10 {
+      Some(vec2![-d1.y, d1.x])
+    } else {
+      Some(sum.normalized())
+    }
+  }
+}
+
+fn solve_circle(
   ent: Entity,
   sym_circle: SymbolicCircle,
-  virt_points: &WriteStorage<'a, VirtualPoint>,
-  _virt_lines: &WriteStorage<'a, VirtualLine>, // Don't need virtual lines for now
-  virt_circles: &WriteStorage<'a, VirtualCircle>,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+  virt_circles: &dyn Shapes<VirtualCircle>,
 ) -> SolveResult {
   if virt_circles.contains(ent) {
     SolveResult::AlreadyComputed
@@ -332,6 +830,745 @@ fn solve_circle<'a>(
         },
         None => SolveResult::Request(p1_ent),
       },
+      SymbolicCircle::ThreePoints(p1_ent, p2_ent, p3_ent) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(p2_ent) {
+          Some(&p2) => match virt_points.get(p3_ent) {
+            Some(&p3) => match Circle::from_three_points(p1.into(), p2.into(), p3.into()) {
+              Some(c) => SolveResult::SolvedCircle(c.into()),
+              None => SolveResult::Undefined,
+            },
+            None => SolveResult::Request(p3_ent),
+          },
+          None => SolveResult::Request(p2_ent),
+        },
+        None => SolveResult::Request(p1_ent),
+      },
+      SymbolicCircle::Compass(c_ent, p1_ent, p2_ent) => match virt_points.get(c_ent) {
+        Some(&c) => match virt_points.get(p1_ent) {
+          Some(&p1) => match virt_points.get(p2_ent) {
+            Some(&p2) => SolveResult::SolvedCircle(VirtualCircle {
+              center: c,
+              radius: (p2 - p1).magnitude(),
+            }),
+            None => SolveResult::Request(p2_ent),
+          },
+          None => SolveResult::Request(p1_ent),
+        },
+        None => SolveResult::Request(c_ent),
+      },
+      SymbolicCircle::Transformed(c_ent, trans) => match virt_circles.get(c_ent) {
+        Some(&c) => match solve_transformation(trans, virt_points, virt_lines) {
+          Ok(t) => SolveResult::SolvedCircle(t.apply_circle(c.into()).into()),
+          Err(result) => result,
+        },
+        None => SolveResult::Request(c_ent),
+      },
+      SymbolicCircle::Incircle(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| solved_circle(t.incircle()))
+      }
+      SymbolicCircle::Excircle(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| solved_circle(t.excircle()))
+      }
+      SymbolicCircle::NinePointCircle(p1_ent, p2_ent, p3_ent) => {
+        solve_triangle(p1_ent, p2_ent, p3_ent, virt_points, |t| {
+          solved_circle(t.nine_point_circle())
+        })
+      }
+    }
+  }
+}
+
+/// Solve a center or a circle of the triangle of three points. Those centers are computed
+/// directly, rather than from the lines that meet there, so no helper shape is needed
+fn solve_triangle<F: FnOnce(Triangle) -> SolveResult>(
+  p1_ent: Entity,
+  p2_ent: Entity,
+  p3_ent: Entity,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  solve: F,
+) -> SolveResult {
+  match virt_points.get(p1_ent) {
+    Some(&p1) => match virt_points.get(p2_ent) {
+      Some(&p2) => match virt_points.get(p3_ent) {
+        Some(&p3) => solve(Triangle::new(p1.into(), p2.into(), p3.into())),
+        None => SolveResult::Request(p3_ent),
+      },
+      None => SolveResult::Request(p2_ent),
+    },
+    None => SolveResult::Request(p1_ent),
+  }
+}
+
+fn solved_point(p: Option<Vector2>) -> SolveResult {
+  match p {
+    Some(p) => SolveResult::SolvedPoint(p.into()),
+    None => SolveResult::Undefined,
+  }
+}
+
+fn solved_circle(c: Option<Circle>) -> SolveResult {
+  match c {
+    Some(c) => SolveResult::SolvedCircle(c.into()),
+    None => SolveResult::Undefined,
+  }
+}
+
+fn solve_polygon(
+  ent: Entity,
+  sym_polygon: SymbolicPolygon,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_polygons: &dyn Shapes<VirtualPolygon>,
+) -> SolveResult {
+  if virt_polygons.contains(ent) {
+    SolveResult::AlreadyComputed
+  } else {
+    let mut points = Vec::with_capacity(sym_polygon.vertices().len());
+    for &vertex_ent in sym_polygon.vertices() {
+      match virt_points.get(vertex_ent) {
+        Some(&p) => points.push(p),
+        None => return SolveResult::Request(vertex_ent),
+      }
+    }
+    SolveResult::SolvedPolygon(VirtualPolygon { points })
+  }
+}
+
+fn solve_arc(
+  ent: Entity,
+  sym_arc: SymbolicArc,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_arcs: &dyn Shapes<VirtualArc>,
+) -> SolveResult {
+  if virt_arcs.contains(ent) {
+    SolveResult::AlreadyComputed
+  } else {
+    let [p1_ent, p2_ent, p3_ent] = sym_arc.points();
+    match virt_points.get(p1_ent) {
+      Some(&p1) => match virt_points.get(p2_ent) {
+        Some(&p2) => match virt_points.get(p3_ent) {
+          Some(&p3) => {
+            let arc = match sym_arc {
+              SymbolicArc::ThreePoints(_, _, _, kind) => Arc::from_three_points(p1.into(), p2.into(), p3.into(), kind),
+              SymbolicArc::CenterFromTo(_, _, _, kind) => Arc::from_center(p1.into(), p2.into(), p3.into(), kind),
+            };
+            match arc {
+              Some(a) => SolveResult::SolvedArc(a.into()),
+              None => SolveResult::Undefined,
+            }
+          }
+          None => SolveResult::Request(p3_ent),
+        },
+        None => SolveResult::Request(p2_ent),
+      },
+      None => SolveResult::Request(p1_ent),
+    }
+  }
+}
+
+fn solve_conic(
+  ent: Entity,
+  sym_conic: SymbolicConic,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+  virt_conics: &dyn Shapes<VirtualConic>,
+) -> SolveResult {
+  if virt_conics.contains(ent) {
+    return SolveResult::AlreadyComputed;
+  }
+  let point = |p_ent: Entity| virt_points.get(p_ent).map(|&VirtualPosition(p)| p).ok_or(p_ent);
+  let conic = match sym_conic {
+    SymbolicConic::FivePoints(p1_ent, p2_ent, p3_ent, p4_ent, p5_ent) => {
+      match [p1_ent, p2_ent, p3_ent, p4_ent, p5_ent]
+        .iter()
+        .map(|p_ent| point(*p_ent))
+        .collect::<Result<Vec<_>, _>>()
+      {
+        Ok(points) => Conic::from_five_points([points[0], points[1], points[2], points[3], points[4]]),
+        Err(p_ent) => return SolveResult::Request(p_ent),
+      }
+    }
+    SymbolicConic::FociPoint(f1_ent, f2_ent, p_ent) => match (point(f1_ent), point(f2_ent), point(p_ent)) {
+      (Ok(f1), Ok(f2), Ok(p)) => Conic::ellipse_from_foci(f1, f2, p),
+      (Err(req_ent), _, _) | (_, Err(req_ent), _) | (_, _, Err(req_ent)) => return SolveResult::Request(req_ent),
+    },
+    SymbolicConic::FocusDirectrix(focus_ent, l_ent) => match point(focus_ent) {
+      Ok(focus) => match virt_lines.get(l_ent) {
+        Some(&l) => Conic::parabola_from_focus(focus, l.into()),
+        None => return SolveResult::Request(l_ent),
+      },
+      Err(req_ent) => return SolveResult::Request(req_ent),
+    },
+  };
+  match conic {
+    Some(c) => SolveResult::SolvedConic(VirtualConic(c)),
+    None => SolveResult::Undefined,
+  }
+}
+
+fn solve_measurement(
+  ent: Entity,
+  sym_measurement: SymbolicMeasurement,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+  virt_circles: &dyn Shapes<VirtualCircle>,
+  virt_polygons: &dyn Shapes<VirtualPolygon>,
+  measurements: &dyn Shapes<Measurement>,
+) -> SolveResult {
+  if measurements.contains(ent) {
+    SolveResult::AlreadyComputed
+  } else {
+    match sym_measurement {
+      SymbolicMeasurement::Distance(p1_ent, p2_ent) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(p2_ent) {
+          Some(&p2) => SolveResult::SolvedMeasurement(Measurement((p2 - p1).magnitude().0)),
+          None => SolveResult::Request(p2_ent),
+        },
+        None => SolveResult::Request(p1_ent),
+      },
+      SymbolicMeasurement::Length(l_ent) => match virt_lines.get(l_ent) {
+        Some(&l) if l.line_type == LineType::Segment => {
+          SolveResult::SolvedMeasurement(Measurement((l.to - l.from).magnitude().0))
+        }
+        Some(_) => SolveResult::Undefined, // Only segments have a length
+        None => SolveResult::Request(l_ent),
+      },
+      SymbolicMeasurement::Angle(p1_ent, v_ent, p2_ent) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(v_ent) {
+          Some(&v) => match virt_points.get(p2_ent) {
+            Some(&p2) => {
+              let (d1, d2): (Vector2, Vector2) = ((p1 - v).into(), (p2 - v).into());
+              if d1.is_zero() || d2.is_zero() {
+                SolveResult::Undefined
+              } else {
+                let angle = (d1.x * d2.y - d1.y * d2.x).atan2(d1.dot(d2)).abs();
+                SolveResult::SolvedMeasurement(Measurement(angle))
+              }
+            }
+            None => SolveResult::Request(p2_ent),
+          },
+          None => SolveResult::Request(v_ent),
+        },
+        None => SolveResult::Request(p1_ent),
+      },
+      SymbolicMeasurement::Area(ent) => {
+        if let Some(c) = virt_circles.get(ent) {
+          SolveResult::SolvedMeasurement(Measurement(std::f64::consts::PI * c.radius.0 * c.radius.0))
+        } else if let Some(p) = virt_polygons.get(ent) {
+          let polygon: Polygon = p.clone().into();
+          SolveResult::SolvedMeasurement(Measurement(polygon.area()))
+        } else {
+          SolveResult::Request(ent)
+        }
+      }
+      SymbolicMeasurement::Perimeter(ent) => {
+        if let Some(c) = virt_circles.get(ent) {
+          SolveResult::SolvedMeasurement(Measurement(2.0 * std::f64::consts::PI * c.radius.0))
+        } else if let Some(p) = virt_polygons.get(ent) {
+          let polygon: Polygon = p.clone().into();
+          SolveResult::SolvedMeasurement(Measurement(polygon.perimeter()))
+        } else {
+          SolveResult::Request(ent)
+        }
+      }
+      SymbolicMeasurement::Ratio(m1_ent, m2_ent) => match measurements.get(m1_ent) {
+        Some(&Measurement(m1)) => match measurements.get(m2_ent) {
+          Some(&Measurement(m2)) => {
+            if m2 == 0.0 {
+              SolveResult::Undefined
+            } else {
+              SolveResult::SolvedMeasurement(Measurement(m1 / m2))
+            }
+          }
+          None => SolveResult::Request(m2_ent),
+        },
+        None => SolveResult::Request(m1_ent),
+      },
+    }
+  }
+}
+
+/// The concrete transform of a symbolic transformation, or the solve result telling why
+/// it can't be computed yet
+fn solve_transformation(
+  trans: Transformation,
+  virt_points: &dyn Shapes<VirtualPoint>,
+  virt_lines: &dyn Shapes<VirtualLine>,
+) -> Result<Transform, SolveResult> {
+  match trans {
+    Transformation::Reflect(l_ent) => match virt_lines.get(l_ent) {
+      Some(&l) => Transform::reflect(l.into()).ok_or(SolveResult::Undefined),
+      None => Err(SolveResult::Request(l_ent)),
+    },
+    Transformation::Rotate(c_ent, angle) => match virt_points.get(c_ent) {
+      Some(&c) => Ok(Transform::rotate(c.into(), angle)),
+      None => Err(SolveResult::Request(c_ent)),
+    },
+    Transformation::RotateByAngle(c_ent, p1_ent, v_ent, p2_ent) => match virt_points.get(c_ent) {
+      Some(&c) => match virt_points.get(p1_ent) {
+        Some(&p1) => match virt_points.get(v_ent) {
+          Some(&v) => match virt_points.get(p2_ent) {
+            Some(&p2) => {
+              let (d1, d2): (Vector2, Vector2) = ((p1 - v).into(), (p2 - v).into());
+              if d1.is_zero() || d2.is_zero() {
+                Err(SolveResult::Undefined)
+              } else {
+                let angle = (d1.x * d2.y - d1.y * d2.x).atan2(d1.dot(d2));
+                Ok(Transform::rotate(c.into(), angle))
+              }
+            }
+            None => Err(SolveResult::Request(p2_ent)),
+          },
+          None => Err(SolveResult::Request(v_ent)),
+        },
+        None => Err(SolveResult::Request(p1_ent)),
+      },
+      None => Err(SolveResult::Request(c_ent)),
+    },
+    Transformation::Translate(p1_ent, p2_ent) => match virt_points.get(p1_ent) {
+      Some(&p1) => match virt_points.get(p2_ent) {
+        Some(&p2) => Ok(Transform::translate((p2 - p1).into())),
+        None => Err(SolveResult::Request(p2_ent)),
+      },
+      None => Err(SolveResult::Request(p1_ent)),
+    },
+    Transformation::Dilate(c_ent, factor) => match virt_points.get(c_ent) {
+      Some(&c) => Ok(Transform::dilate(c.into(), factor)),
+      None => Err(SolveResult::Request(c_ent)),
+    },
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::setup_core_lib;
+  use std::collections::HashMap;
+
+  /// A world running the whole core lib, with the elements of a construction script
+  struct Sketch<'a, 'b> {
+    world: World,
+    dispatcher: Dispatcher<'a, 'b>,
+    names: HashMap<String, Entity>,
+  }
+
+  impl<'a, 'b> Sketch<'a, 'b> {
+    fn new(source: &str) -> Self {
+      let mut world = World::new();
+      let mut builder = DispatcherBuilder::new();
+      setup_core_lib(&mut builder);
+      let mut dispatcher = builder.build();
+      dispatcher.setup(&mut world);
+      let script = {
+        let entities = world.entities();
+        CompiledScript::compile(source, styles(), || entities.create()).unwrap()
+      };
+      let names = script.write(
+        &mut world.fetch_mut::<CommandEventChannel>(),
+        &mut world.fetch_mut::<HistoryEventChannel>(),
+      );
+      let mut sketch = Self {
+        world,
+        dispatcher,
+        names,
+      };
+      sketch.step();
+      sketch
+    }
+
+    /// Add an element the script can't express
+    fn with<F: FnOnce(&HashMap<String, Entity>) -> GeometrySymbol>(mut self, name: &str, symbol: F) -> Self {
+      let ent = self.world.create_entity().build();
+      let command = match symbol(&self.names) {
+        GeometrySymbol::Point(sym) => {
+          Command::PointInsert(InsertPointEvent::InsertPointByHistory(ent, sym, styles().point))
+        }
+        GeometrySymbol::Line(sym) => Command::LineInsert(InsertLineEvent::InsertLineByHistory(ent, sym, styles().line)),
+        GeometrySymbol::Circle(sym) => {
+          Command::CircleInsert(InsertCircleEvent::InsertCircleByHistory(ent, sym, styles().circle))
+        }
+        GeometrySymbol::Polygon(sym) => {
+          Command::PolygonInsert(InsertPolygonEvent::InsertPolygonByHistory(ent, sym, styles().polygon))
+        }
+        GeometrySymbol::Measurement(sym) => {
+          Command::MeasurementInsert(InsertMeasurementEvent::InsertMeasurementByHistory(ent, sym))
+        }
+        _ => panic!("only points, lines, circles, polygons and measurements are needed"),
+      };
+      self.names.insert(name.to_string(), ent);
+      self.command(command);
+      self
+    }
+
+    fn command(&mut self, command: Command) {
+      self
+        .world
+        .fetch_mut::<CommandEventChannel>()
+        .single_write(CommandEvent {
+          command,
+          event_id: None,
+        });
+      self.step();
+    }
+
+    fn step(&mut self) {
+      self.dispatcher.dispatch(&self.world);
+      self.world.maintain();
+    }
+
+    /// Move a free or a fixed point, as one frame of a drag
+    fn move_point(&mut self, name: &str, position: Vector2) {
+      let ent = self.names[name];
+      let before = *self.world.read_storage::<SymbolicPoint>().get(ent).unwrap();
+      let after = match before {
+        SymbolicPoint::Free(_) => SymbolicPoint::Free(position.into()),
+        SymbolicPoint::Fixed(_) => SymbolicPoint::Fixed(position.into()),
+        _ => panic!("{} can't be moved", name),
+      };
+      self.command(Command::Update(UpdateEvent::UpdatePoint(ent, before, after)));
     }
+
+    /// Whether the element is defined, checking that it has a shape exactly when it is
+    fn is_defined(&self, name: &str) -> bool {
+      let ent = self.names[name];
+      let has_shape = self.world.read_storage::<VirtualPoint>().contains(ent)
+        || self.world.read_storage::<VirtualLine>().contains(ent)
+        || self.world.read_storage::<VirtualCircle>().contains(ent)
+        || self.world.read_storage::<VirtualArc>().contains(ent)
+        || self.world.read_storage::<VirtualConic>().contains(ent);
+      let is_undefined = self.world.read_storage::<Undefined>().contains(ent);
+      assert_ne!(has_shape, is_undefined, "{} has a shape and is undefined", name);
+      has_shape
+    }
+
+    /// The value of a measurement, checking that there's none exactly when it is undefined
+    fn measure(&self, name: &str) -> Option<f64> {
+      let ent = self.names[name];
+      let value = self.world.read_storage::<Measurement>().get(ent).map(|m| m.0);
+      let is_undefined = self.world.read_storage::<Undefined>().contains(ent);
+      assert_ne!(value.is_some(), is_undefined, "{} has a value and is undefined", name);
+      value
+    }
+  }
+
+  fn styles() -> FileDefaultStyles {
+    FileDefaultStyles {
+      point: DefaultPointStyle::default().get(),
+      line: DefaultLineStyle::default().get(),
+      circle: DefaultCircleStyle::default().get(),
+      polygon: DefaultPolygonStyle::default().get(),
+    }
+  }
+
+  /// Drag `moved` to a position making `target` undefined, then back to one where it's defined
+  fn check(mut sketch: Sketch, target: &str, moved: &str, degenerate: Vector2, valid: Vector2) {
+    assert!(sketch.is_defined(target), "{} should start defined", target);
+    sketch.move_point(moved, degenerate);
+    assert!(!sketch.is_defined(target), "{} should be undefined", target);
+    sketch.move_point(moved, (degenerate + valid) / 2.0);
+    sketch.move_point(moved, valid);
+    assert!(sketch.is_defined(target), "{} should be defined again", target);
+  }
+
+  const TWO_LINES: &str = "A = free(0, 0); B = free(2, 0); C = free(0, 2); D = free(1, 3)";
+  const ONE_CIRCLE: &str = "A = free(0, 0); B = free(2, 0); c = circle(A, B)";
+
+  #[test]
+  fn test_undefined_points() {
+    check(
+      Sketch::new("P = free(1, 1)"),
+      "P",
+      "P",
+      vec2![f64::NAN, 0.],
+      vec2![1., 1.],
+    );
+    check(
+      Sketch::new("P = fixed(1, 1)"),
+      "P",
+      "P",
+      vec2![0., f64::NAN],
+      vec2![1., 1.],
+    );
+    let source = format!(
+      "{}; I = intersect(AB, CD); M = midpoint(A, I); N = midpoint(M, B)",
+      TWO_LINES
+    );
+    check(Sketch::new(&source), "M", "D", vec2![2., 2.], vec2![1., 3.]);
+    check(Sketch::new(&source), "N", "D", vec2![2., 2.], vec2![1., 3.]);
+    check(Sketch::new(&source), "I", "D", vec2![0., 2.], vec2![1., 3.]);
+    let source = format!("{}; P = on_line(CD, 0.5)", TWO_LINES);
+    check(Sketch::new(&source), "P", "D", vec2![0., 2.], vec2![1., 3.]);
+    let source = format!("{}; P = on_circle(c, 1)", ONE_CIRCLE);
+    check(Sketch::new(&source), "P", "B", vec2![0., 0.], vec2![2., 0.]);
+    let source = format!(
+      "{}; C = free(-5, 1); D = free(5, 1); P = intersect(c, CD, 1)",
+      ONE_CIRCLE
+    );
+    check(Sketch::new(&source), "P", "D", vec2![-5., 5.], vec2![5., 1.]);
+    let source = format!(
+      "{}; C = free(1, 0); D = free(3, 0); d = circle(C, D); P = intersect(c, d, 2)",
+      ONE_CIRCLE
+    );
+    check(Sketch::new(&source), "P", "C", vec2![0., 0.], vec2![1., 0.]);
+    let sketch = Sketch::new(&format!("{}; l = line(A, B); P = free(1, 1)", TWO_LINES)).with("Q", |names| {
+      GeometrySymbol::Point(SymbolicPoint::Transformed(
+        names["P"],
+        Transformation::Reflect(names["l"]),
+      ))
+    });
+    check(sketch, "Q", "B", vec2![0., 0.], vec2![2., 0.]);
+
+    // Only the centroid of a flat triangle is still defined
+    for function in &["circumcenter", "orthocenter", "incenter", "nine_point_center"] {
+      let source = format!("{}; P = {}(A, B, C)", TWO_LINES, function);
+      check(Sketch::new(&source), "P", "C", vec2![1., 0.], vec2![0., 2.]);
+    }
+    let mut sketch = Sketch::new(&format!("{}; G = centroid(A, B, C)", TWO_LINES));
+    sketch.move_point("C", vec2![1., 0.]);
+    assert!(sketch.is_defined("G"));
+  }
+
+  #[test]
+  fn test_undefined_lines() {
+    for function in &["line", "ray", "segment", "perp_bisector"] {
+      let source = format!("{}; l = {}(A, B)", TWO_LINES, function);
+      check(Sketch::new(&source), "l", "B", vec2![0., 0.], vec2![2., 0.]);
+    }
+    for function in &["parallel", "perp"] {
+      let source = format!("{}; l = {}(AB, C)", TWO_LINES, function);
+      check(Sketch::new(&source), "l", "B", vec2![0., 0.], vec2![2., 0.]);
+    }
+    let source = format!("{}; l = angle_bisector(B, A, C)", TWO_LINES);
+    check(Sketch::new(&source), "l", "C", vec2![0., 0.], vec2![0., 2.]);
+    let source = format!("{}; P = free(2, 0); l = tangent(c, P)", ONE_CIRCLE);
+    check(Sketch::new(&source), "l", "P", vec2![5., 0.], vec2![2., 0.]);
+    let source = format!("{}; P = free(5, 0); l = tangent(c, P, 1)", ONE_CIRCLE);
+    check(Sketch::new(&source), "l", "P", vec2![0.5, 0.], vec2![5., 0.]);
+    let sketch = Sketch::new(&format!("{}; l = line(A, B); k = line(C, D)", TWO_LINES)).with("m", |names| {
+      GeometrySymbol::Line(SymbolicLine::Transformed(
+        names["l"],
+        Transformation::Reflect(names["k"]),
+      ))
+    });
+    check(sketch, "m", "D", vec2![0., 2.], vec2![1., 3.]);
+  }
+
+  #[test]
+  fn test_undefined_circles() {
+    check(Sketch::new(ONE_CIRCLE), "c", "B", vec2![0., 0.], vec2![2., 0.]);
+    let source = format!("{}; c = circle(A, B, C)", TWO_LINES);
+    check(Sketch::new(&source), "c", "C", vec2![1., 0.], vec2![0., 2.]);
+    let source = format!("{}; c = compass(C, A, B)", TWO_LINES);
+    check(Sketch::new(&source), "c", "B", vec2![0., 0.], vec2![2., 0.]);
+    let sketch = Sketch::new(&format!("{}; c = circle(A, B); k = line(C, D)", TWO_LINES)).with("d", |names| {
+      GeometrySymbol::Circle(SymbolicCircle::Transformed(
+        names["c"],
+        Transformation::Reflect(names["k"]),
+      ))
+    });
+    check(sketch, "d", "D", vec2![0., 2.], vec2![1., 3.]);
+    for function in &["incircle", "excircle", "nine_point_circle"] {
+      let source = format!("{}; c = {}(A, B, C)", TWO_LINES, function);
+      check(Sketch::new(&source), "c", "C", vec2![1., 0.], vec2![0., 2.]);
+    }
+  }
+
+  #[test]
+  fn test_undefined_arcs() {
+    let source = "A = free(1, 0); B = free(0, 1); C = free(-1, 0); a = arc(A, B, C)";
+    check(Sketch::new(source), "a", "B", vec2![0., 0.], vec2![0., 1.]);
+    let source = "O = free(0, 0); A = free(1, 0); B = free(0, 1); s = sector(O, A, B)";
+    check(Sketch::new(source), "s", "A", vec2![0., 0.], vec2![1., 0.]);
+
+    // A vertical line crosses the circle once above and once below, and only the crossing on
+    // the arc is defined. Bending the arc down swaps them
+    let mut sketch = Sketch::new(&format!(
+      "{}; E = free(0.5, -5); F = free(0.5, 5); P = intersect(a, EF, 1); Q = intersect(a, EF, 2)",
+      "A = free(1, 0); B = free(0, 1); C = free(-1, 0); a = arc(A, B, C)"
+    ));
+    let (p, q) = (sketch.is_defined("P"), sketch.is_defined("Q"));
+    assert!(p != q);
+    sketch.move_point("B", vec2![0., -1.]);
+    assert_eq!((sketch.is_defined("P"), sketch.is_defined("Q")), (q, p));
+  }
+
+  #[test]
+  fn test_undefined_conics() {
+    let source = "A = free(-2, 0); B = free(2, 0); C = free(0, 1); D = free(0, -1); E = free(1.2, 0.8); \
+                  k = conic(A, B, C, D, E)";
+    check(Sketch::new(source), "k", "E", vec2![0., 0.], vec2![1.2, 0.8]);
+    let source = "A = free(-1, 0); B = free(1, 0); C = free(0, 1); k = ellipse(A, B, C)";
+    check(Sketch::new(source), "k", "C", vec2![0., 0.], vec2![0., 1.]);
+    let source = "F = free(0, 1); A = free(-1, -1); B = free(1, -1); d = line(A, B); k = parabola(F, d)";
+    check(Sketch::new(source), "k", "F", vec2![0., -1.], vec2![0., 1.]);
+
+    // The two crossings of a line are ordered along it, and the line leaves the ellipse between
+    let mut sketch = Sketch::new(&format!(
+      "{}; E = free(-5, 0.5); G = free(5, 0.5); P = intersect(k, EG, 1); Q = intersect(k, EG, 2)",
+      "A = free(-1, 0); B = free(1, 0); C = free(0, 1); k = ellipse(A, B, C)"
+    ));
+    let x_of = |sketch: &Sketch, name: &str| {
+      sketch
+        .world
+        .read_storage::<VirtualPoint>()
+        .get(sketch.names[name])
+        .unwrap()
+        .0
+        .x
+    };
+    assert!(x_of(&sketch, "P") < x_of(&sketch, "Q"));
+    sketch.move_point("G", vec2![5., 5.]);
+    sketch.move_point("E", vec2![-5., 5.]);
+    assert!(!sketch.is_defined("P") && !sketch.is_defined("Q"));
+  }
+
+  #[test]
+  fn test_angle_bisector_direction() {
+    let dir = angle_bisector_direction(vec2![2.0, 0.0], vec2![0.0, 5.0]).unwrap();
+    assert!((dir - vec2![1.0, 1.0].normalized()).magnitude() < 1e-10);
+    let dir = angle_bisector_direction(vec2![1.0, 0.0], vec2![-3.0, 0.0]).unwrap();
+    assert!((dir - vec2![0.0, 1.0]).magnitude() < 1e-10);
+    assert!(angle_bisector_direction(vec2![0.0, 0.0], vec2![1.0, 0.0]).is_none());
+  }
+
+  #[test]
+  fn test_measurements() {
+    let source = "A = free(0, 0); B = free(3, 0); C = free(3, 4); s = segment(A, B); l = line(B, C); \
+                  c = circle(A, B)";
+    let mut sketch = Sketch::new(source).with("p", |names| {
+      GeometrySymbol::Polygon(SymbolicPolygon::Vertices(vec![names["A"], names["B"], names["C"]]))
+    });
+    type Measure = fn(&HashMap<String, Entity>) -> SymbolicMeasurement;
+    let measurements: [(&str, Measure); 13] = [
+      ("distance", |n| SymbolicMeasurement::Distance(n["A"], n["C"])),
+      ("gap", |n| SymbolicMeasurement::Distance(n["A"], n["B"])),
+      ("length", |n| SymbolicMeasurement::Length(n["s"])),
+      ("line_length", |n| SymbolicMeasurement::Length(n["l"])),
+      ("angle", |n| SymbolicMeasurement::Angle(n["B"], n["A"], n["C"])),
+      ("reversed_angle", |n| SymbolicMeasurement::Angle(n["C"], n["A"], n["B"])),
+      ("circle_area", |n| SymbolicMeasurement::Area(n["c"])),
+      ("circle_perimeter", |n| SymbolicMeasurement::Perimeter(n["c"])),
+      ("polygon_area", |n| SymbolicMeasurement::Area(n["p"])),
+      ("polygon_perimeter", |n| SymbolicMeasurement::Perimeter(n["p"])),
+      ("ratio", |n| SymbolicMeasurement::Ratio(n["length"], n["distance"])),
+      ("gap_ratio", |n| SymbolicMeasurement::Ratio(n["gap"], n["distance"])),
+      ("inverse_ratio", |n| SymbolicMeasurement::Ratio(n["distance"], n["gap"])),
+    ];
+    for (name, measure) in measurements.iter() {
+      sketch = sketch.with(name, |names| GeometrySymbol::Measurement(measure(names)));
+    }
+    let close = |sketch: &Sketch, name: &str, value: f64| {
+      let measured = sketch
+        .measure(name)
+        .unwrap_or_else(|| panic!("{} should be defined", name));
+      assert!(
+        (measured - value).abs() < 1e-9,
+        "{} is {} instead of {}",
+        name,
+        measured,
+        value
+      );
+    };
+    close(&sketch, "distance", 5.0);
+    close(&sketch, "length", 3.0);
+    assert_eq!(sketch.measure("line_length"), None, "only segments have a length");
+    close(&sketch, "angle", 4f64.atan2(3.0));
+    close(&sketch, "reversed_angle", 4f64.atan2(3.0));
+    close(&sketch, "circle_area", 9.0 * std::f64::consts::PI);
+    close(&sketch, "circle_perimeter", 6.0 * std::f64::consts::PI);
+    close(&sketch, "polygon_area", 6.0);
+    close(&sketch, "polygon_perimeter", 12.0);
+    close(&sketch, "ratio", 0.6);
+    close(&sketch, "inverse_ratio", 5.0 / 3.0);
+
+    // An arm of zero length leaves the angle undefined, and so does a ratio to zero. The segment
+    // is undefined too, and its length with it
+    sketch.move_point("B", vec2![0., 0.]);
+    assert_eq!(sketch.measure("angle"), None);
+    assert_eq!(sketch.measure("inverse_ratio"), None);
+    assert_eq!(sketch.measure("length"), None);
+    close(&sketch, "gap_ratio", 0.0);
+    sketch.move_point("B", vec2![-3., 0.]);
+    close(&sketch, "angle", std::f64::consts::PI - 4f64.atan2(3.0));
+    close(&sketch, "inverse_ratio", 5.0 / 3.0);
+  }
+
+  /// Three selected points transfer the distance between the first two to a circle around the
+  /// third, which keeps following that distance
+  #[test]
+  fn test_compass_transfer() {
+    let mut sketch = Sketch::new("A = free(0, 0); B = free(3, 4); C = free(5, 5)");
+    sketch.command(Command::Select(SelectEvent::DeselectAll));
+    for name in &["A", "B", "C"] {
+      let ent = sketch.names[*name];
+      sketch.command(Command::Select(SelectEvent::Select(ent)));
+    }
+    sketch.command(Command::CircleInsert(
+      InsertCircleEvent::InsertCompassCircleFromSelection,
+    ));
+    let (a, b, c) = (sketch.names["A"], sketch.names["B"], sketch.names["C"]);
+    let circle_ent = {
+      let sym_circles = sketch.world.read_storage::<SymbolicCircle>();
+      let entities = sketch.world.entities();
+      let circles = (&entities, &sym_circles).join().collect::<Vec<_>>();
+      assert_eq!(circles.len(), 1);
+      match *circles[0].1 {
+        SymbolicCircle::Compass(center, p1, p2) => assert_eq!((center, p1, p2), (c, a, b)),
+        _ => panic!("expected a compass circle"),
+      }
+      circles[0].0
+    };
+    let circle = |sketch: &Sketch| *sketch.world.read_storage::<VirtualCircle>().get(circle_ent).unwrap();
+    assert!((circle(&sketch).radius.0 - 5.0).abs() < 1e-9);
+    assert!((circle(&sketch).center.0 - vec2![5., 5.]).magnitude() < 1e-9);
+    sketch.move_point("B", vec2![6., 8.]);
+    assert!((circle(&sketch).radius.0 - 10.0).abs() < 1e-9);
+    sketch.move_point("C", vec2![-1., 2.]);
+    assert!((circle(&sketch).center.0 - vec2![-1., 2.]).magnitude() < 1e-9);
+    assert!((circle(&sketch).radius.0 - 10.0).abs() < 1e-9);
+  }
+
+  /// The locus of the points as far from a focus as from a directrix is a parabola
+  #[test]
+  fn test_parabola_locus() {
+    let mut sketch = Sketch::new(
+      "F = free(0, 1); A = free(-1, -1); B = free(1, -1); d = line(A, B); D = on_line(d, 0.5); \
+       n = perp(d, D); m = perp_bisector(F, D); P = intersect(n, m); trace = locus(D, P)",
+    );
+    let on_parabola = |sketch: &Sketch, focus_y: f64| {
+      let locus = sketch
+        .world
+        .read_storage::<VirtualLocus>()
+        .get(sketch.names["trace"])
+        .cloned();
+      let locus: Polyline = locus.expect("the locus should be defined").into();
+      assert_eq!(locus.paths.len(), 1);
+      assert!(locus.paths[0].len() > 100);
+      let (vertex, p) = ((focus_y - 1.0) / 2.0, (focus_y + 1.0) / 2.0);
+      for point in locus.paths.iter().flatten() {
+        assert!((point.y - vertex - point.x * point.x / (4.0 * p)).abs() < 1e-9);
+      }
+    };
+    on_parabola(&sketch, 1.0);
+
+    // The locus follows the focus, while the driver and the traced point stay where they are
+    let p = *sketch
+      .world
+      .read_storage::<VirtualPoint>()
+      .get(sketch.names["P"])
+      .unwrap();
+    sketch.move_point("F", vec2![0., 2.]);
+    on_parabola(&sketch, 2.0);
+    let moved = *sketch
+      .world
+      .read_storage::<VirtualPoint>()
+      .get(sketch.names["P"])
+      .unwrap();
+    assert!((moved.0.x - p.0.x).abs() < 1e-9);
+
+    // It's undefined as long as its directrix is
+    sketch.move_point("B", vec2![-1., -1.]);
+    assert!(sketch.world.read_storage::<Undefined>().contains(sketch.names["trace"]));
+    sketch.move_point("B", vec2![1., -1.]);
+    on_parabola(&sketch, 2.0);
   }
 }
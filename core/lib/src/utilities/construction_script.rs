@@ -0,0 +1,923 @@
+use crate::{
+  components::{labels::Label, symbolics::*},
+  events::*,
+  math::*,
+  utilities::*,
+};
+use specs::prelude::*;
+use std::{collections::HashMap, fmt};
+
+/// A construction written as text, one statement per line or separated by `;`. Numbers may
+/// have an exponent, and the arguments between parentheses may span several lines:
+///
+/// ```text
+/// A = free(0, 0); B = free(3, 1e-1)
+/// c = circle(A, B)
+/// l = perp(AB, A)      # AB is the line through A and B
+/// P = intersect(c, l,
+///               1)
+/// ```
+///
+/// Compiling a script gives the commands inserting its elements, and every named element gets
+/// its name as label. They are recorded in the history like any other insertion, and `write`
+/// makes them a single transaction so that the whole script is undone at once.
+pub struct CompiledScript {
+  pub commands: Vec<CommandEvent>,
+  pub names: HashMap<String, Entity>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptError {
+  pub line: usize,   // Starting from 1
+  pub column: usize, // Starting from 1
+  pub kind: ScriptErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptErrorKind {
+  UnexpectedCharacter(char),
+  UnexpectedToken(String, &'static str), // Found, expected
+  UnknownFunction(String),
+  UnknownName(String),
+  AmbiguousName(String),
+  Redefined(String),
+  WrongKind(String, &'static str), // Name, expected kind
+  WrongArgumentCount(String, usize),
+  InvalidIntersection(f64),
+}
+
+impl fmt::Display for ScriptError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}:{}: ", self.line, self.column)?;
+    match &self.kind {
+      ScriptErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+      ScriptErrorKind::UnexpectedToken(found, expected) => write!(f, "expected {}, found {}", expected, found),
+      ScriptErrorKind::UnknownFunction(name) => write!(f, "unknown function {}", name),
+      ScriptErrorKind::UnknownName(name) => write!(f, "{} is not defined", name),
+      ScriptErrorKind::AmbiguousName(name) => write!(f, "{} can be read as more than one line", name),
+      ScriptErrorKind::Redefined(name) => write!(f, "{} is already defined", name),
+      ScriptErrorKind::WrongKind(name, expected) => write!(f, "{} is not a {}", name, expected),
+      ScriptErrorKind::WrongArgumentCount(function, count) => {
+        write!(f, "{} does not take {} arguments", function, count)
+      }
+      ScriptErrorKind::InvalidIntersection(n) => write!(f, "intersection {} should be 1 or 2", n),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Name(String),
+  Number(f64),
+  LeftParen,
+  RightParen,
+  Comma,
+  Equal,
+  Separator, // `;` or a new line
+  End,
+}
+
+impl fmt::Display for Token {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Token::Name(name) => write!(f, "'{}'", name),
+      Token::Number(number) => write!(f, "{}", number),
+      Token::LeftParen => write!(f, "'('"),
+      Token::RightParen => write!(f, "')'"),
+      Token::Comma => write!(f, "','"),
+      Token::Equal => write!(f, "'='"),
+      Token::Separator => write!(f, "end of statement"),
+      Token::End => write!(f, "end of script"),
+    }
+  }
+}
+
+/// A token and where it starts
+struct Spanned<T> {
+  value: T,
+  line: usize,
+  column: usize,
+}
+
+impl<T> Spanned<T> {
+  fn error(&self, kind: ScriptErrorKind) -> ScriptError {
+    ScriptError {
+      line: self.line,
+      column: self.column,
+      kind,
+    }
+  }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Spanned<Token>>, ScriptError> {
+  let mut tokens = vec![];
+  let (mut line, mut column) = (1, 1);
+  let mut chars = source.chars().peekable();
+  let mut depth = 0; // Of parentheses, new lines inside them don't end the statement
+  while let Some(&c) = chars.peek() {
+    let (start_line, start_column) = (line, column);
+    let value = if c == '#' {
+      // Comments go until the end of the line
+      while matches!(chars.peek(), Some(&c) if c != '\n') {
+        chars.next();
+        column += 1;
+      }
+      continue;
+    } else if c == '\n' {
+      chars.next();
+      line += 1;
+      column = 1;
+      if depth == 0 {
+        tokens.push(Spanned {
+          value: Token::Separator,
+          line: start_line,
+          column: start_column,
+        });
+      }
+      continue;
+    } else if c.is_whitespace() {
+      chars.next();
+      column += 1;
+      continue;
+    } else if c.is_ascii_alphabetic() || c == '_' {
+      let mut name = String::new();
+      while let Some(&c) = chars
+        .peek()
+        .filter(|c| c.is_ascii_alphanumeric() || **c == '_' || **c == '\'')
+      {
+        name.push(c);
+        chars.next();
+        column += 1;
+      }
+      Token::Name(name)
+    } else if c.is_ascii_digit() || c == '-' || c == '.' {
+      let mut number = String::new();
+      while let Some(&c) = chars.peek() {
+        let after_exponent = number.ends_with(&['e', 'E'][..]);
+        let is_part = c.is_ascii_digit()
+          || c == '.'
+          || (c == '-' && number.is_empty())
+          || ((c == 'e' || c == 'E') && !number.is_empty())
+          || ((c == '-' || c == '+') && after_exponent);
+        if !is_part {
+          break;
+        }
+        number.push(c);
+        chars.next();
+        column += 1;
+      }
+      match number.parse() {
+        Ok(number) => Token::Number(number),
+        Err(_) => {
+          return Err(ScriptError {
+            line: start_line,
+            column: start_column,
+            kind: ScriptErrorKind::UnexpectedToken(format!("'{}'", number), "a number"),
+          })
+        }
+      }
+    } else {
+      chars.next();
+      column += 1;
+      match c {
+        '(' => {
+          depth += 1;
+          Token::LeftParen
+        }
+        ')' => {
+          depth = (depth - 1).max(0);
+          Token::RightParen
+        }
+        ',' => Token::Comma,
+        '=' => Token::Equal,
+        ';' => Token::Separator,
+        _ => {
+          return Err(ScriptError {
+            line: start_line,
+            column: start_column,
+            kind: ScriptErrorKind::UnexpectedCharacter(c),
+          })
+        }
+      }
+    };
+    tokens.push(Spanned {
+      value,
+      line: start_line,
+      column: start_column,
+    });
+  }
+  tokens.push(Spanned {
+    value: Token::End,
+    line,
+    column,
+  });
+  Ok(tokens)
+}
+
+/// `name = function(arguments)`, the name being optional
+struct Statement {
+  name: Option<Spanned<String>>,
+  function: Spanned<String>,
+  arguments: Vec<Spanned<Token>>, // Names and numbers only
+}
+
+fn parse(tokens: Vec<Spanned<Token>>) -> Result<Vec<Statement>, ScriptError> {
+  let mut statements = vec![];
+  let mut tokens = tokens.into_iter();
+  let mut next = || tokens.next().expect("the tokens always finish with the end");
+  loop {
+    let first = next();
+    let (name, function) = match first.value {
+      Token::Separator => continue,
+      Token::End => break,
+      Token::Name(first_name) => {
+        let second = next();
+        match second.value {
+          Token::Equal => match next() {
+            Spanned {
+              value: Token::Name(function),
+              line,
+              column,
+            } => (
+              Some(Spanned {
+                value: first_name,
+                line: first.line,
+                column: first.column,
+              }),
+              Spanned {
+                value: function,
+                line,
+                column,
+              },
+            ),
+            token => return Err(unexpected(token, "a function")),
+          },
+          Token::LeftParen => {
+            statements.push(Statement {
+              name: None,
+              function: Spanned {
+                value: first_name,
+                line: first.line,
+                column: first.column,
+              },
+              arguments: parse_arguments(&mut next)?,
+            });
+            if end_of_statement(next())? {
+              break;
+            }
+            continue;
+          }
+          _ => return Err(unexpected(second, "'=' or '('")),
+        }
+      }
+      _ => return Err(unexpected(first, "a name")),
+    };
+    let left_paren = next();
+    if left_paren.value != Token::LeftParen {
+      return Err(unexpected(left_paren, "'('"));
+    }
+    statements.push(Statement {
+      name,
+      function,
+      arguments: parse_arguments(&mut next)?,
+    });
+    if end_of_statement(next())? {
+      break;
+    }
+  }
+  Ok(statements)
+}
+
+/// The arguments after the opening parenthesis, up to the closing one
+fn parse_arguments<F>(next: &mut F) -> Result<Vec<Spanned<Token>>, ScriptError>
+where
+  F: FnMut() -> Spanned<Token>,
+{
+  let mut arguments = vec![];
+  let token = next();
+  if token.value == Token::RightParen {
+    return Ok(arguments);
+  }
+  let mut argument = token;
+  loop {
+    match argument.value {
+      Token::Name(_) | Token::Number(_) => arguments.push(argument),
+      _ => return Err(unexpected(argument, "a name or a number")),
+    }
+    let token = next();
+    match token.value {
+      Token::Comma => argument = next(),
+      Token::RightParen => return Ok(arguments),
+      _ => return Err(unexpected(token, "',' or ')'")),
+    }
+  }
+}
+
+/// Whether the statement was the last one
+fn end_of_statement(token: Spanned<Token>) -> Result<bool, ScriptError> {
+  match token.value {
+    Token::Separator => Ok(false),
+    Token::End => Ok(true),
+    _ => Err(unexpected(token, "';' or a new line")),
+  }
+}
+
+fn unexpected(token: Spanned<Token>, expected: &'static str) -> ScriptError {
+  token.error(ScriptErrorKind::UnexpectedToken(token.value.to_string(), expected))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Kind {
+  Point,
+  Line,
+  Circle,
+  Locus,
+  Arc,
+  Conic,
+}
+
+impl Kind {
+  fn name(self) -> &'static str {
+    match self {
+      Kind::Point => "point",
+      Kind::Line => "line",
+      Kind::Circle => "circle",
+      Kind::Locus => "locus",
+      Kind::Arc => "arc",
+      Kind::Conic => "conic",
+    }
+  }
+}
+
+struct Compiler<F: FnMut() -> Entity> {
+  create_entity: F,
+  styles: FileDefaultStyles,
+  elements: HashMap<String, (Entity, Kind)>,
+  commands: Vec<CommandEvent>,
+}
+
+impl<F: FnMut() -> Entity> Compiler<F> {
+  fn compile(&mut self, statement: &Statement) -> Result<(), ScriptError> {
+    if let Some(name) = &statement.name {
+      if self.elements.contains_key(&name.value) {
+        return Err(name.error(ScriptErrorKind::Redefined(name.value.clone())));
+      }
+    }
+    let args = &statement.arguments;
+    let count_error = || {
+      statement.function.error(ScriptErrorKind::WrongArgumentCount(
+        statement.function.value.clone(),
+        args.len(),
+      ))
+    };
+    let geometry = match (statement.function.value.as_str(), args.len()) {
+      ("free", 2) => GeometrySymbol::Point(SymbolicPoint::Free(self.position(&args[0], &args[1])?)),
+      ("fixed", 2) => GeometrySymbol::Point(SymbolicPoint::Fixed(self.position(&args[0], &args[1])?)),
+      ("midpoint", 2) => GeometrySymbol::Point(SymbolicPoint::MidPoint(self.point(&args[0])?, self.point(&args[1])?)),
+      ("on_line", 2) => GeometrySymbol::Point(SymbolicPoint::OnLine(
+        self.line(&args[0])?,
+        VirtualScalar(number(&args[1])?),
+      )),
+      ("on_circle", 2) => GeometrySymbol::Point(SymbolicPoint::OnCircle(self.circle(&args[0])?, number(&args[1])?)),
+      ("on_arc", 2) => GeometrySymbol::Point(SymbolicPoint::OnArc(self.arc(&args[0])?, number(&args[1])?)),
+      ("intersect", 2) | ("intersect", 3) => {
+        let id = match args.get(2) {
+          Some(arg) => intersect_id(arg)?,
+          None => CircleIntersectId::First,
+        };
+        match (self.kind(&args[0])?, self.kind(&args[1])?) {
+          (Kind::Line, Kind::Line) if args.len() == 2 => GeometrySymbol::Point(SymbolicPoint::LineLineIntersect(
+            self.line(&args[0])?,
+            self.line(&args[1])?,
+          )),
+          (Kind::Line, Kind::Line) => return Err(count_error()),
+          (Kind::Circle, Kind::Line) => GeometrySymbol::Point(SymbolicPoint::CircleLineIntersect(
+            self.circle(&args[0])?,
+            self.line(&args[1])?,
+            id,
+          )),
+          (Kind::Line, Kind::Circle) => GeometrySymbol::Point(SymbolicPoint::CircleLineIntersect(
+            self.circle(&args[1])?,
+            self.line(&args[0])?,
+            id,
+          )),
+          (Kind::Circle, Kind::Circle) => GeometrySymbol::Point(SymbolicPoint::CircleCircleIntersect(
+            self.circle(&args[0])?,
+            self.circle(&args[1])?,
+            id,
+          )),
+          (Kind::Arc, Kind::Line) => GeometrySymbol::Point(SymbolicPoint::ArcLineIntersect(
+            self.arc(&args[0])?,
+            self.line(&args[1])?,
+            id,
+          )),
+          (Kind::Line, Kind::Arc) => GeometrySymbol::Point(SymbolicPoint::ArcLineIntersect(
+            self.arc(&args[1])?,
+            self.line(&args[0])?,
+            id,
+          )),
+          (Kind::Arc, Kind::Circle) => GeometrySymbol::Point(SymbolicPoint::ArcCircleIntersect(
+            self.arc(&args[0])?,
+            self.circle(&args[1])?,
+            id,
+          )),
+          (Kind::Circle, Kind::Arc) => GeometrySymbol::Point(SymbolicPoint::ArcCircleIntersect(
+            self.arc(&args[1])?,
+            self.circle(&args[0])?,
+            id,
+          )),
+          (Kind::Arc, Kind::Arc) => return Err(wrong_kind(&args[1], "line or circle")),
+          (Kind::Conic, Kind::Line) => GeometrySymbol::Point(SymbolicPoint::ConicLineIntersect(
+            self.conic(&args[0])?,
+            self.line(&args[1])?,
+            id,
+          )),
+          (Kind::Line, Kind::Conic) => GeometrySymbol::Point(SymbolicPoint::ConicLineIntersect(
+            self.conic(&args[1])?,
+            self.line(&args[0])?,
+            id,
+          )),
+          (Kind::Conic, _) => return Err(wrong_kind(&args[1], "line")),
+          (_, Kind::Conic) => return Err(wrong_kind(&args[0], "line")),
+          (Kind::Point, _) | (Kind::Locus, _) => return Err(wrong_kind(&args[0], "line or circle")),
+          (_, Kind::Point) | (_, Kind::Locus) => return Err(wrong_kind(&args[1], "line or circle")),
+        }
+      }
+      ("line", 2) => GeometrySymbol::Line(SymbolicLine::Straight(self.point(&args[0])?, self.point(&args[1])?)),
+      ("ray", 2) => GeometrySymbol::Line(SymbolicLine::Ray(self.point(&args[0])?, self.point(&args[1])?)),
+      ("segment", 2) => GeometrySymbol::Line(SymbolicLine::Segment(self.point(&args[0])?, self.point(&args[1])?)),
+      ("parallel", 2) => GeometrySymbol::Line(SymbolicLine::Parallel(self.line(&args[0])?, self.point(&args[1])?)),
+      ("perp", 2) => GeometrySymbol::Line(SymbolicLine::Perpendicular(self.line(&args[0])?, self.point(&args[1])?)),
+      ("angle_bisector", 3) => GeometrySymbol::Line(SymbolicLine::AngleBisector(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+        self.point(&args[2])?,
+      )),
+      ("perp_bisector", 2) => GeometrySymbol::Line(SymbolicLine::PerpendicularBisector(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+      )),
+      ("tangent", 2) => GeometrySymbol::Line(SymbolicLine::TangentAt(self.circle(&args[0])?, self.point(&args[1])?)),
+      ("tangent", 3) => GeometrySymbol::Line(SymbolicLine::TangentFromPoint(
+        self.circle(&args[0])?,
+        self.point(&args[1])?,
+        intersect_id(&args[2])?,
+      )),
+      ("circle", 2) => GeometrySymbol::Circle(SymbolicCircle::CenterRadius(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+      )),
+      ("circle", 3) => GeometrySymbol::Circle(SymbolicCircle::ThreePoints(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+        self.point(&args[2])?,
+      )),
+      ("compass", 3) => GeometrySymbol::Circle(SymbolicCircle::Compass(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+        self.point(&args[2])?,
+      )),
+      ("centroid", 3) => GeometrySymbol::Point(self.triangle(args, SymbolicPoint::Centroid)?),
+      ("circumcenter", 3) => GeometrySymbol::Point(self.triangle(args, SymbolicPoint::Circumcenter)?),
+      ("orthocenter", 3) => GeometrySymbol::Point(self.triangle(args, SymbolicPoint::Orthocenter)?),
+      ("incenter", 3) => GeometrySymbol::Point(self.triangle(args, SymbolicPoint::Incenter)?),
+      ("nine_point_center", 3) => GeometrySymbol::Point(self.triangle(args, SymbolicPoint::NinePointCenter)?),
+      ("incircle", 3) => GeometrySymbol::Circle(self.triangle(args, SymbolicCircle::Incircle)?),
+      ("excircle", 3) => GeometrySymbol::Circle(self.triangle(args, SymbolicCircle::Excircle)?),
+      ("nine_point_circle", 3) => GeometrySymbol::Circle(self.triangle(args, SymbolicCircle::NinePointCircle)?),
+      ("locus", 2) => GeometrySymbol::Locus(SymbolicLocus::Traced(self.point(&args[0])?, self.point(&args[1])?)),
+      ("arc", 3) => self.arc_symbol(args, false, ArcKind::Arc)?,
+      ("circular_segment", 3) => self.arc_symbol(args, false, ArcKind::Segment)?,
+      ("center_arc", 3) => self.arc_symbol(args, true, ArcKind::Arc)?,
+      ("sector", 3) => self.arc_symbol(args, true, ArcKind::Sector)?,
+      ("conic", 5) => GeometrySymbol::Conic(SymbolicConic::FivePoints(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+        self.point(&args[2])?,
+        self.point(&args[3])?,
+        self.point(&args[4])?,
+      )),
+      ("ellipse", 3) => GeometrySymbol::Conic(SymbolicConic::FociPoint(
+        self.point(&args[0])?,
+        self.point(&args[1])?,
+        self.point(&args[2])?,
+      )),
+      ("parabola", 2) => GeometrySymbol::Conic(SymbolicConic::FocusDirectrix(
+        self.point(&args[0])?,
+        self.line(&args[1])?,
+      )),
+      (
+        "free" | "fixed" | "midpoint" | "on_line" | "on_circle" | "on_arc" | "intersect" | "line" | "ray" | "segment"
+        | "parallel" | "perp" | "angle_bisector" | "perp_bisector" | "tangent" | "circle" | "compass" | "locus" | "arc"
+        | "circular_segment" | "center_arc" | "sector" | "conic" | "ellipse" | "parabola" | "centroid" | "circumcenter"
+        | "orthocenter" | "incenter" | "nine_point_center" | "incircle" | "excircle" | "nine_point_circle",
+        _,
+      ) => return Err(count_error()),
+      (function, _) => {
+        return Err(
+          statement
+            .function
+            .error(ScriptErrorKind::UnknownFunction(function.to_string())),
+        )
+      }
+    };
+    let kind = match geometry {
+      GeometrySymbol::Point(_) => Kind::Point,
+      GeometrySymbol::Line(_) => Kind::Line,
+      GeometrySymbol::Locus(_) => Kind::Locus,
+      GeometrySymbol::Arc(_) => Kind::Arc,
+      GeometrySymbol::Conic(_) => Kind::Conic,
+      _ => Kind::Circle,
+    };
+    let ent = self.insert(geometry);
+    if let Some(name) = &statement.name {
+      self.commands.push(CommandEvent {
+        command: Command::Label(LabelEvent::SetLabel(ent, Label::new(name.value.clone()))),
+        event_id: None,
+      });
+      self.elements.insert(name.value.clone(), (ent, kind));
+    }
+    Ok(())
+  }
+
+  fn insert(&mut self, geometry: GeometrySymbol) -> Entity {
+    let ent = (self.create_entity)();
+    let command = match geometry {
+      GeometrySymbol::Point(sym_point) => Command::PointInsert(InsertPointEvent::InsertPointWithEntity(
+        ent,
+        sym_point,
+        self.styles.point,
+      )),
+      GeometrySymbol::Line(sym_line) => {
+        Command::LineInsert(InsertLineEvent::InsertLineWithEntity(ent, sym_line, self.styles.line))
+      }
+      GeometrySymbol::Circle(sym_circle) => Command::CircleInsert(InsertCircleEvent::InsertCircleWithEntity(
+        ent,
+        sym_circle,
+        self.styles.circle,
+      )),
+      GeometrySymbol::Polygon(sym_polygon) => Command::PolygonInsert(InsertPolygonEvent::InsertPolygonWithEntity(
+        ent,
+        sym_polygon,
+        self.styles.polygon,
+      )),
+      GeometrySymbol::Measurement(sym_measurement) => Command::MeasurementInsert(
+        InsertMeasurementEvent::InsertMeasurementWithEntity(ent, sym_measurement),
+      ),
+      GeometrySymbol::Locus(sym_locus) => Command::LocusInsert(InsertLocusEvent::InsertLocusWithEntity(
+        ent,
+        sym_locus,
+        self.styles.line,
+      )),
+      GeometrySymbol::Arc(sym_arc) => {
+        Command::ArcInsert(InsertArcEvent::InsertArcWithEntity(ent, sym_arc, self.styles.polygon))
+      }
+      GeometrySymbol::Conic(sym_conic) => Command::ConicInsert(InsertConicEvent::InsertConicWithEntity(
+        ent,
+        sym_conic,
+        self.styles.line,
+      )),
+    };
+    self.commands.push(CommandEvent {
+      command,
+      event_id: None,
+    });
+    ent
+  }
+
+  /// The kind of a named element. A name made of two point names, like `AB`, is the line
+  /// through them
+  fn kind(&self, arg: &Spanned<Token>) -> Result<Kind, ScriptError> {
+    match &arg.value {
+      Token::Name(name) => match self.elements.get(name) {
+        Some((_, kind)) => Ok(*kind),
+        None => self.split_points(arg, name).map(|_| Kind::Line),
+      },
+      _ => Err(wrong_kind(arg, "line or circle")),
+    }
+  }
+
+  fn element(&self, arg: &Spanned<Token>, expected: Kind) -> Result<Entity, ScriptError> {
+    match &arg.value {
+      Token::Name(name) => match self.elements.get(name) {
+        Some((ent, kind)) if *kind == expected => Ok(*ent),
+        Some(_) => Err(wrong_kind(arg, expected.name())),
+        None => Err(arg.error(ScriptErrorKind::UnknownName(name.clone()))),
+      },
+      _ => Err(wrong_kind(arg, expected.name())),
+    }
+  }
+
+  fn point(&self, arg: &Spanned<Token>) -> Result<Entity, ScriptError> {
+    self.element(arg, Kind::Point)
+  }
+
+  fn circle(&self, arg: &Spanned<Token>) -> Result<Entity, ScriptError> {
+    self.element(arg, Kind::Circle)
+  }
+
+  fn arc(&self, arg: &Spanned<Token>) -> Result<Entity, ScriptError> {
+    self.element(arg, Kind::Arc)
+  }
+
+  fn conic(&self, arg: &Spanned<Token>) -> Result<Entity, ScriptError> {
+    self.element(arg, Kind::Conic)
+  }
+
+  /// An arc from three points, the first one being the center when `from_center` is set
+  fn arc_symbol(
+    &self,
+    args: &[Spanned<Token>],
+    from_center: bool,
+    kind: ArcKind,
+  ) -> Result<GeometrySymbol, ScriptError> {
+    let (p1, p2, p3) = (self.point(&args[0])?, self.point(&args[1])?, self.point(&args[2])?);
+    Ok(GeometrySymbol::Arc(if from_center {
+      SymbolicArc::CenterFromTo(p1, p2, p3, kind)
+    } else {
+      SymbolicArc::ThreePoints(p1, p2, p3, kind)
+    }))
+  }
+
+  /// A center or a circle of the triangle of three points. An excircle is the one opposite to the
+  /// first point
+  fn triangle<T, S: FnOnce(Entity, Entity, Entity) -> T>(
+    &self,
+    args: &[Spanned<Token>],
+    symbol: S,
+  ) -> Result<T, ScriptError> {
+    Ok(symbol(
+      self.point(&args[0])?,
+      self.point(&args[1])?,
+      self.point(&args[2])?,
+    ))
+  }
+
+  /// A named line, or the hidden line through two named points. The same two points always
+  /// give the same line
+  fn line(&mut self, arg: &Spanned<Token>) -> Result<Entity, ScriptError> {
+    if let Token::Name(name) = &arg.value {
+      if !self.elements.contains_key(name) {
+        let (p1, p2) = self.split_points(arg, name)?;
+        let ent = self.insert(GeometrySymbol::Line(SymbolicLine::Straight(p1, p2)));
+        self.commands.push(CommandEvent {
+          command: Command::Hide(HideEvent::Hide(ent)),
+          event_id: None,
+        });
+        self.elements.insert(name.clone(), (ent, Kind::Line));
+        return Ok(ent);
+      }
+    }
+    self.element(arg, Kind::Line)
+  }
+
+  fn split_points(&self, arg: &Spanned<Token>, name: &str) -> Result<(Entity, Entity), ScriptError> {
+    let points = (1..name.len())
+      .filter(|&i| name.is_char_boundary(i))
+      .filter_map(
+        |i| match (self.elements.get(&name[..i]), self.elements.get(&name[i..])) {
+          (Some((p1, Kind::Point)), Some((p2, Kind::Point))) if p1 != p2 => Some((*p1, *p2)),
+          _ => None,
+        },
+      )
+      .collect::<Vec<_>>();
+    match points.as_slice() {
+      [points] => Ok(*points),
+      [] => Err(arg.error(ScriptErrorKind::UnknownName(name.to_string()))),
+      _ => Err(arg.error(ScriptErrorKind::AmbiguousName(name.to_string()))),
+    }
+  }
+
+  fn position(&self, x: &Spanned<Token>, y: &Spanned<Token>) -> Result<VirtualPosition, ScriptError> {
+    Ok(VirtualPosition(vec2![number(x)?, number(y)?]))
+  }
+}
+
+fn number(arg: &Spanned<Token>) -> Result<f64, ScriptError> {
+  match arg.value {
+    Token::Number(number) => Ok(number),
+    _ => Err(unexpected_argument(arg, "a number")),
+  }
+}
+
+/// Intersections are numbered from 1 in scripts
+fn intersect_id(arg: &Spanned<Token>) -> Result<CircleIntersectId, ScriptError> {
+  let n = number(arg)?;
+  if n == 1.0 {
+    Ok(CircleIntersectId::First)
+  } else if n == 2.0 {
+    Ok(CircleIntersectId::Second)
+  } else {
+    Err(arg.error(ScriptErrorKind::InvalidIntersection(n)))
+  }
+}
+
+fn wrong_kind(arg: &Spanned<Token>, expected: &'static str) -> ScriptError {
+  match &arg.value {
+    Token::Name(name) => arg.error(ScriptErrorKind::WrongKind(name.clone(), expected)),
+    _ => unexpected_argument(arg, expected),
+  }
+}
+
+fn unexpected_argument(arg: &Spanned<Token>, expected: &'static str) -> ScriptError {
+  arg.error(ScriptErrorKind::UnexpectedToken(arg.value.to_string(), expected))
+}
+
+impl CompiledScript {
+  /// Compile a script, calling `create_entity` once per inserted element. The elements get
+  /// the default styles. When there's an error, the entities already created stay empty
+  pub fn compile<F>(source: &str, styles: FileDefaultStyles, create_entity: F) -> Result<Self, ScriptError>
+  where
+    F: FnMut() -> Entity,
+  {
+    let statements = parse(tokenize(source)?)?;
+    let mut compiler = Compiler {
+      create_entity,
+      styles,
+      elements: HashMap::new(),
+      commands: vec![],
+    };
+    for statement in &statements {
+      compiler.compile(statement)?;
+    }
+
+    // Inserting selects the element, so deselect everything afterwards
+    compiler.commands.push(CommandEvent {
+      command: Command::Select(SelectEvent::DeselectAll),
+      event_id: None,
+    });
+    Ok(Self {
+      commands: compiler.commands,
+      names: compiler
+        .elements
+        .into_iter()
+        .map(|(name, (ent, _))| (name, ent))
+        .collect(),
+    })
+  }
+
+  /// Write the commands inserting the script as one transaction of the history. Gives the
+  /// entity of every name
+  pub fn write(
+    self,
+    command_event_channel: &mut CommandEventChannel,
+    history_event_channel: &mut HistoryEventChannel,
+  ) -> HashMap<String, Entity> {
+    history_event_channel.single_write(HistoryEvent::BeginTransaction);
+    command_event_channel.iter_write(self.commands);
+    history_event_channel.single_write(HistoryEvent::CommitTransaction);
+    self.names
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::resources::*;
+
+  fn compile(source: &str) -> Result<CompiledScript, ScriptError> {
+    let styles = FileDefaultStyles {
+      point: DefaultPointStyle::default().get(),
+      line: DefaultLineStyle::default().get(),
+      circle: DefaultCircleStyle::default().get(),
+      polygon: DefaultPolygonStyle::default().get(),
+    };
+    let mut world = World::new();
+    CompiledScript::compile(source, styles, || world.create_entity().build())
+  }
+
+  #[test]
+  fn test_compile() {
+    let script =
+      compile("A = free(0,0); B = free(3,1); c = circle(A,B)\nl = perp(AB, A) # Through A\nP = intersect(c, l, 2)")
+        .unwrap();
+    let (a, b, c, l, ab) = (
+      script.names["A"],
+      script.names["B"],
+      script.names["c"],
+      script.names["l"],
+      script.names["AB"],
+    );
+    let inserted = script
+      .commands
+      .iter()
+      .filter_map(|event| match &event.command {
+        Command::PointInsert(InsertPointEvent::InsertPointWithEntity(ent, sym, _)) => {
+          Some((*ent, GeometrySymbol::Point(*sym)))
+        }
+        Command::LineInsert(InsertLineEvent::InsertLineWithEntity(ent, sym, _)) => {
+          Some((*ent, GeometrySymbol::Line(*sym)))
+        }
+        Command::CircleInsert(InsertCircleEvent::InsertCircleWithEntity(ent, sym, _)) => {
+          Some((*ent, GeometrySymbol::Circle(*sym)))
+        }
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(inserted.len(), 6);
+    match inserted[2].1 {
+      GeometrySymbol::Circle(SymbolicCircle::CenterRadius(center, p)) => assert_eq!((center, p), (a, b)),
+      _ => panic!("expected a circle"),
+    }
+    match inserted[3].1 {
+      GeometrySymbol::Line(SymbolicLine::Straight(p1, p2)) => assert_eq!((inserted[3].0, p1, p2), (ab, a, b)),
+      _ => panic!("expected the line AB"),
+    }
+    match inserted[4].1 {
+      GeometrySymbol::Line(SymbolicLine::Perpendicular(line, p)) => assert_eq!((line, p), (ab, a)),
+      _ => panic!("expected a perpendicular"),
+    }
+    match inserted[5].1 {
+      GeometrySymbol::Point(SymbolicPoint::CircleLineIntersect(circle, line, CircleIntersectId::Second)) => {
+        assert_eq!((circle, line), (c, l))
+      }
+      _ => panic!("expected an intersection"),
+    }
+
+    // The line AB is hidden, and only the named elements are labelled
+    let hidden = script
+      .commands
+      .iter()
+      .filter(|event| matches!(event.command, Command::Hide(HideEvent::Hide(ent)) if ent == ab))
+      .count();
+    assert_eq!(hidden, 1);
+    let labels = script
+      .commands
+      .iter()
+      .filter(|event| matches!(event.command, Command::Label(LabelEvent::SetLabel(_, _))))
+      .count();
+    assert_eq!(labels, 5);
+  }
+
+  #[test]
+  fn test_parse() {
+    let positions = |source: &str| {
+      compile(source)
+        .unwrap()
+        .commands
+        .into_iter()
+        .filter_map(|event| match event.command {
+          Command::PointInsert(InsertPointEvent::InsertPointWithEntity(_, SymbolicPoint::Free(p), _)) => Some(p.0),
+          _ => None,
+        })
+        .collect::<Vec<_>>()
+    };
+
+    // Numbers may have an exponent
+    assert_eq!(
+      positions("A = free(1e2, -2.5E-1); B = free(3e+1, .5)"),
+      vec![vec2![100.0, -0.25], vec2![30.0, 0.5]]
+    );
+
+    // The arguments may span several lines, which still separate the statements outside of them
+    assert_eq!(
+      positions("A = free(\n  1,\n  2\n)\nB = free(3,\n 4) # Comment\n"),
+      vec![vec2![1.0, 2.0], vec2![3.0, 4.0]]
+    );
+    let script = compile("A = free(0, 0)\nB = free(1, 0)\nc = circle(\n  A,\n  B)\nP = intersect(c, AB,\n 2)").unwrap();
+    assert_eq!(script.names.len(), 5);
+  }
+
+  #[test]
+  fn test_errors() {
+    let error = |source: &str| compile(source).err().map(|err| (err.line, err.column, err.kind));
+    assert_eq!(
+      error("A = free(0, 0)\nB = midpoint(A, C)"),
+      Some((2, 17, ScriptErrorKind::UnknownName("C".to_string())))
+    );
+    assert_eq!(
+      error("A = free(0, 0)\nc = circle(A, 1)"),
+      Some((2, 15, ScriptErrorKind::UnexpectedToken("1".to_string(), "point")))
+    );
+    assert_eq!(
+      error("A = free(0 0)"),
+      Some((1, 12, ScriptErrorKind::UnexpectedToken("0".to_string(), "',' or ')'")))
+    );
+    assert_eq!(
+      error("A = free(0, 0); A = free(1, 1)"),
+      Some((1, 17, ScriptErrorKind::Redefined("A".to_string())))
+    );
+    assert_eq!(
+      error("A = fre(0, 0)"),
+      Some((1, 5, ScriptErrorKind::UnknownFunction("fre".to_string())))
+    );
+    assert_eq!(
+      error("A = free(0, 0)\n  l = line(A, A) $"),
+      Some((2, 18, ScriptErrorKind::UnexpectedCharacter('$')))
+    );
+    assert_eq!(
+      error("A = free(0, 0); B = free(1, 0); c = circle(A, B); P = intersect(c, A)"),
+      Some((1, 68, ScriptErrorKind::WrongKind("A".to_string(), "line or circle")))
+    );
+    assert_eq!(
+      error("A = free(1e, 0)"),
+      Some((1, 10, ScriptErrorKind::UnexpectedToken("'1e'".to_string(), "a number")))
+    );
+    assert_eq!(
+      error("A = free(0,\n0\nB = free(1, 1)"),
+      Some((3, 1, ScriptErrorKind::UnexpectedToken("'B'".to_string(), "',' or ')'")))
+    );
+    let message = compile("A = free(0, 0)\nB = midpoint(A, C)").err().unwrap().to_string();
+    assert_eq!(message, "2:17: C is not defined");
+  }
+}
@@ -1,17 +1,27 @@
 use crate::components::{styles::*, symbolics::*};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum Geometry {
   Point(SymbolicPoint, PointStyle),
   Line(SymbolicLine, LineStyle),
   Circle(SymbolicCircle, CircleStyle),
+  Polygon(SymbolicPolygon, PolygonStyle),
+  Measurement(SymbolicMeasurement),
+  Locus(SymbolicLocus, LineStyle),
+  Arc(SymbolicArc, PolygonStyle), // The fill is only drawn for sectors and circular segments
+  Conic(SymbolicConic, LineStyle),
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub enum GeometrySymbol {
   Point(SymbolicPoint),
   Line(SymbolicLine),
   Circle(SymbolicCircle),
+  Polygon(SymbolicPolygon),
+  Measurement(SymbolicMeasurement),
+  Locus(SymbolicLocus),
+  Arc(SymbolicArc),
+  Conic(SymbolicConic),
 }
 
 impl Into<GeometrySymbol> for Geometry {
@@ -20,6 +30,11 @@ impl Into<GeometrySymbol> for Geometry {
       Geometry::Point(sym_point, _) => GeometrySymbol::Point(sym_point),
       Geometry::Line(sym_line, _) => GeometrySymbol::Line(sym_line),
       Geometry::Circle(sym_circle, _) => GeometrySymbol::Circle(sym_circle),
+      Geometry::Polygon(sym_polygon, _) => GeometrySymbol::Polygon(sym_polygon),
+      Geometry::Measurement(sym_measurement) => GeometrySymbol::Measurement(sym_measurement),
+      Geometry::Locus(sym_locus, _) => GeometrySymbol::Locus(sym_locus),
+      Geometry::Arc(sym_arc, _) => GeometrySymbol::Arc(sym_arc),
+      Geometry::Conic(sym_conic, _) => GeometrySymbol::Conic(sym_conic),
     }
   }
 }
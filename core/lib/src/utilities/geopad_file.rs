@@ -0,0 +1,878 @@
+use crate::{
+  components::{labels::*, styles::*, symbolics::*},
+  math::*,
+  resources::*,
+  utilities::*,
+};
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use std::{cell::RefCell, collections::HashMap, fmt, fs::File, io, path::Path};
+
+pub static GEOPAD_FILE_VERSION: u32 = 1;
+
+pub static GEOPAD_FILE_EXTENSION: &str = "geopad";
+
+#[derive(Debug)]
+pub enum GeopadFileError {
+  Io(io::Error),
+  Format(serde_json::Error),
+  UnsupportedVersion(u32),
+  InvalidReference(usize), // Index of the element holding the invalid reference
+  CyclicReference(usize),  // Index of an element depending on itself, maybe through others
+  MissingReference(Entity),
+}
+
+impl fmt::Display for GeopadFileError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      GeopadFileError::Io(err) => write!(f, "io error: {}", err),
+      GeopadFileError::Format(err) => write!(f, "malformed file: {}", err),
+      GeopadFileError::UnsupportedVersion(version) => write!(
+        f,
+        "unsupported file version {} (newest supported is {})",
+        version, GEOPAD_FILE_VERSION
+      ),
+      GeopadFileError::InvalidReference(index) => write!(f, "element {} references an invalid element", index),
+      GeopadFileError::CyclicReference(index) => write!(f, "element {} depends on itself", index),
+      GeopadFileError::MissingReference(ent) => write!(f, "element refers to {:?} which is not saved", ent),
+    }
+  }
+}
+
+impl From<io::Error> for GeopadFileError {
+  fn from(err: io::Error) -> Self {
+    GeopadFileError::Io(err)
+  }
+}
+
+impl From<serde_json::Error> for GeopadFileError {
+  fn from(err: serde_json::Error) -> Self {
+    GeopadFileError::Format(err)
+  }
+}
+
+/// The content of a `.geopad` file. Entities are not stable across sessions, so every
+/// reference to another element is stored as the index of that element in `elements`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeopadFile {
+  pub version: u32,
+  pub viewport: FileViewport,
+  pub default_styles: FileDefaultStyles,
+  pub elements: Vec<FileElement>,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct FileViewport {
+  pub virtual_center: Vector2,
+  pub virtual_size: Vector2,
+  pub screen_size: Vector2,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct FileDefaultStyles {
+  pub point: PointStyle,
+  pub line: LineStyle,
+  pub circle: CircleStyle,
+  #[serde(default = "default_polygon_style")]
+  pub polygon: PolygonStyle,
+}
+
+fn default_polygon_style() -> PolygonStyle {
+  DefaultPolygonStyle::default().get()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileElement {
+  pub geometry: FileGeometry,
+  pub hidden: bool,
+  #[serde(default)]
+  pub label: Option<Label>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FileGeometry {
+  Point(FilePoint, PointStyle),
+  Line(FileLine, LineStyle),
+  Circle(FileCircle, CircleStyle),
+  Polygon(FilePolygon, PolygonStyle),
+  Measurement(FileMeasurement),
+  Locus(FileLocus, LineStyle),
+  Arc(FileArc, PolygonStyle),
+  Conic(FileConic, LineStyle),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FilePoint {
+  Fixed(Vector2),
+  Free(Vector2),
+  MidPoint(usize, usize),
+  OnLine(usize, f64),
+  LineLineIntersect(usize, usize),
+  OnCircle(usize, f64),
+  CircleLineIntersect(usize, usize, CircleIntersectId),
+  CircleCircleIntersect(usize, usize, CircleIntersectId),
+  Transformed(usize, FileTransformation),
+  OnArc(usize, f64),
+  ArcLineIntersect(usize, usize, CircleIntersectId),
+  ArcCircleIntersect(usize, usize, CircleIntersectId),
+  ConicLineIntersect(usize, usize, CircleIntersectId),
+  Centroid(usize, usize, usize),
+  Circumcenter(usize, usize, usize),
+  Orthocenter(usize, usize, usize),
+  Incenter(usize, usize, usize),
+  NinePointCenter(usize, usize, usize),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileLine {
+  Straight(usize, usize),
+  Ray(usize, usize),
+  Segment(usize, usize),
+  Parallel(usize, usize),
+  Perpendicular(usize, usize),
+  AngleBisector(usize, usize, usize),
+  PerpendicularBisector(usize, usize),
+  TangentAt(usize, usize),
+  TangentFromPoint(usize, usize, CircleIntersectId),
+  Transformed(usize, FileTransformation),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileCircle {
+  CenterRadius(usize, usize),
+  ThreePoints(usize, usize, usize),
+  Compass(usize, usize, usize),
+  Transformed(usize, FileTransformation),
+  Incircle(usize, usize, usize),
+  Excircle(usize, usize, usize),
+  NinePointCircle(usize, usize, usize),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FilePolygon {
+  Vertices(Vec<usize>),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileMeasurement {
+  Distance(usize, usize),
+  Length(usize),
+  Angle(usize, usize, usize),
+  Area(usize),
+  Perimeter(usize),
+  Ratio(usize, usize),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileLocus {
+  Traced(usize, usize),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileArc {
+  ThreePoints(usize, usize, usize, ArcKind),
+  CenterFromTo(usize, usize, usize, ArcKind),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileConic {
+  FivePoints(usize, usize, usize, usize, usize),
+  FociPoint(usize, usize, usize),
+  FocusDirectrix(usize, usize),
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum FileTransformation {
+  Reflect(usize),
+  Rotate(usize, f64),
+  RotateByAngle(usize, usize, usize, usize),
+  Translate(usize, usize),
+  Dilate(usize, f64),
+}
+
+impl FileGeometry {
+  /// The geometry with every reference to another element replaced by its index
+  pub fn new<F>(geometry: Geometry, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match geometry {
+      Geometry::Point(sym_point, style) => FileGeometry::Point(FilePoint::new(sym_point, index)?, style),
+      Geometry::Line(sym_line, style) => FileGeometry::Line(FileLine::new(sym_line, index)?, style),
+      Geometry::Circle(sym_circle, style) => FileGeometry::Circle(FileCircle::new(sym_circle, index)?, style),
+      Geometry::Polygon(sym_polygon, style) => FileGeometry::Polygon(FilePolygon::new(sym_polygon, index)?, style),
+      Geometry::Measurement(sym_measurement) => {
+        FileGeometry::Measurement(FileMeasurement::new(sym_measurement, index)?)
+      }
+      Geometry::Locus(sym_locus, style) => FileGeometry::Locus(FileLocus::new(sym_locus, index)?, style),
+      Geometry::Arc(sym_arc, style) => FileGeometry::Arc(FileArc::new(sym_arc, index)?, style),
+      Geometry::Conic(sym_conic, style) => FileGeometry::Conic(FileConic::new(sym_conic, index)?, style),
+    })
+  }
+
+  /// The geometry with every index replaced by the entity of that element
+  pub fn geometry<F: Fn(usize) -> Entity>(&self, ent: &F) -> Geometry {
+    match self {
+      FileGeometry::Point(file_point, style) => Geometry::Point(file_point.symbolic(ent), *style),
+      FileGeometry::Line(file_line, style) => Geometry::Line(file_line.symbolic(ent), *style),
+      FileGeometry::Circle(file_circle, style) => Geometry::Circle(file_circle.symbolic(ent), *style),
+      FileGeometry::Polygon(file_polygon, style) => Geometry::Polygon(file_polygon.symbolic(ent), *style),
+      FileGeometry::Measurement(file_measurement) => Geometry::Measurement(file_measurement.symbolic(ent)),
+      FileGeometry::Locus(file_locus, style) => Geometry::Locus(file_locus.symbolic(ent), *style),
+      FileGeometry::Arc(file_arc, style) => Geometry::Arc(file_arc.symbolic(ent), *style),
+      FileGeometry::Conic(file_conic, style) => Geometry::Conic(file_conic.symbolic(ent), *style),
+    }
+  }
+}
+
+impl FilePoint {
+  pub fn new<F>(sym_point: SymbolicPoint, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_point {
+      SymbolicPoint::Fixed(pos) => FilePoint::Fixed(pos.0),
+      SymbolicPoint::Free(pos) => FilePoint::Free(pos.0),
+      SymbolicPoint::MidPoint(p1, p2) => FilePoint::MidPoint(index(p1)?, index(p2)?),
+      SymbolicPoint::OnLine(l, t) => FilePoint::OnLine(index(l)?, t.0),
+      SymbolicPoint::LineLineIntersect(l1, l2) => FilePoint::LineLineIntersect(index(l1)?, index(l2)?),
+      SymbolicPoint::OnCircle(c, theta) => FilePoint::OnCircle(index(c)?, theta),
+      SymbolicPoint::CircleLineIntersect(c, l, id) => FilePoint::CircleLineIntersect(index(c)?, index(l)?, id),
+      SymbolicPoint::CircleCircleIntersect(c1, c2, id) => FilePoint::CircleCircleIntersect(index(c1)?, index(c2)?, id),
+      SymbolicPoint::Transformed(p, trans) => FilePoint::Transformed(index(p)?, FileTransformation::new(trans, index)?),
+      SymbolicPoint::OnArc(a, fraction) => FilePoint::OnArc(index(a)?, fraction),
+      SymbolicPoint::ArcLineIntersect(a, l, id) => FilePoint::ArcLineIntersect(index(a)?, index(l)?, id),
+      SymbolicPoint::ArcCircleIntersect(a, c, id) => FilePoint::ArcCircleIntersect(index(a)?, index(c)?, id),
+      SymbolicPoint::ConicLineIntersect(c, l, id) => FilePoint::ConicLineIntersect(index(c)?, index(l)?, id),
+      SymbolicPoint::Centroid(p1, p2, p3) => FilePoint::Centroid(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicPoint::Circumcenter(p1, p2, p3) => FilePoint::Circumcenter(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicPoint::Orthocenter(p1, p2, p3) => FilePoint::Orthocenter(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicPoint::Incenter(p1, p2, p3) => FilePoint::Incenter(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicPoint::NinePointCenter(p1, p2, p3) => FilePoint::NinePointCenter(index(p1)?, index(p2)?, index(p3)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicPoint {
+    match self {
+      FilePoint::Fixed(pos) => SymbolicPoint::Fixed(pos.into()),
+      FilePoint::Free(pos) => SymbolicPoint::Free(pos.into()),
+      FilePoint::MidPoint(p1, p2) => SymbolicPoint::MidPoint(ent(p1), ent(p2)),
+      FilePoint::OnLine(l, t) => SymbolicPoint::OnLine(ent(l), t.into()),
+      FilePoint::LineLineIntersect(l1, l2) => SymbolicPoint::LineLineIntersect(ent(l1), ent(l2)),
+      FilePoint::OnCircle(c, theta) => SymbolicPoint::OnCircle(ent(c), theta),
+      FilePoint::CircleLineIntersect(c, l, id) => SymbolicPoint::CircleLineIntersect(ent(c), ent(l), id),
+      FilePoint::CircleCircleIntersect(c1, c2, id) => SymbolicPoint::CircleCircleIntersect(ent(c1), ent(c2), id),
+      FilePoint::Transformed(p, trans) => SymbolicPoint::Transformed(ent(p), trans.transformation(ent)),
+      FilePoint::OnArc(a, fraction) => SymbolicPoint::OnArc(ent(a), fraction),
+      FilePoint::ArcLineIntersect(a, l, id) => SymbolicPoint::ArcLineIntersect(ent(a), ent(l), id),
+      FilePoint::ArcCircleIntersect(a, c, id) => SymbolicPoint::ArcCircleIntersect(ent(a), ent(c), id),
+      FilePoint::ConicLineIntersect(c, l, id) => SymbolicPoint::ConicLineIntersect(ent(c), ent(l), id),
+      FilePoint::Centroid(p1, p2, p3) => SymbolicPoint::Centroid(ent(p1), ent(p2), ent(p3)),
+      FilePoint::Circumcenter(p1, p2, p3) => SymbolicPoint::Circumcenter(ent(p1), ent(p2), ent(p3)),
+      FilePoint::Orthocenter(p1, p2, p3) => SymbolicPoint::Orthocenter(ent(p1), ent(p2), ent(p3)),
+      FilePoint::Incenter(p1, p2, p3) => SymbolicPoint::Incenter(ent(p1), ent(p2), ent(p3)),
+      FilePoint::NinePointCenter(p1, p2, p3) => SymbolicPoint::NinePointCenter(ent(p1), ent(p2), ent(p3)),
+    }
+  }
+}
+
+impl FileLine {
+  pub fn new<F>(sym_line: SymbolicLine, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_line {
+      SymbolicLine::Straight(p1, p2) => FileLine::Straight(index(p1)?, index(p2)?),
+      SymbolicLine::Ray(p1, p2) => FileLine::Ray(index(p1)?, index(p2)?),
+      SymbolicLine::Segment(p1, p2) => FileLine::Segment(index(p1)?, index(p2)?),
+      SymbolicLine::Parallel(l, p) => FileLine::Parallel(index(l)?, index(p)?),
+      SymbolicLine::Perpendicular(l, p) => FileLine::Perpendicular(index(l)?, index(p)?),
+      SymbolicLine::AngleBisector(p1, v, p2) => FileLine::AngleBisector(index(p1)?, index(v)?, index(p2)?),
+      SymbolicLine::PerpendicularBisector(p1, p2) => FileLine::PerpendicularBisector(index(p1)?, index(p2)?),
+      SymbolicLine::TangentAt(c, p) => FileLine::TangentAt(index(c)?, index(p)?),
+      SymbolicLine::TangentFromPoint(c, p, ity) => FileLine::TangentFromPoint(index(c)?, index(p)?, ity),
+      SymbolicLine::Transformed(l, trans) => FileLine::Transformed(index(l)?, FileTransformation::new(trans, index)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicLine {
+    match self {
+      FileLine::Straight(p1, p2) => SymbolicLine::Straight(ent(p1), ent(p2)),
+      FileLine::Ray(p1, p2) => SymbolicLine::Ray(ent(p1), ent(p2)),
+      FileLine::Segment(p1, p2) => SymbolicLine::Segment(ent(p1), ent(p2)),
+      FileLine::Parallel(l, p) => SymbolicLine::Parallel(ent(l), ent(p)),
+      FileLine::Perpendicular(l, p) => SymbolicLine::Perpendicular(ent(l), ent(p)),
+      FileLine::AngleBisector(p1, v, p2) => SymbolicLine::AngleBisector(ent(p1), ent(v), ent(p2)),
+      FileLine::PerpendicularBisector(p1, p2) => SymbolicLine::PerpendicularBisector(ent(p1), ent(p2)),
+      FileLine::TangentAt(c, p) => SymbolicLine::TangentAt(ent(c), ent(p)),
+      FileLine::TangentFromPoint(c, p, ity) => SymbolicLine::TangentFromPoint(ent(c), ent(p), ity),
+      FileLine::Transformed(l, trans) => SymbolicLine::Transformed(ent(l), trans.transformation(ent)),
+    }
+  }
+}
+
+impl FileCircle {
+  pub fn new<F>(sym_circle: SymbolicCircle, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_circle {
+      SymbolicCircle::CenterRadius(c, p) => FileCircle::CenterRadius(index(c)?, index(p)?),
+      SymbolicCircle::ThreePoints(p1, p2, p3) => FileCircle::ThreePoints(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicCircle::Compass(c, p1, p2) => FileCircle::Compass(index(c)?, index(p1)?, index(p2)?),
+      SymbolicCircle::Transformed(c, trans) => {
+        FileCircle::Transformed(index(c)?, FileTransformation::new(trans, index)?)
+      }
+      SymbolicCircle::Incircle(p1, p2, p3) => FileCircle::Incircle(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicCircle::Excircle(p1, p2, p3) => FileCircle::Excircle(index(p1)?, index(p2)?, index(p3)?),
+      SymbolicCircle::NinePointCircle(p1, p2, p3) => FileCircle::NinePointCircle(index(p1)?, index(p2)?, index(p3)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicCircle {
+    match self {
+      FileCircle::CenterRadius(c, p) => SymbolicCircle::CenterRadius(ent(c), ent(p)),
+      FileCircle::ThreePoints(p1, p2, p3) => SymbolicCircle::ThreePoints(ent(p1), ent(p2), ent(p3)),
+      FileCircle::Compass(c, p1, p2) => SymbolicCircle::Compass(ent(c), ent(p1), ent(p2)),
+      FileCircle::Transformed(c, trans) => SymbolicCircle::Transformed(ent(c), trans.transformation(ent)),
+      FileCircle::Incircle(p1, p2, p3) => SymbolicCircle::Incircle(ent(p1), ent(p2), ent(p3)),
+      FileCircle::Excircle(p1, p2, p3) => SymbolicCircle::Excircle(ent(p1), ent(p2), ent(p3)),
+      FileCircle::NinePointCircle(p1, p2, p3) => SymbolicCircle::NinePointCircle(ent(p1), ent(p2), ent(p3)),
+    }
+  }
+}
+
+impl FilePolygon {
+  pub fn new<F>(sym_polygon: SymbolicPolygon, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_polygon {
+      SymbolicPolygon::Vertices(ps) => FilePolygon::Vertices(ps.into_iter().map(index).collect::<Result<_, _>>()?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(&self, ent: &F) -> SymbolicPolygon {
+    match self {
+      FilePolygon::Vertices(ps) => SymbolicPolygon::Vertices(ps.iter().map(|&p| ent(p)).collect()),
+    }
+  }
+}
+
+impl FileMeasurement {
+  pub fn new<F>(sym_measurement: SymbolicMeasurement, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_measurement {
+      SymbolicMeasurement::Distance(p1, p2) => FileMeasurement::Distance(index(p1)?, index(p2)?),
+      SymbolicMeasurement::Length(l) => FileMeasurement::Length(index(l)?),
+      SymbolicMeasurement::Angle(p1, v, p2) => FileMeasurement::Angle(index(p1)?, index(v)?, index(p2)?),
+      SymbolicMeasurement::Area(s) => FileMeasurement::Area(index(s)?),
+      SymbolicMeasurement::Perimeter(s) => FileMeasurement::Perimeter(index(s)?),
+      SymbolicMeasurement::Ratio(m1, m2) => FileMeasurement::Ratio(index(m1)?, index(m2)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicMeasurement {
+    match self {
+      FileMeasurement::Distance(p1, p2) => SymbolicMeasurement::Distance(ent(p1), ent(p2)),
+      FileMeasurement::Length(l) => SymbolicMeasurement::Length(ent(l)),
+      FileMeasurement::Angle(p1, v, p2) => SymbolicMeasurement::Angle(ent(p1), ent(v), ent(p2)),
+      FileMeasurement::Area(s) => SymbolicMeasurement::Area(ent(s)),
+      FileMeasurement::Perimeter(s) => SymbolicMeasurement::Perimeter(ent(s)),
+      FileMeasurement::Ratio(m1, m2) => SymbolicMeasurement::Ratio(ent(m1), ent(m2)),
+    }
+  }
+}
+
+impl FileLocus {
+  pub fn new<F>(sym_locus: SymbolicLocus, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_locus {
+      SymbolicLocus::Traced(driver, traced) => FileLocus::Traced(index(driver)?, index(traced)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(&self, ent: &F) -> SymbolicLocus {
+    match *self {
+      FileLocus::Traced(driver, traced) => SymbolicLocus::Traced(ent(driver), ent(traced)),
+    }
+  }
+}
+
+impl FileArc {
+  pub fn new<F>(sym_arc: SymbolicArc, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_arc {
+      SymbolicArc::ThreePoints(p1, p2, p3, kind) => FileArc::ThreePoints(index(p1)?, index(p2)?, index(p3)?, kind),
+      SymbolicArc::CenterFromTo(c, p1, p2, kind) => FileArc::CenterFromTo(index(c)?, index(p1)?, index(p2)?, kind),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicArc {
+    match self {
+      FileArc::ThreePoints(p1, p2, p3, kind) => SymbolicArc::ThreePoints(ent(p1), ent(p2), ent(p3), kind),
+      FileArc::CenterFromTo(c, p1, p2, kind) => SymbolicArc::CenterFromTo(ent(c), ent(p1), ent(p2), kind),
+    }
+  }
+}
+
+impl FileConic {
+  pub fn new<F>(sym_conic: SymbolicConic, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match sym_conic {
+      SymbolicConic::FivePoints(p1, p2, p3, p4, p5) => {
+        FileConic::FivePoints(index(p1)?, index(p2)?, index(p3)?, index(p4)?, index(p5)?)
+      }
+      SymbolicConic::FociPoint(f1, f2, p) => FileConic::FociPoint(index(f1)?, index(f2)?, index(p)?),
+      SymbolicConic::FocusDirectrix(f, l) => FileConic::FocusDirectrix(index(f)?, index(l)?),
+    })
+  }
+
+  pub fn symbolic<F: Fn(usize) -> Entity>(self, ent: &F) -> SymbolicConic {
+    match self {
+      FileConic::FivePoints(p1, p2, p3, p4, p5) => {
+        SymbolicConic::FivePoints(ent(p1), ent(p2), ent(p3), ent(p4), ent(p5))
+      }
+      FileConic::FociPoint(f1, f2, p) => SymbolicConic::FociPoint(ent(f1), ent(f2), ent(p)),
+      FileConic::FocusDirectrix(f, l) => SymbolicConic::FocusDirectrix(ent(f), ent(l)),
+    }
+  }
+}
+
+impl FileTransformation {
+  pub fn new<F>(trans: Transformation, index: &mut F) -> Result<Self, GeopadFileError>
+  where
+    F: FnMut(Entity) -> Result<usize, GeopadFileError>,
+  {
+    Ok(match trans {
+      Transformation::Reflect(l) => FileTransformation::Reflect(index(l)?),
+      Transformation::Rotate(c, angle) => FileTransformation::Rotate(index(c)?, angle),
+      Transformation::RotateByAngle(c, p1, v, p2) => {
+        FileTransformation::RotateByAngle(index(c)?, index(p1)?, index(v)?, index(p2)?)
+      }
+      Transformation::Translate(p1, p2) => FileTransformation::Translate(index(p1)?, index(p2)?),
+      Transformation::Dilate(c, factor) => FileTransformation::Dilate(index(c)?, factor),
+    })
+  }
+
+  pub fn transformation<F: Fn(usize) -> Entity>(self, ent: &F) -> Transformation {
+    match self {
+      FileTransformation::Reflect(l) => Transformation::Reflect(ent(l)),
+      FileTransformation::Rotate(c, angle) => Transformation::Rotate(ent(c), angle),
+      FileTransformation::RotateByAngle(c, p1, v, p2) => {
+        Transformation::RotateByAngle(ent(c), ent(p1), ent(v), ent(p2))
+      }
+      FileTransformation::Translate(p1, p2) => Transformation::Translate(ent(p1), ent(p2)),
+      FileTransformation::Dilate(c, factor) => Transformation::Dilate(ent(c), factor),
+    }
+  }
+}
+
+impl GeopadFile {
+  /// Build the file content from a list of `(entity, geometry, hidden)`. Every entity
+  /// referenced by a geometry must be part of the list.
+  pub fn new(
+    viewport: &Viewport,
+    default_styles: FileDefaultStyles,
+    geometries: &[(Entity, Geometry, bool)],
+  ) -> Result<Self, GeopadFileError> {
+    let indices = geometries
+      .iter()
+      .enumerate()
+      .map(|(i, (ent, _, _))| (*ent, i))
+      .collect::<HashMap<_, _>>();
+    let mut index = |ent: Entity| indices.get(&ent).copied().ok_or(GeopadFileError::MissingReference(ent));
+    let mut elements = Vec::with_capacity(geometries.len());
+    for (_, geometry, hidden) in geometries {
+      elements.push(FileElement {
+        geometry: FileGeometry::new(geometry.clone(), &mut index)?,
+        hidden: *hidden,
+        label: None,
+      });
+    }
+    Ok(Self {
+      version: GEOPAD_FILE_VERSION,
+      viewport: FileViewport {
+        virtual_center: viewport.virtual_center,
+        virtual_size: viewport.virtual_size,
+        screen_size: viewport.screen_size,
+      },
+      default_styles,
+      elements,
+    })
+  }
+
+  pub fn from_json(s: &str) -> Result<Self, GeopadFileError> {
+    let file: Self = serde_json::from_str(s)?;
+    file.check_version()?;
+    Ok(file)
+  }
+
+  pub fn to_json(&self) -> Result<String, GeopadFileError> {
+    Ok(serde_json::to_string_pretty(self)?)
+  }
+
+  pub fn load(path: &Path) -> Result<Self, GeopadFileError> {
+    let file: Self = serde_json::from_reader(io::BufReader::new(File::open(path)?))?;
+    file.check_version()?;
+    Ok(file)
+  }
+
+  pub fn save(&self, path: &Path) -> Result<(), GeopadFileError> {
+    serde_json::to_writer_pretty(io::BufWriter::new(File::create(path)?), self)?;
+    Ok(())
+  }
+
+  /// Turn the elements back into geometries, calling `create_entity` once per element.
+  /// All references are checked before any entity is created.
+  pub fn geometries<F>(&self, mut create_entity: F) -> Result<Vec<(Entity, Geometry, bool)>, GeopadFileError>
+  where
+    F: FnMut() -> Entity,
+  {
+    self.check_references()?;
+    let ents = self.elements.iter().map(|_| create_entity()).collect::<Vec<_>>();
+    let ent = |i: usize| ents[i];
+    let geometries = self
+      .elements
+      .iter()
+      .zip(ents.iter())
+      .map(|(element, entity)| (*entity, element.geometry.geometry(&ent), element.hidden))
+      .collect();
+    Ok(geometries)
+  }
+
+  fn check_version(&self) -> Result<(), GeopadFileError> {
+    if self.version > GEOPAD_FILE_VERSION {
+      Err(GeopadFileError::UnsupportedVersion(self.version))
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Every reference has to point to an element of the right kind, and no element may depend
+  /// on itself, which the solver could never resolve. Elements may come in any order
+  pub fn check_references(&self) -> Result<(), GeopadFileError> {
+    // The references of the element being checked, gathered by the checks below
+    let referenced = RefCell::new(vec![]);
+    let geometry_at = |i: usize| {
+      referenced.borrow_mut().push(i);
+      self.elements.get(i).map(|e| &e.geometry)
+    };
+    let is_point = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Point(_, _)));
+    let is_line = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Line(_, _)));
+    let is_circle = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Circle(_, _)));
+    let is_arc = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Arc(_, _)));
+    let is_conic = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Conic(_, _)));
+    let is_segment = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Line(FileLine::Segment(_, _), _)));
+    let is_shape = |i: usize| {
+      matches!(
+        geometry_at(i),
+        Some(FileGeometry::Circle(_, _)) | Some(FileGeometry::Polygon(_, _))
+      )
+    };
+    let is_measurement = |i: usize| matches!(geometry_at(i), Some(FileGeometry::Measurement(_)));
+    let is_transformation = |trans: FileTransformation| match trans {
+      FileTransformation::Reflect(l) => is_line(l),
+      FileTransformation::Rotate(c, _) | FileTransformation::Dilate(c, _) => is_point(c),
+      FileTransformation::RotateByAngle(c, p1, v, p2) => is_point(c) && is_point(p1) && is_point(v) && is_point(p2),
+      FileTransformation::Translate(p1, p2) => is_point(p1) && is_point(p2),
+    };
+    let mut dependencies = Vec::with_capacity(self.elements.len());
+    for (index, element) in self.elements.iter().enumerate() {
+      let valid = match &element.geometry {
+        FileGeometry::Point(file_point, _) => match *file_point {
+          FilePoint::Fixed(_) | FilePoint::Free(_) => true,
+          FilePoint::MidPoint(p1, p2) => is_point(p1) && is_point(p2),
+          FilePoint::OnLine(l, _) => is_line(l),
+          FilePoint::LineLineIntersect(l1, l2) => is_line(l1) && is_line(l2),
+          FilePoint::OnCircle(c, _) => is_circle(c),
+          FilePoint::CircleLineIntersect(c, l, _) => is_circle(c) && is_line(l),
+          FilePoint::CircleCircleIntersect(c1, c2, _) => is_circle(c1) && is_circle(c2),
+          FilePoint::Transformed(p, trans) => is_point(p) && is_transformation(trans),
+          FilePoint::OnArc(a, _) => is_arc(a),
+          FilePoint::ArcLineIntersect(a, l, _) => is_arc(a) && is_line(l),
+          FilePoint::ArcCircleIntersect(a, c, _) => is_arc(a) && is_circle(c),
+          FilePoint::ConicLineIntersect(c, l, _) => is_conic(c) && is_line(l),
+          FilePoint::Centroid(p1, p2, p3)
+          | FilePoint::Circumcenter(p1, p2, p3)
+          | FilePoint::Orthocenter(p1, p2, p3)
+          | FilePoint::Incenter(p1, p2, p3)
+          | FilePoint::NinePointCenter(p1, p2, p3) => is_point(p1) && is_point(p2) && is_point(p3),
+        },
+        FileGeometry::Line(file_line, _) => match *file_line {
+          FileLine::Straight(p1, p2)
+          | FileLine::Ray(p1, p2)
+          | FileLine::Segment(p1, p2)
+          | FileLine::PerpendicularBisector(p1, p2) => is_point(p1) && is_point(p2),
+          FileLine::Parallel(l, p) | FileLine::Perpendicular(l, p) => is_line(l) && is_point(p),
+          FileLine::AngleBisector(p1, v, p2) => is_point(p1) && is_point(v) && is_point(p2),
+          FileLine::TangentAt(c, p) | FileLine::TangentFromPoint(c, p, _) => is_circle(c) && is_point(p),
+          FileLine::Transformed(l, trans) => is_line(l) && is_transformation(trans),
+        },
+        FileGeometry::Circle(file_circle, _) => match *file_circle {
+          FileCircle::CenterRadius(c, p) => is_point(c) && is_point(p),
+          FileCircle::ThreePoints(p1, p2, p3)
+          | FileCircle::Compass(p1, p2, p3)
+          | FileCircle::Incircle(p1, p2, p3)
+          | FileCircle::Excircle(p1, p2, p3)
+          | FileCircle::NinePointCircle(p1, p2, p3) => is_point(p1) && is_point(p2) && is_point(p3),
+          FileCircle::Transformed(c, trans) => is_circle(c) && is_transformation(trans),
+        },
+        FileGeometry::Polygon(file_polygon, _) => match file_polygon {
+          FilePolygon::Vertices(ps) => ps.len() >= 3 && ps.iter().all(|&p| is_point(p)),
+        },
+        FileGeometry::Measurement(file_measurement) => match *file_measurement {
+          FileMeasurement::Distance(p1, p2) => is_point(p1) && is_point(p2),
+          FileMeasurement::Length(l) => is_segment(l),
+          FileMeasurement::Angle(p1, v, p2) => is_point(p1) && is_point(v) && is_point(p2),
+          FileMeasurement::Area(s) | FileMeasurement::Perimeter(s) => is_shape(s),
+          FileMeasurement::Ratio(m1, m2) => is_measurement(m1) && is_measurement(m2),
+        },
+        FileGeometry::Locus(file_locus, _) => match *file_locus {
+          FileLocus::Traced(d, p) => d != p && is_point(d) && is_point(p),
+        },
+        FileGeometry::Arc(file_arc, _) => match *file_arc {
+          FileArc::ThreePoints(p1, p2, p3, _) | FileArc::CenterFromTo(p1, p2, p3, _) => {
+            is_point(p1) && is_point(p2) && is_point(p3)
+          }
+        },
+        FileGeometry::Conic(file_conic, _) => match *file_conic {
+          FileConic::FivePoints(p1, p2, p3, p4, p5) => [p1, p2, p3, p4, p5].iter().all(|&p| is_point(p)),
+          FileConic::FociPoint(f1, f2, p) => is_point(f1) && is_point(f2) && is_point(p),
+          FileConic::FocusDirectrix(f, l) => is_point(f) && is_line(l),
+        },
+      };
+      if !valid {
+        return Err(GeopadFileError::InvalidReference(index));
+      }
+      dependencies.push(referenced.replace(vec![]));
+    }
+    check_cycles(&dependencies)
+  }
+}
+
+/// Solve the elements in dependency order. The ones that can't be reached are on a cycle or
+/// depend on one
+fn check_cycles(dependencies: &[Vec<usize>]) -> Result<(), GeopadFileError> {
+  let mut dependents = vec![vec![]; dependencies.len()];
+  for (index, references) in dependencies.iter().enumerate() {
+    for &reference in references {
+      dependents[reference].push(index);
+    }
+  }
+  let mut unsolved = dependencies.iter().map(Vec::len).collect::<Vec<_>>();
+  let mut solvable = (0..dependencies.len())
+    .filter(|&i| unsolved[i] == 0)
+    .collect::<Vec<_>>();
+  while let Some(index) = solvable.pop() {
+    for &dependent in &dependents[index] {
+      unsolved[dependent] -= 1;
+      if unsolved[dependent] == 0 {
+        solvable.push(dependent);
+      }
+    }
+  }
+  match unsolved.iter().position(|&count| count > 0) {
+    Some(index) => Err(GeopadFileError::CyclicReference(index)),
+    None => Ok(()),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn default_styles() -> FileDefaultStyles {
+    FileDefaultStyles {
+      point: DefaultPointStyle::default().get(),
+      line: DefaultLineStyle::default().get(),
+      circle: DefaultCircleStyle::default().get(),
+      polygon: DefaultPolygonStyle::default().get(),
+    }
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let mut world = World::new();
+    let (a, b, l, c, p) = (
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+    );
+    let point_style = DefaultPointStyle::default().get();
+    let geometries = vec![
+      (
+        a,
+        Geometry::Point(SymbolicPoint::Free(vec2![1., 2.].into()), point_style),
+        false,
+      ),
+      (
+        b,
+        Geometry::Point(SymbolicPoint::Fixed(vec2![3., -1.].into()), point_style),
+        true,
+      ),
+      (
+        l,
+        Geometry::Line(SymbolicLine::Ray(a, b), DefaultLineStyle::default().get()),
+        false,
+      ),
+      (
+        c,
+        Geometry::Circle(SymbolicCircle::CenterRadius(a, b), DefaultCircleStyle::default().get()),
+        false,
+      ),
+      (
+        p,
+        Geometry::Point(
+          SymbolicPoint::CircleLineIntersect(c, l, CircleIntersectId::Second),
+          point_style,
+        ),
+        false,
+      ),
+    ];
+    let mut file = GeopadFile::new(&Viewport::default(), default_styles(), &geometries).unwrap();
+    file.elements[0].label = Some(Label::new("A".to_string()));
+    let loaded = GeopadFile::from_json(&file.to_json().unwrap()).unwrap();
+    assert_eq!(loaded.version, GEOPAD_FILE_VERSION);
+    assert_eq!(loaded.elements[0].label, Some(Label::new("A".to_string())));
+    assert_eq!(loaded.elements[1].label, None);
+
+    let loaded_geometries = loaded.geometries(|| world.create_entity().build()).unwrap();
+    assert_eq!(loaded_geometries.len(), 5);
+    let ents = loaded_geometries.iter().map(|(ent, _, _)| *ent).collect::<Vec<_>>();
+    assert!(loaded_geometries[1].2);
+    match loaded_geometries[0].1 {
+      Geometry::Point(SymbolicPoint::Free(pos), _) => assert_eq!(pos.0, vec2![1., 2.]),
+      _ => panic!("expected a free point"),
+    }
+    match loaded_geometries[4].1 {
+      Geometry::Point(SymbolicPoint::CircleLineIntersect(c, l, CircleIntersectId::Second), _) => {
+        assert_eq!(c, ents[3]);
+        assert_eq!(l, ents[2]);
+      }
+      _ => panic!("expected a circle line intersection"),
+    }
+  }
+
+  #[test]
+  fn test_missing_reference() {
+    let mut world = World::new();
+    let (a, b) = (world.create_entity().build(), world.create_entity().build());
+    let geometries = vec![(
+      a,
+      Geometry::Line(SymbolicLine::Straight(a, b), DefaultLineStyle::default().get()),
+      false,
+    )];
+    match GeopadFile::new(&Viewport::default(), default_styles(), &geometries) {
+      Err(GeopadFileError::MissingReference(ent)) => assert_eq!(ent, b),
+      _ => panic!("expected a missing reference"),
+    }
+  }
+
+  #[test]
+  fn test_measurement_references() {
+    let mut world = World::new();
+    let (a, b, s, m) = (
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+    );
+    let point_style = DefaultPointStyle::default().get();
+    let mut geometries = vec![
+      (
+        a,
+        Geometry::Point(SymbolicPoint::Free(vec2![0., 0.].into()), point_style),
+        false,
+      ),
+      (
+        b,
+        Geometry::Point(SymbolicPoint::Free(vec2![3., 4.].into()), point_style),
+        false,
+      ),
+      (
+        s,
+        Geometry::Line(SymbolicLine::Segment(a, b), DefaultLineStyle::default().get()),
+        false,
+      ),
+      (m, Geometry::Measurement(SymbolicMeasurement::Length(s)), false),
+    ];
+    let file = GeopadFile::new(&Viewport::default(), default_styles(), &geometries).unwrap();
+    assert!(file.check_references().is_ok());
+
+    // A length can only be measured on a segment
+    geometries[3].1 = Geometry::Measurement(SymbolicMeasurement::Length(a));
+    let file = GeopadFile::new(&Viewport::default(), default_styles(), &geometries).unwrap();
+    match file.check_references() {
+      Err(GeopadFileError::InvalidReference(index)) => assert_eq!(index, 3),
+      _ => panic!("expected an invalid reference"),
+    }
+  }
+
+  #[test]
+  fn test_cyclic_references() {
+    let point = |file_point| FileElement {
+      geometry: FileGeometry::Point(file_point, DefaultPointStyle::default().get()),
+      hidden: false,
+      label: None,
+    };
+    let line = |file_line| FileElement {
+      geometry: FileGeometry::Line(file_line, DefaultLineStyle::default().get()),
+      hidden: false,
+      label: None,
+    };
+    let check = |elements| {
+      let mut file = GeopadFile::new(&Viewport::default(), default_styles(), &[]).unwrap();
+      file.elements = elements;
+      file.check_references()
+    };
+
+    // Elements may come before the ones they depend on
+    let result = check(vec![
+      line(FileLine::Segment(1, 2)),
+      point(FilePoint::Free(vec2![0., 0.])),
+      point(FilePoint::Free(vec2![1., 0.])),
+    ]);
+    assert!(result.is_ok());
+
+    // But none may depend on itself
+    let result = check(vec![
+      point(FilePoint::Free(vec2![0., 0.])),
+      point(FilePoint::MidPoint(0, 1)),
+    ]);
+    match result {
+      Err(GeopadFileError::CyclicReference(index)) => assert_eq!(index, 1),
+      _ => panic!("expected a cyclic reference"),
+    }
+    let result = check(vec![
+      point(FilePoint::Free(vec2![0., 0.])),
+      line(FileLine::Parallel(2, 0)),
+      line(FileLine::Perpendicular(1, 0)),
+      point(FilePoint::OnLine(2, 0.5)),
+    ]);
+    match result {
+      Err(GeopadFileError::CyclicReference(index)) => assert_eq!(index, 1),
+      _ => panic!("expected a cyclic reference"),
+    }
+  }
+
+  #[test]
+  fn test_newer_version() {
+    let mut file = GeopadFile::new(&Viewport::default(), default_styles(), &[]).unwrap();
+    file.version = GEOPAD_FILE_VERSION + 1;
+    match GeopadFile::from_json(&file.to_json().unwrap()) {
+      Err(GeopadFileError::UnsupportedVersion(_)) => (),
+      _ => panic!("expected an unsupported version"),
+    }
+  }
+}
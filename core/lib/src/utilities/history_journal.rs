@@ -0,0 +1,626 @@
+use crate::{
+  components::labels::Label,
+  resources::{History, Modification},
+  utilities::*,
+};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use specs::prelude::*;
+use std::{
+  cell::RefCell,
+  collections::{HashMap, HashSet},
+  fs::{self, File},
+  io::{self, BufRead, Write},
+  path::{Path, PathBuf},
+};
+
+pub static HISTORY_JOURNAL_EXTENSION: &str = "geopad-journal";
+
+static SESSION_JOURNAL_PREFIX: &str = "session-";
+
+/// Where the sessions keep their journals
+pub fn session_journal_dir() -> PathBuf {
+  std::env::temp_dir().join("geopad-sessions")
+}
+
+/// The journal of the running session, one per process so that several sessions can run at
+/// once. A journal still there that no session holds was left by one that did not quit properly
+pub fn session_journal_path(dir: &Path) -> PathBuf {
+  dir.join(format!(
+    "{}{}.{}",
+    SESSION_JOURNAL_PREFIX,
+    std::process::id(),
+    HISTORY_JOURNAL_EXTENSION
+  ))
+}
+
+/// One line of a journal. Entities are not stable across sessions, so every entity is
+/// stored as an index given the first time the journal sees it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalEntry {
+  Base(Box<GeopadFile>, Vec<usize>), // The new content, and the index of each of its elements
+  Push(JournalModification),
+  Undo,
+  Redo,
+  Clear,
+}
+
+/// A `Modification` as stored in a journal. References inside the geometries are indices
+/// too, rather than indices in the file elements
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalModification {
+  RemoveMany(Vec<(usize, FileGeometry)>),
+  InsertMany(Vec<(usize, FileGeometry)>),
+  UpdatePoint(usize, FilePoint, FilePoint),
+  UpdateStyles(Vec<(usize, Style, Style)>),
+  HideMany(Vec<usize>),
+  UnhideMany(Vec<usize>),
+  Group(Vec<JournalModification>),
+}
+
+/// An append-only file with every change made to the history, one JSON entry per line. If
+/// the application crashes, replaying the journal gives back the elements and the history
+/// since the last `Base`, that is since the start of the session or the last loaded file.
+pub struct HistoryJournal {
+  file: File,
+  indices: HashMap<Entity, usize>,
+}
+
+/// What replaying a journal gives back. The labels are in the same order as the geometries
+pub struct JournalReplay {
+  pub geometries: Vec<(Entity, Geometry, bool)>,
+  pub labels: Vec<Option<Label>>,
+  pub history: History,
+  indices: HashMap<Entity, usize>, // The index in the journal of every entity the replay created
+}
+
+impl HistoryJournal {
+  /// Start a new journal at `path`, replacing any previous one. The journal stays locked until
+  /// it is dropped, which tells the other sessions it is in use
+  pub fn create(path: &Path) -> Result<Self, GeopadFileError> {
+    // Lock it before it gets its name, so that no other session takes it for a crashed one
+    let creating = path.with_extension("creating");
+    let file = File::create(&creating)?;
+    file.try_lock_exclusive()?;
+    fs::rename(&creating, path)?;
+    Ok(Self {
+      file,
+      indices: HashMap::new(),
+    })
+  }
+
+  /// Move the latest journal in `dir` left by a session that did not quit properly out of the
+  /// way, and remove the older ones. The journals of the sessions still running are locked and
+  /// stay where they are. Gives where the journal went, so that the front end can offer to
+  /// recover it with `FileEvent::Recover`
+  pub fn take_previous(dir: &Path) -> Option<PathBuf> {
+    let mut crashed = fs::read_dir(dir)
+      .ok()?
+      .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+      .filter(|path| {
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        name.starts_with(SESSION_JOURNAL_PREFIX) && name.ends_with(HISTORY_JOURNAL_EXTENSION)
+      })
+      .filter(|path| match File::open(path) {
+        Ok(file) => file.try_lock_exclusive().is_ok(),
+        Err(_) => false,
+      })
+      .filter_map(|path| Some((fs::metadata(&path).ok()?.modified().ok()?, path)))
+      .collect::<Vec<_>>();
+    crashed.sort();
+    let (_, latest) = crashed.pop()?;
+    for (_, path) in crashed {
+      fs::remove_file(path).ok();
+    }
+    let previous = dir.join(format!("previous.{}", HISTORY_JOURNAL_EXTENSION));
+
+    // Renaming does not replace an existing file on every platform
+    fs::remove_file(&previous).ok();
+    match fs::rename(&latest, &previous) {
+      Ok(()) => Some(previous),
+      Err(err) => {
+        eprintln!("[history_journal] Cannot move the previous journal aside: {}", err);
+        None
+      }
+    }
+  }
+
+  /// Record every change of the history of `world` in a new journal at `path`, until the
+  /// session ends
+  pub fn begin_session(world: &mut World, path: &Path) {
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir).ok();
+    }
+    match HistoryJournal::create(path) {
+      Ok(journal) => world.fetch_mut::<History>().set_journal(Some(journal)),
+      Err(err) => eprintln!("[history_journal] Cannot create the journal: {}", err),
+    }
+  }
+
+  /// Stop recording and remove the journal, since the session quit properly
+  pub fn end_session(world: &mut World, path: &Path) {
+    world.fetch_mut::<History>().set_journal(None);
+    fs::remove_file(path).ok();
+  }
+
+  /// Read all the entries of a journal. The last line is ignored when it is incomplete, which
+  /// happens when the application crashed while writing it
+  pub fn read(path: &Path) -> Result<Vec<JournalEntry>, GeopadFileError> {
+    let lines = io::BufReader::new(File::open(path)?)
+      .lines()
+      .collect::<Result<Vec<_>, _>>()?;
+    let mut entries = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+      match serde_json::from_str(line) {
+        Ok(entry) => entries.push(entry),
+        Err(_) if i + 1 == lines.len() => (),
+        Err(err) => return Err(err.into()),
+      }
+    }
+    Ok(entries)
+  }
+
+  /// Apply the entries to an empty world, calling `create_entity` once for every element the
+  /// journal knows about, including the removed ones that can come back by undoing
+  pub fn replay<F>(entries: &[JournalEntry], create_entity: F) -> JournalReplay
+  where
+    F: FnMut() -> Entity,
+  {
+    let create_entity = RefCell::new(create_entity);
+    let ents = RefCell::new(HashMap::new());
+    let ent = |index: usize| {
+      *ents
+        .borrow_mut()
+        .entry(index)
+        .or_insert_with(|| create_entity.borrow_mut()())
+    };
+
+    let mut state = HashMap::new();
+    let mut labels = HashMap::new();
+    let mut history = History::default();
+    for entry in entries {
+      match entry {
+        JournalEntry::Base(file, indices) => {
+          state.clear();
+          labels.clear();
+          history.clear();
+          for (element, &index) in file.elements.iter().zip(indices.iter()) {
+            // References inside the file are indices of its elements
+            let geometry = element.geometry.geometry(&|i: usize| ent(indices[i]));
+            state.insert(ent(index), (geometry, element.hidden));
+            if let Some(label) = &element.label {
+              labels.insert(ent(index), label.clone());
+            }
+          }
+        }
+        JournalEntry::Push(modification) => {
+          let modification = modification.modification(&ent);
+          apply(&mut state, &modification, false);
+          history.push(modification);
+        }
+        JournalEntry::Undo => {
+          if let Some(modification) = history.undo() {
+            apply(&mut state, modification, true);
+          }
+        }
+        JournalEntry::Redo => {
+          if let Some(modification) = history.redo() {
+            apply(&mut state, modification, false);
+          }
+        }
+        JournalEntry::Clear => history.clear(),
+      }
+    }
+
+    // The journal indexes the references of an element before the element itself, so sorting by
+    // index puts every element before what depends on it
+    let indices = ents
+      .into_inner()
+      .into_iter()
+      .map(|(index, ent)| (ent, index))
+      .collect::<HashMap<_, _>>();
+    let mut geometries = state
+      .into_iter()
+      .map(|(ent, (geometry, hidden))| (ent, geometry, hidden))
+      .collect::<Vec<_>>();
+    geometries.sort_by_key(|(ent, _, _)| indices[ent]);
+    let labels = geometries.iter().map(|(ent, _, _)| labels.remove(ent)).collect();
+    JournalReplay {
+      geometries,
+      labels,
+      history,
+      indices,
+    }
+  }
+
+  pub(crate) fn record_base(&mut self, file: &GeopadFile, entities: &[Entity]) {
+    let indices = entities.iter().map(|ent| self.index(*ent)).collect();
+    self.write(&JournalEntry::Base(Box::new(file.clone()), indices));
+  }
+
+  /// Go on with the session replayed from `entries`. The content starts again from `empty`, then
+  /// the entries since the last base are written again with the same indices, so that the
+  /// recovered history survives another crash
+  pub(crate) fn record_replay(&mut self, empty: &GeopadFile, entries: &[JournalEntry], replay: &JournalReplay) {
+    self.write(&JournalEntry::Base(Box::new(empty.clone()), vec![]));
+    self.indices = replay.indices.clone();
+    let last_base = entries
+      .iter()
+      .rposition(|entry| matches!(entry, JournalEntry::Base(_, _)))
+      .unwrap_or(0);
+    for entry in &entries[last_base..] {
+      self.write(entry);
+    }
+  }
+
+  pub(crate) fn record_push(&mut self, modification: &Modification) {
+    let modification = JournalModification::new(modification, &mut |ent| self.index(ent));
+    self.write(&JournalEntry::Push(modification));
+  }
+
+  pub(crate) fn record_undo(&mut self) {
+    self.write(&JournalEntry::Undo);
+  }
+
+  pub(crate) fn record_redo(&mut self) {
+    self.write(&JournalEntry::Redo);
+  }
+
+  pub(crate) fn record_clear(&mut self) {
+    self.write(&JournalEntry::Clear);
+  }
+
+  fn index(&mut self, ent: Entity) -> usize {
+    let next = self.indices.len();
+    *self.indices.entry(ent).or_insert(next)
+  }
+
+  /// Every entry is written at once so that a crash can only cut the last line
+  fn write(&mut self, entry: &JournalEntry) {
+    let result = serde_json::to_string(entry)
+      .map_err(GeopadFileError::from)
+      .and_then(|line| Ok(self.file.write_all(format!("{}\n", line).as_bytes())?));
+    if let Err(err) = result {
+      eprintln!("[history_journal] Cannot write the journal: {}", err);
+    }
+  }
+}
+
+impl JournalModification {
+  pub fn new(modification: &Modification, index: &mut dyn FnMut(Entity) -> usize) -> Self {
+    // Every entity has an index so the conversions can't fail
+    let mut index = |ent: Entity| -> Result<usize, GeopadFileError> { Ok(index(ent)) };
+    let mut geometries = |geometries: &HashMap<Entity, Geometry>| {
+      geometries
+        .iter()
+        .map(|(ent, geometry)| {
+          let file_geometry = FileGeometry::new(geometry.clone(), &mut index).unwrap();
+          (index(*ent).unwrap(), file_geometry)
+        })
+        .collect()
+    };
+    match modification {
+      Modification::RemoveMany(removals) => JournalModification::RemoveMany(geometries(removals)),
+      Modification::InsertMany(insertions) => JournalModification::InsertMany(geometries(insertions)),
+      Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => JournalModification::UpdatePoint(
+        index(*ent).unwrap(),
+        FilePoint::new(*old_sym_point, &mut index).unwrap(),
+        FilePoint::new(*new_sym_point, &mut index).unwrap(),
+      ),
+      Modification::UpdateStyles(updates) => JournalModification::UpdateStyles(
+        updates
+          .iter()
+          .map(|(ent, (old_style, new_style))| (index(*ent).unwrap(), *old_style, *new_style))
+          .collect(),
+      ),
+      Modification::HideMany(entities) => {
+        JournalModification::HideMany(entities.iter().map(|ent| index(*ent).unwrap()).collect())
+      }
+      Modification::UnhideMany(entities) => {
+        JournalModification::UnhideMany(entities.iter().map(|ent| index(*ent).unwrap()).collect())
+      }
+      Modification::Group(modifications) => JournalModification::Group(
+        modifications
+          .iter()
+          .map(|modification| JournalModification::new(modification, &mut |ent| index(ent).unwrap()))
+          .collect(),
+      ),
+    }
+  }
+
+  pub fn modification<F: Fn(usize) -> Entity>(&self, ent: &F) -> Modification {
+    let geometries = |geometries: &Vec<(usize, FileGeometry)>| {
+      geometries
+        .iter()
+        .map(|(index, geometry)| (ent(*index), geometry.geometry(ent)))
+        .collect()
+    };
+    match self {
+      JournalModification::RemoveMany(removals) => Modification::RemoveMany(geometries(removals)),
+      JournalModification::InsertMany(insertions) => Modification::InsertMany(geometries(insertions)),
+      JournalModification::UpdatePoint(index, old_point, new_point) => {
+        Modification::UpdatePoint(ent(*index), old_point.symbolic(ent), new_point.symbolic(ent))
+      }
+      JournalModification::UpdateStyles(updates) => Modification::UpdateStyles(
+        updates
+          .iter()
+          .map(|(index, old_style, new_style)| (ent(*index), (*old_style, *new_style)))
+          .collect(),
+      ),
+      JournalModification::HideMany(indices) => Modification::HideMany(indices.iter().map(|i| ent(*i)).collect()),
+      JournalModification::UnhideMany(indices) => Modification::UnhideMany(indices.iter().map(|i| ent(*i)).collect()),
+      JournalModification::Group(modifications) => Modification::Group(
+        modifications
+          .iter()
+          .map(|modification| modification.modification(ent))
+          .collect(),
+      ),
+    }
+  }
+}
+
+/// Apply a modification to the elements, the same way undoing or redoing it changes the world
+fn apply(state: &mut HashMap<Entity, (Geometry, bool)>, modification: &Modification, undo: bool) {
+  let insert = |state: &mut HashMap<Entity, (Geometry, bool)>, geometries: &HashMap<Entity, Geometry>| {
+    for (ent, geometry) in geometries {
+      state.insert(*ent, (geometry.clone(), false));
+    }
+  };
+  let remove = |state: &mut HashMap<Entity, (Geometry, bool)>, geometries: &HashMap<Entity, Geometry>| {
+    for ent in geometries.keys() {
+      state.remove(ent);
+    }
+  };
+  let set_hidden = |state: &mut HashMap<Entity, (Geometry, bool)>, entities: &HashSet<Entity>, hidden: bool| {
+    for ent in entities {
+      if let Some(element) = state.get_mut(ent) {
+        element.1 = hidden;
+      }
+    }
+  };
+  match modification {
+    Modification::InsertMany(insertions) if undo => remove(state, insertions),
+    Modification::InsertMany(insertions) => insert(state, insertions),
+    Modification::RemoveMany(removals) if undo => insert(state, removals),
+    Modification::RemoveMany(removals) => remove(state, removals),
+    Modification::UpdatePoint(ent, old_sym_point, new_sym_point) => {
+      if let Some((Geometry::Point(sym_point, _), _)) = state.get_mut(ent) {
+        *sym_point = if undo { *old_sym_point } else { *new_sym_point };
+      }
+    }
+    Modification::UpdateStyles(updates) => {
+      for (ent, (old_style, new_style)) in updates {
+        if let Some((geometry, _)) = state.get_mut(ent) {
+          set_style(geometry, if undo { *old_style } else { *new_style });
+        }
+      }
+    }
+    Modification::HideMany(entities) => set_hidden(state, entities, !undo),
+    Modification::UnhideMany(entities) => set_hidden(state, entities, undo),
+    Modification::Group(modifications) if undo => {
+      for modification in modifications.iter().rev() {
+        apply(state, modification, true);
+      }
+    }
+    Modification::Group(modifications) => {
+      for modification in modifications {
+        apply(state, modification, false);
+      }
+    }
+  }
+}
+
+fn set_style(geometry: &mut Geometry, style: Style) {
+  match (geometry, style) {
+    (Geometry::Point(_, point_style), Style::Point(style)) => *point_style = style,
+    (Geometry::Line(_, line_style), Style::Line(style)) => *line_style = style,
+    (Geometry::Locus(_, line_style), Style::Line(style)) => *line_style = style,
+    (Geometry::Conic(_, line_style), Style::Line(style)) => *line_style = style,
+    (Geometry::Circle(_, circle_style), Style::Circle(style)) => *circle_style = style,
+    (Geometry::Polygon(_, polygon_style), Style::Polygon(style)) => *polygon_style = style,
+    (Geometry::Arc(_, polygon_style), Style::Polygon(style)) => *polygon_style = style,
+    _ => (),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::{components::symbolics::*, math::*, resources::*};
+
+  #[test]
+  fn test_replay() {
+    let path = std::env::temp_dir().join(format!("test_replay.{}", HISTORY_JOURNAL_EXTENSION));
+    let mut world = World::new();
+    let (a, b, l) = (
+      world.create_entity().build(),
+      world.create_entity().build(),
+      world.create_entity().build(),
+    );
+    let point_style = DefaultPointStyle::default().get();
+    let mut history = History::default();
+    history.set_journal(Some(HistoryJournal::create(&path).unwrap()));
+
+    let mut insertions = HashMap::new();
+    insertions.insert(
+      a,
+      Geometry::Point(SymbolicPoint::Free(vec2![0., 0.].into()), point_style),
+    );
+    insertions.insert(
+      b,
+      Geometry::Point(SymbolicPoint::Free(vec2![1., 0.].into()), point_style),
+    );
+    history.push(Modification::InsertMany(insertions));
+    let mut insertions = HashMap::new();
+    insertions.insert(
+      l,
+      Geometry::Line(SymbolicLine::Segment(a, b), DefaultLineStyle::default().get()),
+    );
+    history.push(Modification::InsertMany(insertions));
+    let mut hidden = HashSet::new();
+    hidden.insert(a);
+    history.push(Modification::HideMany(hidden));
+    history.undo();
+    history.push(Modification::UpdatePoint(
+      b,
+      SymbolicPoint::Free(vec2![1., 0.].into()),
+      SymbolicPoint::Free(vec2![2., 3.].into()),
+    ));
+    history.set_journal(None);
+
+    let entries = HistoryJournal::read(&path).unwrap();
+    assert_eq!(entries.len(), 5);
+    let mut replay = HistoryJournal::replay(&entries, || world.create_entity().build());
+    assert_eq!(replay.geometries.len(), 3);
+    assert!(replay.geometries.iter().all(|(_, _, hidden)| !hidden));
+    let moved = replay
+      .geometries
+      .iter()
+      .find(|(_, geometry, _)| match geometry {
+        Geometry::Point(SymbolicPoint::Free(pos), _) => pos.0 == vec2![2., 3.],
+        _ => false,
+      })
+      .map(|(ent, _, _)| *ent)
+      .expect("expected the moved point");
+    match replay.geometries[2].1 {
+      Geometry::Line(SymbolicLine::Segment(p1, p2), _) => assert!(p1 == moved || p2 == moved),
+      _ => panic!("expected a segment"),
+    }
+
+    // Pushing the update after undoing dropped the hiding
+    assert_eq!(replay.history.len(), 3);
+    match replay.history.undo() {
+      Some(Modification::UpdatePoint(ent, _, _)) => assert_eq!(*ent, moved),
+      _ => panic!("expected the point update"),
+    }
+    std::fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn test_replay_order() {
+    let path = std::env::temp_dir().join(format!("test_replay_order.{}", HISTORY_JOURNAL_EXTENSION));
+    let mut world = World::new();
+    let point_style = DefaultPointStyle::default().get();
+    let line_style = DefaultLineStyle::default().get();
+    let mut history = History::default();
+    history.set_journal(Some(HistoryJournal::create(&path).unwrap()));
+
+    // The points and the segments joining them are inserted at once, in no particular order
+    let points = (0..4).map(|_| world.create_entity().build()).collect::<Vec<_>>();
+    let mut insertions = HashMap::new();
+    for (i, point) in points.iter().enumerate() {
+      let position = vec2![i as f64, 0.].into();
+      insertions.insert(*point, Geometry::Point(SymbolicPoint::Free(position), point_style));
+    }
+    for pair in points.windows(2) {
+      let segment = SymbolicLine::Segment(pair[0], pair[1]);
+      insertions.insert(world.create_entity().build(), Geometry::Line(segment, line_style));
+    }
+    history.push(Modification::InsertMany(insertions));
+    history.set_journal(None);
+
+    let entries = HistoryJournal::read(&path).unwrap();
+    let replay = HistoryJournal::replay(&entries, || world.create_entity().build());
+    assert_eq!(replay.geometries.len(), 7);
+    for (i, (_, geometry, _)) in replay.geometries.iter().enumerate() {
+      if let Geometry::Line(SymbolicLine::Segment(p1, p2), _) = geometry {
+        let position = |ent: Entity| {
+          replay
+            .geometries
+            .iter()
+            .position(|(other, _, _)| *other == ent)
+            .unwrap()
+        };
+        assert!(position(*p1) < i && position(*p2) < i);
+      }
+    }
+    std::fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn test_recover_twice() {
+    let (first, second) = (
+      std::env::temp_dir().join(format!("test_recover_first.{}", HISTORY_JOURNAL_EXTENSION)),
+      std::env::temp_dir().join(format!("test_recover_second.{}", HISTORY_JOURNAL_EXTENSION)),
+    );
+    let mut world = World::new();
+    let point_style = DefaultPointStyle::default().get();
+    let insert = |ent: Entity, x: f64| {
+      let mut insertions = HashMap::new();
+      insertions.insert(
+        ent,
+        Geometry::Point(SymbolicPoint::Free(vec2![x, 0.].into()), point_style),
+      );
+      Modification::InsertMany(insertions)
+    };
+    let (a, b) = (world.create_entity().build(), world.create_entity().build());
+    let mut history = History::default();
+    history.set_journal(Some(HistoryJournal::create(&first).unwrap()));
+    history.push(insert(a, 0.));
+    history.push(insert(b, 1.));
+    history.undo();
+    history.set_journal(None);
+
+    // The session recovered from the first journal crashes in turn, after inserting a point
+    let entries = HistoryJournal::read(&first).unwrap();
+    let replay = HistoryJournal::replay(&entries, || world.create_entity().build());
+    let mut history = History::default();
+    history.set_journal(Some(HistoryJournal::create(&second).unwrap()));
+    let styles = FileDefaultStyles {
+      point: point_style,
+      line: DefaultLineStyle::default().get(),
+      circle: DefaultCircleStyle::default().get(),
+      polygon: DefaultPolygonStyle::default().get(),
+    };
+    let empty = GeopadFile::new(&Viewport::default(), styles, &[]).unwrap();
+    history.record_replay(&empty, &entries, &replay);
+    history.replace_entries(replay.history);
+    let c = world.create_entity().build();
+    history.push(insert(c, 2.));
+    history.set_journal(None);
+
+    // Both the recovered history and what came after are still there
+    let mut replay = HistoryJournal::replay(&HistoryJournal::read(&second).unwrap(), || {
+      world.create_entity().build()
+    });
+    assert_eq!(replay.geometries.len(), 2);
+    assert_eq!(replay.history.len(), 2);
+    assert!(replay.history.undo().is_some());
+    assert!(replay.history.undo().is_some());
+    assert!(replay.history.undo().is_none());
+    std::fs::remove_file(&first).unwrap();
+    std::fs::remove_file(&second).unwrap();
+  }
+
+  #[test]
+  fn test_session() {
+    let dir = std::env::temp_dir().join("test_sessions");
+    std::fs::remove_dir_all(&dir).ok();
+    let path = session_journal_path(&dir);
+    let mut world = World::new();
+    world.insert(History::default());
+    let mut entities = HashSet::new();
+    entities.insert(world.create_entity().build());
+
+    // A session that is running or that quit properly leaves nothing to recover
+    assert!(HistoryJournal::take_previous(&dir).is_none());
+    HistoryJournal::begin_session(&mut world, &path);
+    world
+      .fetch_mut::<History>()
+      .push(Modification::HideMany(entities.clone()));
+    assert!(HistoryJournal::take_previous(&dir).is_none());
+    assert!(path.exists());
+    HistoryJournal::end_session(&mut world, &path);
+    assert!(HistoryJournal::take_previous(&dir).is_none());
+
+    // One that crashed releases its journal without removing it, and the next session moves
+    // it aside
+    HistoryJournal::begin_session(&mut world, &path);
+    world.fetch_mut::<History>().push(Modification::HideMany(entities));
+    world.fetch_mut::<History>().set_journal(None);
+    let previous = HistoryJournal::take_previous(&dir).unwrap();
+    assert!(!path.exists());
+    assert_eq!(HistoryJournal::read(&previous).unwrap().len(), 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+}
@@ -1,9 +1,18 @@
+mod construction_script;
 mod geometry;
+mod geopad_file;
+mod history_journal;
 mod screen_space;
 mod spatial_hash_table;
+mod style;
+mod system_data;
 mod virtual_space;
 
+pub use construction_script::*;
 pub use geometry::*;
+pub use geopad_file::*;
+pub use history_journal::*;
 pub use screen_space::*;
 pub use spatial_hash_table::*;
+pub use style::*;
 pub use virtual_space::*;
@@ -253,3 +253,198 @@ impl Intersect<ScreenCircle> for ScreenLine {
     c.intersect(l).into()
   }
 }
+
+/// Angles go the other way than in virtual space, since the y axis goes down on the screen
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenArc {
+  pub center: ScreenPosition,
+  pub radius: ScreenScalar,
+  pub start: f64,
+  pub sweep: f64,
+  pub kind: ArcKind,
+}
+
+impl ScreenArc {
+  pub fn circle(self) -> ScreenCircle {
+    ScreenCircle {
+      center: self.center,
+      radius: self.radius,
+    }
+  }
+
+  pub fn contains_point(self, p: ScreenPosition) -> bool {
+    let a: Arc = self.into();
+    a.contains_angle(a.angle_of(p.into()))
+  }
+
+  /// Inside of the filled shape. A plain arc contains nothing
+  pub fn contains(self, p: ScreenPosition) -> bool {
+    let a: Arc = self.into();
+    a.contains(p.into())
+  }
+
+  pub fn closest_point(self, p: ScreenPosition) -> ScreenPosition {
+    let a: Arc = self.into();
+    a.closest_point(p.into()).into()
+  }
+
+  /// How far along the arc the point closest to `p` is, counted from the start of the virtual
+  /// arc, which is the end of this one
+  pub fn fraction_of(self, p: ScreenPosition) -> f64 {
+    let a: Arc = self.into();
+    1.0 - a.fraction_of(p.into())
+  }
+
+  pub fn border_distance(self, p: ScreenPosition) -> ScreenScalar {
+    let a: Arc = self.into();
+    a.border_distance(p.into()).into()
+  }
+
+  pub fn bounding_box(self) -> AABB {
+    let a: Arc = self.into();
+    a.bounding_box()
+  }
+}
+
+impl Into<Arc> for ScreenArc {
+  fn into(self) -> Arc {
+    Arc {
+      center: self.center.into(),
+      radius: self.radius.into(),
+      start: self.start,
+      sweep: self.sweep,
+      kind: self.kind,
+    }
+  }
+}
+
+impl From<Arc> for ScreenArc {
+  fn from(a: Arc) -> Self {
+    Self {
+      center: a.center.into(),
+      radius: a.radius.into(),
+      start: a.start,
+      sweep: a.sweep,
+      kind: a.kind,
+    }
+  }
+}
+
+/// The equation of the conic in screen coordinates, along with the samples drawn for it
+#[derive(Debug, Clone)]
+pub struct ScreenConic {
+  pub conic: Conic,
+  pub paths: Vec<Vec<ScreenPosition>>,
+}
+
+impl ScreenConic {
+  pub fn distance(&self, p: ScreenPosition) -> ScreenScalar {
+    let polyline: Polyline = self.clone().into();
+    polyline.distance(p.into()).into()
+  }
+
+  pub fn bounding_box(&self) -> Option<AABB> {
+    let polyline: Polyline = self.clone().into();
+    polyline.bounding_box()
+  }
+}
+
+impl Into<Polyline> for ScreenConic {
+  fn into(self) -> Polyline {
+    Polyline {
+      paths: self
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
+
+impl Intersect<ScreenLine> for ScreenConic {
+  type Output = ScreenCircleIntersect;
+
+  fn intersect(self, other: ScreenLine) -> Self::Output {
+    let l: Line = other.into();
+    self.conic.intersect(l).into()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScreenPolygon {
+  pub points: Vec<ScreenPosition>,
+}
+
+impl ScreenPolygon {
+  pub fn contains(&self, p: ScreenPosition) -> bool {
+    let poly: Polygon = self.clone().into();
+    poly.contains(p.into())
+  }
+
+  pub fn border_distance(&self, p: ScreenPosition) -> ScreenScalar {
+    let poly: Polygon = self.clone().into();
+    poly.border_distance(p.into()).into()
+  }
+
+  pub fn bounding_box(&self) -> Option<AABB> {
+    let poly: Polygon = self.clone().into();
+    poly.bounding_box()
+  }
+}
+
+impl Into<Polygon> for ScreenPolygon {
+  fn into(self) -> Polygon {
+    Polygon {
+      points: self.points.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<Polygon> for ScreenPolygon {
+  fn from(p: Polygon) -> Self {
+    Self {
+      points: p.points.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScreenLocus {
+  pub paths: Vec<Vec<ScreenPosition>>,
+}
+
+impl ScreenLocus {
+  pub fn distance(&self, p: ScreenPosition) -> ScreenScalar {
+    let polyline: Polyline = self.clone().into();
+    polyline.distance(p.into()).into()
+  }
+
+  pub fn bounding_box(&self) -> Option<AABB> {
+    let polyline: Polyline = self.clone().into();
+    polyline.bounding_box()
+  }
+}
+
+impl Into<Polyline> for ScreenLocus {
+  fn into(self) -> Polyline {
+    Polyline {
+      paths: self
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
+
+impl From<Polyline> for ScreenLocus {
+  fn from(p: Polyline) -> Self {
+    Self {
+      paths: p
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
@@ -160,6 +160,53 @@ impl<T: Clone + Eq + Hash> SpatialHashTable<T> {
     }
   }
 
+  /// A polygon is a filled region, so it is hashed to every tile its bounding box covers
+  pub fn insert_polygon(&mut self, ent: T, p: Polygon) {
+    if let Some(bbox) = p.bounding_box() {
+      self.insert_aabb(ent, bbox);
+    }
+  }
+
+  /// A filled arc is hashed like a polygon, while a plain one only goes to the tiles its circle
+  /// crosses within its bounding box
+  pub fn insert_arc(&mut self, ent: T, a: Arc) {
+    let bbox = a.bounding_box();
+    if a.kind != ArcKind::Arc {
+      self.insert_aabb(ent, bbox);
+      return;
+    }
+    let (left, top) = self.get_tile(bbox.min());
+    let (right, bottom) = self.get_tile(bbox.max());
+    for j in top.max(0)..(bottom.min(self.y_tiles as i64) + 1) {
+      for i in left.max(0)..(right.min(self.x_tiles as i64) + 1) {
+        let tile_aabb = self.tile_to_aabb((i, j));
+        let closest_dist = (tile_aabb.get_closest_point_to(a.center) - a.center).magnitude();
+        let furthest_dist = (tile_aabb.get_furthest_point_to(a.center) - a.center).magnitude();
+        if closest_dist <= a.radius && a.radius <= furthest_dist {
+          self.insert(ent.clone(), (i, j));
+        }
+      }
+    }
+  }
+
+  /// A polyline is hashed like each of its segments
+  pub fn insert_polyline(&mut self, ent: T, p: Polyline) {
+    for segment in p.segments() {
+      self.insert_line(ent.clone(), segment);
+    }
+  }
+
+  /// Hash to every tile the rectangle covers, e.g. for a label
+  pub fn insert_aabb(&mut self, ent: T, aabb: AABB) {
+    let (left, top) = self.get_tile(aabb.min());
+    let (right, bottom) = self.get_tile(aabb.max());
+    for j in top.max(0)..(bottom.min(self.y_tiles as i64) + 1) {
+      for i in left.max(0)..(right.min(self.x_tiles as i64) + 1) {
+        self.insert(ent.clone(), (i, j));
+      }
+    }
+  }
+
   pub fn remove_from_all(&mut self, ent: T) {
     for set in &mut self.table {
       set.remove(&ent);
@@ -0,0 +1,99 @@
+use crate::{components::styles::*, math::*};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum Style {
+  Point(PointStyle),
+  Line(LineStyle),
+  Circle(CircleStyle),
+  Polygon(PolygonStyle),
+}
+
+/// A change to one property of a style. The same change can apply to any kind of element:
+/// the stroke is the line itself or the border of a shape, and the fill is the inside of a
+/// point or a shape.
+#[derive(Debug, Copy, Clone)]
+pub enum StyleUpdate {
+  Color(Color),       // Stroke color
+  Width(f64),         // Stroke width
+  Fill(Color),        // Fill color
+  Radius(f64),        // Point radius
+  Dash(Option<Dash>), // Stroke dash pattern, solid when there's none
+}
+
+impl Style {
+  /// The style with the update applied. Updates that don't make sense for the kind of
+  /// element, like the radius of a line, leave the style as it is
+  pub fn updated(self, update: StyleUpdate) -> Self {
+    match (self, update) {
+      (Style::Point(style), StyleUpdate::Color(color)) => Style::Point(PointStyle {
+        border_color: color,
+        ..style
+      }),
+      (Style::Point(style), StyleUpdate::Width(width)) => Style::Point(PointStyle {
+        border_width: width,
+        ..style
+      }),
+      (Style::Point(style), StyleUpdate::Fill(color)) => Style::Point(PointStyle { color, ..style }),
+      (Style::Point(style), StyleUpdate::Radius(radius)) => Style::Point(PointStyle { radius, ..style }),
+      (Style::Line(style), StyleUpdate::Color(color)) => Style::Line(LineStyle { color, ..style }),
+      (Style::Line(style), StyleUpdate::Width(width)) => Style::Line(LineStyle { width, ..style }),
+      (Style::Line(style), StyleUpdate::Dash(dash)) => Style::Line(LineStyle { dash, ..style }),
+      (Style::Circle(style), StyleUpdate::Color(color)) => Style::Circle(CircleStyle {
+        border: LineStyle { color, ..style.border },
+        ..style
+      }),
+      (Style::Circle(style), StyleUpdate::Width(width)) => Style::Circle(CircleStyle {
+        border: LineStyle { width, ..style.border },
+        ..style
+      }),
+      (Style::Circle(style), StyleUpdate::Dash(dash)) => Style::Circle(CircleStyle {
+        border: LineStyle { dash, ..style.border },
+        ..style
+      }),
+      (Style::Circle(style), StyleUpdate::Fill(fill)) => Style::Circle(CircleStyle { fill, ..style }),
+      (Style::Polygon(style), StyleUpdate::Color(color)) => Style::Polygon(PolygonStyle {
+        border: LineStyle { color, ..style.border },
+        ..style
+      }),
+      (Style::Polygon(style), StyleUpdate::Width(width)) => Style::Polygon(PolygonStyle {
+        border: LineStyle { width, ..style.border },
+        ..style
+      }),
+      (Style::Polygon(style), StyleUpdate::Dash(dash)) => Style::Polygon(PolygonStyle {
+        border: LineStyle { dash, ..style.border },
+        ..style
+      }),
+      (Style::Polygon(style), StyleUpdate::Fill(fill)) => Style::Polygon(PolygonStyle { fill, ..style }),
+      (style, _) => style,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::resources::*;
+
+  #[test]
+  fn test_style_update() {
+    let red = rgb!(1.0, 0.0, 0.0);
+    match Style::Circle(DefaultCircleStyle::default().get()).updated(StyleUpdate::Color(red)) {
+      Style::Circle(style) => assert_eq!(style.border.color, red),
+      _ => panic!("expected a circle style"),
+    }
+    match Style::Circle(DefaultCircleStyle::default().get()).updated(StyleUpdate::Dash(Some(Dash::dotted()))) {
+      Style::Circle(style) => assert_eq!(style.border.dash, Some(Dash::dotted())),
+      _ => panic!("expected a circle style"),
+    }
+    match Style::Point(DefaultPointStyle::default().get()).updated(StyleUpdate::Fill(red)) {
+      Style::Point(style) => assert_eq!(style.color, red),
+      _ => panic!("expected a point style"),
+    }
+    let line_style = DefaultLineStyle::default().get();
+    match Style::Line(line_style).updated(StyleUpdate::Radius(10.0)) {
+      Style::Line(style) => assert_eq!(style.width, line_style.width),
+      _ => panic!("expected a line style"),
+    }
+  }
+}
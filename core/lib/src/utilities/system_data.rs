@@ -0,0 +1,49 @@
+/// Declares a struct of storages fetched together as one `SystemData`, so that a system can pass
+/// all the storages of, say, the virtual shapes around as one argument
+///
+/// ```ignore
+/// system_data! {
+///   pub struct ScreenShapes<'a> {
+///     pub points: ReadStorage<'a, ScreenPoint>,
+///     pub lines: ReadStorage<'a, ScreenLine>,
+///   }
+/// }
+/// ```
+#[macro_export]
+macro_rules! system_data {
+  (
+    $(#[$meta:meta])*
+    $vis:vis struct $name:ident<$lt:lifetime> {
+      $($(#[$field_meta:meta])* $field_vis:vis $field:ident: $ty:ty,)*
+    }
+  ) => {
+    $(#[$meta])*
+    $vis struct $name<$lt> {
+      $($(#[$field_meta])* $field_vis $field: $ty,)*
+    }
+
+    impl<$lt> ::specs::shred::SystemData<$lt> for $name<$lt> {
+      fn setup(world: &mut ::specs::World) {
+        $(<$ty as ::specs::shred::SystemData<$lt>>::setup(world);)*
+      }
+
+      fn fetch(world: &$lt ::specs::World) -> Self {
+        Self {
+          $($field: <$ty as ::specs::shred::SystemData<$lt>>::fetch(world),)*
+        }
+      }
+
+      fn reads() -> Vec<::specs::shred::ResourceId> {
+        let mut reads = Vec::new();
+        $(reads.extend(<$ty as ::specs::shred::SystemData<$lt>>::reads());)*
+        reads
+      }
+
+      fn writes() -> Vec<::specs::shred::ResourceId> {
+        let mut writes = Vec::new();
+        $(writes.extend(<$ty as ::specs::shred::SystemData<$lt>>::writes());)*
+        writes
+      }
+    }
+  };
+}
@@ -96,6 +96,13 @@ impl Into<Line> for VirtualLine {
   }
 }
 
+impl VirtualLine {
+  pub fn is_degenerate(self) -> bool {
+    let l: Line = self.into();
+    l.is_degenerate()
+  }
+}
+
 impl From<Line> for VirtualLine {
   fn from(l: Line) -> Self {
     Self {
@@ -131,6 +138,18 @@ impl Into<Circle> for VirtualCircle {
   }
 }
 
+impl VirtualCircle {
+  pub fn is_degenerate(self) -> bool {
+    let c: Circle = self.into();
+    c.is_degenerate()
+  }
+
+  pub fn tangent_points(self, p: VirtualPosition) -> VirtualCircleIntersect {
+    let c: Circle = self.into();
+    c.tangent_points(p.into()).into()
+  }
+}
+
 impl From<Circle> for VirtualCircle {
   fn from(c: Circle) -> Self {
     Self {
@@ -186,3 +205,129 @@ impl Intersect<VirtualCircle> for VirtualLine {
     c.intersect(l).into()
   }
 }
+
+/// Angles are counterclockwise in virtual space, see `Arc`
+#[derive(Debug, Clone, Copy)]
+pub struct VirtualArc {
+  pub center: VirtualPosition,
+  pub radius: VirtualScalar,
+  pub start: f64,
+  pub sweep: f64,
+  pub kind: ArcKind,
+}
+
+impl Into<Arc> for VirtualArc {
+  fn into(self) -> Arc {
+    Arc {
+      center: self.center.into(),
+      radius: self.radius.into(),
+      start: self.start,
+      sweep: self.sweep,
+      kind: self.kind,
+    }
+  }
+}
+
+impl From<Arc> for VirtualArc {
+  fn from(a: Arc) -> Self {
+    Self {
+      center: a.center.into(),
+      radius: a.radius.into(),
+      start: a.start,
+      sweep: a.sweep,
+      kind: a.kind,
+    }
+  }
+}
+
+impl VirtualArc {
+  pub fn is_degenerate(self) -> bool {
+    let a: Arc = self.into();
+    a.is_degenerate()
+  }
+
+  pub fn circle(self) -> VirtualCircle {
+    VirtualCircle {
+      center: self.center,
+      radius: self.radius,
+    }
+  }
+
+  pub fn contains_point(self, p: VirtualPosition) -> bool {
+    let a: Arc = self.into();
+    a.contains_angle(a.angle_of(p.into()))
+  }
+
+  pub fn point_at_fraction(self, fraction: f64) -> VirtualPosition {
+    let a: Arc = self.into();
+    a.point_at_fraction(fraction).into()
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VirtualConic(pub Conic);
+
+impl VirtualConic {
+  pub fn is_degenerate(self) -> bool {
+    self.0.is_degenerate()
+  }
+}
+
+impl Intersect<VirtualLine> for VirtualConic {
+  type Output = VirtualCircleIntersect;
+
+  fn intersect(self, other: VirtualLine) -> Self::Output {
+    let l: Line = other.into();
+    self.0.intersect(l).into()
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct VirtualPolygon {
+  pub points: Vec<VirtualPosition>,
+}
+
+impl Into<Polygon> for VirtualPolygon {
+  fn into(self) -> Polygon {
+    Polygon {
+      points: self.points.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+impl From<Polygon> for VirtualPolygon {
+  fn from(p: Polygon) -> Self {
+    Self {
+      points: p.points.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct VirtualLocus {
+  pub paths: Vec<Vec<VirtualPosition>>,
+}
+
+impl Into<Polyline> for VirtualLocus {
+  fn into(self) -> Polyline {
+    Polyline {
+      paths: self
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
+
+impl From<Polyline> for VirtualLocus {
+  fn from(p: Polyline) -> Self {
+    Self {
+      paths: p
+        .paths
+        .into_iter()
+        .map(|path| path.into_iter().map(Into::into).collect())
+        .collect(),
+    }
+  }
+}
@@ -0,0 +1,16 @@
+use crate::resources::Animation;
+use shrev::*;
+use specs::prelude::*;
+
+pub enum AnimationEvent {
+  Animate(Entity, Animation), // Only points on lines or on circles are animated
+  Unanimate(Entity),          // Records where the point went, when it moved
+  Play,
+  Pause,
+  Step, // Move the points by one step, usually while paused
+  Stop, // Pause and record the positions of all the points as one modification
+}
+
+pub type AnimationEventChannel = EventChannel<AnimationEvent>;
+
+pub type AnimationEventReader = ReaderId<AnimationEvent>;
@@ -0,0 +1,435 @@
+use crate::{events::*, resources::*, setup_core_ui};
+use core_lib::{events::*, math::*, resources::*, utilities::*};
+use specs::prelude::*;
+use std::collections::HashMap;
+
+/// Runs the full core-ui dispatcher without any window, for tests. The input state and the
+/// mouse events are driven the same way the front ends drive them, and every geometry and
+/// marker event emitted during the frames is kept until it's cleared.
+pub struct Harness<'a, 'b> {
+  pub world: World,
+  dispatcher: Dispatcher<'a, 'b>,
+  geometry_event_reader: GeometryEventReader,
+  marker_event_reader: MarkerEventReader,
+  geometry_events: Vec<GeometryEvent>,
+  marker_events: Vec<MarkerEvent>,
+}
+
+impl<'a, 'b> Default for Harness<'a, 'b> {
+  fn default() -> Self {
+    let mut world = World::new();
+    let mut builder = DispatcherBuilder::new();
+    setup_core_ui(&mut builder);
+    let mut dispatcher = builder.build();
+    dispatcher.setup(&mut world);
+    let geometry_event_reader = world.fetch_mut::<GeometryEventChannel>().register_reader();
+    let marker_event_reader = world.fetch_mut::<MarkerEventChannel>().register_reader();
+    Self {
+      world,
+      dispatcher,
+      geometry_event_reader,
+      marker_event_reader,
+      geometry_events: vec![],
+      marker_events: vec![],
+    }
+  }
+}
+
+impl<'a, 'b> Harness<'a, 'b> {
+  /// Run one frame, then forget the relative input like the window does before the next one
+  pub fn step(&mut self) {
+    self.dispatcher.dispatch(&self.world);
+    self.world.maintain();
+    let geometry_event_channel = self.world.fetch::<GeometryEventChannel>();
+    self
+      .geometry_events
+      .extend(geometry_event_channel.read(&mut self.geometry_event_reader).cloned());
+    let marker_event_channel = self.world.fetch::<MarkerEventChannel>();
+    self
+      .marker_events
+      .extend(marker_event_channel.read(&mut self.marker_event_reader).cloned());
+    self.world.fetch_mut::<InputState>().reset_relative_data();
+  }
+
+  pub fn step_frames(&mut self, count: usize) {
+    for _ in 0..count {
+      self.step();
+    }
+  }
+
+  pub fn command(&mut self, command: Command) {
+    self
+      .world
+      .fetch_mut::<CommandEventChannel>()
+      .single_write(CommandEvent {
+        command,
+        event_id: None,
+      });
+  }
+
+  pub fn commands(&mut self, commands: Vec<CommandEvent>) {
+    self.world.fetch_mut::<CommandEventChannel>().iter_write(commands);
+  }
+
+  /// Change the input state directly, for what the helpers below don't cover
+  pub fn input<F: FnOnce(&mut InputState)>(&mut self, change: F) {
+    change(&mut self.world.fetch_mut::<InputState>());
+  }
+
+  /// Insert the elements of a construction script and run a frame. Panics when the script
+  /// doesn't compile, and gives the entity of every name otherwise
+  pub fn insert_script(&mut self, source: &str) -> HashMap<String, Entity> {
+    let styles = FileDefaultStyles {
+      point: self.world.fetch::<DefaultPointStyle>().get(),
+      line: self.world.fetch::<DefaultLineStyle>().get(),
+      circle: self.world.fetch::<DefaultCircleStyle>().get(),
+      polygon: self.world.fetch::<DefaultPolygonStyle>().get(),
+    };
+    let script = {
+      let entities = self.world.entities();
+      match CompiledScript::compile(source, styles, || entities.create()) {
+        Ok(script) => script,
+        Err(err) => panic!("{}", err),
+      }
+    };
+    let names = script.write(
+      &mut self.world.fetch_mut::<CommandEventChannel>(),
+      &mut self.world.fetch_mut::<HistoryEventChannel>(),
+    );
+    self.step();
+    names
+  }
+
+  pub fn change_tool(&mut self, tool: Tool) {
+    self
+      .world
+      .fetch_mut::<ToolChangeEventChannel>()
+      .single_write(ToolChangeEvent(tool));
+    self.step();
+  }
+
+  /// Move the mouse without running a frame. It starts a drag when the left button is held
+  pub fn mouse_move(&mut self, position: ScreenPosition) {
+    let mut input_state = self.world.fetch_mut::<InputState>();
+    let mut mouse_event_channel = self.world.fetch_mut::<MouseEventChannel>();
+    let movement = ScreenPosition(position.0 - input_state.mouse_abs_pos.0);
+    input_state.mouse_abs_pos = position;
+    input_state.mouse_rel_movement = input_state.mouse_rel_movement + movement;
+    if input_state.is_mouse_left_button_dragging {
+      mouse_event_channel.single_write(MouseEvent::DragMove(movement, position));
+    } else if input_state.mouse_left_button.is_activated() {
+      input_state.is_mouse_left_button_dragging = true;
+      mouse_event_channel.single_write(MouseEvent::DragBegin(position));
+    }
+  }
+
+  pub fn mouse_down(&mut self) {
+    let mut input_state = self.world.fetch_mut::<InputState>();
+    input_state.mouse_left_button.set(true);
+    let position = input_state.mouse_abs_pos;
+    self
+      .world
+      .fetch_mut::<MouseEventChannel>()
+      .single_write(MouseEvent::MouseDown(position));
+  }
+
+  /// Releasing the button ends the drag, or clicks when there was no drag
+  pub fn mouse_up(&mut self) {
+    let mut input_state = self.world.fetch_mut::<InputState>();
+    let mut mouse_event_channel = self.world.fetch_mut::<MouseEventChannel>();
+    input_state.mouse_left_button.set(false);
+    let position = input_state.mouse_abs_pos;
+    mouse_event_channel.single_write(MouseEvent::MouseUp(position));
+    if input_state.is_mouse_left_button_dragging {
+      input_state.is_mouse_left_button_dragging = false;
+      mouse_event_channel.single_write(MouseEvent::DragEnd(position));
+    } else {
+      mouse_event_channel.single_write(MouseEvent::Click(position));
+    }
+  }
+
+  /// Move to `position`, press and release the left button, one frame each
+  pub fn click(&mut self, position: ScreenPosition) {
+    self.mouse_move(position);
+    self.step();
+    self.mouse_down();
+    self.step();
+    self.mouse_up();
+    self.step();
+  }
+
+  /// Press at `from` and release at `to`, moving in `moves` frames. The drag begins at
+  /// `from`, before the first move
+  pub fn drag(&mut self, from: ScreenPosition, to: ScreenPosition, moves: usize) {
+    self.mouse_move(from);
+    self.step();
+    self.mouse_down();
+    self.step();
+    self.mouse_move(from);
+    self.step();
+    let moves = moves.max(1);
+    for i in 1..=moves {
+      let t = i as f64 / moves as f64;
+      self.mouse_move(ScreenPosition(from.0 + (to.0 - from.0) * t));
+      self.step();
+    }
+    self.mouse_up();
+    self.step();
+  }
+
+  pub fn key_down(&mut self, key: Key) {
+    self.world.fetch_mut::<InputState>().keyboard.set(key, true);
+  }
+
+  pub fn key_up(&mut self, key: Key) {
+    self.world.fetch_mut::<InputState>().keyboard.set(key, false);
+  }
+
+  /// Press and release a key, one frame each
+  pub fn press(&mut self, key: Key) {
+    self.key_down(key);
+    self.step();
+    self.key_up(key);
+    self.step();
+  }
+
+  /// A copy of the component of `ent`, if it has one
+  pub fn get<T: Component + Clone>(&self, ent: Entity) -> Option<T> {
+    self.world.read_storage::<T>().get(ent).cloned()
+  }
+
+  /// Where a virtual position is on the screen
+  pub fn to_screen(&self, position: Vector2) -> ScreenPosition {
+    VirtualPosition(position).to_screen(&self.world.fetch::<Viewport>())
+  }
+
+  pub fn geometry_events(&self) -> &[GeometryEvent] {
+    &self.geometry_events
+  }
+
+  pub fn marker_events(&self) -> &[MarkerEvent] {
+    &self.marker_events
+  }
+
+  pub fn clear_events(&mut self) {
+    self.geometry_events.clear();
+    self.marker_events.clear();
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use core_lib::components::{labels::*, markers::*, screen_shapes::*, symbolics::*, virtual_shapes::*};
+
+  #[test]
+  fn test_select_via_mouse() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(3, 1)");
+    let (a, b) = (names["A"], names["B"]);
+    assert!(harness.get::<Selected>(a).is_none());
+
+    harness.clear_events();
+    harness.click(harness.to_screen(vec2![0., 0.]));
+    assert!(harness.get::<Selected>(a).is_some());
+    assert!(harness
+      .marker_events()
+      .iter()
+      .any(|event| matches!(event, MarkerEvent::Select(ent) if *ent == a)));
+
+    // Shift adds to the selection, and clicking on nothing deselects everything
+    harness.key_down(Key::LShift);
+    harness.click(harness.to_screen(vec2![3., 1.]));
+    harness.key_up(Key::LShift);
+    assert!(harness.get::<Selected>(a).is_some() && harness.get::<Selected>(b).is_some());
+    harness.click(harness.to_screen(vec2![-5., 5.]));
+    assert!(harness.get::<Selected>(a).is_none() && harness.get::<Selected>(b).is_none());
+  }
+
+  #[test]
+  fn test_move_point_via_drag() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(3, 1); m = midpoint(A, B)");
+    let (a, m) = (names["A"], names["m"]);
+
+    harness.clear_events();
+    harness.drag(harness.to_screen(vec2![0., 0.]), harness.to_screen(vec2![1., 3.]), 5);
+    match harness.get::<SymbolicPoint>(a) {
+      Some(SymbolicPoint::Free(position)) => assert!((position.0 - vec2![1., 3.]).magnitude() < 1e-9),
+      _ => panic!("expected a free point"),
+    }
+
+    // The dependent point follows, and the whole drag is one update in the history
+    let midpoint = harness.get::<VirtualPoint>(m).unwrap();
+    assert!((midpoint.0 - vec2![2., 2.]).magnitude() < 1e-9);
+    let finished = harness
+      .geometry_events()
+      .iter()
+      .filter(|event| matches!(event, GeometryEvent::PointUpdateFinished(ent, _, _, false) if *ent == a))
+      .count();
+    assert_eq!(finished, 1);
+    assert!(harness.world.fetch_mut::<History>().undo().is_some());
+  }
+
+  #[test]
+  fn test_animate_point_on_segment() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(4, 0); s = segment(A, B); P = on_line(s, 0.5)");
+    let p = names["P"];
+    let history_len = harness.world.fetch::<History>().len();
+
+    let animation = Animation {
+      speed: 1.0,
+      mode: AnimationMode::Bounce,
+    };
+    harness.world.fetch_mut::<DeltaTime>().set(0.2);
+    harness
+      .world
+      .fetch_mut::<AnimationEventChannel>()
+      .iter_write(vec![AnimationEvent::Animate(p, animation), AnimationEvent::Play]);
+    harness.step_frames(4);
+    match harness.get::<SymbolicPoint>(p) {
+      Some(SymbolicPoint::OnLine(_, VirtualScalar(t))) => assert!((t - 0.9).abs() < 1e-9),
+      _ => panic!("expected a point on line"),
+    }
+    assert_eq!(harness.world.fetch::<History>().len(), history_len);
+
+    // It moves once more in the frame it stops, stays there, and one undo brings it back
+    harness
+      .world
+      .fetch_mut::<AnimationEventChannel>()
+      .single_write(AnimationEvent::Stop);
+    harness.step_frames(2);
+    assert!(!harness.world.fetch::<AnimationState>().is_playing());
+    assert_eq!(harness.world.fetch::<History>().len(), history_len + 1);
+    let point = harness.get::<VirtualPoint>(p).unwrap();
+    assert!((point.0 - vec2![2.8, 0.]).magnitude() < 1e-9);
+    harness
+      .world
+      .fetch_mut::<HistoryEventChannel>()
+      .single_write(HistoryEvent::Undo);
+    harness.step();
+    let point = harness.get::<VirtualPoint>(p).unwrap();
+    assert!((point.0 - vec2![2., 0.]).magnitude() < 1e-9);
+  }
+
+  #[test]
+  fn test_measurement_label() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(3, 4)");
+    let (a, b) = (names["A"], names["B"]);
+    harness.clear_events();
+    harness.command(Command::MeasurementInsert(InsertMeasurementEvent::InsertMeasurement(
+      SymbolicMeasurement::Distance(a, b),
+    )));
+    harness.step();
+    let distance = harness
+      .geometry_events()
+      .iter()
+      .find_map(|event| match event {
+        GeometryEvent::Inserted(ent, Geometry::Measurement(_), _) => Some(*ent),
+        _ => None,
+      })
+      .unwrap();
+    assert_eq!(harness.get::<ScreenLabel>(distance).unwrap().text, "5.00");
+
+    // The value follows the points, and stays between them
+    harness.drag(harness.to_screen(vec2![3., 4.]), harness.to_screen(vec2![6., 8.]), 3);
+    let label = harness.get::<ScreenLabel>(distance).unwrap();
+    assert_eq!(label.text, "10.00");
+    let middle = (harness.to_screen(vec2![0., 0.]).0 + harness.to_screen(vec2![6., 8.]).0) / 2.0;
+    assert!((label.rect.min() - (middle + MEASUREMENT_LABEL_OFFSET)).magnitude() < 1e-9);
+  }
+
+  #[test]
+  fn test_undo_excircles_at_once() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(4, 0); C = free(1, 3)");
+    harness.command(Command::Select(SelectEvent::DeselectAll));
+    for name in &["A", "B", "C"] {
+      harness.command(Command::Select(SelectEvent::Select(names[*name])));
+    }
+    harness.step();
+    let history_len = harness.world.fetch::<History>().len();
+
+    harness.clear_events();
+    harness.command(Command::CircleInsert(
+      InsertCircleEvent::InsertTriangleCircleFromSelection(TriangleCircle::Excircles),
+    ));
+    harness.step();
+    let excircles = harness
+      .geometry_events()
+      .iter()
+      .filter_map(|event| match event {
+        GeometryEvent::Inserted(ent, Geometry::Circle(_, _), false) => Some(*ent),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(excircles.len(), 3);
+    assert_eq!(harness.world.fetch::<History>().len(), history_len + 1);
+
+    harness
+      .world
+      .fetch_mut::<HistoryEventChannel>()
+      .single_write(HistoryEvent::Undo);
+    harness.step_frames(2);
+    assert!(excircles
+      .iter()
+      .all(|ent| harness.get::<SymbolicCircle>(*ent).is_none()));
+    assert_eq!(harness.world.fetch::<History>().len(), history_len + 1);
+  }
+
+  #[test]
+  fn test_undo_script_at_once() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(4, 0)\nl = perp(AB, A)\nc = circle(A, B)");
+    assert_eq!(harness.world.fetch::<History>().len(), 1);
+
+    harness
+      .world
+      .fetch_mut::<HistoryEventChannel>()
+      .single_write(HistoryEvent::Undo);
+    harness.step_frames(2);
+    assert!(["A", "B"]
+      .iter()
+      .all(|name| harness.get::<SymbolicPoint>(names[*name]).is_none()));
+    assert!(["AB", "l"]
+      .iter()
+      .all(|name| harness.get::<SymbolicLine>(names[*name]).is_none()));
+    assert!(harness.get::<SymbolicCircle>(names["c"]).is_none());
+  }
+
+  #[test]
+  fn test_change_animation_speed() {
+    let mut harness = Harness::default();
+    let names = harness.insert_script("A = free(0, 0); B = free(4, 0); s = segment(A, B); P = on_line(s, 0.5)");
+    let (p, s) = (names["P"], names["s"]);
+    harness.command(Command::Select(SelectEvent::Select(p)));
+    harness.step();
+    let command = if cfg!(target_os = "macos") {
+      Key::LCommand
+    } else {
+      Key::LCtrl
+    };
+    harness.key_down(command);
+    harness.key_down(Key::LShift);
+    harness.press(Key::P);
+    let speed = harness.world.fetch::<AnimationState>().get(p).unwrap().speed;
+
+    // Only the selected points that are animated change
+    harness.command(Command::Select(SelectEvent::Select(s)));
+    harness.step();
+    harness.press(Key::RightBracket);
+    harness.press(Key::RightBracket);
+    assert_eq!(
+      harness.world.fetch::<AnimationState>().get(p).unwrap().speed,
+      speed * 4.0
+    );
+    assert!(harness.world.fetch::<AnimationState>().get(s).is_none());
+    harness.press(Key::LeftBracket);
+    assert_eq!(
+      harness.world.fetch::<AnimationState>().get(p).unwrap().speed,
+      speed * 2.0
+    );
+  }
+}
@@ -7,6 +7,7 @@ use core_lib::setup_core_lib;
 use specs::prelude::*;
 
 pub mod events;
+pub mod harness;
 pub mod resources;
 pub mod systems;
 pub mod utilities;
@@ -38,15 +39,35 @@ pub fn setup_core_ui<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
     "move_viewport_via_scroll",
     &[],
   );
+  builder.add(
+    interactions::viewport::ZoomViewportViaScroll::default(),
+    "zoom_viewport_via_scroll",
+    &[],
+  );
+  builder.add(
+    interactions::viewport::ZoomViewportViaKeyboard::default(),
+    "zoom_viewport_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::viewport::ToggleGridViaKeyboard::default(),
+    "toggle_grid_via_keyboard",
+    &[],
+  );
   builder.add(
     interactions::history::UndoRedoViaKeyboard::default(),
     "undo_redo_via_keyboard",
     &[],
   );
+  builder.add(
+    interactions::animation::AnimateViaKeyboard::default(),
+    "animate_via_keyboard",
+    &[],
+  );
   builder.add(
     interactions::geometry::point::SnapPointViaMouse::default(),
     "snap_point_via_mouse",
-    &[],
+    &["toggle_grid_via_keyboard"],
   );
   builder.add(interactions::marker::SeldeViaMouse::default(), "selde_via_mouse", &[]);
   builder.add(
@@ -61,6 +82,11 @@ pub fn setup_core_ui<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
   );
 
   // Geometry interactions (not depend on snap point)
+  builder.add(
+    interactions::label::MoveLabelViaDrag::default(),
+    "move_label_via_drag",
+    &[],
+  );
   builder.add(
     interactions::geometry::point::MovePointViaDrag::default(),
     "move_point_via_drag",
@@ -71,6 +97,16 @@ pub fn setup_core_ui<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
     "create_midpoint_via_keyboard",
     &[],
   );
+  builder.add(
+    interactions::geometry::point::CreateTriangleCenterViaKeyboard::default(),
+    "create_triangle_center_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::circle::CreateCompassCircleViaKeyboard::default(),
+    "create_compass_circle_via_keyboard",
+    &[],
+  );
   builder.add(
     interactions::geometry::line::CreateParallelViaKeyboard::default(),
     "create_parallel_via_keyboard",
@@ -81,6 +117,76 @@ pub fn setup_core_ui<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
     "create_perpendicular_via_keyboard",
     &[],
   );
+  builder.add(
+    interactions::geometry::line::CreateAngleBisectorViaKeyboard::default(),
+    "create_angle_bisector_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::line::CreatePerpendicularBisectorViaKeyboard::default(),
+    "create_perpendicular_bisector_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::line::CreateTangentsViaKeyboard::default(),
+    "create_tangents_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::circle::CreateThreePointCircleViaKeyboard::default(),
+    "create_three_point_circle_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::circle::CreateTriangleCircleViaKeyboard::default(),
+    "create_triangle_circle_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::arc::CreateArcViaKeyboard::default(),
+    "create_arc_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::conic::CreateConicViaKeyboard::default(),
+    "create_conic_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::polygon::CreatePolygonViaKeyboard::default(),
+    "create_polygon_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::locus::CreateLocusViaKeyboard::default(),
+    "create_locus_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::measurement::CreateAngleMeasurementViaKeyboard::default(),
+    "create_angle_measurement_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::measurement::CreateAreaMeasurementViaKeyboard::default(),
+    "create_area_measurement_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::measurement::CreateDistanceMeasurementViaKeyboard::default(),
+    "create_distance_measurement_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::measurement::CreatePerimeterMeasurementViaKeyboard::default(),
+    "create_perimeter_measurement_via_keyboard",
+    &[],
+  );
+  builder.add(
+    interactions::geometry::measurement::CreateRatioMeasurementViaKeyboard::default(),
+    "create_ratio_measurement_via_keyboard",
+    &[],
+  );
   builder.add(
     interactions::geometry::RemoveSelectedViaKeyboard::default(),
     "remove_selected_via_keyboard",
@@ -126,6 +232,13 @@ pub fn setup_core_ui<'a, 'b>(builder: &mut DispatcherBuilder<'a, 'b>) {
     &["change_tool_via_keyboard", "change_line_tool_via_keyboard"],
   );
 
+  // Animations
+  builder.add(
+    animations::AnimatePoints::default(),
+    "animate_points",
+    &["animate_via_keyboard"],
+  );
+
   // Setup the core library
   setup_core_lib(builder);
 
@@ -0,0 +1,179 @@
+use core_lib::{components::symbolics::SymbolicPoint, utilities::VirtualScalar};
+use specs::prelude::*;
+use std::{collections::HashMap, f64::consts::PI};
+
+static DEFAULT_LINE_SPEED: f64 = 0.25;
+static DEFAULT_CIRCLE_SPEED: f64 = PI / 4.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AnimationMode {
+  Loop,   // Jump back to the start at the end
+  Bounce, // Turn back at either end
+}
+
+/// How a point moves along its line, circle or arc. The speed is in parameter per second: the
+/// fraction of the way from the first point of the line to the second one, radians around
+/// the circle, or the fraction of the arc. A negative speed goes the other way.
+///
+/// On a line the parameter stays between the two points, even for straight lines and rays.
+/// On a circle it stays within one turn
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Animation {
+  pub speed: f64,
+  pub mode: AnimationMode,
+}
+
+impl Animation {
+  /// Points on lines and arcs bounce between their ends, points on circles go round. Other
+  /// points cannot be animated
+  pub fn default_for(sym_point: &SymbolicPoint) -> Option<Self> {
+    match sym_point {
+      SymbolicPoint::OnLine(_, _) | SymbolicPoint::OnArc(_, _) => Some(Self {
+        speed: DEFAULT_LINE_SPEED,
+        mode: AnimationMode::Bounce,
+      }),
+      SymbolicPoint::OnCircle(_, _) => Some(Self {
+        speed: DEFAULT_CIRCLE_SPEED,
+        mode: AnimationMode::Loop,
+      }),
+      _ => None,
+    }
+  }
+
+  /// The point after `dt` seconds when going in `direction`, and the direction to go on with.
+  /// Nothing when the point doesn't move
+  pub fn advance(&self, sym_point: SymbolicPoint, direction: f64, dt: f64) -> Option<(SymbolicPoint, f64)> {
+    if self.speed == 0.0 || dt <= 0.0 {
+      return None;
+    }
+    match sym_point {
+      SymbolicPoint::OnLine(l_ent, VirtualScalar(t)) => {
+        let (t, direction) = self.advance_param(t, direction, dt, 1.0);
+        Some((SymbolicPoint::OnLine(l_ent, VirtualScalar(t)), direction))
+      }
+      SymbolicPoint::OnCircle(c_ent, theta) => {
+        let (theta, direction) = self.advance_param(theta, direction, dt, 2.0 * PI);
+        Some((SymbolicPoint::OnCircle(c_ent, theta), direction))
+      }
+      SymbolicPoint::OnArc(a_ent, fraction) => {
+        let (fraction, direction) = self.advance_param(fraction, direction, dt, 1.0);
+        Some((SymbolicPoint::OnArc(a_ent, fraction), direction))
+      }
+      _ => None,
+    }
+  }
+
+  fn advance_param(&self, param: f64, direction: f64, dt: f64, length: f64) -> (f64, f64) {
+    let forward = self.speed * direction >= 0.0;
+    let distance = self.speed.abs() * dt;
+    match self.mode {
+      AnimationMode::Loop => {
+        let param = if forward { param + distance } else { param - distance };
+        (param.rem_euclid(length), direction)
+      }
+      AnimationMode::Bounce => {
+        // Unfold the way there and back into one turn of twice the length
+        let unfolded = if forward { param } else { 2.0 * length - param };
+        let unfolded = (unfolded + distance).rem_euclid(2.0 * length);
+        let going_up = unfolded <= length;
+        let param = if going_up { unfolded } else { 2.0 * length - unfolded };
+        let direction = if going_up == (self.speed >= 0.0) { 1.0 } else { -1.0 };
+        (param, direction)
+      }
+    }
+  }
+}
+
+pub struct AnimatedPoint {
+  pub animation: Animation,
+  pub direction: f64,
+
+  // Where the point was before it started moving, to record one modification on stop
+  pub start: Option<SymbolicPoint>,
+}
+
+/// The points being animated, and whether they are playing. It's changed through the
+/// `AnimationEvent` channel
+pub struct AnimationState {
+  playing: bool,
+  points: HashMap<Entity, AnimatedPoint>,
+}
+
+impl Default for AnimationState {
+  fn default() -> Self {
+    Self {
+      playing: false,
+      points: HashMap::new(),
+    }
+  }
+}
+
+impl AnimationState {
+  pub fn is_playing(&self) -> bool {
+    self.playing
+  }
+
+  pub fn set_playing(&mut self, playing: bool) {
+    self.playing = playing;
+  }
+
+  pub fn get(&self, ent: Entity) -> Option<Animation> {
+    self.points.get(&ent).map(|animated| animated.animation)
+  }
+
+  /// Start animating the point, or change its animation while keeping where it started
+  pub fn animate(&mut self, ent: Entity, animation: Animation) {
+    let animated = self.points.entry(ent).or_insert(AnimatedPoint {
+      animation,
+      direction: 1.0,
+      start: None,
+    });
+    animated.animation = animation;
+  }
+
+  pub fn unanimate(&mut self, ent: Entity) -> Option<AnimatedPoint> {
+    self.points.remove(&ent)
+  }
+
+  pub fn points_mut(&mut self) -> impl Iterator<Item = (&Entity, &mut AnimatedPoint)> {
+    self.points.iter_mut()
+  }
+
+  pub fn retain<F: FnMut(&Entity, &mut AnimatedPoint) -> bool>(&mut self, keep: F) {
+    self.points.retain(keep);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_animation_advance() {
+    let line = World::new().create_entity().build();
+    let bounce = Animation {
+      speed: 0.5,
+      mode: AnimationMode::Bounce,
+    };
+    let param = |result: Option<(SymbolicPoint, f64)>| match result {
+      Some((SymbolicPoint::OnLine(_, VirtualScalar(t)), direction)) => (t, direction),
+      _ => panic!("expected a point on line"),
+    };
+
+    // Going past the end turns back, and going past the start turns forward again
+    let (t, direction) = param(bounce.advance(SymbolicPoint::OnLine(line, VirtualScalar(0.8)), 1.0, 1.0));
+    assert!((t - 0.7).abs() < 1e-10 && direction < 0.0);
+    let (t, direction) = param(bounce.advance(SymbolicPoint::OnLine(line, VirtualScalar(t)), direction, 1.0));
+    assert!((t - 0.2).abs() < 1e-10 && direction < 0.0);
+    let (t, direction) = param(bounce.advance(SymbolicPoint::OnLine(line, VirtualScalar(t)), direction, 1.0));
+    assert!((t - 0.3).abs() < 1e-10 && direction > 0.0);
+
+    // A negative speed starts the other way, and looping wraps around
+    let backwards = Animation {
+      speed: -0.5,
+      mode: AnimationMode::Loop,
+    };
+    let (t, direction) = param(backwards.advance(SymbolicPoint::OnLine(line, VirtualScalar(0.2)), 1.0, 1.0));
+    assert!((t - 0.7).abs() < 1e-10 && direction > 0.0);
+  }
+}
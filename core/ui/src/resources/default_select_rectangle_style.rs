@@ -10,6 +10,7 @@ impl Default for DefaultSelectRectangleStyle {
       border: LineStyle {
         color: rgba!(0.0, 0.0, 0.0, 0.2),
         width: 1.0,
+        dash: None,
       },
     })
   }
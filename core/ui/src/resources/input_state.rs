@@ -12,6 +12,7 @@ pub struct InputState {
   pub mouse_abs_pos: ScreenPosition,
   pub mouse_rel_movement: ScreenPosition,
   pub rel_scroll: Vector2,
+  pub rel_pinch: f64, // How much the fingers spread apart, negative when they get closer
   pub in_focus: ActiveState,
   pub keyboard: Keyboard,
 }
@@ -27,6 +28,7 @@ impl Default for InputState {
       mouse_rel_movement: vec2![0., 0.].into(),
       in_focus: ActiveState::default(),
       rel_scroll: vec2![0., 0.],
+      rel_pinch: 0.0,
       keyboard: Keyboard::default(),
     }
   }
@@ -39,6 +41,7 @@ impl InputState {
     self.mouse_rel_movement = vec2![0., 0.].into();
     self.in_focus.reset_relative_data();
     self.rel_scroll = vec2![0., 0.];
+    self.rel_pinch = 0.0;
     self.keyboard.reset_relative_data();
   }
 }
@@ -133,6 +136,11 @@ impl Keyboard {
     self.is_activated(Key::LShift) || self.is_activated(Key::RShift)
   }
 
+  /// Unlike `is_command_activated`, this is the control key on every platform
+  pub fn is_ctrl_activated(&self) -> bool {
+    self.is_activated(Key::LCtrl) || self.is_activated(Key::RCtrl)
+  }
+
   pub fn is_command_activated(&self) -> bool {
     if cfg!(target_os = "macos") {
       self.is_activated(Key::LCommand) || self.is_activated(Key::RCommand)
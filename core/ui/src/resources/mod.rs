@@ -1,3 +1,4 @@
+mod animation_state;
 mod default_select_rectangle_style;
 mod delta_time;
 mod exit_state;
@@ -8,6 +9,7 @@ mod snap_line;
 mod snap_point;
 mod tool_state;
 
+pub use animation_state::*;
 pub use default_select_rectangle_style::*;
 pub use delta_time::*;
 pub use exit_state::*;
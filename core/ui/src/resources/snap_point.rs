@@ -37,5 +37,10 @@ pub enum SnapPointType {
   SnapOnCircle(Entity, f64),                                         // f64 is theta
   SnapOnCircleLineIntersection(Entity, Entity, CircleIntersectId),   // Circle, Line, type
   SnapOnCircleCircleIntersection(Entity, Entity, CircleIntersectId), // Circle, Circle, type
+  SnapOnArc(Entity, f64),                                            // f64 is the fraction along the arc
+  SnapOnArcLineIntersection(Entity, Entity, CircleIntersectId),      // Arc, Line, type
+  SnapOnArcCircleIntersection(Entity, Entity, CircleIntersectId),    // Arc, Circle, type
+  SnapOnConicLineIntersection(Entity, Entity, CircleIntersectId),    // Conic, Line, type
+  SnapOnGrid(VirtualPosition),                                       // Lattice point of the grid
   NotSnapped,
 }
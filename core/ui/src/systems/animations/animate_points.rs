@@ -0,0 +1,134 @@
+use crate::{events::*, resources::*};
+use core_lib::{components::symbolics::*, events::*};
+use specs::prelude::*;
+use std::collections::HashMap;
+
+static STEP_TIME: f64 = 0.1; // Seconds
+
+/// Moves the animated points along their lines and circles. Every frame is an `UpdatePoint`,
+/// which stays out of the history. Stopping records where the points went as one modification
+pub struct AnimatePoints {
+  animation_event_reader: Option<AnimationEventReader>,
+}
+
+impl Default for AnimatePoints {
+  fn default() -> Self {
+    Self {
+      animation_event_reader: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for AnimatePoints {
+  type SystemData = (
+    Read<'a, AnimationEventChannel>,
+    Read<'a, DeltaTime>,
+    Write<'a, AnimationState>,
+    Write<'a, CommandEventChannel>,
+    Write<'a, HistoryEventChannel>,
+    ReadStorage<'a, SymbolicPoint>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.animation_event_reader = Some(world.fetch_mut::<AnimationEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      animation_event_channel,
+      delta_time,
+      mut animation_state,
+      mut command_event_channel,
+      mut history_event_channel,
+      sym_points,
+    ): Self::SystemData,
+  ) {
+    let mut elapsed = if animation_state.is_playing() {
+      delta_time.get()
+    } else {
+      0.0
+    };
+    let mut stop = false;
+    if let Some(reader) = &mut self.animation_event_reader {
+      for event in animation_event_channel.read(reader) {
+        match event {
+          AnimationEvent::Animate(ent, animation) => {
+            if let Some(sym_point) = sym_points.get(*ent) {
+              if Animation::default_for(sym_point).is_some() {
+                animation_state.animate(*ent, *animation);
+              }
+            }
+          }
+          AnimationEvent::Unanimate(ent) => {
+            if let Some(animated) = animation_state.unanimate(*ent) {
+              if let (Some(start), Some(sym_point)) = (animated.start, sym_points.get(*ent)) {
+                command_event_channel.single_write(CommandEvent {
+                  command: Command::Update(UpdateEvent::UpdatePointEnd(*ent, start, *sym_point)),
+                  event_id: None,
+                });
+              }
+            }
+          }
+          AnimationEvent::Play => animation_state.set_playing(true),
+          AnimationEvent::Pause => {
+            animation_state.set_playing(false);
+            elapsed = 0.0;
+          }
+          AnimationEvent::Step => elapsed += STEP_TIME,
+          AnimationEvent::Stop => stop = true,
+        }
+      }
+    }
+
+    // Forget the points that were removed, or that are not on a line or a circle anymore
+    animation_state.retain(|ent, _| match sym_points.get(*ent) {
+      Some(sym_point) => Animation::default_for(sym_point).is_some(),
+      None => false,
+    });
+
+    // The commands only apply later in the frame, so keep where the points are going
+    let mut moved = HashMap::new();
+    if elapsed > 0.0 {
+      for (ent, animated) in animation_state.points_mut() {
+        if let Some(&sym_point) = sym_points.get(*ent) {
+          if let Some((next, direction)) = animated.animation.advance(sym_point, animated.direction, elapsed) {
+            // Only the points that moved have a start, and are recorded on stop
+            animated.start.get_or_insert(sym_point);
+            animated.direction = direction;
+            moved.insert(*ent, next);
+            command_event_channel.single_write(CommandEvent {
+              command: Command::Update(UpdateEvent::UpdatePoint(*ent, sym_point, next)),
+              event_id: None,
+            });
+          }
+        }
+      }
+    }
+
+    if stop {
+      animation_state.set_playing(false);
+      let mut finished = vec![];
+      for (ent, animated) in animation_state.points_mut() {
+        if let Some(start) = animated.start.take() {
+          let end = match moved.get(ent) {
+            Some(&next) => next,
+            None => *sym_points.get(*ent).unwrap(),
+          };
+          finished.push((*ent, start, end));
+        }
+      }
+      if !finished.is_empty() {
+        history_event_channel.single_write(HistoryEvent::BeginTransaction);
+        for (ent, start, end) in finished {
+          command_event_channel.single_write(CommandEvent {
+            command: Command::Update(UpdateEvent::UpdatePointEnd(ent, start, end)),
+            event_id: None,
+          });
+        }
+        history_event_channel.single_write(HistoryEvent::CommitTransaction);
+      }
+    }
+  }
+}
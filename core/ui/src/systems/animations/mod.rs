@@ -0,0 +1,3 @@
+mod animate_points;
+
+pub use animate_points::*;
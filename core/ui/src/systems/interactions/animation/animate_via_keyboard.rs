@@ -0,0 +1,94 @@
+use crate::{events::*, resources::*};
+use core_lib::components::{markers::*, symbolics::*};
+use specs::prelude::*;
+
+// How much faster or slower the selected points get on each key stroke
+static SPEED_FACTOR: f64 = 2.0;
+
+#[derive(Default)]
+pub struct AnimateViaKeyboard;
+
+impl<'a> System<'a> for AnimateViaKeyboard {
+  type SystemData = (
+    Entities<'a>,
+    Read<'a, InputState>,
+    Read<'a, AnimationState>,
+    Write<'a, AnimationEventChannel>,
+    ReadStorage<'a, SymbolicPoint>,
+    ReadStorage<'a, Selected>,
+  );
+
+  fn run(
+    &mut self,
+    (entities, input_state, animation_state, mut animation_event_channel, sym_points, selecteds): Self::SystemData,
+  ) {
+    if !input_state.keyboard.is_command_activated() {
+      return;
+    }
+    if input_state.keyboard.just_activated(Key::P) {
+      if input_state.keyboard.is_shift_activated() {
+        // Animate the selected points, or stop animating them when they all are already
+        let selected: Vec<_> = (&entities, &sym_points, &selecteds)
+          .join()
+          .filter_map(|(ent, sym_point, _)| Animation::default_for(sym_point).map(|animation| (ent, animation)))
+          .collect();
+        if selected.iter().all(|(ent, _)| animation_state.get(*ent).is_some()) {
+          animation_event_channel.iter_write(selected.into_iter().map(|(ent, _)| AnimationEvent::Unanimate(ent)));
+        } else {
+          let animate_events: Vec<_> = selected
+            .into_iter()
+            .filter(|(ent, _)| animation_state.get(*ent).is_none())
+            .map(|(ent, animation)| AnimationEvent::Animate(ent, animation))
+            .collect();
+          animation_event_channel.iter_write(animate_events);
+        }
+      } else if animation_state.is_playing() {
+        animation_event_channel.single_write(AnimationEvent::Pause);
+      } else {
+        animation_event_channel.single_write(AnimationEvent::Play);
+      }
+    } else if input_state.keyboard.just_activated(Key::RightBracket) {
+      if input_state.keyboard.is_shift_activated() {
+        change_speed(
+          &entities,
+          &animation_state,
+          &mut animation_event_channel,
+          &selecteds,
+          SPEED_FACTOR,
+        );
+      } else {
+        animation_event_channel.single_write(AnimationEvent::Step);
+      }
+    } else if input_state.keyboard.just_activated(Key::LeftBracket) && input_state.keyboard.is_shift_activated() {
+      change_speed(
+        &entities,
+        &animation_state,
+        &mut animation_event_channel,
+        &selecteds,
+        1.0 / SPEED_FACTOR,
+      );
+    } else if input_state.keyboard.just_activated(Key::Period) {
+      animation_event_channel.single_write(AnimationEvent::Stop);
+    }
+  }
+}
+
+/// Multiply the speed of the selected points that are animated. The others keep theirs
+fn change_speed<'a>(
+  entities: &Entities<'a>,
+  animation_state: &AnimationState,
+  animation_event_channel: &mut AnimationEventChannel,
+  selecteds: &ReadStorage<'a, Selected>,
+  factor: f64,
+) {
+  let animate_events: Vec<_> = (entities, selecteds)
+    .join()
+    .filter_map(|(ent, _)| {
+      animation_state.get(ent).map(|animation| {
+        let speed = animation.speed * factor;
+        AnimationEvent::Animate(ent, Animation { speed, ..animation })
+      })
+    })
+    .collect();
+  animation_event_channel.iter_write(animate_events);
+}
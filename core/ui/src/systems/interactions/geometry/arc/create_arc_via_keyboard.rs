@@ -0,0 +1,28 @@
+use crate::resources::*;
+use core_lib::{events::*, math::ArcKind};
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateArcViaKeyboard;
+
+impl<'a> System<'a> for CreateArcViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let shift = input_state.keyboard.is_shift_activated();
+    let kind = if input_state.keyboard.just_activated(Key::F) {
+      Some(if shift { ArcKind::Sector } else { ArcKind::Arc })
+    } else if input_state.keyboard.just_activated(Key::Y) && !shift {
+      Some(ArcKind::Segment)
+    } else {
+      None
+    };
+    if let (true, Some(kind)) = (cmd, kind) {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::ArcInsert(InsertArcEvent::InsertArcFromSelection(kind)),
+        event_id: None,
+      });
+    }
+  }
+}
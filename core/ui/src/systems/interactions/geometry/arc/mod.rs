@@ -0,0 +1,3 @@
+mod create_arc_via_keyboard;
+
+pub use create_arc_via_keyboard::*;
@@ -0,0 +1,22 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateCompassCircleViaKeyboard;
+
+impl<'a> System<'a> for CreateCompassCircleViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let no_shift = !input_state.keyboard.is_shift_activated();
+    let k = input_state.keyboard.just_activated(Key::K);
+    if cmd && no_shift && k {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertCompassCircleFromSelection),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -0,0 +1,22 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateThreePointCircleViaKeyboard;
+
+impl<'a> System<'a> for CreateThreePointCircleViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let no_shift = !input_state.keyboard.is_shift_activated();
+    let t = input_state.keyboard.just_activated(Key::T);
+    if cmd && no_shift && t {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertThreePointCircleFromSelection),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -0,0 +1,29 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateTriangleCircleViaKeyboard;
+
+impl<'a> System<'a> for CreateTriangleCircleViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let keyboard = &input_state.keyboard;
+    let circle = if keyboard.just_activated(Key::D6) {
+      Some(TriangleCircle::Incircle)
+    } else if keyboard.just_activated(Key::D7) {
+      Some(TriangleCircle::Excircles)
+    } else if keyboard.just_activated(Key::D8) {
+      Some(TriangleCircle::NinePointCircle)
+    } else {
+      None
+    };
+    if let (true, Some(circle)) = (keyboard.is_command_activated(), circle) {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::CircleInsert(InsertCircleEvent::InsertTriangleCircleFromSelection(circle)),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -1,3 +1,9 @@
 mod create_circle_via_mouse;
+mod create_compass_circle_via_keyboard;
+mod create_three_point_circle_via_keyboard;
+mod create_triangle_circle_via_keyboard;
 
 pub use create_circle_via_mouse::*;
+pub use create_compass_circle_via_keyboard::*;
+pub use create_three_point_circle_via_keyboard::*;
+pub use create_triangle_circle_via_keyboard::*;
@@ -0,0 +1,3 @@
+mod create_conic_via_keyboard;
+
+pub use create_conic_via_keyboard::*;
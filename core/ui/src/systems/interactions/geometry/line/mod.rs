@@ -1,7 +1,13 @@
+mod create_angle_bisector_via_keyboard;
 mod create_line_via_mouse;
 mod create_parallel_via_keyboard;
+mod create_perpendicular_bisector_via_keyboard;
 mod create_perpendicular_via_keyboard;
+mod create_tangents_via_keyboard;
 
+pub use create_angle_bisector_via_keyboard::*;
 pub use create_line_via_mouse::*;
 pub use create_parallel_via_keyboard::*;
+pub use create_perpendicular_bisector_via_keyboard::*;
 pub use create_perpendicular_via_keyboard::*;
+pub use create_tangents_via_keyboard::*;
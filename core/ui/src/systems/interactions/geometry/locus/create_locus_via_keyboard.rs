@@ -0,0 +1,21 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateLocusViaKeyboard;
+
+impl<'a> System<'a> for CreateLocusViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let r = input_state.keyboard.just_activated(Key::R);
+    if cmd && r {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::LocusInsert(InsertLocusEvent::InsertLocusFromSelection),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -0,0 +1,3 @@
+mod create_locus_via_keyboard;
+
+pub use create_locus_via_keyboard::*;
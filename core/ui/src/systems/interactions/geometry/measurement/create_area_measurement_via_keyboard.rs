@@ -0,0 +1,22 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateAreaMeasurementViaKeyboard;
+
+impl<'a> System<'a> for CreateAreaMeasurementViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let shift = input_state.keyboard.is_shift_activated();
+    let u = input_state.keyboard.just_activated(Key::U);
+    if cmd && !shift && u {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::MeasurementInsert(InsertMeasurementEvent::InsertAreaFromSelection),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -0,0 +1,11 @@
+mod create_angle_measurement_via_keyboard;
+mod create_area_measurement_via_keyboard;
+mod create_distance_measurement_via_keyboard;
+mod create_perimeter_measurement_via_keyboard;
+mod create_ratio_measurement_via_keyboard;
+
+pub use create_angle_measurement_via_keyboard::*;
+pub use create_area_measurement_via_keyboard::*;
+pub use create_distance_measurement_via_keyboard::*;
+pub use create_perimeter_measurement_via_keyboard::*;
+pub use create_ratio_measurement_via_keyboard::*;
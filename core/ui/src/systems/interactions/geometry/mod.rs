@@ -1,6 +1,11 @@
+pub mod arc;
 pub mod circle;
+pub mod conic;
 pub mod line;
+pub mod locus;
+pub mod measurement;
 pub mod point;
+pub mod polygon;
 
 mod remove_selected_via_keyboard;
 
@@ -60,6 +60,7 @@ impl<'a> System<'a> for CreatePointViaMouse {
             if let Some(SnapPoint { position, symbol }) = maybe_snap_point.get() {
               let maybe_sym_point = match symbol {
                 SnapPointType::NotSnapped => Some(SymbolicPoint::Free(position.to_virtual(&*viewport))),
+                SnapPointType::SnapOnGrid(lattice_point) => Some(SymbolicPoint::Free(lattice_point)),
                 SnapPointType::SnapOnLine(l_ent, t) => Some(SymbolicPoint::OnLine(l_ent, t.into())),
                 SnapPointType::SnapOnLineLineIntersection(l1_ent, l2_ent) => {
                   Some(SymbolicPoint::LineLineIntersect(l1_ent, l2_ent))
@@ -71,6 +72,16 @@ impl<'a> System<'a> for CreatePointViaMouse {
                 SnapPointType::SnapOnCircleCircleIntersection(c1_ent, c2_ent, id) => {
                   Some(SymbolicPoint::CircleCircleIntersect(c1_ent, c2_ent, id))
                 }
+                SnapPointType::SnapOnArc(a_ent, fraction) => Some(SymbolicPoint::OnArc(a_ent, fraction)),
+                SnapPointType::SnapOnArcLineIntersection(a_ent, l_ent, id) => {
+                  Some(SymbolicPoint::ArcLineIntersect(a_ent, l_ent, id))
+                }
+                SnapPointType::SnapOnArcCircleIntersection(a_ent, c_ent, id) => {
+                  Some(SymbolicPoint::ArcCircleIntersect(a_ent, c_ent, id))
+                }
+                SnapPointType::SnapOnConicLineIntersection(c_ent, l_ent, id) => {
+                  Some(SymbolicPoint::ConicLineIntersect(c_ent, l_ent, id))
+                }
                 SnapPointType::SnapOnPoint(_) => None,
               };
               if let Some(sym_point) = maybe_sym_point {
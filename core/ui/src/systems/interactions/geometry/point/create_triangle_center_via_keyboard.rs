@@ -0,0 +1,33 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreateTriangleCenterViaKeyboard;
+
+impl<'a> System<'a> for CreateTriangleCenterViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let keyboard = &input_state.keyboard;
+    let center = if keyboard.just_activated(Key::D1) {
+      Some(TriangleCenter::Centroid)
+    } else if keyboard.just_activated(Key::D2) {
+      Some(TriangleCenter::Circumcenter)
+    } else if keyboard.just_activated(Key::D3) {
+      Some(TriangleCenter::Orthocenter)
+    } else if keyboard.just_activated(Key::D4) {
+      Some(TriangleCenter::Incenter)
+    } else if keyboard.just_activated(Key::D5) {
+      Some(TriangleCenter::NinePointCenter)
+    } else {
+      None
+    };
+    if let (true, Some(center)) = (keyboard.is_command_activated(), center) {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::PointInsert(InsertPointEvent::InsertTriangleCenterFromSelection(center)),
+        event_id: None,
+      });
+    }
+  }
+}
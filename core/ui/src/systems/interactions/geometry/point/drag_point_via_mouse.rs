@@ -1,4 +1,8 @@
-use crate::{events::*, resources::*, utilities::hitting_object};
+use crate::{
+  events::*,
+  resources::*,
+  utilities::{hitting_label, hitting_object},
+};
 use core_lib::{
   components::{screen_shapes::*, symbolics::*},
   events::*,
@@ -35,11 +39,10 @@ impl<'a> System<'a> for MovePointViaDrag {
     Write<'a, MouseEventChannel>,
     Read<'a, SpatialEntityMap>,
     Read<'a, Viewport>,
+    Read<'a, Grid>,
     Write<'a, CommandEventChannel>,
     ReadStorage<'a, SymbolicPoint>,
-    ReadStorage<'a, ScreenPoint>,
-    ReadStorage<'a, ScreenLine>,
-    ReadStorage<'a, ScreenCircle>,
+    ScreenShapes<'a>,
   );
 
   fn setup(&mut self, world: &mut World) {
@@ -56,11 +59,10 @@ impl<'a> System<'a> for MovePointViaDrag {
       mut mouse_event_channel,
       spatial_entity_map,
       viewport,
+      grid,
       mut command_event_channel,
       sym_points,
-      scrn_points,
-      scrn_lines,
-      scrn_circles,
+      scrn,
     ): Self::SystemData,
   ) {
     // First use tool change to setup mouse event reader.
@@ -86,15 +88,10 @@ impl<'a> System<'a> for MovePointViaDrag {
       for event in mouse_event_channel.read(reader_id) {
         match event {
           MouseEvent::DragBegin(start_position) => {
-            if !input_state.keyboard.is_shift_activated() {
-              if let Some(entity) = hitting_object(
-                *start_position,
-                &spatial_entity_map,
-                &scrn_points,
-                &scrn_lines,
-                &scrn_circles,
-                SELECT_DIST_THRES,
-              ) {
+            // Dragging a label moves the label instead
+            let on_label = hitting_label(*start_position, &spatial_entity_map, &scrn.labels).is_some();
+            if !input_state.keyboard.is_shift_activated() && !on_label {
+              if let Some(entity) = hitting_object(*start_position, &spatial_entity_map, &scrn, SELECT_DIST_THRES) {
                 if let Some(sym_point) = sym_points.get(entity) {
                   self.dragging_point = Some((entity, *sym_point));
                   self.start_position = Some(*start_position);
@@ -111,9 +108,7 @@ impl<'a> System<'a> for MovePointViaDrag {
           MouseEvent::DragMove(_, curr_position) => match self.dragging_point {
             Some((ent, _)) => {
               if let Some(old_sym_point) = sym_points.get(ent) {
-                if let Some(new_sym_point) =
-                  get_update(*old_sym_point, *curr_position, &viewport, &scrn_lines, &scrn_circles)
-                {
+                if let Some(new_sym_point) = get_update(*old_sym_point, *curr_position, &viewport, &grid, &scrn) {
                   command_event_channel.single_write(CommandEvent {
                     command: Command::Update(UpdateEvent::UpdatePoint(ent, *old_sym_point, new_sym_point)),
                     event_id: None,
@@ -126,9 +121,7 @@ impl<'a> System<'a> for MovePointViaDrag {
           MouseEvent::DragEnd(curr_position) => {
             match self.dragging_point {
               Some((ent, old_sym_point)) => {
-                if let Some(new_sym_point) =
-                  get_update(old_sym_point, *curr_position, &viewport, &scrn_lines, &scrn_circles)
-                {
+                if let Some(new_sym_point) = get_update(old_sym_point, *curr_position, &viewport, &grid, &scrn) {
                   command_event_channel.single_write(CommandEvent {
                     command: Command::Update(UpdateEvent::UpdatePointEnd(ent, old_sym_point, new_sym_point)),
                     event_id: None,
@@ -150,16 +143,20 @@ fn get_update<'a>(
   old_sym_point: SymbolicPoint,
   curr_position: ScreenPosition,
   viewport: &Viewport,
-  scrn_lines: &ReadStorage<'a, ScreenLine>,
-  scrn_circles: &ReadStorage<'a, ScreenCircle>,
+  grid: &Grid,
+  scrn: &ScreenShapes<'a>,
 ) -> Option<SymbolicPoint> {
   match old_sym_point {
     SymbolicPoint::Free(_) => {
       let new_position = curr_position.to_virtual(&viewport);
-      Some(SymbolicPoint::Free(new_position))
+      if grid.snap {
+        Some(SymbolicPoint::Free(Grid::snap_position(new_position, viewport)))
+      } else {
+        Some(SymbolicPoint::Free(new_position))
+      }
     }
     SymbolicPoint::OnLine(l_ent, _) => {
-      if let Some(line) = scrn_lines.get(l_ent) {
+      if let Some(line) = scrn.lines.get(l_ent) {
         let closest_point = line.get_closest_point(curr_position);
         let new_t = line.rel_t_of_point(closest_point);
         Some(SymbolicPoint::OnLine(l_ent, new_t.into()))
@@ -168,7 +165,7 @@ fn get_update<'a>(
       }
     }
     SymbolicPoint::OnCircle(c_ent, _) => {
-      if let Some(circle) = scrn_circles.get(c_ent) {
+      if let Some(circle) = scrn.circles.get(c_ent) {
         let projected_position = curr_position.project(*circle);
         let p_to_cen: Vector2 = (projected_position - circle.center).into();
         let new_theta = -p_to_cen.y.atan2(p_to_cen.x);
@@ -177,6 +174,10 @@ fn get_update<'a>(
         None
       }
     }
+    SymbolicPoint::OnArc(a_ent, _) => scrn
+      .arcs
+      .get(a_ent)
+      .map(|arc| SymbolicPoint::OnArc(a_ent, arc.fraction_of(curr_position))),
     _ => None,
   }
 }
@@ -1,6 +1,7 @@
 mod click_on_existing_point;
 mod create_midpoint_via_keyboard;
 mod create_point_via_mouse;
+mod create_triangle_center_via_keyboard;
 mod drag_point_via_mouse;
 mod emit_active_point_event;
 mod snap_point_via_mouse;
@@ -8,6 +9,7 @@ mod snap_point_via_mouse;
 pub use click_on_existing_point::*;
 pub use create_midpoint_via_keyboard::*;
 pub use create_point_via_mouse::*;
+pub use create_triangle_center_via_keyboard::*;
 pub use drag_point_via_mouse::*;
 pub use emit_active_point_event::*;
 pub use snap_point_via_mouse::*;
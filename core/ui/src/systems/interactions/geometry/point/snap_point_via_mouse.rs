@@ -22,32 +22,49 @@ impl<'a> System<'a> for SnapPointViaMouse {
     Read<'a, InputState>,
     Read<'a, ToolState>,
     Read<'a, SpatialEntityMap>,
+    Read<'a, Viewport>,
+    Read<'a, Grid>,
     Write<'a, MaybeSnapPoint>,
     ReadStorage<'a, ScreenPoint>,
     ReadStorage<'a, ScreenLine>,
     ReadStorage<'a, ScreenCircle>,
+    ReadStorage<'a, ScreenArc>,
+    ReadStorage<'a, ScreenConic>,
   );
 
   fn run(
     &mut self,
     (
-            input_state,
-            tool_state,
-            spatial_entity_map,
-            mut maybe_snap_point,
-            scrn_points,
-            scrn_lines,
-            scrn_circles,
-        ): Self::SystemData,
+      input_state,
+      tool_state,
+      spatial_entity_map,
+      viewport,
+      grid,
+      mut maybe_snap_point,
+      scrn_points,
+      scrn_lines,
+      scrn_circles,
+      scrn_arcs,
+      scrn_conics,
+    ): Self::SystemData,
   ) {
     if tool_state.need_snap_point() {
       let mouse_pos = input_state.mouse_abs_pos;
 
-      // Set the snap point to free point as a default case
-      maybe_snap_point.set(SnapPoint {
-        position: mouse_pos,
-        symbol: SnapPointType::NotSnapped,
-      });
+      // Set the snap point to free point as a default case, or to the closest lattice point
+      // when snapping to grid. Snapping to the shapes still goes first
+      if grid.snap {
+        let lattice_point = Grid::snap_position(mouse_pos.to_virtual(&*viewport), &*viewport);
+        maybe_snap_point.set(SnapPoint {
+          position: lattice_point.to_screen(&*viewport),
+          symbol: SnapPointType::SnapOnGrid(lattice_point),
+        });
+      } else {
+        maybe_snap_point.set(SnapPoint {
+          position: mouse_pos,
+          symbol: SnapPointType::NotSnapped,
+        });
+      }
 
       // Then get the potential neighbors
       let neighbor_entities = spatial_entity_map.get_entities_near_point(mouse_pos.into(), SNAP_TO_POINT_THRES.into());
@@ -57,6 +74,8 @@ impl<'a> System<'a> for SnapPointViaMouse {
       let mut is_snapping_to_point = false;
       let mut closest_lines: Vec<(Entity, ScreenLine)> = vec![];
       let mut closest_circles: Vec<(Entity, ScreenCircle)> = vec![];
+      let mut closest_arcs: Vec<(Entity, ScreenArc)> = vec![];
+      let mut closest_conics: Vec<(Entity, &ScreenConic)> = vec![];
       let mut maybe_smallest_dist_to_line: Option<f64> = None;
       let mut maybe_snap_point_on_line = None;
       let mut maybe_smallest_dist_to_circle: Option<f64> = None;
@@ -117,6 +136,29 @@ impl<'a> System<'a> for SnapPointViaMouse {
               });
             }
           }
+        } else if let Some(a) = scrn_arcs.get(entity) {
+          // Like a circle, but only along the extent of the arc
+          let a = *a;
+          let proj_point = a.closest_point(mouse_pos);
+          let dist = (proj_point - mouse_pos).magnitude();
+          if dist <= SNAP_TO_CIRCLE_THRES {
+            closest_arcs.push((entity, a));
+          }
+          let norm_dist = dist / SNAP_TO_CIRCLE_THRES;
+          if norm_dist < 1.0 && !is_snapping_to_point {
+            if maybe_smallest_dist_to_circle.is_none() || norm_dist < maybe_smallest_dist_to_circle.unwrap() {
+              maybe_smallest_dist_to_circle = Some(norm_dist);
+              maybe_snap_point_on_circle = Some(SnapPoint {
+                position: proj_point,
+                symbol: SnapPointType::SnapOnArc(entity, a.fraction_of(mouse_pos)),
+              });
+            }
+          }
+        } else if let Some(c) = scrn_conics.get(entity) {
+          // Points are not bound to conics, only their intersections with lines can be snapped to
+          if c.distance(mouse_pos) <= SNAP_TO_CIRCLE_THRES {
+            closest_conics.push((entity, c));
+          }
         }
       }
 
@@ -162,7 +204,7 @@ impl<'a> System<'a> for SnapPointViaMouse {
 
           for ((line_ent, line), (circle_ent, circle)) in closest_lines.iter().cartesian_product(&closest_circles) {
             let ci = line.intersect(*circle);
-            check_circle_intersection(mouse_pos, ci, maybe_smallest_dist.clone(), &mut |m| match m {
+            check_circle_intersection(mouse_pos, ci, maybe_smallest_dist.clone(), |_| true, &mut |m| match m {
               Some((p, norm_dist, ty)) => {
                 maybe_smallest_dist = Some(norm_dist);
                 maybe_snap_point.set(SnapPoint {
@@ -175,6 +217,41 @@ impl<'a> System<'a> for SnapPointViaMouse {
             });
           }
 
+          // The intersections with the circle of an arc keep their ids, so only the ones on the
+          // arc can be snapped to
+          for ((line_ent, line), (arc_ent, arc)) in closest_lines.iter().cartesian_product(&closest_arcs) {
+            let ci = line.intersect(arc.circle());
+            let on_arc = |p: ScreenPosition| arc.contains_point(p);
+            check_circle_intersection(mouse_pos, ci, maybe_smallest_dist.clone(), on_arc, &mut |m| match m {
+              Some((p, norm_dist, ty)) => {
+                maybe_smallest_dist = Some(norm_dist);
+                maybe_snap_point.set(SnapPoint {
+                  position: p,
+                  symbol: SnapPointType::SnapOnArcLineIntersection(*arc_ent, *line_ent, ty),
+                });
+                has_circle_line_itsct = true;
+              }
+              None => (),
+            });
+          }
+
+          // The intersections with a conic are ordered along the line, which keeps its direction in
+          // virtual space, so no reversing is needed here
+          for ((line_ent, line), (conic_ent, conic)) in closest_lines.iter().cartesian_product(&closest_conics) {
+            let ci = (*conic).clone().intersect(*line);
+            check_circle_intersection(mouse_pos, ci, maybe_smallest_dist.clone(), |_| true, &mut |m| match m {
+              Some((p, norm_dist, ty)) => {
+                maybe_smallest_dist = Some(norm_dist);
+                maybe_snap_point.set(SnapPoint {
+                  position: p,
+                  symbol: SnapPointType::SnapOnConicLineIntersection(*conic_ent, *line_ent, ty),
+                });
+                has_circle_line_itsct = true;
+              }
+              None => (),
+            });
+          }
+
           if !has_circle_line_itsct {
             for comb in closest_circles.iter().combinations(2) {
               if let &[(c1_ent, c1), (c2_ent, c2)] = &*comb {
@@ -186,6 +263,7 @@ impl<'a> System<'a> for SnapPointViaMouse {
                   mouse_pos,
                   c1.intersect(*c2).reverse(),
                   maybe_smallest_dist.clone(),
+                  |_| true,
                   &mut |m| match m {
                     Some((p, norm_dist, ty)) => {
                       maybe_smallest_dist = Some(norm_dist);
@@ -199,6 +277,25 @@ impl<'a> System<'a> for SnapPointViaMouse {
                 );
               }
             }
+            for ((arc_ent, arc), (circle_ent, circle)) in closest_arcs.iter().cartesian_product(&closest_circles) {
+              // Reversed for the same reason as above, the arc being the first circle
+              check_circle_intersection(
+                mouse_pos,
+                arc.circle().intersect(*circle).reverse(),
+                maybe_smallest_dist.clone(),
+                |p| arc.contains_point(p),
+                &mut |m| match m {
+                  Some((p, norm_dist, ty)) => {
+                    maybe_smallest_dist = Some(norm_dist);
+                    maybe_snap_point.set(SnapPoint {
+                      position: p,
+                      symbol: SnapPointType::SnapOnArcCircleIntersection(*arc_ent, *circle_ent, ty),
+                    });
+                  }
+                  None => (),
+                },
+              );
+            }
           }
         }
       }
@@ -208,21 +305,25 @@ impl<'a> System<'a> for SnapPointViaMouse {
   }
 }
 
-fn check_circle_intersection<F>(
+/// Only the intersection points passing `keep` can be snapped to
+fn check_circle_intersection<K, F>(
   mouse_pos: ScreenPosition,
   ci: ScreenCircleIntersect,
   maybe_smallest_dist: Option<f64>,
+  keep: K,
   callback: &mut F,
 ) where
+  K: Fn(ScreenPosition) -> bool,
   F: FnMut(Option<(ScreenPosition, f64, CircleIntersectId)>) -> (),
 {
   match ci {
     ScreenCircleIntersect::TwoPoints(p1, p2) => {
       let (dist_1, dist_2) = ((p1 - mouse_pos).magnitude(), (p2 - mouse_pos).magnitude());
-      let (ty, p) = if dist_1 < dist_2 {
-        (CircleIntersectId::First, p1)
-      } else {
-        (CircleIntersectId::Second, p2)
+      let (ty, p) = match (keep(p1), keep(p2)) {
+        (true, true) if dist_1 < dist_2 => (CircleIntersectId::First, p1),
+        (true, false) => (CircleIntersectId::First, p1),
+        (_, true) => (CircleIntersectId::Second, p2),
+        (false, false) => return,
       };
       let norm_dist = (mouse_pos - p).magnitude() / SNAP_TO_INTERSECTION_THRES;
       if norm_dist < 1.0 {
@@ -231,7 +332,7 @@ fn check_circle_intersection<F>(
         }
       }
     }
-    ScreenCircleIntersect::OnePoint(p) => {
+    ScreenCircleIntersect::OnePoint(p) if keep(p) => {
       let norm_dist = (mouse_pos - p).magnitude() / SNAP_TO_INTERSECTION_THRES;
       if norm_dist < 1.0 {
         if maybe_smallest_dist.is_none() || norm_dist < maybe_smallest_dist.unwrap() {
@@ -239,6 +340,6 @@ fn check_circle_intersection<F>(
         }
       }
     }
-    ScreenCircleIntersect::None => (),
+    _ => (),
   }
 }
@@ -0,0 +1,21 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct CreatePolygonViaKeyboard;
+
+impl<'a> System<'a> for CreatePolygonViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, CommandEventChannel>);
+
+  fn run(&mut self, (input_state, mut command_event_channel): Self::SystemData) {
+    let cmd = input_state.keyboard.is_command_activated();
+    let g = input_state.keyboard.just_activated(Key::G);
+    if cmd && g {
+      command_event_channel.single_write(CommandEvent {
+        command: Command::PolygonInsert(InsertPolygonEvent::InsertPolygonFromSelection),
+        event_id: None,
+      });
+    }
+  }
+}
@@ -0,0 +1,3 @@
+mod create_polygon_via_keyboard;
+
+pub use create_polygon_via_keyboard::*;
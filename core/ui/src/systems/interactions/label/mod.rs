@@ -0,0 +1,3 @@
+mod move_label_via_drag;
+
+pub use move_label_via_drag::*;
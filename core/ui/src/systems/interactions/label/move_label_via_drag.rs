@@ -0,0 +1,100 @@
+use crate::{events::*, resources::*, utilities::hitting_label};
+use core_lib::{
+  components::{labels::*, screen_shapes::*},
+  events::*,
+  math::*,
+  resources::*,
+  utilities::*,
+};
+use specs::prelude::*;
+
+pub struct MoveLabelViaDrag {
+  tool_change_event_reader: Option<ToolChangeEventReader>,
+  mouse_event_reader: Option<MouseEventReader>,
+  dragging_label: Option<(Entity, Vector2)>,
+  start_position: Option<ScreenPosition>,
+}
+
+impl Default for MoveLabelViaDrag {
+  fn default() -> Self {
+    Self {
+      tool_change_event_reader: None,
+      mouse_event_reader: None,
+      dragging_label: None,
+      start_position: None,
+    }
+  }
+}
+
+impl<'a> System<'a> for MoveLabelViaDrag {
+  type SystemData = (
+    Read<'a, ToolChangeEventChannel>,
+    Write<'a, MouseEventChannel>,
+    Read<'a, SpatialEntityMap>,
+    Write<'a, CommandEventChannel>,
+    ReadStorage<'a, Label>,
+    ReadStorage<'a, ScreenLabel>,
+  );
+
+  fn setup(&mut self, world: &mut World) {
+    Self::SystemData::setup(world);
+    self.tool_change_event_reader = Some(world.fetch_mut::<ToolChangeEventChannel>().register_reader());
+    self.mouse_event_reader = Some(world.fetch_mut::<MouseEventChannel>().register_reader());
+  }
+
+  fn run(
+    &mut self,
+    (
+      tool_change_event_channel,
+      mut mouse_event_channel,
+      spatial_entity_map,
+      mut command_event_channel,
+      labels,
+      scrn_labels,
+    ): Self::SystemData,
+  ) {
+    // Same as dragging points, only listen to the mouse when the tool state is select
+    if let Some(reader_id) = &mut self.tool_change_event_reader {
+      for event in tool_change_event_channel.read(reader_id) {
+        match event {
+          ToolChangeEvent(Tool::Select) => {
+            self.mouse_event_reader = Some(mouse_event_channel.register_reader());
+          }
+          _ => {
+            self.mouse_event_reader = None;
+          }
+        }
+      }
+    }
+
+    if let Some(reader_id) = &mut self.mouse_event_reader {
+      for event in mouse_event_channel.read(reader_id) {
+        match event {
+          MouseEvent::DragBegin(start_position) => {
+            if let Some(entity) = hitting_label(*start_position, &spatial_entity_map, &scrn_labels) {
+              if let Some(label) = labels.get(entity) {
+                self.dragging_label = Some((entity, label.offset));
+                self.start_position = Some(*start_position);
+              }
+            }
+          }
+          MouseEvent::DragMove(_, curr_position) | MouseEvent::DragEnd(curr_position) => {
+            if let (Some((ent, start_offset)), Some(start_position)) = (self.dragging_label, self.start_position) {
+              // The offset is in screen space, so it just follows the mouse
+              let offset = start_offset + (*curr_position - start_position).0;
+              command_event_channel.single_write(CommandEvent {
+                command: Command::Label(LabelEvent::MoveLabel(ent, offset)),
+                event_id: None,
+              });
+            }
+            if let MouseEvent::DragEnd(_) = event {
+              self.dragging_label = None;
+              self.start_position = None;
+            }
+          }
+          _ => (),
+        }
+      }
+    }
+  }
+}
@@ -1,4 +1,8 @@
-use crate::{events::*, resources::*, utilities::hitting_object};
+use crate::{
+  events::*,
+  resources::*,
+  utilities::{hitting_label, hitting_object},
+};
 use core_lib::{
   components::{markers::*, screen_shapes::*},
   events::*,
@@ -38,9 +42,7 @@ impl<'a> System<'a> for SeldeViaMouse {
     Read<'a, SpatialEntityMap>,
     Write<'a, CommandEventChannel>,
     Write<'a, SelectRectangle>,
-    ReadStorage<'a, ScreenPoint>,
-    ReadStorage<'a, ScreenLine>,
-    ReadStorage<'a, ScreenCircle>,
+    ScreenShapes<'a>,
     ReadStorage<'a, Selected>,
   );
 
@@ -59,9 +61,7 @@ impl<'a> System<'a> for SeldeViaMouse {
       spatial_entity_map,
       mut command_event_channel,
       mut select_rectangle,
-      scrn_points,
-      scrn_lines,
-      scrn_circles,
+      scrn,
       selecteds,
     ): Self::SystemData,
   ) {
@@ -89,15 +89,10 @@ impl<'a> System<'a> for SeldeViaMouse {
       for event in mouse_event_channel.read(reader_id) {
         match event {
           MouseEvent::MouseDown(mouse_pos) => {
-            // Check if hitting something
-            if let Some(entity) = hitting_object(
-              *mouse_pos,
-              &*spatial_entity_map,
-              &scrn_points,
-              &scrn_lines,
-              &scrn_circles,
-              SELECT_DIST_THRES,
-            ) {
+            // Check if hitting something. Clicking a label selects its element
+            if let Some(entity) = hitting_label(*mouse_pos, &*spatial_entity_map, &scrn.labels)
+              .or_else(|| hitting_object(*mouse_pos, &*spatial_entity_map, &scrn, SELECT_DIST_THRES))
+            {
               // Check if shift is held
               if input_state.keyboard.is_shift_activated() {
                 // If has shift, select or deselect based on previous state
@@ -133,15 +128,8 @@ impl<'a> System<'a> for SeldeViaMouse {
           }
           MouseEvent::DragBegin(start_position) => {
             // We need the dragging begin from an empty space
-            if hitting_object(
-              *start_position,
-              &*spatial_entity_map,
-              &scrn_points,
-              &scrn_lines,
-              &scrn_circles,
-              SELECT_DIST_THRES,
-            )
-            .is_none()
+            if hitting_label(*start_position, &*spatial_entity_map, &scrn.labels).is_none()
+              && hitting_object(*start_position, &*spatial_entity_map, &scrn, SELECT_DIST_THRES).is_none()
             {
               // If ther's no shift, clear the selection
               if !input_state.keyboard.is_shift_activated() {
@@ -163,8 +151,7 @@ impl<'a> System<'a> for SeldeViaMouse {
               select_rectangle.set(rect);
 
               // Select all the elements intersecting with AABB
-              let mut new_entities =
-                get_entities_in_aabb(rect, &*spatial_entity_map, &scrn_points, &scrn_lines, &scrn_circles);
+              let mut new_entities = get_entities_in_aabb(rect, &*spatial_entity_map, &scrn);
               let mut to_remove = vec![];
               for entity in &self.drag_selected_new_entities {
                 if !new_entities.contains(entity) {
@@ -204,28 +191,59 @@ impl<'a> System<'a> for SeldeViaMouse {
 fn get_entities_in_aabb<'a>(
   aabb: AABB,
   spatial_entity_map: &SpatialEntityMap,
-  scrn_points: &ReadStorage<'a, ScreenPoint>,
-  scrn_lines: &ReadStorage<'a, ScreenLine>,
-  scrn_circles: &ReadStorage<'a, ScreenCircle>,
+  scrn: &ScreenShapes<'a>,
 ) -> HashSet<Entity> {
   let mut result = HashSet::new();
 
   // Loop through all potential neighbors
   for entity in spatial_entity_map.get_entities_near_aabb(aabb) {
-    if let Some(point) = scrn_points.get(entity) {
+    if let Some(point) = scrn.points.get(entity) {
       if aabb.contains((*point).into()) {
         result.insert(entity);
       }
-    } else if let Some(line) = scrn_lines.get(entity) {
+    } else if let Some(line) = scrn.lines.get(entity) {
       let line: Line = (*line).into();
       if line.intersect(aabb).is_some() {
         result.insert(entity);
       }
-    } else if let Some(circle) = scrn_circles.get(entity) {
+    } else if let Some(circle) = scrn.circles.get(entity) {
       let circle: Circle = (*circle).into();
       if circle.intersect(aabb).is_some() {
         result.insert(entity);
       }
+    } else if let Some(locus) = scrn.loci.get(entity) {
+      let locus: Polyline = locus.clone().into();
+      let crossing = locus
+        .segments()
+        .into_iter()
+        .any(|segment| aabb.contains(segment.from) || segment.intersect(aabb).is_some());
+      if crossing {
+        result.insert(entity);
+      }
+    } else if let Some(conic) = scrn.conics.get(entity) {
+      let conic: Polyline = conic.clone().into();
+      let crossing = conic
+        .segments()
+        .into_iter()
+        .any(|segment| aabb.contains(segment.from) || segment.intersect(aabb).is_some());
+      if crossing {
+        result.insert(entity);
+      }
+    } else if let Some(polygon) = scrn.polygons.get(entity) {
+      // Either a side crosses the rectangle, or the rectangle is entirely inside the polygon
+      let polygon: Polygon = polygon.clone().into();
+      let crossing = polygon
+        .edges()
+        .into_iter()
+        .any(|edge| aabb.contains(edge.from) || edge.intersect(aabb).is_some());
+      if crossing || polygon.contains(aabb.min()) {
+        result.insert(entity);
+      }
+    } else if let Some(arc) = scrn.arcs.get(entity) {
+      let arc: Arc = (*arc).into();
+      if arc.intersect(aabb).is_some() {
+        result.insert(entity);
+      }
     }
   }
 
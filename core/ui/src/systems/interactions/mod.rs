@@ -1,6 +1,8 @@
+pub mod animation;
 pub mod exit;
 pub mod geometry;
 pub mod history;
+pub mod label;
 pub mod marker;
 pub mod tool;
 pub mod viewport;
@@ -14,7 +14,8 @@ impl<'a> System<'a> for ChangeLineToolViaKeyboard {
 
   fn run(&mut self, (input_state, tool_state, mut tool_change_event_channel): Self::SystemData) {
     match tool_state.get() {
-      Tool::Line(_) => {
+      // With the command key, the digits create triangle centers and circles instead
+      Tool::Line(_) if !input_state.keyboard.is_command_activated() => {
         if input_state.keyboard.just_activated(Key::D1) {
           tool_change_event_channel.single_write(ToolChangeEvent(Tool::Line(LineType::Straight)));
         } else if input_state.keyboard.just_activated(Key::D2) {
@@ -44,7 +44,8 @@ impl<'a> System<'a> for MoveViewportViaScroll {
       }
     }
 
-    if self.can_scroll {
+    // Scrolling with control held zooms instead, see `ZoomViewportViaScroll`
+    if self.can_scroll && !input_state.keyboard.is_ctrl_activated() {
       if !input_state.rel_scroll.is_zero() {
         let raw_movement = input_state.rel_scroll * delta_time.get() * SPEED;
         let movement = if cfg!(target_os = "macos") {
@@ -0,0 +1,20 @@
+use crate::resources::*;
+use core_lib::resources::*;
+use specs::prelude::*;
+
+#[derive(Default)]
+pub struct ToggleGridViaKeyboard;
+
+impl<'a> System<'a> for ToggleGridViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, Grid>);
+
+  fn run(&mut self, (input_state, mut grid): Self::SystemData) {
+    if input_state.keyboard.is_command_activated() && input_state.keyboard.just_activated(Key::E) {
+      if input_state.keyboard.is_shift_activated() {
+        grid.snap = !grid.snap;
+      } else {
+        grid.visible = !grid.visible;
+      }
+    }
+  }
+}
@@ -2,7 +2,7 @@ use crate::{events::*, resources::*};
 use core_lib::{events::*, math::*, resources::*};
 use specs::prelude::*;
 
-static SPEED: f64 = 1.0;
+use super::ZOOM_SPEED;
 
 pub struct ViewportDragTool {
   tool_change_event_reader: Option<ToolChangeEventReader>,
@@ -21,7 +21,6 @@ impl Default for ViewportDragTool {
 impl<'a> System<'a> for ViewportDragTool {
   type SystemData = (
     Read<'a, InputState>,
-    Read<'a, DeltaTime>,
     Read<'a, Viewport>,
     Read<'a, ToolChangeEventChannel>,
     Write<'a, MouseEventChannel>,
@@ -37,7 +36,6 @@ impl<'a> System<'a> for ViewportDragTool {
     &mut self,
     (
             input_state,
-            delta_time,
             viewport,
             tool_change_event_channel,
             mut mouse_event_channel,
@@ -77,14 +75,11 @@ impl<'a> System<'a> for ViewportDragTool {
         }
       }
 
-      // Then handle scroll. Note that this scale is mouse position dependent
-      if input_state.rel_scroll.y != 0.0 {
-        let m: Vector2 = input_state.mouse_abs_pos.into();
-        let delta = input_state.rel_scroll.y * delta_time.get() * SPEED;
-        let dcx = (m.x - viewport.half_screen_width()) * -delta / viewport.screen_width();
-        let dcy = (viewport.half_screen_height() - m.y) * -delta / viewport.screen_width();
-        viewport_event_channel.single_write(ViewportEvent::Scale(delta));
-        viewport_event_channel.single_write(ViewportEvent::Move(vec2![dcx, dcy]));
+      // Then handle scroll, which zooms around the mouse. Scrolling with control held is
+      // already a zoom in every tool, see `ZoomViewportViaScroll`
+      if input_state.rel_scroll.y != 0.0 && !input_state.keyboard.is_ctrl_activated() {
+        let factor = (input_state.rel_scroll.y * ZOOM_SPEED).exp();
+        viewport_event_channel.single_write(ViewportEvent::Zoom(factor, input_state.mouse_abs_pos));
       }
     }
   }
@@ -0,0 +1,30 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+static ZOOM_FACTOR: f64 = 1.25;
+
+/// Zoom in and out around the mouse with `Cmd =` and `Cmd -`, and zoom to fit all the
+/// elements with `Cmd 0`
+#[derive(Default)]
+pub struct ZoomViewportViaKeyboard;
+
+impl<'a> System<'a> for ZoomViewportViaKeyboard {
+  type SystemData = (Read<'a, InputState>, Write<'a, ViewportEventChannel>);
+
+  fn run(&mut self, (input_state, mut viewport_event_channel): Self::SystemData) {
+    let keyboard = &input_state.keyboard;
+    if !keyboard.is_command_activated() {
+      return;
+    }
+
+    // `Cmd Shift -` is taken by the parallel lines
+    if keyboard.just_activated(Key::Equals) || keyboard.just_activated(Key::Plus) {
+      viewport_event_channel.single_write(ViewportEvent::Zoom(ZOOM_FACTOR, input_state.mouse_abs_pos));
+    } else if keyboard.just_activated(Key::Minus) && !keyboard.is_shift_activated() {
+      viewport_event_channel.single_write(ViewportEvent::Zoom(1.0 / ZOOM_FACTOR, input_state.mouse_abs_pos));
+    } else if keyboard.just_activated(Key::D0) {
+      viewport_event_channel.single_write(ViewportEvent::ZoomToFit);
+    }
+  }
+}
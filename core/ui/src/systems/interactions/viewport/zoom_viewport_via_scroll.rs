@@ -0,0 +1,25 @@
+use crate::resources::*;
+use core_lib::events::*;
+use specs::prelude::*;
+
+/// How fast scrolling zooms. The zoom factor is exponential in the scroll so that scrolling
+/// back and forth goes back to the same scale
+pub static ZOOM_SPEED: f64 = 0.1;
+
+/// Zoom around the mouse when scrolling with control held, or when pinching
+#[derive(Default)]
+pub struct ZoomViewportViaScroll;
+
+impl<'a> System<'a> for ZoomViewportViaScroll {
+  type SystemData = (Read<'a, InputState>, Write<'a, ViewportEventChannel>);
+
+  fn run(&mut self, (input_state, mut viewport_event_channel): Self::SystemData) {
+    let mut zoom = input_state.rel_pinch;
+    if input_state.keyboard.is_ctrl_activated() {
+      zoom += input_state.rel_scroll.y * ZOOM_SPEED;
+    }
+    if zoom != 0.0 {
+      viewport_event_channel.single_write(ViewportEvent::Zoom(zoom.exp(), input_state.mouse_abs_pos));
+    }
+  }
+}
@@ -1,3 +1,4 @@
+pub mod animations;
 pub mod interactions;
 pub mod renderers;
 pub mod state_managers;
@@ -0,0 +1,18 @@
+use core_lib::{components::screen_shapes::*, resources::*, utilities::*};
+use specs::prelude::*;
+
+/// The element whose label is under the mouse, if any. Labels are drawn on top of everything,
+/// so callers check them before the shapes
+pub fn hitting_label<'a>(
+  mouse_pos: ScreenPosition,
+  spatial_entity_map: &SpatialEntityMap,
+  scrn_labels: &ReadStorage<'a, ScreenLabel>,
+) -> Option<Entity> {
+  spatial_entity_map
+    .get_entities_near_point(mouse_pos.into(), 0.0)
+    .into_iter()
+    .find(|ent| match scrn_labels.get(*ent) {
+      Some(label) => label.rect.contains(mouse_pos.into()),
+      None => false,
+    })
+}
@@ -4,41 +4,81 @@ use specs::prelude::*;
 pub fn hitting_object<'a>(
   mouse_pos: ScreenPosition,
   spatial_entity_map: &SpatialEntityMap,
-  scrn_points: &ReadStorage<'a, ScreenPoint>,
-  scrn_lines: &ReadStorage<'a, ScreenLine>,
-  scrn_circles: &ReadStorage<'a, ScreenCircle>,
+  scrn: &ScreenShapes<'a>,
   threshold: ScreenScalar,
 ) -> Option<Entity> {
   // Maybe selected...
   let mut maybe_selected_point: Option<(Entity, ScreenScalar)> = None;
   let mut maybe_selected_line: Option<(Entity, ScreenScalar)> = None;
   let mut maybe_selected_circle: Option<(Entity, ScreenScalar)> = None;
+  let mut maybe_selected_locus: Option<(Entity, ScreenScalar)> = None;
+  let mut maybe_selected_polygon: Option<(Entity, ScreenScalar)> = None;
 
   // Use spatial hash table to get potential neighbors
   let neighbor_entities = spatial_entity_map.get_entities_near_point(mouse_pos.into(), threshold.into());
   for entity in neighbor_entities {
-    if let Some(p) = scrn_points.get(entity) {
+    if let Some(p) = scrn.points.get(entity) {
       let dist = (*p - mouse_pos).magnitude();
       if dist < threshold && (maybe_selected_point.is_none() || dist < maybe_selected_point.unwrap().1) {
         maybe_selected_point = Some((entity, dist));
       }
-    } else if let Some(l) = scrn_lines.get(entity) {
+    } else if let Some(l) = scrn.lines.get(entity) {
       let proj_point = l.get_closest_point(mouse_pos);
       let dist = (proj_point - mouse_pos).magnitude();
       if dist < threshold && (maybe_selected_line.is_none() || dist < maybe_selected_line.unwrap().1) {
         maybe_selected_line = Some((entity, dist));
       }
-    } else if let Some(c) = scrn_circles.get(entity) {
+    } else if let Some(c) = scrn.circles.get(entity) {
       let dist = (mouse_pos - mouse_pos.project(*c)).magnitude();
       if dist < threshold && (maybe_selected_circle.is_none() || dist < maybe_selected_circle.unwrap().1) {
         maybe_selected_circle = Some((entity, dist));
       }
+    } else if let Some(locus) = scrn.loci.get(entity) {
+      let dist = locus.distance(mouse_pos);
+      if dist < threshold && (maybe_selected_locus.is_none() || dist < maybe_selected_locus.unwrap().1) {
+        maybe_selected_locus = Some((entity, dist));
+      }
+    } else if let Some(conic) = scrn.conics.get(entity) {
+      let dist = conic.distance(mouse_pos);
+      if dist < threshold && (maybe_selected_locus.is_none() || dist < maybe_selected_locus.unwrap().1) {
+        maybe_selected_locus = Some((entity, dist));
+      }
+    } else if let Some(poly) = scrn.polygons.get(entity) {
+      // Anywhere inside the polygon counts as hitting it
+      let dist = if poly.contains(mouse_pos) {
+        ScreenScalar(0.0)
+      } else {
+        poly.border_distance(mouse_pos)
+      };
+      if dist < threshold && (maybe_selected_polygon.is_none() || dist < maybe_selected_polygon.unwrap().1) {
+        maybe_selected_polygon = Some((entity, dist));
+      }
+    } else if let Some(a) = scrn.arcs.get(entity) {
+      // A plain arc is hit like a circle, only along its extent. Sectors and circular segments
+      // are hit anywhere inside, like polygons
+      if a.kind == ArcKind::Arc {
+        let dist = (mouse_pos - a.closest_point(mouse_pos)).magnitude();
+        if dist < threshold && (maybe_selected_circle.is_none() || dist < maybe_selected_circle.unwrap().1) {
+          maybe_selected_circle = Some((entity, dist));
+        }
+      } else {
+        let dist = if a.contains(mouse_pos) {
+          ScreenScalar(0.0)
+        } else {
+          a.border_distance(mouse_pos)
+        };
+        if dist < threshold && (maybe_selected_polygon.is_none() || dist < maybe_selected_polygon.unwrap().1) {
+          maybe_selected_polygon = Some((entity, dist));
+        }
+      }
     }
   }
 
-  // Return point in priority to line
+  // Return point in priority to line. Polygons come last since they cover the shapes drawn on them
   maybe_selected_point
     .or(maybe_selected_line)
     .or(maybe_selected_circle)
+    .or(maybe_selected_locus)
+    .or(maybe_selected_polygon)
     .map(|(ent, _)| ent)
 }
@@ -1,3 +1,5 @@
+mod hitting_label;
 mod hitting_object;
 
+pub use hitting_label::*;
 pub use hitting_object::*;